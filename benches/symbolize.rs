@@ -6,6 +6,7 @@ use std::path::Path;
 use blazesym::symbolize::Elf;
 use blazesym::symbolize::GsymFile;
 use blazesym::symbolize::Input;
+use blazesym::symbolize::Kernel;
 use blazesym::symbolize::Process;
 use blazesym::symbolize::Source;
 use blazesym::symbolize::Symbolizer;
@@ -124,6 +125,48 @@ fn symbolize_gsym() {
     assert_eq!(result.name, "abort_creds");
 }
 
+/// Symbolize an address using kallsyms, end-to-end, i.e., including
+/// parsing of the file.
+fn symbolize_kallsyms_impl(name_arena: bool) {
+    let kallsyms = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("kallsyms");
+    // Provide a (small) kernel image to prevent a search for one on
+    // the system, which would skew results.
+    let image = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-no-dwarf.bin");
+    let src = Source::Kernel(Kernel {
+        kallsyms: Some(kallsyms),
+        kernel_image: Some(image),
+        ..Default::default()
+    });
+    let symbolizer = Symbolizer::builder()
+        .enable_code_info(false)
+        .enable_name_arena(name_arena)
+        .build();
+
+    let result = symbolizer
+        .symbolize_single(black_box(&src), black_box(Input::AbsAddr(0xc0008001)))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+
+    assert_eq!(result.name, "stext");
+}
+
+/// Symbolize an address using kallsyms, with symbol names being
+/// allocated individually.
+fn symbolize_kallsyms() {
+    symbolize_kallsyms_impl(false)
+}
+
+/// Symbolize an address using kallsyms, with symbol names being placed
+/// in an arena.
+fn symbolize_kallsyms_arena() {
+    symbolize_kallsyms_impl(true)
+}
+
 /// Symbolize multiple addresses in a Gsym file.
 ///
 /// Addresses with high inlined function count were chosen, to
@@ -162,6 +205,8 @@ where
     M: Measurement,
 {
     bench_fn!(group, symbolize_process);
+    bench_fn!(group, symbolize_kallsyms);
+    bench_fn!(group, symbolize_kallsyms_arena);
     if cfg!(feature = "generate-large-test-files") {
         bench_fn!(group, symbolize_elf);
         bench_fn!(group, symbolize_dwarf_no_lines);
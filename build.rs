@@ -193,6 +193,23 @@ fn strip(src: &Path, dst: impl AsRef<OsStr>, options: &[&str]) {
     let () = adjust_mtime(&dst).unwrap();
 }
 
+/// Invoke `objcopy` on `src`, placing the result at `dst`.
+fn objcopy(src: &Path, dst: impl AsRef<OsStr>, options: &[&str]) {
+    let dst = src.with_file_name(dst);
+    println!("cargo:rerun-if-changed={}", src.display());
+    println!("cargo:rerun-if-changed={}", dst.display());
+
+    let () = run(
+        "objcopy",
+        options
+            .iter()
+            .map(OsStr::new)
+            .chain([src.as_os_str(), dst.as_os_str()]),
+    )
+    .expect("failed to run `objcopy`");
+    let () = adjust_mtime(&dst).unwrap();
+}
+
 /// Strip all DWARF information from an ELF binary, in an attempt to
 /// leave only ELF symbols in place.
 fn elf(src: &Path, dst: impl AsRef<OsStr>) {
@@ -320,11 +337,77 @@ fn prepare_test_files(crate_root: &Path) {
         "libtest-so.so",
         &["-shared", "-fPIC", "-Wl,--build-id=sha1"],
     );
+    // A shared object with only a dynamic symbol table (`.dynsym`).
+    strip(
+        &src.with_file_name("libtest-so.so"),
+        "libtest-so-dynsym-only.so",
+        &["--strip-all"],
+    );
     cc(
         &src,
         "libtest-so-no-separate-code.so",
         &["-shared", "-fPIC", "-Wl,--build-id=md5,-z,noseparate-code"],
     );
+    // A shared object mimicking one that got prelinked, i.e., with its
+    // first loadable segment residing at a non-zero virtual address.
+    cc(
+        &src,
+        "libtest-so-prelinked.so",
+        &["-shared", "-fPIC", "-Wl,-Ttext-segment=0x40000000"],
+    );
+    // A shared object requesting an executable stack.
+    cc(
+        &src,
+        "libtest-so-execstack.so",
+        &["-shared", "-fPIC", "-Wl,-z,execstack"],
+    );
+    // A shared object with a System V style `.hash` section instead
+    // of `.gnu_hash`.
+    cc(
+        &src,
+        "libtest-so-sysv-hash.so",
+        &["-shared", "-fPIC", "-Wl,--hash-style=sysv"],
+    );
+
+    let src = crate_root.join("data").join("test-tls.c");
+    cc(&src, "libtest-tls.so", &["-shared", "-fPIC"]);
+
+    let src = crate_root.join("data").join("test-so-split.c");
+    cc(
+        &src,
+        "libtest-so-split-full.so",
+        &["-shared", "-fPIC", "-Wl,--build-id=sha1"],
+    );
+    // Split the shared object into a runtime file, containing only
+    // `.dynsym`, and a separate debug file with the full `.symtab`.
+    let src = crate_root.join("data").join("libtest-so-split-full.so");
+    strip(&src, "libtest-so-split.so", &["--strip-all"]);
+    strip(&src, "libtest-so-split-debug.so", &["--only-keep-debug"]);
+
+    // A split shared object whose runtime file was relocated (as
+    // prelinking would do) after its debug file got split off, causing
+    // section addresses to differ between the two.
+    let src = crate_root.join("data").join("test-so-split.c");
+    cc(
+        &src,
+        "libtest-so-split-dwarf.so",
+        &["-shared", "-fPIC", "-g"],
+    );
+    cc(
+        &src,
+        "libtest-so-split-shifted-full.so",
+        &["-shared", "-fPIC", "-g", "-Wl,-Ttext-segment=0x40000000"],
+    );
+    let src = crate_root.join("data").join("libtest-so-split-dwarf.so");
+    strip(
+        &src,
+        "libtest-so-split-dwarf-debug.so",
+        &["--only-keep-debug"],
+    );
+    let src = crate_root
+        .join("data")
+        .join("libtest-so-split-shifted-full.so");
+    strip(&src, "libtest-so-split-shifted.so", &["--strip-all"]);
 
     let src = crate_root.join("data").join("test-exe.c");
     cc(&src, "test-no-debug.bin", &["-g0", "-Wl,--build-id=none"]);
@@ -332,6 +415,96 @@ fn prepare_test_files(crate_root: &Path) {
     cc(&src, "test-dwarf-v3.bin", &["-gstrict-dwarf", "-gdwarf-3"]);
     cc(&src, "test-dwarf-v4.bin", &["-gstrict-dwarf", "-gdwarf-4"]);
     cc(&src, "test-dwarf-v5.bin", &["-gstrict-dwarf", "-gdwarf-5"]);
+    cc(&src, "test-dwarf-zlib.bin", &["-gdwarf-5", "-gz=zlib"]);
+    cc(
+        &src,
+        "test-dwarf-zlib-gnu.bin",
+        &["-gdwarf-5", "-gz=zlib-gnu"],
+    );
+
+    let src = crate_root.join("data").join("test-cold.c");
+    let opts = ["-O2", "-freorder-blocks-and-partition"];
+    cc(
+        &src,
+        "test-cold-dwarf-v4.bin",
+        &[&opts[..], &["-gdwarf-4"]].concat(),
+    );
+    cc(
+        &src,
+        "test-cold-dwarf-v5.bin",
+        &[&opts[..], &["-gdwarf-5"]].concat(),
+    );
+
+    let src = crate_root.join("data").join("test-dwarf-v5-md5.s");
+    cc(
+        &src,
+        "test-dwarf-v5-md5.bin",
+        &["-shared", "-nostdlib", "-gdwarf-5"],
+    );
+
+    // A shared object with a line table marking the end of a
+    // function's prologue.
+    let src = crate_root.join("data").join("test-prologue.s");
+    cc(&src, "test-prologue.bin", &["-shared", "-nostdlib", "-g"]);
+
+    // A shared object with a function whose extent is only conveyed by
+    // DWARF location lists.
+    let src = crate_root.join("data").join("test-loclist-range.s");
+    cc(&src, "test-loclist-range.bin", &["-shared", "-nostdlib"]);
+
+    // A relocatable object containing symbols with special section
+    // indices.
+    let src = crate_root.join("data").join("test-special-shndx.s");
+    cc(&src, "test-special-shndx.bin", &["-c"]);
+
+    // A relocatable object containing section groups.
+    let src = crate_root.join("data").join("test-section-groups.s");
+    cc(&src, "test-section-groups.bin", &["-c"]);
+
+    // A shared object calling an `ifunc` through the PLT.
+    let src = crate_root.join("data").join("test-ifunc.c");
+    cc(&src, "libtest-ifunc.so", &["-shared", "-fPIC", "-nostdlib"]);
+
+    // An executable with two separately mapped executable segments.
+    let src = crate_root.join("data").join("test-multi-exec.s");
+    let script = crate_root.join("data").join("test-multi-exec.ld");
+    let script = script.to_str().unwrap();
+    println!("cargo:rerun-if-changed={script}");
+    cc(
+        &src,
+        "test-multi-exec.bin",
+        &[
+            "-nostdlib",
+            "-static",
+            "-Wl,--build-id=none",
+            "-Wl,-e,first",
+            &format!("-Wl,-T,{script}"),
+        ],
+    );
+
+    // A shared object carrying a Go build ID note.
+    let src = crate_root.join("data").join("test-go-buildid.s");
+    cc(&src, "test-go-buildid.bin", &["-shared", "-nostdlib"]);
+
+    // A shared object containing same-named functions in different
+    // sections.
+    let src = crate_root.join("data").join("test-sections.c");
+    let src_cu2 = crate_root.join("data").join("test-sections-cu2.c");
+    let src_cu2 = src_cu2.to_str().unwrap();
+    println!("cargo:rerun-if-changed={src_cu2}");
+    cc(
+        &src,
+        "test-sections.bin",
+        &[
+            "-shared",
+            "-fPIC",
+            "-nostdlib",
+            "-gdwarf-4",
+            "-O0",
+            // TODO: Eventually we may want to make `cc` multi-input-file aware.
+            src_cu2,
+        ],
+    );
 
     let src = crate_root.join("data").join("test-stable-addresses.c");
     let src_cu2 = crate_root.join("data").join("test-stable-addresses-cu2.c");
@@ -368,9 +541,62 @@ fn prepare_test_files(crate_root: &Path) {
         ],
     );
 
+    // A fully static binary, with only a `.symtab` and no `.dynsym`.
+    cc(
+        &src,
+        "test-stable-addresses-static.bin",
+        &[
+            "-g0",
+            "-static",
+            "-T",
+            ld_script,
+            "-Wl,--build-id=none",
+            "-O0",
+            "-nostdlib",
+            src_cu2,
+        ],
+    );
+
+    // A relocatable object, similar in nature to a kernel module.
+    cc(
+        &src,
+        "test-stable-addresses-rel.bin",
+        &["-c", "-g0", "-O0", "-fno-pic"],
+    );
+    cc(
+        &src,
+        "test-stable-addresses-rel-dwarf.bin",
+        &["-c", "-gdwarf-4", "-O0", "-fno-pic"],
+    );
+
     let src = crate_root.join("data").join("test-stable-addresses.bin");
     gsym(&src, "test-stable-addresses.gsym");
     dwarf(&src, "test-stable-addresses-dwarf-only.bin");
+    strip(&src, "test-stable-addresses-stripped.bin", &["--strip-all"]);
+    // A binary with a local duplicate of the (global) `factorial`
+    // symbol, lacking size information.
+    objcopy(
+        &src,
+        "test-stable-addresses-dup-sym.bin",
+        &["--add-symbol", "factorial=.text:0x100,local,function"],
+    );
+
+    let src = crate_root.join("data").join("test-overlay.c");
+    let ld_script = crate_root.join("data").join("test-overlay.ld");
+    let ld_script = ld_script.to_str().unwrap();
+    println!("cargo:rerun-if-changed={ld_script}");
+    cc(
+        &src,
+        "test-overlay.bin",
+        &[
+            "-gdwarf-4",
+            "-T",
+            ld_script,
+            "-Wl,--build-id=none",
+            "-O0",
+            "-nostdlib",
+        ],
+    );
 
     let src = crate_root.join("data").join("kallsyms.xz");
     let mut dst = src.clone();
@@ -24,6 +24,11 @@ typedef enum blaze_sym_type {
    * The symbol is a variable.
    */
   BLAZE_SYM_VAR,
+  /**
+   * The symbol is a thread-local variable. Its address is an offset
+   * into the thread-local storage block of the defining module.
+   */
+  BLAZE_SYM_TLS,
 } blaze_sym_type;
 
 /**
@@ -60,6 +65,11 @@ typedef struct blaze_sym_info {
    * See [`inspect::SymInfo::size`].
    */
   size_t size;
+  /**
+   * See [`inspect::SymInfo::end`]. Zero if the end address is
+   * unknown.
+   */
+  uintptr_t end;
   /**
    * See [`inspect::SymInfo::file_offset`].
    */
@@ -244,6 +254,7 @@ typedef struct blaze_symbolizer_opts {
    * Demangling happens on a best-effort basis. Currently supported
    * languages are Rust and C++ and the flag will have no effect if
    * the underlying language does not mangle symbols (such as C).
+   * When disabled, symbol names are reported verbatim.
    */
   bool demangle;
 } blaze_symbolizer_opts;
@@ -342,6 +353,13 @@ typedef struct blaze_sym {
  *
  * Instances of [`blaze_result`] are returned by any of the `blaze_symbolize_*`
  * variants. They should be freed by calling [`blaze_result_free`].
+ *
+ * All `blaze_symbolize_*` variants symbolize a batch of addresses in a
+ * single call. The resulting object, including all symbols, inlined
+ * function information, and strings it references, lives in a single
+ * allocation that is owned by the caller. None of the referenced data
+ * must be freed individually and all of it becomes invalid once the
+ * object is freed.
  */
 typedef struct blaze_result {
   /**
@@ -690,6 +708,9 @@ const struct blaze_result *blaze_symbolize_gsym_file_file_addrs(blaze_symbolizer
 /**
  * Free an array returned by any of the `blaze_symbolize_*` variants.
  *
+ * This function releases the [`blaze_result`] object along with all
+ * data referenced by it. Passing `NULL` is a no-op.
+ *
  * # Safety
  * The pointer must have been returned by any of the `blaze_symbolize_*`
  * variants.
@@ -18,6 +18,7 @@ use std::ptr;
 #[cfg(doc)]
 use blazesym::inspect;
 use blazesym::inspect::Elf;
+use blazesym::inspect::ElfSymSource;
 use blazesym::inspect::Inspector;
 use blazesym::inspect::Source;
 use blazesym::inspect::SymInfo;
@@ -49,6 +50,11 @@ impl From<Elf> for blaze_inspect_elf_src {
         let Elf {
             path,
             debug_info,
+            sym_source: _,
+            section: _,
+            dedup: _,
+            demangle: _,
+            limit: _,
             _non_exhaustive: (),
         } = other;
         Self {
@@ -69,6 +75,11 @@ impl From<blaze_inspect_elf_src> for Elf {
                 unsafe { CString::from_raw(path as *mut _) }.into_bytes(),
             )),
             debug_info,
+            sym_source: ElfSymSource::default(),
+            section: None,
+            dedup: false,
+            demangle: false,
+            limit: None,
             _non_exhaustive: (),
         }
     }
@@ -84,6 +95,11 @@ impl From<&blaze_inspect_elf_src> for Elf {
             ))
             .to_path_buf(),
             debug_info: *debug_info,
+            sym_source: ElfSymSource::default(),
+            section: None,
+            dedup: false,
+            demangle: false,
+            limit: None,
             _non_exhaustive: (),
         }
     }
@@ -101,6 +117,9 @@ pub enum blaze_sym_type {
     BLAZE_SYM_FUNC,
     /// The symbol is a variable.
     BLAZE_SYM_VAR,
+    /// The symbol is a thread-local variable. Its address is an offset
+    /// into the thread-local storage block of the defining module.
+    BLAZE_SYM_TLS,
 }
 
 impl From<SymType> for blaze_sym_type {
@@ -109,6 +128,7 @@ impl From<SymType> for blaze_sym_type {
             SymType::Unknown => blaze_sym_type::BLAZE_SYM_UNKNOWN,
             SymType::Function => blaze_sym_type::BLAZE_SYM_FUNC,
             SymType::Variable => blaze_sym_type::BLAZE_SYM_VAR,
+            SymType::Tls => blaze_sym_type::BLAZE_SYM_TLS,
         }
     }
 }
@@ -124,6 +144,9 @@ pub struct blaze_sym_info {
     pub addr: Addr,
     /// See [`inspect::SymInfo::size`].
     pub size: usize,
+    /// See [`inspect::SymInfo::end`]. Zero if the end address is
+    /// unknown.
+    pub end: Addr,
     /// See [`inspect::SymInfo::file_offset`].
     pub file_offset: u64,
     /// See [`inspect::SymInfo::obj_file_name`].
@@ -168,8 +191,10 @@ fn convert_syms_list_to_c(syms_list: Vec<Vec<SymInfo>>) -> *const *const blaze_s
         unsafe { *syms_ptr = sym_ptr };
         for SymInfo {
             name,
+            demangled_name: _,
             addr,
             size,
+            end,
             sym_type,
             file_offset,
             obj_file_name,
@@ -197,9 +222,11 @@ fn convert_syms_list_to_c(syms_list: Vec<Vec<SymInfo>>) -> *const *const blaze_s
                     name: name_ptr,
                     addr,
                     size,
+                    end: end.unwrap_or(0),
                     sym_type: match sym_type {
                         SymType::Function => blaze_sym_type::BLAZE_SYM_FUNC,
                         SymType::Variable => blaze_sym_type::BLAZE_SYM_VAR,
+                        SymType::Tls => blaze_sym_type::BLAZE_SYM_TLS,
                         SymType::Unknown => blaze_sym_type::BLAZE_SYM_UNKNOWN,
                     },
                     file_offset: file_offset.unwrap_or(0),
@@ -213,6 +240,7 @@ fn convert_syms_list_to_c(syms_list: Vec<Vec<SymInfo>>) -> *const *const blaze_s
                 name: ptr::null(),
                 addr: 0,
                 size: 0,
+                end: 0,
                 sym_type: blaze_sym_type::BLAZE_SYM_UNKNOWN,
                 file_offset: 0,
                 obj_file_name: ptr::null(),
@@ -348,13 +376,14 @@ mod tests {
             name: ptr::null(),
             addr: 42,
             size: 1337,
+            end: 1379,
             file_offset: 31,
             obj_file_name: ptr::null(),
             sym_type: blaze_sym_type::BLAZE_SYM_VAR,
         };
         assert_eq!(
             format!("{info:?}"),
-            "blaze_sym_info { name: 0x0, addr: 42, size: 1337, file_offset: 31, obj_file_name: 0x0, sym_type: BLAZE_SYM_VAR }"
+            "blaze_sym_info { name: 0x0, addr: 42, size: 1337, end: 1379, file_offset: 31, obj_file_name: 0x0, sym_type: BLAZE_SYM_VAR }"
         );
     }
 
@@ -375,6 +404,7 @@ mod tests {
                     );
                     assert_eq!(c_sym.addr, sym.addr);
                     assert_eq!(c_sym.size, sym.size);
+                    assert_eq!(c_sym.end, sym.end.unwrap_or(0));
                     assert_eq!(c_sym.sym_type, blaze_sym_type::from(sym.sym_type));
                     assert_eq!(Some(c_sym.file_offset), sym.file_offset);
                     assert_eq!(
@@ -403,8 +433,10 @@ mod tests {
         // Test conversion with a single symbol.
         let syms = vec![vec![SymInfo {
             name: "sym1".into(),
+            demangled_name: None,
             addr: 0xdeadbeef,
             size: 42,
+            end: Some(0xdeadbeef + 42),
             sym_type: SymType::Function,
             file_offset: Some(1337),
             obj_file_name: Some(Path::new("/tmp/foobar.so").into()),
@@ -415,16 +447,20 @@ mod tests {
         let syms = vec![vec![
             SymInfo {
                 name: "sym1".into(),
+                demangled_name: None,
                 addr: 0xdeadbeef,
                 size: 42,
+                end: Some(0xdeadbeef + 42),
                 sym_type: SymType::Function,
                 file_offset: Some(1337),
                 obj_file_name: Some(Path::new("/tmp/foobar.so").into()),
             },
             SymInfo {
                 name: "sym2".into(),
+                demangled_name: None,
                 addr: 0xdeadbeef + 52,
                 size: 45,
+                end: Some(0xdeadbeef + 52 + 45),
                 sym_type: SymType::Unknown,
                 file_offset: Some(1338),
                 obj_file_name: Some(Path::new("other.so").into()),
@@ -436,16 +472,20 @@ mod tests {
         let syms = vec![
             vec![SymInfo {
                 name: "sym1".into(),
+                demangled_name: None,
                 addr: 0xdeadbeef,
                 size: 42,
+                end: Some(0xdeadbeef + 42),
                 sym_type: SymType::Function,
                 file_offset: Some(1337),
                 obj_file_name: Some(Path::new("/tmp/foobar.so").into()),
             }],
             vec![SymInfo {
                 name: "sym2".into(),
+                demangled_name: None,
                 addr: 0xdeadbeef + 52,
                 size: 45,
+                end: Some(0xdeadbeef + 52 + 45),
                 sym_type: SymType::Unknown,
                 file_offset: Some(1338),
                 obj_file_name: Some(Path::new("other.so").into()),
@@ -456,8 +496,10 @@ mod tests {
         // Test conversion of a `SymInfo` vector with many elements.
         let sym = SymInfo {
             name: "sym1".into(),
+            demangled_name: None,
             addr: 0xdeadbeef,
             size: 42,
+            end: Some(0xdeadbeef + 42),
             sym_type: SymType::Function,
             file_offset: Some(1337),
             obj_file_name: Some(Path::new("/tmp/foobar.so").into()),
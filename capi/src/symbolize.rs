@@ -49,6 +49,11 @@ impl From<&blaze_symbolize_src_elf> for Elf {
         let blaze_symbolize_src_elf { path } = elf;
         Self {
             path: unsafe { from_cstr(*path) },
+            debug_file: None,
+            overlay: None,
+            build_id: None,
+            inlined_fns: None,
+            sym_sources: None,
             _non_exhaustive: (),
         }
     }
@@ -87,6 +92,7 @@ impl From<&blaze_symbolize_src_kernel> for Kernel {
         Self {
             kallsyms: (!kallsyms.is_null()).then(|| unsafe { from_cstr(*kallsyms) }),
             kernel_image: (!kernel_image.is_null()).then(|| unsafe { from_cstr(*kernel_image) }),
+            livepatches: Vec::new(),
             _non_exhaustive: (),
         }
     }
@@ -112,6 +118,7 @@ impl From<&blaze_symbolize_src_process> for Process {
         let blaze_symbolize_src_process { pid } = process;
         Self {
             pid: (*pid).into(),
+            resolve_plt: false,
             _non_exhaustive: (),
         }
     }
@@ -236,6 +243,13 @@ pub struct blaze_sym {
 ///
 /// Instances of [`blaze_result`] are returned by any of the `blaze_symbolize_*`
 /// variants. They should be freed by calling [`blaze_result_free`].
+///
+/// All `blaze_symbolize_*` variants symbolize a batch of addresses in a
+/// single call. The resulting object, including all symbols, inlined
+/// function information, and strings it references, lives in a single
+/// allocation that is owned by the caller. None of the referenced data
+/// must be freed individually and all of it becomes invalid once the
+/// object is freed.
 #[repr(C)]
 #[derive(Debug)]
 pub struct blaze_result {
@@ -277,6 +291,7 @@ pub struct blaze_symbolizer_opts {
     /// Demangling happens on a best-effort basis. Currently supported
     /// languages are Rust and C++ and the flag will have no effect if
     /// the underlying language does not mangle symbols (such as C).
+    /// When disabled, symbol names are reported verbatim.
     pub demangle: bool,
 }
 
@@ -387,7 +402,7 @@ fn convert_symbolizedresults_to_c(results: Vec<Symbolized>) -> *const blaze_resu
     // blaze_sym, and C strings of symbol and path.
     let (strtab_size, inlined_fn_cnt) = results.iter().fold((0, 0), |acc, sym| match sym {
         Symbolized::Sym(sym) => (acc.0 + sym_strtab_size(sym), acc.1 + sym.inlined.len()),
-        Symbolized::Unknown => acc,
+        Symbolized::Unknown | Symbolized::BuildIdMismatch => acc,
     });
 
     let buf_size = strtab_size
@@ -457,7 +472,7 @@ fn convert_symbolizedresults_to_c(results: Vec<Symbolized>) -> *const blaze_resu
                     inlined_last = unsafe { inlined_last.add(1) };
                 }
             }
-            Symbolized::Unknown => {
+            Symbolized::Unknown | Symbolized::BuildIdMismatch => {
                 // Unknown symbols/addresses are just represented with all
                 // fields set to zero.
                 // SAFETY: `syms_last` is pointing to a writable and properly
@@ -633,6 +648,9 @@ pub unsafe extern "C" fn blaze_symbolize_gsym_file_file_addrs(
 
 /// Free an array returned by any of the `blaze_symbolize_*` variants.
 ///
+/// This function releases the [`blaze_result`] object along with all
+/// data referenced by it. Passing `NULL` is a no-op.
+///
 /// # Safety
 /// The pointer must have been returned by any of the `blaze_symbolize_*`
 /// variants.
@@ -833,6 +851,7 @@ mod tests {
         // A single symbol with inlined function information.
         let results = vec![Symbolized::Sym(Sym {
             name: "test".into(),
+            mangled_name: None,
             addr: 0x1337,
             offset: 0x1338,
             size: Some(42),
@@ -841,6 +860,10 @@ mod tests {
                 file: OsStr::new("a-file").into(),
                 line: Some(42),
                 column: Some(43),
+                md5: None,
+                flags: None,
+                context: None,
+                file_exists: None,
                 _non_exhaustive: (),
             }),
             inlined: vec![InlinedFn {
@@ -850,11 +873,17 @@ mod tests {
                     file: OsStr::new("another-file").into(),
                     line: Some(42),
                     column: Some(43),
+                    md5: None,
+                    flags: None,
+                    context: None,
+                    file_exists: None,
                     _non_exhaustive: (),
                 }),
+                repeat: 1,
                 _non_exhaustive: (),
             }]
             .into_boxed_slice(),
+            inlined_truncated: false,
             _non_exhaustive: (),
         })];
         let result = convert_symbolizedresults_to_c(results);
@@ -866,6 +895,7 @@ mod tests {
             Symbolized::Unknown,
             Symbolized::Sym(Sym {
                 name: "test".into(),
+                mangled_name: None,
                 addr: 0x1337,
                 offset: 0x1338,
                 size: None,
@@ -873,9 +903,11 @@ mod tests {
                 inlined: vec![InlinedFn {
                     name: "inlined_fn".into(),
                     code_info: None,
+                    repeat: 1,
                     _non_exhaustive: (),
                 }]
                 .into_boxed_slice(),
+                inlined_truncated: false,
                 _non_exhaustive: (),
             }),
             Symbolized::Unknown,
@@ -906,6 +938,47 @@ mod tests {
         let () = unsafe { blaze_symbolizer_free(symbolizer) };
     }
 
+    /// Check that we can symbolize a batch of addresses in a single
+    /// call.
+    #[test]
+    fn symbolize_batch() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let path_c = CString::new(path.to_str().unwrap()).unwrap();
+        let elf_src = blaze_symbolize_src_elf {
+            path: path_c.as_ptr(),
+        };
+
+        let symbolizer = blaze_symbolizer_new();
+        let addrs = [0x2000100, 0x100, 0x2000100, 0x2000000];
+        let result = unsafe {
+            blaze_symbolize_elf_file_addrs(symbolizer, &elf_src, addrs.as_ptr(), addrs.len())
+        };
+        assert!(!result.is_null());
+
+        let result = unsafe { &*result };
+        assert_eq!(result.cnt, addrs.len());
+        let syms = unsafe { slice::from_raw_parts(result.syms.as_ptr(), result.cnt) };
+        let names = syms
+            .iter()
+            .map(|sym| (!sym.name.is_null()).then(|| unsafe { CStr::from_ptr(sym.name) }))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                Some(CStr::from_bytes_with_nul(b"factorial\0").unwrap()),
+                None,
+                Some(CStr::from_bytes_with_nul(b"factorial\0").unwrap()),
+                Some(CStr::from_bytes_with_nul(b"main\0").unwrap()),
+            ]
+        );
+
+        let () = unsafe { blaze_result_free(result) };
+        let () = unsafe { blaze_symbolizer_free(symbolizer) };
+    }
+
     /// Make sure that we can symbolize an address using ELF, DWARF, and
     /// GSYM.
     #[test]
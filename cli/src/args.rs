@@ -25,7 +25,7 @@ fn parse_pid(s: &str) -> Result<Pid> {
 }
 
 /// Parse an address from a string.
-fn parse_addr(s: &str) -> Result<Addr> {
+pub(crate) fn parse_addr(s: &str) -> Result<Addr> {
     // In our world addresses are always represented in hex, with or without 0x
     // prefix.
     Addr::from_str_radix(s.trim_start_matches("0x"), 16)
@@ -90,6 +90,9 @@ pub struct Elf {
     pub path: PathBuf,
     /// The addresses to symbolize.
     ///
+    /// If no addresses are provided, they are read from standard
+    /// input, one per line.
+    ///
     /// Addresses are assumed to already be normalized to the file
     /// itself (i.e., with relocation and address randomization effects
     /// removed).
@@ -104,6 +107,9 @@ pub struct Gsym {
     pub path: PathBuf,
     /// The addresses to symbolize.
     ///
+    /// If no addresses are provided, they are read from standard
+    /// input, one per line.
+    ///
     /// Addresses are assumed to already be normalized to the file
     /// itself (i.e., with relocation and address randomization effects
     /// removed).
@@ -118,6 +124,9 @@ pub struct Process {
     #[arg(value_parser = parse_pid)]
     pub pid: Pid,
     /// The addresses to symbolize.
+    ///
+    /// If no addresses are provided, they are read from standard
+    /// input, one per line.
     #[arg(value_parser = parse_addr)]
     pub addrs: Vec<Addr>,
 }
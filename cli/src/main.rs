@@ -2,6 +2,10 @@
 
 mod args;
 
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
 use anyhow::Context;
 use anyhow::Result;
 
@@ -20,6 +24,13 @@ use tracing_subscriber::fmt::time::SystemTime;
 use tracing_subscriber::FmtSubscriber;
 
 const ADDR_WIDTH: usize = 16;
+/// The number of addresses to symbolize at once when reading them from
+/// a stream.
+const STREAM_BATCH_SIZE: usize = 1024;
+
+
+/// A function creating the symbolization input for a set of addresses.
+type InputFn = for<'addrs> fn(&'addrs [Addr]) -> symbolize::Input<&'addrs [Addr]>;
 
 
 fn format_build_id_bytes(build_id: &[u8]) -> String {
@@ -70,11 +81,15 @@ fn normalize(normalize: args::Normalize) -> Result<()> {
     Ok(())
 }
 
-fn print_frame(
+fn print_frame<W>(
+    writer: &mut W,
     name: &str,
     addr_info: Option<(Addr, Addr, usize)>,
     code_info: &Option<symbolize::CodeInfo>,
-) {
+) -> io::Result<()>
+where
+    W: Write,
+{
     let code_info = code_info.as_ref().map(|code_info| {
         let path = code_info.to_path();
         let path = path.display();
@@ -88,14 +103,16 @@ fn print_frame(
 
     if let Some((input_addr, addr, offset)) = addr_info {
         // If we have various address information bits we have a new symbol.
-        println!(
+        writeln!(
+            writer,
             "{input_addr:#0width$x}: {name} @ {addr:#x}+{offset:#x}{code_info}",
             code_info = code_info.as_deref().unwrap_or(""),
             width = ADDR_WIDTH
         )
     } else {
         // Otherwise we are dealing with an inlined call.
-        println!(
+        writeln!(
+            writer,
             "{:width$}  {name}{code_info} [inlined]",
             " ",
             code_info = code_info
@@ -107,34 +124,15 @@ fn print_frame(
     }
 }
 
-/// The handler for the 'symbolize' command.
-fn symbolize(symbolize: args::Symbolize) -> Result<()> {
-    let symbolizer = Symbolizer::new();
-    let (src, input, addrs) = match symbolize {
-        args::Symbolize::Elf(args::Elf { path, ref addrs }) => {
-            let src = symbolize::Source::from(symbolize::Elf::new(path));
-            let addrs = addrs.as_slice();
-            let input = symbolize::Input::VirtOffset(addrs);
-            (src, input, addrs)
-        }
-        args::Symbolize::Gsym(args::Gsym { path, ref addrs }) => {
-            let src = symbolize::Source::from(symbolize::GsymFile::new(path));
-            let addrs = addrs.as_slice();
-            let input = symbolize::Input::VirtOffset(addrs);
-            (src, input, addrs)
-        }
-        args::Symbolize::Process(args::Process { pid, ref addrs }) => {
-            let src = symbolize::Source::from(symbolize::Process::new(pid));
-            let addrs = addrs.as_slice();
-            let input = symbolize::Input::AbsAddr(addrs);
-            (src, input, addrs)
-        }
-    };
-
-    let syms = symbolizer
-        .symbolize(&src, input)
-        .context("failed to symbolize addresses")?;
-
+/// Print the symbolization results for the provided input addresses.
+fn print_symbolized<W>(
+    writer: &mut W,
+    addrs: &[Addr],
+    syms: Vec<symbolize::Symbolized<'_>>,
+) -> io::Result<()>
+where
+    W: Write,
+{
     for (input_addr, sym) in addrs.iter().copied().zip(syms) {
         match sym {
             symbolize::Symbolized::Sym(symbolize::Sym {
@@ -145,19 +143,113 @@ fn symbolize(symbolize: args::Symbolize) -> Result<()> {
                 inlined,
                 ..
             }) => {
-                print_frame(&name, Some((input_addr, addr, offset)), &code_info);
+                let () = print_frame(writer, &name, Some((input_addr, addr, offset)), &code_info)?;
                 for frame in inlined.iter() {
-                    print_frame(&frame.name, None, &frame.code_info);
+                    let () = print_frame(writer, &frame.name, None, &frame.code_info)?;
                 }
             }
-            symbolize::Symbolized::Unknown => {
-                println!("{input_addr:#0width$x}: <no-symbol>", width = ADDR_WIDTH)
+            symbolize::Symbolized::Unknown => writeln!(
+                writer,
+                "{input_addr:#0width$x}: <no-symbol>",
+                width = ADDR_WIDTH
+            )?,
+            symbolize::Symbolized::BuildIdMismatch => writeln!(
+                writer,
+                "{input_addr:#0width$x}: <build-id-mismatch>",
+                width = ADDR_WIDTH
+            )?,
+        }
+    }
+    Ok(())
+}
+
+/// Symbolize the addresses read from `reader`, one per line, and
+/// write the results to `writer`.
+///
+/// Addresses are symbolized in batches of bounded size and results
+/// are written as soon as a batch has been processed, meaning that
+/// arbitrarily large inputs can be handled with bounded memory. Lines
+/// that fail to parse result in an error record being written, but do
+/// not cause processing to stop.
+fn symbolize_stream<R, W>(
+    symbolizer: &Symbolizer,
+    src: &symbolize::Source,
+    input: InputFn,
+    reader: R,
+    writer: &mut W,
+) -> Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    let flush = |addrs: &mut Vec<Addr>, writer: &mut W| -> Result<()> {
+        if !addrs.is_empty() {
+            let syms = symbolizer
+                .symbolize(src, input(addrs))
+                .context("failed to symbolize addresses")?;
+            let () = print_symbolized(writer, addrs, syms)?;
+            let () = addrs.clear();
+        }
+        Ok(())
+    };
+
+    let mut addrs = Vec::with_capacity(STREAM_BATCH_SIZE);
+    for line in reader.lines() {
+        let line = line.context("failed to read input line")?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue
+        }
+
+        match args::parse_addr(line) {
+            Ok(addr) => {
+                let () = addrs.push(addr);
+                if addrs.len() >= STREAM_BATCH_SIZE {
+                    let () = flush(&mut addrs, writer)?;
+                }
+            }
+            Err(err) => {
+                // Emit everything parsed so far first, to keep output
+                // in input order.
+                let () = flush(&mut addrs, writer)?;
+                let () = writeln!(writer, "{line}: error: {err:#}")?;
             }
         }
     }
+    let () = flush(&mut addrs, writer)?;
     Ok(())
 }
 
+/// The handler for the 'symbolize' command.
+fn symbolize(symbolize: args::Symbolize) -> Result<()> {
+    let symbolizer = Symbolizer::new();
+    let (src, input, addrs): (_, InputFn, _) = match symbolize {
+        args::Symbolize::Elf(args::Elf { path, addrs }) => {
+            let src = symbolize::Source::from(symbolize::Elf::new(path));
+            (src, |addrs| symbolize::Input::VirtOffset(addrs), addrs)
+        }
+        args::Symbolize::Gsym(args::Gsym { path, addrs }) => {
+            let src = symbolize::Source::from(symbolize::GsymFile::new(path));
+            (src, |addrs| symbolize::Input::VirtOffset(addrs), addrs)
+        }
+        args::Symbolize::Process(args::Process { pid, addrs }) => {
+            let src = symbolize::Source::from(symbolize::Process::new(pid));
+            (src, |addrs| symbolize::Input::AbsAddr(addrs), addrs)
+        }
+    };
+
+    let mut stdout = io::stdout().lock();
+    if addrs.is_empty() {
+        let stdin = io::stdin().lock();
+        symbolize_stream(&symbolizer, &src, input, stdin, &mut stdout)
+    } else {
+        let syms = symbolizer
+            .symbolize(&src, input(&addrs))
+            .context("failed to symbolize addresses")?;
+        let () = print_symbolized(&mut stdout, &addrs, syms)?;
+        Ok(())
+    }
+}
 
 fn main() -> Result<()> {
     let args = args::Args::parse();
@@ -0,0 +1,19 @@
+/* A function that the compiler splits into a hot and a cold part when
+ * compiling with -freorder-blocks-and-partition, resulting in
+ * non-contiguous address ranges described by DW_AT_ranges. */
+
+#include <stdlib.h>
+
+__attribute__((noinline)) int
+hot_cold(int x) {
+  if (__builtin_expect(x == 1337, 0)) {
+    volatile int y = x * 3;
+    abort();
+  }
+  return x + 1;
+}
+
+int
+main(int argc, char *argv[]) {
+  return hot_cold(argc);
+}
@@ -0,0 +1,15 @@
+# A minimal assembly file with DWARF 5 line information containing MD5
+# checksums for source files, which not all compilers emit. The
+# checksum is a byte level palindrome, because assemblers disagree on
+# the byte order in which to emit it.
+	.file 0 "/blazesym" "test-dwarf-v5-md5.c" md5 0x0123456789abcdefefcdab8967452301
+	.file 1 "test-dwarf-v5-md5.c" md5 0x0123456789abcdefefcdab8967452301
+	.text
+	.globl	checksummed
+	.type	checksummed, @function
+checksummed:
+	.loc 1 3 1
+	nop
+	.loc 1 4 1
+	ret
+	.size	checksummed, .-checksummed
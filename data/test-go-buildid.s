@@ -0,0 +1,17 @@
+# A shared object carrying a `.note.go.buildid` note, laid out the way
+# the Go linker emits it.
+
+	.section .note.go.buildid,"a",@note
+	.balign 4
+	.long 4			# n_namesz
+	.long 2f - 1f		# n_descsz
+	.long 4			# n_type (NT_GO_BUILD_ID)
+	.asciz "Go\0"
+1:	.ascii "abcdefghijklmnopqrst/uvwxyz0123456789ABC/DEFGHIJKLMNOPQRSTUVW/XYZabcdefghijklmnop"
+2:	.balign 4
+
+	.text
+	.globl main
+	.type main,@function
+main:
+	ret
@@ -0,0 +1,22 @@
+/* A shared object calling an `ifunc` through its PLT entry. */
+
+static int __attribute__((noinline)) ifunc_impl(void) {
+  return 42;
+}
+
+static int (*resolve_ifunc_fn(void))(void) {
+  return ifunc_impl;
+}
+
+int ifunc_fn(void) __attribute__((ifunc("resolve_ifunc_fn")));
+
+/* Retrieve the address of the PLT entry used for calling `ifunc_fn`. */
+void *ifunc_fn_plt(void) {
+  void *addr;
+  asm("lea ifunc_fn@PLT(%%rip), %0" : "=r"(addr));
+  return addr;
+}
+
+int call_ifunc_fn(void) {
+  return ifunc_fn();
+}
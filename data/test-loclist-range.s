@@ -0,0 +1,86 @@
+# A minimal assembly file with hand written DWARF debug information
+# describing functions that lack any address range attributes. The
+# extent of `loclisted` can only be inferred from the location list of
+# its parameter, while nothing at all is known about `rangeless`.
+	.text
+	.globl	loclisted
+	.type	loclisted, @function
+loclisted:
+	mov	%edi, %eax
+.Lmid:
+	add	$1, %eax
+	ret
+.Lfunc_end:
+	.size	loclisted, .-loclisted
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	.uleb128 1		# abbreviation code
+	.uleb128 0x11		# DW_TAG_compile_unit
+	.byte	1		# DW_CHILDREN_yes
+	.uleb128 0x25		# DW_AT_producer
+	.uleb128 0x8		# DW_FORM_string
+	.uleb128 0x13		# DW_AT_language
+	.uleb128 0xb		# DW_FORM_data1
+	.uleb128 0x3		# DW_AT_name
+	.uleb128 0x8		# DW_FORM_string
+	.uleb128 0x11		# DW_AT_low_pc
+	.uleb128 0x1		# DW_FORM_addr
+	.uleb128 0x12		# DW_AT_high_pc
+	.uleb128 0x7		# DW_FORM_data8
+	.byte	0, 0
+	.uleb128 2		# abbreviation code
+	.uleb128 0x2e		# DW_TAG_subprogram
+	.byte	1		# DW_CHILDREN_yes
+	.uleb128 0x3		# DW_AT_name
+	.uleb128 0x8		# DW_FORM_string
+	.uleb128 0x3f		# DW_AT_external
+	.uleb128 0x19		# DW_FORM_flag_present
+	.byte	0, 0
+	.uleb128 3		# abbreviation code
+	.uleb128 0x5		# DW_TAG_formal_parameter
+	.byte	0		# DW_CHILDREN_no
+	.uleb128 0x3		# DW_AT_name
+	.uleb128 0x8		# DW_FORM_string
+	.uleb128 0x2		# DW_AT_location
+	.uleb128 0x17		# DW_FORM_sec_offset
+	.byte	0, 0
+	.byte	0
+
+	.section .debug_info,"",@progbits
+.Linfo:
+	.long	.Linfo_end - .Linfo - 4	# unit length
+	.value	4		# DWARF version
+	.long	.Labbrev	# abbreviation table offset
+	.byte	8		# address size
+	.uleb128 1		# DW_TAG_compile_unit
+	.string	"handwritten"
+	.byte	0xc		# DW_LANG_C99
+	.string	"test-loclist-range.c"
+	.quad	loclisted
+	.quad	.Lfunc_end - loclisted
+	.uleb128 2		# DW_TAG_subprogram
+	.string	"loclisted"
+	.uleb128 3		# DW_TAG_formal_parameter
+	.string	"x"
+	.long	.Lloc
+	.byte	0		# end of DW_TAG_subprogram children
+	.uleb128 2		# DW_TAG_subprogram
+	.string	"rangeless"
+	.byte	0		# end of DW_TAG_subprogram children
+	.byte	0		# end of DW_TAG_compile_unit children
+.Linfo_end:
+
+	.section .debug_loc,"",@progbits
+.Lloc:
+	# Entries are relative to the unit's base address.
+	.quad	0
+	.quad	.Lmid - loclisted
+	.value	1
+	.byte	0x55		# DW_OP_reg5 (rdi)
+	.quad	.Lmid - loclisted
+	.quad	.Lfunc_end - loclisted
+	.value	1
+	.byte	0x50		# DW_OP_reg0 (rax)
+	.quad	0
+	.quad	0
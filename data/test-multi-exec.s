@@ -0,0 +1,22 @@
+# Two functions placed into separate executable sections, which the
+# accompanying linker script maps into distinct executable `PT_LOAD`
+# segments. The first segment has a zero-initialized tail covering
+# the file offsets of the second one.
+	.section .text.first,"ax",@progbits
+	.globl	first
+	.type	first, @function
+first:
+	mov	$1, %eax
+	ret
+	.size	first, .-first
+
+	.section .bss.first,"aw",@nobits
+	.zero	0x4000
+
+	.section .text.second,"ax",@progbits
+	.globl	second
+	.type	second, @function
+second:
+	mov	$2, %eax
+	ret
+	.size	second, .-second
@@ -0,0 +1,21 @@
+/*
+ * A program making use of overlays: the functions in `.ovly0` and
+ * `.ovly1` are linked to the same virtual address range (see
+ * test-overlay.ld) and only one of them is supposed to be mapped at
+ * any given time.
+ */
+
+__attribute__((section(".ovly0"), noinline))
+int ovly0_fn(int x) {
+  return x * 2;
+}
+
+__attribute__((section(".ovly1"), noinline))
+int ovly1_fn(int x) {
+  return x + 42;
+}
+
+__attribute__((section(".text.main")))
+int main(int argc, const char *argv[]) {
+  return ovly0_fn(argc) + ovly1_fn(argc);
+}
@@ -0,0 +1,16 @@
+# A minimal assembly file with a DWARF line table marking the end of
+# a function's prologue, which GCC does not emit.
+	.file 1 "test-prologue.c"
+	.text
+	.globl	prologued
+	.type	prologued, @function
+prologued:
+	.loc 1 3 1
+	push	%rbp
+	mov	%rsp, %rbp
+	.loc 1 4 3 prologue_end
+	mov	$42, %eax
+	.loc 1 5 1
+	pop	%rbp
+	ret
+	.size	prologued, .-prologued
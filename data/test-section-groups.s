@@ -0,0 +1,18 @@
+# An object file containing two section groups: a COMDAT group
+# containing a function along with its data, as would be emitted for a
+# C++ inline function, and a non-COMDAT group.
+
+	.section .text.comdat_fn,"axG",@progbits,comdat_fn,comdat
+	.globl comdat_fn
+	.type comdat_fn,@function
+comdat_fn:
+	ret
+
+	.section .data.comdat_fn,"awG",@progbits,comdat_fn,comdat
+	.byte 42
+
+	.section .text.group_fn,"axG",@progbits,group_fn
+	.globl group_fn
+	.type group_fn,@function
+group_fn:
+	ret
@@ -0,0 +1,13 @@
+/*
+ * The second compilation unit of test-sections.bin, defining a `handler`
+ * function in `.text`.
+ */
+
+__attribute__((noinline))
+static int handler(int x) {
+  return x + 1;
+}
+
+int hot_path(int x) {
+  return handler(x);
+}
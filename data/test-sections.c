@@ -0,0 +1,14 @@
+/*
+ * A shared object defining two distinct functions named `handler`, one
+ * of which is placed in `.cold` (see test-sections-cu2.c for the other
+ * one).
+ */
+
+__attribute__((section(".cold"), noinline))
+static int handler(int x) {
+  return x - 1;
+}
+
+int cold_path(int x) {
+  return handler(x);
+}
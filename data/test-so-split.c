@@ -0,0 +1,10 @@
+// A shared object with a function that is only present in `.symtab`,
+// but not in `.dynsym`.
+
+__attribute__((noinline, visibility("hidden"))) int the_hidden_answer(void) {
+  return 42;
+}
+
+int the_answer(void) {
+  return the_hidden_answer();
+}
@@ -0,0 +1,15 @@
+# An object containing symbols with special section indices: an
+# absolute symbol (`SHN_ABS`) and a common one (`SHN_COMMON`), whose
+# value is its alignment and not an address.
+	.text
+	.globl	func
+	.type	func, @function
+func:
+	ret
+	.size	func, .-func
+
+	.globl	abs_sym
+	.type	abs_sym, @function
+	.set	abs_sym, 0x10
+
+	.comm	common_sym, 64, 32
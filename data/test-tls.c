@@ -0,0 +1,7 @@
+__thread int tls_counter = 42;
+__thread long tls_zeroed;
+
+int tls_increment(void) {
+  tls_zeroed += 1;
+  return ++tls_counter;
+}
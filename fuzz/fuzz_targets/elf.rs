@@ -0,0 +1,47 @@
+//! Fuzz ELF (and DWARF) parsing by symbolizing and inspecting
+//! arbitrary data.
+
+#![no_main]
+
+use std::io::Write as _;
+
+use blazesym::inspect;
+use blazesym::inspect::Inspector;
+use blazesym::symbolize;
+use blazesym::symbolize::Symbolizer;
+use blazesym::Addr;
+
+use libfuzzer_sys::fuzz_target;
+
+use tempfile::NamedTempFile;
+
+
+fuzz_target!(|data: &[u8]| {
+    let mut file = NamedTempFile::new().unwrap();
+    let () = file.write_all(data).unwrap();
+    let path = file.path();
+
+    // Use part of the input as addresses to look up, so that the fuzzer
+    // has a chance to steer lookups into parsed data structures.
+    let addrs = data
+        .chunks_exact(8)
+        .take(16)
+        .map(|chunk| Addr::from_ne_bytes(chunk.try_into().unwrap()))
+        .collect::<Vec<_>>();
+
+    for debug_syms in [false, true] {
+        let symbolizer = Symbolizer::builder()
+            .enable_debug_syms(debug_syms)
+            .build();
+        let src = symbolize::Source::from(symbolize::Elf::new(path));
+        let _result = symbolizer.symbolize(&src, symbolize::Input::VirtOffset(&addrs));
+        let _result = symbolizer.symbolize(&src, symbolize::Input::FileOffset(&addrs));
+
+        let mut elf = inspect::Elf::new(path);
+        elf.debug_info = debug_syms;
+        let src = inspect::Source::from(elf);
+        let inspector = Inspector::new();
+        let _result = inspector.lookup(&["main", "_start"], &src);
+        let _result = inspector.for_each(&src, (), |(), _sym| ());
+    }
+});
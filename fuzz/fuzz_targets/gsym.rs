@@ -0,0 +1,22 @@
+//! Fuzz Gsym parsing by symbolizing arbitrary data.
+
+#![no_main]
+
+use blazesym::symbolize;
+use blazesym::symbolize::Symbolizer;
+use blazesym::Addr;
+
+use libfuzzer_sys::fuzz_target;
+
+
+fuzz_target!(|data: &[u8]| {
+    let addrs = data
+        .chunks_exact(8)
+        .take(16)
+        .map(|chunk| Addr::from_ne_bytes(chunk.try_into().unwrap()))
+        .collect::<Vec<_>>();
+
+    let symbolizer = Symbolizer::new();
+    let src = symbolize::Source::from(symbolize::GsymData::new(data));
+    let _result = symbolizer.symbolize(&src, symbolize::Input::VirtOffset(&addrs));
+});
@@ -0,0 +1,223 @@
+use std::path::Path;
+
+use crate::elf::types::PT_LOAD;
+use crate::elf::types::PT_PHDR;
+use crate::elf::ElfParser;
+use crate::util::ReadRaw as _;
+use crate::Addr;
+use crate::IntoError as _;
+use crate::Result;
+
+
+/// End of the auxiliary vector.
+const AT_NULL: u64 = 0;
+/// Runtime address of the program headers of the executable.
+const AT_PHDR: u64 = 3;
+/// Runtime address of the entry point of the executable.
+const AT_ENTRY: u64 = 9;
+
+
+/// The auxiliary vector entries relevant to us.
+#[derive(Debug, Default, PartialEq)]
+struct Auxv {
+    phdr: Option<Addr>,
+    entry: Option<Addr>,
+}
+
+/// Parse an auxiliary vector, as found in `/proc/<pid>/auxv`.
+fn parse_auxv(mut data: &[u8]) -> Result<Auxv> {
+    let mut auxv = Auxv::default();
+    while !data.is_empty() {
+        let key = data
+            .read_u64()
+            .ok_or_invalid_data(|| "failed to read auxiliary vector entry type")?;
+        let value = data
+            .read_u64()
+            .ok_or_invalid_data(|| "failed to read auxiliary vector entry value")?;
+
+        match key {
+            AT_NULL => break,
+            AT_PHDR => auxv.phdr = Some(value),
+            AT_ENTRY => auxv.entry = Some(value),
+            _ => (),
+        }
+    }
+    Ok(auxv)
+}
+
+/// Find the virtual address of the program headers of the ELF file
+/// represented by `parser`.
+fn phdrs_vaddr(parser: &ElfParser) -> Result<Option<Addr>> {
+    let phdrs = parser.program_headers()?;
+    if let Some(phdr) = phdrs.iter().find(|phdr| phdr.p_type == PT_PHDR) {
+        return Ok(Some(phdr.p_vaddr))
+    }
+
+    // Without a `PT_PHDR` segment the program headers may still be
+    // mapped as part of a loadable segment.
+    let e_phoff = parser.elf_header()?.e_phoff;
+    let vaddr = phdrs.iter().find_map(|phdr| {
+        if phdr.p_type == PT_LOAD {
+            let offset = e_phoff.checked_sub(phdr.p_offset)?;
+            if offset < phdr.p_filesz {
+                return phdr.p_vaddr.checked_add(offset)
+            }
+        }
+        None
+    });
+    Ok(vaddr)
+}
+
+/// Calculate the load bias of the executable represented by `parser`,
+/// i.e., the difference between its runtime and its file virtual
+/// addresses.
+fn load_bias(auxv: &Auxv, parser: &ElfParser) -> Result<Option<Addr>> {
+    if let Some(phdr) = auxv.phdr {
+        if let Some(vaddr) = phdrs_vaddr(parser)? {
+            return Ok(phdr.checked_sub(vaddr))
+        }
+    }
+
+    if let Some(entry) = auxv.entry {
+        let e_entry = parser.elf_header()?.e_entry;
+        // An executable without entry point cannot have been started.
+        if e_entry != 0 {
+            return Ok(entry.checked_sub(e_entry))
+        }
+    }
+    Ok(None)
+}
+
+fn exe_load_addr_from_auxv_impl(auxv: &[u8], parser: &ElfParser) -> Result<Option<Addr>> {
+    let auxv = parse_auxv(auxv)?;
+    let bias = if let Some(bias) = load_bias(&auxv, parser)? {
+        bias
+    } else {
+        return Ok(None)
+    };
+    let load_addr = parser.load_base()?.and_then(|base| base.checked_add(bias));
+    Ok(load_addr)
+}
+
+
+/// Determine the load address of a process' main executable based on
+/// the process' auxiliary vector.
+///
+/// `auxv` is the raw auxiliary vector of the process, as can be read
+/// from `/proc/<pid>/auxv` (possibly at an earlier point in time or on
+/// a different system) and `exe` is the path to the process' main
+/// executable. The load bias is derived from the `AT_PHDR` entry or,
+/// if unavailable, the `AT_ENTRY` one.
+///
+/// The result is suitable for usage as the `load_addr` of
+/// [`AddressKind::Runtime`][crate::symbolize::AddressKind::Runtime].
+/// `None` is returned if the auxiliary vector does not contain the
+/// necessary information.
+///
+/// ```no_run
+/// # use std::fs::read;
+/// # use std::path::Path;
+/// let auxv = read("/proc/self/auxv").unwrap();
+/// let exe = Path::new("/proc/self/exe");
+/// let load_addr = blazesym::helper::exe_load_addr_from_auxv(&auxv, &exe).unwrap();
+/// ```
+pub fn exe_load_addr_from_auxv<P>(auxv: &[u8], exe: &P) -> Result<Option<Addr>>
+where
+    P: AsRef<Path>,
+{
+    let parser = ElfParser::open(exe.as_ref())?;
+    exe_load_addr_from_auxv_impl(auxv, &parser)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env::current_exe;
+    use std::fs::read;
+
+    use test_log::test;
+
+    use crate::ErrorKind;
+
+
+    fn make_auxv(entries: &[(u64, u64)]) -> Vec<u8> {
+        entries
+            .iter()
+            .flat_map(|(key, value)| key.to_ne_bytes().into_iter().chain(value.to_ne_bytes()))
+            .collect()
+    }
+
+
+    /// Check that we can parse an auxiliary vector.
+    #[test]
+    fn auxv_parsing() {
+        let data = make_auxv(&[(AT_ENTRY, 0x1000), (42, 1), (AT_PHDR, 0x40), (AT_NULL, 0)]);
+        let auxv = parse_auxv(&data).unwrap();
+        assert_eq!(
+            auxv,
+            Auxv {
+                phdr: Some(0x40),
+                entry: Some(0x1000),
+            }
+        );
+
+        // Nothing after `AT_NULL` should be considered.
+        let data = make_auxv(&[(AT_NULL, 0), (AT_PHDR, 0x40)]);
+        let auxv = parse_auxv(&data).unwrap();
+        assert_eq!(auxv, Auxv::default());
+
+        let err = parse_auxv(&data[..12]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    /// Check that we derive the correct load address from an auxiliary
+    /// vector.
+    #[test]
+    fn load_addr_derivation() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let parser = ElfParser::open(&path).unwrap();
+        let base = parser.load_base().unwrap().unwrap();
+        let e_entry = parser.elf_header().unwrap().e_entry;
+        let phdrs = phdrs_vaddr(&parser).unwrap().unwrap();
+
+        let bias = 0x7f0000000000;
+        let data = make_auxv(&[(AT_PHDR, bias + phdrs), (AT_NULL, 0)]);
+        let load_addr = exe_load_addr_from_auxv_impl(&data, &parser).unwrap();
+        assert_eq!(load_addr, Some(base + bias));
+
+        let data = make_auxv(&[(AT_ENTRY, bias + e_entry)]);
+        let load_addr = exe_load_addr_from_auxv_impl(&data, &parser).unwrap();
+        if e_entry != 0 {
+            assert_eq!(load_addr, Some(base + bias));
+        } else {
+            assert_eq!(load_addr, None);
+        }
+
+        let data = make_auxv(&[(42, 1337)]);
+        let load_addr = exe_load_addr_from_auxv_impl(&data, &parser).unwrap();
+        assert_eq!(load_addr, None);
+    }
+
+    /// Check that we can determine the load address of the running
+    /// process' main executable.
+    #[test]
+    fn own_load_addr() {
+        let auxv = read("/proc/self/auxv").unwrap();
+        let exe = current_exe().unwrap();
+        let load_addr = exe_load_addr_from_auxv(&auxv, &exe).unwrap().unwrap();
+
+        let maps = read("/proc/self/maps").unwrap();
+        let maps = String::from_utf8(maps).unwrap();
+        let first = maps
+            .lines()
+            .find(|line| line.ends_with(exe.to_str().unwrap()))
+            .unwrap();
+        let start = first.split('-').next().unwrap();
+        let start = Addr::from_str_radix(start, 16).unwrap();
+        assert_eq!(load_addr, start);
+    }
+}
@@ -0,0 +1,110 @@
+//! Functionality for demangling symbol names.
+//!
+//! The same demangling logic is used internally by the
+//! [`Symbolizer`][crate::symbolize::Symbolizer]. It is exposed here to
+//! make it usable on symbol names acquired by other means.
+//!
+//! ```
+//! use blazesym::demangle::demangle;
+//! use blazesym::symbolize::SrcLang;
+//!
+//! // With the `demangle` feature enabled this results in
+//! // `core::panicking::panic_fmt`.
+//! let _name = demangle("_ZN4core9panicking9panic_fmt17h5f1a6fd39197ad62E", SrcLang::Rust);
+//!
+//! // Names that do not look mangled are handed back unchanged.
+//! let name = demangle("main", SrcLang::Unknown);
+//! assert_eq!(name, "main");
+//! ```
+
+use std::borrow::Cow;
+
+use crate::symbolize::SrcLang;
+
+
+/// Demangle a symbol name using the demangling scheme for the given language.
+#[cfg(feature = "demangle")]
+pub(crate) fn maybe_demangle(name: Cow<'_, str>, language: SrcLang) -> Cow<'_, str> {
+    match language {
+        SrcLang::Rust => rustc_demangle::try_demangle(name.as_ref())
+            .ok()
+            .as_ref()
+            .map(|x| Cow::Owned(format!("{x:#}"))),
+        SrcLang::Cpp => cpp_demangle::Symbol::new(name.as_ref())
+            .ok()
+            .and_then(|x| x.demangle(&Default::default()).ok().map(Cow::Owned)),
+        // C does not mangle symbol names.
+        SrcLang::C => None,
+        SrcLang::Unknown => rustc_demangle::try_demangle(name.as_ref())
+            .map(|x| Cow::Owned(format!("{x:#}")))
+            .ok()
+            .or_else(|| {
+                cpp_demangle::Symbol::new(name.as_ref())
+                    .ok()
+                    .and_then(|sym| sym.demangle(&Default::default()).ok().map(Cow::Owned))
+            }),
+    }
+    .unwrap_or(name)
+}
+
+#[cfg(not(feature = "demangle"))]
+pub(crate) fn maybe_demangle(name: Cow<'_, str>, _language: SrcLang) -> Cow<'_, str> {
+    // Demangling is disabled.
+    name
+}
+
+
+/// Demangle the symbol `name` using the demangling scheme for the
+/// provided language.
+///
+/// For [`SrcLang::Unknown`], Rust demangling is attempted first,
+/// followed by C++ demangling. If `name` does not look mangled (or the
+/// `demangle` feature is disabled), it is returned unchanged.
+#[inline]
+pub fn demangle(name: &str, lang: SrcLang) -> Cow<'_, str> {
+    maybe_demangle(Cow::Borrowed(name), lang)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+
+    /// Make sure that we can demangle symbols.
+    #[test]
+    fn demangling() {
+        if !cfg!(feature = "demangle") {
+            return
+        }
+
+        let symbol = Cow::Borrowed("_ZN4core9panicking9panic_fmt17h5f1a6fd39197ad62E");
+        let name = maybe_demangle(symbol, SrcLang::Rust);
+        assert_eq!(name, "core::panicking::panic_fmt");
+
+        let symbol = Cow::Borrowed("_ZStlsISt11char_traitsIcEERSt13basic_ostreamIcT_ES5_PKc");
+        let name = maybe_demangle(symbol, SrcLang::Cpp);
+        assert_eq!(
+            name,
+            "std::basic_ostream<char, std::char_traits<char> >& std::operator<< <std::char_traits<char> >(std::basic_ostream<char, std::char_traits<char> >&, char const*)"
+        );
+
+        let name = demangle(
+            "_ZN4core9panicking9panic_fmt17h5f1a6fd39197ad62E",
+            SrcLang::Unknown,
+        );
+        assert_eq!(name, "core::panicking::panic_fmt");
+    }
+
+    /// Check that names that do not look mangled are passed through
+    /// unchanged.
+    #[test]
+    fn unmangled_passthrough() {
+        for lang in [SrcLang::C, SrcLang::Cpp, SrcLang::Rust, SrcLang::Unknown] {
+            let name = demangle("main", lang);
+            assert!(matches!(name, Cow::Borrowed("main")), "{name:?}");
+        }
+    }
+}
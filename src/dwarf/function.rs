@@ -39,6 +39,10 @@ use super::range::RangeAttributes;
 use super::reader::R;
 
 
+/// The maximum nesting depth of inlined functions that we support.
+const MAX_INLINE_DEPTH: usize = 256;
+
+
 fn name_entry<R>(
     unit: &gimli::Unit<R>,
     offset: gimli::UnitOffset<R::Offset>,
@@ -230,6 +234,108 @@ impl<'dwarf> InlinedFunctions<'dwarf> {
 }
 
 
+/// Parse all `DW_TAG_inlined_subroutine` DIEs of a unit.
+///
+/// Each inlined function is reported along with the address ranges it
+/// covers. Contrary to [`InlinedFunctions`], no sorting or other lookup
+/// related processing happens.
+pub(super) fn parse_unit_inlined_functions<'dwarf>(
+    unit: &gimli::Unit<R<'dwarf>>,
+    sections: &gimli::Dwarf<R<'dwarf>>,
+) -> Result<Vec<(InlinedFunction<'dwarf>, Vec<gimli::Range>)>, Error> {
+    let mut inlined_functions = Vec::new();
+    let mut inlined_addresses = Vec::new();
+    let mut entries = unit.entries_raw(None)?;
+    while !entries.is_empty() {
+        let depth = entries.next_depth();
+        if let Some(abbrev) = entries.read_abbreviation()? {
+            if abbrev.tag() == gimli::DW_TAG_inlined_subroutine {
+                // Note that this call consumes all nested inlined
+                // functions as well.
+                InlinedFunction::parse(
+                    &mut entries,
+                    abbrev,
+                    depth,
+                    unit,
+                    sections,
+                    &mut inlined_functions,
+                    &mut inlined_addresses,
+                    0,
+                )?;
+            } else {
+                entries.skip_attributes(abbrev.attributes())?;
+            }
+        }
+    }
+
+    // Ranges are pushed right after the function they belong to, so
+    // they are ordered by function index.
+    let mut inlined_addresses = inlined_addresses.into_iter().peekable();
+    let inlined = inlined_functions
+        .into_iter()
+        .enumerate()
+        .map(|(idx, inlined_fn)| {
+            let mut ranges = Vec::new();
+            while let Some(addr) = inlined_addresses.next_if(|addr| addr.function == idx) {
+                let () = ranges.push(addr.range);
+            }
+            (inlined_fn, ranges)
+        })
+        .collect();
+    Ok(inlined)
+}
+
+
+/// Infer the extent of the function described by the DIE at `offset`
+/// from the location lists of the variables and parameters it declares.
+///
+/// Variable locations are only described for addresses within their
+/// function, so the union of all location list entries approximates
+/// the function's extent. This is meant as a fallback for producers
+/// that omit the function's address range.
+fn location_list_range<'dwarf>(
+    offset: gimli::UnitOffset<<R<'dwarf> as gimli::Reader>::Offset>,
+    unit: &gimli::Unit<R<'dwarf>>,
+    sections: &gimli::Dwarf<R<'dwarf>>,
+) -> Result<Option<gimli::Range>, Error> {
+    let mut tree = unit.entries_tree(Some(offset))?;
+    let root = tree.root()?;
+    let mut children = root.children();
+    let mut extent = None::<gimli::Range>;
+    while let Some(child) = children.next()? {
+        let entry = child.entry();
+        if !matches!(
+            entry.tag(),
+            gimli::DW_TAG_formal_parameter | gimli::DW_TAG_variable
+        ) {
+            continue
+        }
+
+        let value = match entry.attr_value(gimli::DW_AT_location)? {
+            Some(value) => value,
+            None => continue,
+        };
+        if let Some(offset) = sections.attr_locations_offset(unit, value)? {
+            let mut locations = sections.locations(unit, offset)?;
+            while let Some(location) = locations.next()? {
+                let range = location.range;
+                if range.begin >= range.end {
+                    continue
+                }
+                extent = Some(match extent {
+                    Some(extent) => gimli::Range {
+                        begin: extent.begin.min(range.begin),
+                        end: extent.end.max(range.end),
+                    },
+                    None => range,
+                });
+            }
+        }
+    }
+    Ok(extent)
+}
+
+
 /// A single address range for a function.
 ///
 /// It is possible for a function to have multiple address ranges; this
@@ -237,7 +343,7 @@ impl<'dwarf> InlinedFunctions<'dwarf> {
 /// `function` field.
 #[derive(Debug)]
 pub(crate) struct FunctionAddress {
-    range: gimli::Range,
+    pub(crate) range: gimli::Range,
     /// An index into `Functions::functions`.
     pub(crate) function: usize,
 }
@@ -248,17 +354,50 @@ pub(crate) struct Function<'dwarf> {
     /// The function's name, if present.
     pub(crate) name: Option<R<'dwarf>>,
     /// The function's range (begin and end address).
+    ///
+    /// For functions with non-contiguous address ranges (e.g., as
+    /// produced by hot/cold splitting) this is the first range listed,
+    /// which conventionally contains the function's entry point.
     pub(crate) range: Option<gimli::Range>,
+    /// The index of the file the function is declared in
+    /// (`DW_AT_decl_file`), if present.
+    pub(crate) decl_file: Option<u64>,
+    /// The line the function is declared at (`DW_AT_decl_line`), if
+    /// present.
+    pub(crate) decl_line: Option<u32>,
     /// List of inlined function calls.
     pub(super) inlined_functions: OnceCell<InlinedFunctions<'dwarf>>,
 }
 
+/// Extract the file index from a `DW_AT_call_file` or `DW_AT_decl_file`
+/// attribute value.
+fn file_index(value: gimli::AttributeValue<R<'_>>, unit: &gimli::Unit<R<'_>>) -> Option<u64> {
+    // There is a spec issue [1] with how DW_AT_call_file is
+    // specified in DWARF 5. Before, a file index of 0 would
+    // indicate no source file, however in DWARF 5 this could
+    // be a valid index into the file table.
+    //
+    // Implementations such as LLVM generates a file index
+    // of 0 when DWARF 5 is used.
+    //
+    // Thus, if we see a version of 5 or later, treat a file
+    // index of 0 as such.
+    // [1]: http://wiki.dwarfstd.org/index.php?title=DWARF5_Line_Table_File_Numbers
+    match value {
+        gimli::AttributeValue::FileIndex(fi) if fi > 0 || unit.header.version() >= 5 => Some(fi),
+        _ => None,
+    }
+}
+
+
 impl Debug for Function<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         let Self {
             dw_die_offset,
             name,
             range,
+            decl_file,
+            decl_line,
             inlined_functions: _,
         } = self;
 
@@ -272,6 +411,8 @@ impl Debug for Function<'_> {
                 },
             )
             .field("range", range)
+            .field("decl_file", decl_file)
+            .field("decl_line", decl_line)
             .finish()
     }
 }
@@ -299,6 +440,9 @@ impl<'dwarf> Functions<'dwarf> {
                 if abbrev.tag() == gimli::DW_TAG_subprogram {
                     let mut name = None;
                     let mut ranges = RangeAttributes::default();
+                    let mut decl_file = None;
+                    let mut decl_line = None;
+                    let mut extentless = false;
                     for spec in abbrev.attributes() {
                         match entries.read_attribute(*spec) {
                             Ok(ref attr) => {
@@ -343,6 +487,17 @@ impl<'dwarf> Functions<'dwarf> {
                                         ranges.ranges_offset =
                                             sections.attr_ranges_offset(unit, attr.value())?;
                                     }
+                                    gimli::DW_AT_decl_file => {
+                                        decl_file = file_index(attr.value(), unit);
+                                    }
+                                    gimli::DW_AT_decl_line => {
+                                        decl_line = attr.udata_value().map(|line| line as u32);
+                                    }
+                                    gimli::DW_AT_declaration | gimli::DW_AT_inline => {
+                                        // Declarations and abstract instances
+                                        // do not occupy any code themselves.
+                                        extentless = true;
+                                    }
                                     _ => {}
                                 };
                             }
@@ -351,18 +506,43 @@ impl<'dwarf> Functions<'dwarf> {
                     }
 
                     let function_index = functions.len();
+                    let mut first_range = None;
                     let added = ranges.for_each_range(sections, unit, |range| {
+                        if first_range.is_none() {
+                            first_range = Some(range);
+                        }
                         addresses.push(FunctionAddress {
                             range,
                             function: function_index,
                         });
                     })?;
 
+                    let added = if !added && !extentless && abbrev.has_children() {
+                        // As a last resort, infer the function's
+                        // extent from the location lists of its
+                        // variables.
+                        match location_list_range(dw_die_offset, unit, sections)? {
+                            Some(range) => {
+                                first_range = Some(range);
+                                addresses.push(FunctionAddress {
+                                    range,
+                                    function: function_index,
+                                });
+                                true
+                            }
+                            None => false,
+                        }
+                    } else {
+                        added
+                    };
+
                     if added {
                         let function = Function {
                             dw_die_offset,
                             name,
-                            range: ranges.bounds(),
+                            range: ranges.bounds().or(first_range),
+                            decl_file,
+                            decl_line,
                             inlined_functions: OnceCell::new(),
                         };
                         functions.push(function);
@@ -437,6 +617,12 @@ impl<'dwarf> Function<'dwarf> {
                     gimli::DW_TAG_subprogram => {
                         Function::skip(entries, abbrev, next_depth)?;
                     }
+                    gimli::DW_TAG_inlined_subroutine if inlined_depth >= MAX_INLINE_DEPTH => {
+                        // Malformed data may nest inlined functions
+                        // arbitrarily deep. Ignore everything past our
+                        // limit so that we don't exhaust the stack.
+                        Function::skip(entries, abbrev, next_depth)?;
+                    }
                     gimli::DW_TAG_inlined_subroutine => {
                         InlinedFunction::parse(
                             entries,
@@ -537,22 +723,7 @@ impl<'dwarf> InlinedFunction<'dwarf> {
                         }
                     }
                     gimli::DW_AT_call_file => {
-                        // There is a spec issue [1] with how DW_AT_call_file is
-                        // specified in DWARF 5. Before, a file index of 0 would
-                        // indicate no source file, however in DWARF 5 this could
-                        // be a valid index into the file table.
-                        //
-                        // Implementations such as LLVM generates a file index
-                        // of 0 when DWARF 5 is used.
-                        //
-                        // Thus, if we see a version of 5 or later, treat a file
-                        // index of 0 as such.
-                        // [1]: http://wiki.dwarfstd.org/index.php?title=DWARF5_Line_Table_File_Numbers
-                        if let gimli::AttributeValue::FileIndex(fi) = attr.value() {
-                            if fi > 0 || unit.header.version() >= 5 {
-                                call_file = Some(fi);
-                            }
-                        }
+                        call_file = file_index(attr.value(), unit);
                     }
                     gimli::DW_AT_call_line => {
                         call_line = attr.udata_value().unwrap_or(0) as u32;
@@ -617,6 +788,8 @@ mod tests {
             dw_die_offset: gimli::UnitOffset(24),
             name: None,
             range: None,
+            decl_file: None,
+            decl_line: None,
             inlined_functions: OnceCell::new(),
         };
         assert_ne!(format!("{func:?}"), "");
@@ -32,6 +32,8 @@ use std::num::NonZeroU64;
 use std::os::unix::ffi::OsStrExt as _;
 use std::path::Path;
 
+use crate::symbolize::LineFlags;
+
 use super::reader::R;
 
 
@@ -50,7 +52,7 @@ fn render_file<'dwarf>(
     file: &gimli::FileEntry<R<'dwarf>, <R<'dwarf> as gimli::Reader>::Offset>,
     header: &gimli::LineProgramHeader<R<'dwarf>, <R<'dwarf> as gimli::Reader>::Offset>,
     sections: &gimli::Dwarf<R<'dwarf>>,
-) -> Result<(Cow<'dwarf, Path>, &'dwarf OsStr), gimli::Error> {
+) -> Result<LineFile<'dwarf>, gimli::Error> {
     let dir = if let Some(ref comp_dir) = dw_unit.comp_dir {
         Path::new(OsStr::from_bytes(comp_dir.slice()))
     } else {
@@ -69,12 +71,24 @@ fn render_file<'dwarf>(
         Cow::default()
     };
 
+    // The MD5 checksum is only present in DWARF 5 line tables and even
+    // there it is optional.
+    let md5 = if header.file_has_md5() {
+        Some(*file.md5())
+    } else {
+        None
+    };
     let f = sections.attr_string(dw_unit, file.path_name())?;
     let file = OsStr::from_bytes(f.slice());
-    Ok((dir, file))
+    Ok((dir, file, md5))
 }
 
 
+/// A file referenced by a line program: its directory, its name, and
+/// its MD5 checksum, if known.
+pub(crate) type LineFile<'dwarf> = (Cow<'dwarf, Path>, &'dwarf OsStr, Option<[u8; 16]>);
+
+
 pub(crate) struct LineSequence {
     pub(crate) start: u64,
     pub(crate) end: u64,
@@ -86,10 +100,11 @@ pub(crate) struct LineRow {
     pub(crate) file_index: u64,
     pub(crate) line: u32,
     pub(crate) column: u32,
+    pub(crate) flags: LineFlags,
 }
 
 pub(crate) struct Lines<'dwarf> {
-    pub(crate) files: Box<[(Cow<'dwarf, Path>, &'dwarf OsStr)]>,
+    pub(crate) files: Box<[LineFile<'dwarf>]>,
     pub(crate) sequences: Box<[LineSequence]>,
 }
 
@@ -124,12 +139,18 @@ impl<'dwarf> Lines<'dwarf> {
                 gimli::ColumnType::LeftEdge => 0,
                 gimli::ColumnType::Column(x) => x.get() as u32,
             };
+            let mut flags = LineFlags::empty();
+            let () = flags.set(LineFlags::IS_STMT, row.is_stmt());
+            let () = flags.set(LineFlags::BASIC_BLOCK, row.basic_block());
+            let () = flags.set(LineFlags::PROLOGUE_END, row.prologue_end());
+            let () = flags.set(LineFlags::EPILOGUE_BEGIN, row.epilogue_begin());
 
             if let Some(last_row) = sequence_rows.last_mut() {
                 if last_row.address == address {
                     last_row.file_index = file_index;
                     last_row.line = line;
                     last_row.column = column;
+                    last_row.flags = flags;
                     continue
                 }
             }
@@ -139,6 +160,7 @@ impl<'dwarf> Lines<'dwarf> {
                 file_index,
                 line,
                 column,
+                flags,
             });
         }
         sequences.sort_by_key(|x| x.start);
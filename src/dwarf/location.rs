@@ -29,6 +29,8 @@ use std::cmp::Ordering;
 use std::ffi::OsStr;
 use std::path::Path;
 
+use crate::symbolize::LineFlags;
+
 use super::lines::LineSequence;
 use super::lines::Lines;
 use super::reader::R;
@@ -46,6 +48,11 @@ pub struct Location<'dwarf> {
     pub line: Option<u32>,
     /// The column number.
     pub column: Option<u32>,
+    /// The MD5 checksum of the file, if available.
+    pub md5: Option<[u8; 16]>,
+    /// The flags of the line table row, if the location is based on
+    /// one.
+    pub flags: Option<LineFlags>,
 }
 
 
@@ -122,9 +129,15 @@ impl<'unit, 'dwarf> Iterator for LocationRangeUnitIter<'unit, 'dwarf> {
                         break
                     }
 
-                    // SANITY: We always have a file present for each
-                    //         `file_index`.
-                    let (dir, file) = self.lines.files.get(row.file_index as usize).unwrap();
+                    let (dir, file, md5) =
+                        if let Some(entry) = self.lines.files.get(row.file_index as usize) {
+                            entry
+                        } else {
+                            // A malformed line program may reference a file
+                            // that does not exist. Just skip the row.
+                            self.row_idx += 1;
+                            continue
+                        };
                     let nextaddr = seq
                         .rows
                         .get(self.row_idx + 1)
@@ -133,7 +146,7 @@ impl<'unit, 'dwarf> Iterator for LocationRangeUnitIter<'unit, 'dwarf> {
 
                     let item = (
                         row.address,
-                        nextaddr - row.address,
+                        nextaddr.saturating_sub(row.address),
                         Location {
                             dir,
                             file,
@@ -143,6 +156,8 @@ impl<'unit, 'dwarf> Iterator for LocationRangeUnitIter<'unit, 'dwarf> {
                             } else {
                                 None
                             },
+                            md5: *md5,
+                            flags: Some(row.flags),
                         },
                     );
                     self.row_idx += 1;
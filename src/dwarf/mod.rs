@@ -6,5 +6,6 @@ mod reader;
 mod resolver;
 mod unit;
 mod units;
+mod vars;
 
 pub(crate) use self::resolver::DwarfResolver;
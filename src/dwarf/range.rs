@@ -66,10 +66,9 @@ impl<R: gimli::Reader> RangeAttributes<R> {
         } else if let (Some(begin), Some(end)) = (self.low_pc, self.high_pc) {
             add_range(gimli::Range { begin, end });
         } else if let (Some(begin), Some(size)) = (self.low_pc, self.size) {
-            add_range(gimli::Range {
-                begin,
-                end: begin + size,
-            });
+            if let Some(end) = begin.checked_add(size) {
+                add_range(gimli::Range { begin, end });
+            }
         }
         Ok(added_any)
     }
@@ -80,7 +79,7 @@ impl<R: gimli::Reader> RangeAttributes<R> {
         } else if let (Some(begin), Some(size)) = (self.low_pc, self.size) {
             Some(gimli::Range {
                 begin,
-                end: begin + size,
+                end: begin.checked_add(size)?,
             })
         } else {
             None
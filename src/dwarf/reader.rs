@@ -16,9 +16,18 @@ pub(crate) type R<'dat> = EndianSlice<'dat, Endianess>;
 
 
 pub(super) fn load_section(parser: &ElfParser, id: SectionId) -> Result<R<'_>> {
-    let result = parser.find_section(id.name())?;
+    let name = id.name();
+    let mut result = parser.find_section(name)?;
+    if result.is_none() {
+        // Older toolchains may have emitted GNU style compressed
+        // sections, which are prefixed with `.zdebug_` instead.
+        if let Some(suffix) = name.strip_prefix(".debug_") {
+            result = parser.find_section(&format!(".zdebug_{suffix}"))?;
+        }
+    }
+
     let data = match result {
-        Some(idx) => parser.section_data(idx)?,
+        Some(idx) => parser.relocated_section_data(idx)?,
         // Make sure to return empty data if a section does not exist.
         None => &[],
     };
@@ -7,16 +7,19 @@ use std::fmt::Result as FmtResult;
 use std::mem;
 use std::mem::swap;
 use std::ops::Deref as _;
-#[cfg(test)]
+use std::ops::Range;
 use std::path::Path;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use gimli::Dwarf;
 
 use crate::elf::ElfParser;
 use crate::inspect::FindAddrOpts;
+use crate::inspect::InlineInstance;
 use crate::inspect::SymInfo;
 use crate::inspect::SymType;
+use crate::inspect::VarInfo;
 use crate::symbolize::AddrCodeInfo;
 use crate::symbolize::CodeInfo;
 use crate::symbolize::IntSym;
@@ -25,6 +28,7 @@ use crate::Addr;
 use crate::Error;
 use crate::Result;
 
+use super::function::Function;
 use super::location::Location;
 use super::reader;
 use super::units::Units;
@@ -34,6 +38,13 @@ impl From<Option<gimli::DwLang>> for SrcLang {
     fn from(other: Option<gimli::DwLang>) -> Self {
         match other {
             Some(gimli::DW_LANG_Rust) => SrcLang::Rust,
+            Some(
+                gimli::DW_LANG_C
+                | gimli::DW_LANG_C89
+                | gimli::DW_LANG_C99
+                | gimli::DW_LANG_C11
+                | gimli::DW_LANG_C17,
+            ) => SrcLang::C,
             Some(
                 gimli::DW_LANG_C_plus_plus
                 | gimli::DW_LANG_C_plus_plus_03
@@ -93,11 +104,15 @@ impl DwarfResolver {
 
     /// Find source code information of an address.
     ///
-    /// `addr` is a normalized address.
+    /// `addr` is a normalized address. If `line_only` is `true`, only
+    /// the source location as per the line table is reported and the
+    /// (comparably expensive) lookup of the containing function as well
+    /// as of inlined functions is skipped.
     pub fn find_code_info(
         &self,
         addr: Addr,
         inlined_fns: bool,
+        line_only: bool,
     ) -> Result<Option<AddrCodeInfo<'_>>> {
         // TODO: This conditional logic is weird and potentially
         //       unnecessary. Consider removing it or moving it higher
@@ -109,6 +124,8 @@ impl DwarfResolver {
                     file,
                     line,
                     column,
+                    md5,
+                    flags,
                 } = direct_location;
 
                 let mut direct_code_info = CodeInfo {
@@ -116,10 +133,14 @@ impl DwarfResolver {
                     file: Cow::Borrowed(file),
                     line,
                     column: column.map(|col| col.try_into().unwrap_or(u16::MAX)),
+                    md5,
+                    flags,
+                    context: None,
+                    file_exists: None,
                     _non_exhaustive: (),
                 };
 
-                let inlined = if inlined_fns {
+                let inlined = if inlined_fns && !line_only {
                     if let Some(inline_stack) = self.units.find_inlined_functions(addr)? {
                         let mut inlined = Vec::with_capacity(inline_stack.len());
                         for result in inline_stack {
@@ -130,6 +151,8 @@ impl DwarfResolver {
                                     file,
                                     line,
                                     column,
+                                    md5,
+                                    flags,
                                 } = location;
 
                                 CodeInfo {
@@ -137,6 +160,10 @@ impl DwarfResolver {
                                     file: Cow::Borrowed(file),
                                     line,
                                     column: column.map(|col| col.try_into().unwrap_or(u16::MAX)),
+                                    md5,
+                                    flags,
+                                    context: None,
+                                    file_exists: None,
                                     _non_exhaustive: (),
                                 }
                             });
@@ -175,23 +202,205 @@ impl DwarfResolver {
         Ok(code_info)
     }
 
+    /// Check whether the file contains information about inlined
+    /// functions.
+    pub(crate) fn has_inlined_fns(&self) -> Result<bool> {
+        let has_inlined = self.units.has_inlined()?;
+        Ok(has_inlined)
+    }
+
+    /// Iterate over all inlined function instances in the file.
+    ///
+    /// Compilation units are processed lazily, so that not all inline
+    /// information has to be kept in memory at the same time.
+    pub(crate) fn all_inlined(&self) -> impl Iterator<Item = Result<InlineInstance<'_>>> {
+        self.units.all_inlined().map(|result| {
+            let (name, ranges, call_site) = result?;
+            let call_site = call_site.map(|location| {
+                let Location {
+                    dir,
+                    file,
+                    line,
+                    column,
+                    md5,
+                    flags,
+                } = location;
+
+                CodeInfo {
+                    dir: Some(Cow::Borrowed(dir)),
+                    file: Cow::Borrowed(file),
+                    line,
+                    column: column.map(|col| col.try_into().unwrap_or(u16::MAX)),
+                    md5,
+                    flags,
+                    context: None,
+                    file_exists: None,
+                    _non_exhaustive: (),
+                }
+            });
+
+            let instance = InlineInstance {
+                name,
+                ranges: ranges
+                    .into_iter()
+                    .map(|range| range.begin..range.end)
+                    .collect(),
+                call_site,
+                _non_exhaustive: (),
+            };
+            Ok(instance)
+        })
+    }
+
+    /// Retrieve the parameters and local variables of the function
+    /// covering `addr` that are in scope at this address.
+    ///
+    /// Variables of lexical blocks not covering `addr` and of inlined
+    /// functions are not reported. Location expressions are reported
+    /// as-is and are left for the caller to evaluate.
+    pub(crate) fn locals(&self, addr: Addr) -> Result<Vec<VarInfo<'_>>> {
+        self.units
+            .find_vars(addr)?
+            .into_iter()
+            .map(|var| {
+                let name = var.name.map(|name| name.to_string()).transpose()?;
+                let info = VarInfo {
+                    name: name.map(Cow::Borrowed),
+                    type_name: var.type_name,
+                    location: var.location.map(|location| Cow::Borrowed(location.slice())),
+                    param: var.param,
+                    _non_exhaustive: (),
+                };
+                Ok(info)
+            })
+            .collect()
+    }
+
+    /// Find the addresses attributed to the given source code line.
+    ///
+    /// This is the inverse operation of
+    /// [`find_code_info`][Self::find_code_info]. If `file` does not match
+    /// the end of any source file path, source files are matched by base
+    /// name only. The returned addresses are sorted.
+    pub(crate) fn find_line(&self, file: &Path, line: u32) -> Result<Vec<Addr>> {
+        let addrs = self.units.find_line(file, line)?;
+        Ok(addrs)
+    }
+
+    /// Iterate over all line table rows in the file.
+    ///
+    /// Each row is reported with the address range it covers and its
+    /// source location. Rows are not reported in any particular order.
+    pub(crate) fn all_lines(&self) -> impl Iterator<Item = Result<(Range<Addr>, CodeInfo<'_>)>> {
+        self.units.all_locations().map(|result| {
+            let (addr, len, location) = result?;
+            let Location {
+                dir,
+                file,
+                line,
+                column,
+                md5,
+                flags,
+            } = location;
+
+            let code_info = CodeInfo {
+                dir: Some(Cow::Borrowed(dir)),
+                file: Cow::Borrowed(file),
+                line,
+                column: column.map(|col| col.try_into().unwrap_or(u16::MAX)),
+                md5,
+                flags,
+                context: None,
+                file_exists: None,
+                _non_exhaustive: (),
+            };
+            Ok((addr..addr.saturating_add(len), code_info))
+        })
+    }
+
+    /// Retrieve the start addresses of all line table rows overlapping
+    /// `range`.
+    ///
+    /// The first reported row may start before `range`, if it covers
+    /// its start.
+    pub(crate) fn line_addrs(&self, range: Range<Addr>) -> Result<Vec<Addr>> {
+        let addrs = self.units.find_line_addrs(range.start, range.end)?;
+        Ok(addrs)
+    }
+
+    /// Retrieve the source location of every line table row of the
+    /// function containing `addr`.
+    ///
+    /// For non-contiguous functions (e.g., as produced by hot/cold
+    /// splitting) rows of all parts are reported. Rows are sorted by
+    /// address. An empty list is returned if no function contains
+    /// `addr`.
+    pub(crate) fn function_lines(&self, addr: Addr) -> Result<Vec<(Addr, PathBuf, u32)>> {
+        let lines = self.units.find_function_lines(addr)?;
+        Ok(lines)
+    }
+
+    /// Retrieve the source location of the entry of the function
+    /// containing `addr`, i.e., the line it is defined at.
+    ///
+    /// `None` is returned if no function contains `addr` or its entry
+    /// is not attributed to any line.
+    pub(crate) fn function_decl_line(&self, addr: Addr) -> Result<Option<(PathBuf, u32)>> {
+        let decl = self.units.find_function_decl_line(addr)?;
+        Ok(decl)
+    }
+
+    /// Retrieve the address of the first line table row in `range`
+    /// that marks the end of a function prologue.
+    ///
+    /// `range` is meant to cover a single function. `None` is returned
+    /// if the line table does not mark the end of its prologue.
+    pub(crate) fn prologue_end(&self, range: Range<Addr>) -> Result<Option<Addr>> {
+        let addr = self.units.find_prologue_end(range.start, range.end)?;
+        Ok(addr)
+    }
+
+    /// Retrieve the paths of all source files referenced by the
+    /// compilation unit covering `addr`.
+    ///
+    /// Paths are resolved against the unit's compilation directory. An
+    /// empty list is returned if no unit covers the address.
+    pub(crate) fn source_files(&self, addr: Addr) -> Result<Vec<PathBuf>> {
+        let files = self.units.find_source_files(addr)?;
+        Ok(files)
+    }
+
+    /// Retrieve the name of the compilation unit covering `addr`.
+    ///
+    /// The name is the unit's primary source file (`DW_AT_name`), as
+    /// opposed to any of the files referenced by its line table. It is
+    /// resolved against the unit's compilation directory. `None` is
+    /// returned if no unit covers the address.
+    pub(crate) fn unit_name(&self, addr: Addr) -> Result<Option<PathBuf>> {
+        let name = self.units.find_unit_name(addr)?;
+        Ok(name)
+    }
+
     /// Lookup the symbol at an address.
     pub(crate) fn find_sym(&self, addr: Addr) -> Result<Option<IntSym<'_>>, Error> {
         let result = self.units.find_function(addr)?;
-        if let Some((function, language)) = result {
+        if let Some((function, range, language)) = result {
             let name = function
                 .name
                 .map(|name| name.to_string())
                 .transpose()?
                 .unwrap_or("");
-            let addr = function.range.map(|range| range.begin).unwrap_or(0);
-            let size = function
-                .range
-                .map(|range| usize::try_from(range.end - range.begin).unwrap_or(usize::MAX));
+            // We report the range covering `addr`, which may not be
+            // the function's primary one if the function is
+            // non-contiguous (e.g., because it got split into a hot and
+            // a cold part). That is in line with how ELF symbol tables
+            // represent such functions (with a separate `.cold`
+            // symbol) and makes sure that the reported symbol always
+            // contains `addr`.
             let sym = IntSym {
                 name,
-                addr,
-                size,
+                addr: range.begin,
+                size: Some(usize::try_from(range.end - range.begin).unwrap_or(usize::MAX)),
                 lang: language.into(),
             };
             Ok(Some(sym))
@@ -218,44 +427,76 @@ impl DwarfResolver {
         let syms = self
             .units
             .find_name(name)
-            .map(|result| {
-                match result {
-                    Ok(function) => {
-                        // SANITY: We found the function by name, so it must have the
-                        //         name attribute set.
-                        let name = function.name.unwrap().to_string().unwrap();
-                        let addr = function
-                            .range
-                            .as_ref()
-                            .map(|range| range.begin as Addr)
-                            .unwrap_or(0);
-                        let size = function
-                            .range
-                            .as_ref()
-                            .and_then(|range| range.end.checked_sub(range.begin))
-                            .map(|size| usize::try_from(size).unwrap_or(usize::MAX))
-                            .unwrap_or(0);
-                        let info = SymInfo {
-                            name: Cow::Borrowed(name),
-                            addr,
-                            size,
-                            sym_type: SymType::Function,
-                            file_offset: opts
-                                .offset_in_file
-                                .then(|| self.parser.find_file_offset(addr))
-                                .transpose()?
-                                .flatten(),
-                            obj_file_name: None,
-                        };
-                        Ok(info)
-                    }
-                    Err(err) => Err(Error::from(err)),
-                }
+            .map(|result| match result {
+                Ok(function) => self.function_sym_info(function, opts),
+                Err(err) => Err(Error::from(err)),
             })
             .collect::<Result<Vec<_>>>()?;
 
         Ok(syms)
     }
+
+    /// Find all functions starting within `range`, in no particular
+    /// order.
+    pub(crate) fn syms_in_range<'slf>(
+        &'slf self,
+        range: Range<Addr>,
+        opts: &FindAddrOpts,
+    ) -> Result<Vec<SymInfo<'slf>>> {
+        if let SymType::Variable = opts.sym_type {
+            return Err(Error::with_unsupported("not implemented"))
+        }
+
+        let syms = self
+            .units
+            .find_functions_in_range(range.start, range.end)?
+            .into_iter()
+            .map(|function| self.function_sym_info(function, opts))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(syms)
+    }
+
+    /// Convert a named function into a `SymInfo` object.
+    fn function_sym_info<'slf, 'dwarf: 'slf>(
+        &'slf self,
+        function: &Function<'dwarf>,
+        opts: &FindAddrOpts,
+    ) -> Result<SymInfo<'slf>> {
+        // SANITY: Callers only hand in functions that have the name
+        //         attribute set.
+        let name = function.name.unwrap().to_string()?;
+        let addr = function
+            .range
+            .as_ref()
+            .map(|range| range.begin as Addr)
+            .unwrap_or(0);
+        let size = function
+            .range
+            .as_ref()
+            .and_then(|range| range.end.checked_sub(range.begin))
+            .map(|size| usize::try_from(size).unwrap_or(usize::MAX))
+            .unwrap_or(0);
+        let end = function
+            .range
+            .as_ref()
+            .filter(|range| range.end > range.begin)
+            .map(|range| range.end as Addr);
+        let info = SymInfo {
+            name: Cow::Borrowed(name),
+            demangled_name: None,
+            addr,
+            size,
+            end,
+            sym_type: SymType::Function,
+            file_offset: opts
+                .offset_in_file
+                .then(|| self.parser.find_file_offset(addr))
+                .transpose()?
+                .flatten(),
+            obj_file_name: None,
+        };
+        Ok(info)
+    }
 }
 
 impl Debug for DwarfResolver {
@@ -294,13 +535,255 @@ mod tests {
             .join("test-stable-addresses.bin");
         let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
 
-        let info = resolver.find_code_info(0x2000100, true).unwrap().unwrap();
+        let info = resolver
+            .find_code_info(0x2000100, true, false)
+            .unwrap()
+            .unwrap();
         assert_ne!(info.direct.1.dir, Some(Cow::Owned(PathBuf::new())));
         assert_eq!(info.direct.1.file, OsStr::new("test-stable-addresses.c"));
         assert_eq!(info.direct.1.line, Some(8));
         assert!(info.direct.1.column.is_some());
     }
 
+    /// Check that we only report the source location itself when
+    /// asking for line information only.
+    #[test]
+    fn line_only_source_location_finding() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+
+        let info = resolver
+            .find_code_info(0x200020a, true, true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.direct.0, None);
+        assert_eq!(info.direct.1.file, OsStr::new("test-stable-addresses.c"));
+        // The location is that of the innermost inlined function, as
+        // per the line table.
+        assert_eq!(info.direct.1.line, Some(21));
+        assert!(info.inlined.is_empty(), "{:?}", info.inlined);
+
+        let full = resolver
+            .find_code_info(0x200020a, false, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(full.direct.1, info.direct.1);
+    }
+
+    /// Check that we report source file MD5 checksums from DWARF 5 line
+    /// tables, if present.
+    #[test]
+    fn source_file_md5() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-dwarf-v5-md5.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+
+        let info = resolver
+            .find_code_info(0x1001, false, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.direct.1.file, OsStr::new("test-dwarf-v5-md5.c"));
+        assert_eq!(info.direct.1.line, Some(4));
+        assert_eq!(
+            info.direct.1.md5,
+            Some([
+                0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xef, 0xcd, 0xab, 0x89, 0x67, 0x45,
+                0x23, 0x01
+            ])
+        );
+
+        // Line tables without checksums should just not report any.
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+        let info = resolver
+            .find_code_info(0x2000100, false, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.direct.1.md5, None);
+    }
+
+    /// Check that we can iterate over all inlined functions.
+    #[test]
+    fn all_inlined_functions() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-dwarf-only.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+
+        let inlined = resolver.all_inlined().collect::<Result<Vec<_>>>().unwrap();
+        let wrapper = inlined
+            .iter()
+            .find(|instance| instance.name == Some("factorial_inline_wrapper"))
+            .unwrap();
+        assert!(
+            wrapper
+                .ranges
+                .iter()
+                .any(|range| range.contains(&0x200020a)),
+            "{wrapper:#x?}"
+        );
+        let call_site = wrapper.call_site.as_ref().unwrap();
+        assert_eq!(call_site.file, OsStr::new("test-stable-addresses.c"));
+        assert_eq!(call_site.line, Some(32));
+
+        let wrapper = inlined
+            .iter()
+            .find(|instance| instance.name == Some("factorial_2nd_layer_inline_wrapper"))
+            .unwrap();
+        let call_site = wrapper.call_site.as_ref().unwrap();
+        assert_eq!(call_site.line, Some(26));
+    }
+
+    /// Check that we can list the source files of the unit covering an
+    /// address.
+    #[test]
+    fn unit_source_files() {
+        for binary in ["test-dwarf-v4.bin", "test-dwarf-v5.bin"] {
+            let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+                .join("data")
+                .join(binary);
+            let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+            let func = resolver
+                .units
+                .find_name("fibonacci")
+                .next()
+                .unwrap()
+                .unwrap();
+            let addr = func.range.as_ref().unwrap().begin;
+
+            let files = resolver.source_files(addr).unwrap();
+            let main = files
+                .iter()
+                .filter(|path| path.file_name() == Some(OsStr::new("test-exe.c")))
+                .collect::<Vec<_>>();
+            assert_eq!(main.len(), 1, "{binary}: {files:?}");
+            assert!(main[0].is_absolute(), "{binary}: {files:?}");
+            assert!(
+                files.iter().any(|path| path.ends_with("stdio.h")),
+                "{binary}: {files:?}"
+            );
+        }
+
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-dwarf-v5.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+        let files = resolver.source_files(0xffffffffffff0000).unwrap();
+        assert_eq!(files, Vec::<PathBuf>::new());
+    }
+
+    /// Check that we can work with zlib compressed debug sections, in
+    /// both the standard and the GNU format.
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn compressed_debug_sections() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-dwarf-v5.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+        let func = resolver
+            .units
+            .find_name("fibonacci")
+            .next()
+            .unwrap()
+            .unwrap();
+        let addr = func.range.as_ref().unwrap().begin;
+        let info = resolver
+            .find_code_info(addr, false, false)
+            .unwrap()
+            .unwrap();
+        let expected = (info.direct.1.file, info.direct.1.line);
+
+        for binary in ["test-dwarf-zlib.bin", "test-dwarf-zlib-gnu.bin"] {
+            let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+                .join("data")
+                .join(binary);
+            let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+            let func = resolver
+                .units
+                .find_name("fibonacci")
+                .next()
+                .unwrap()
+                .unwrap();
+            let addr = func.range.as_ref().unwrap().begin;
+            let info = resolver
+                .find_code_info(addr, false, false)
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                (info.direct.1.file, info.direct.1.line),
+                expected,
+                "{binary}"
+            );
+        }
+    }
+
+    /// Check that we can retrieve the name of the compilation unit
+    /// covering an address.
+    #[test]
+    fn unit_name_lookup() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-dwarf-v5.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+        let func = resolver
+            .units
+            .find_name("fibonacci")
+            .next()
+            .unwrap()
+            .unwrap();
+        let addr = func.range.as_ref().unwrap().begin;
+
+        let name = resolver.unit_name(addr).unwrap().unwrap();
+        assert!(name.is_absolute(), "{name:?}");
+        assert_eq!(name.file_name(), Some(OsStr::new("test-exe.c")));
+
+        let name = resolver.unit_name(0xffffffffffff0000).unwrap();
+        assert_eq!(name, None);
+    }
+
+    /// Check that we can map source code lines back to addresses.
+    #[test]
+    fn find_line_addrs() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-dwarf-only.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+
+        let addrs = resolver
+            .find_line(Path::new("test-stable-addresses.c"), 8)
+            .unwrap();
+        assert!(addrs.contains(&0x2000100), "{addrs:#x?}");
+        for addr in addrs {
+            let info = resolver
+                .find_code_info(addr, false, false)
+                .unwrap()
+                .unwrap();
+            assert_eq!(info.direct.1.line, Some(8));
+        }
+
+        // Paths not matching the recorded ones should still be found by
+        // their base name.
+        let addrs = resolver
+            .find_line(Path::new("/does/not/exist/test-stable-addresses.c"), 8)
+            .unwrap();
+        assert!(addrs.contains(&0x2000100), "{addrs:#x?}");
+
+        let addrs = resolver
+            .find_line(Path::new("test-stable-addresses.c"), 0)
+            .unwrap();
+        assert_eq!(addrs, Vec::<Addr>::new());
+        let addrs = resolver
+            .find_line(Path::new("does-not-exist.c"), 8)
+            .unwrap();
+        assert_eq!(addrs, Vec::<Addr>::new());
+    }
+
     /// Check that we can look up a symbol in DWARF debug information.
     #[test]
     fn lookup_symbol() {
@@ -310,6 +793,7 @@ mod tests {
         let opts = FindAddrOpts {
             offset_in_file: false,
             sym_type: SymType::Function,
+            ..Default::default()
         };
         let resolver = DwarfResolver::open(test_dwarf.as_ref(), true).unwrap();
 
@@ -330,10 +814,176 @@ mod tests {
         let opts = FindAddrOpts {
             offset_in_file: false,
             sym_type: SymType::Variable,
+            ..Default::default()
         };
         let resolver = DwarfResolver::open(test_dwarf.as_ref(), true).unwrap();
 
         let err = resolver.find_addr("factorial", &opts).unwrap_err();
         assert_eq!(err.kind(), ErrorKind::Unsupported);
     }
+
+    /// Check that we attribute addresses in all parts of a
+    /// non-contiguous function, as described by `DW_AT_ranges`, to said
+    /// function.
+    #[test]
+    fn non_contiguous_function() {
+        for binary in ["test-cold-dwarf-v4.bin", "test-cold-dwarf-v5.bin"] {
+            let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+                .join("data")
+                .join(binary);
+            let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+            let opts = FindAddrOpts::default();
+            let syms = resolver.parser.find_addr("hot_cold", &opts).unwrap();
+            let hot = &syms[0];
+            let syms = resolver.parser.find_addr("hot_cold.cold", &opts).unwrap();
+            let cold = &syms[0];
+
+            // Name based lookup should report the function's entry.
+            let syms = resolver.find_addr("hot_cold", &opts).unwrap();
+            assert_eq!(syms.len(), 1, "{binary}");
+            assert_eq!(syms[0].addr, hot.addr, "{binary}");
+
+            for part in [hot, cold] {
+                let last = part.addr + part.size as Addr - 1;
+                for addr in [part.addr, last] {
+                    let sym = resolver.find_sym(addr).unwrap().unwrap();
+                    assert_eq!(sym.name, "hot_cold", "{binary}");
+                    assert_eq!(sym.addr, part.addr, "{binary}");
+                    assert_eq!(sym.size, Some(part.size), "{binary}");
+
+                    let info = resolver
+                        .find_code_info(addr, false, false)
+                        .unwrap()
+                        .unwrap();
+                    assert_eq!(info.direct.1.file, OsStr::new("test-cold.c"), "{binary}");
+                }
+            }
+        }
+    }
+
+    /// Check that we can map all addresses of a non-contiguous function
+    /// to source lines.
+    #[test]
+    fn function_line_mapping() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-cold-dwarf-v5.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+        let opts = FindAddrOpts::default();
+        let syms = resolver.parser.find_addr("hot_cold", &opts).unwrap();
+        let hot = &syms[0];
+        let syms = resolver.parser.find_addr("hot_cold.cold", &opts).unwrap();
+        let cold = &syms[0];
+
+        let lines = resolver.function_lines(hot.addr).unwrap();
+        assert!(lines.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        for (addr, path, line) in &lines {
+            assert!(path.ends_with("test-cold.c"), "{path:?}");
+            assert_ne!(*line, 0);
+            let sym = resolver.find_sym(*addr).unwrap().unwrap();
+            assert_eq!(sym.name, "hot_cold");
+        }
+
+        for part in [hot, cold] {
+            let range = part.addr..part.addr + part.size as Addr;
+            assert!(
+                lines.iter().any(|(addr, ..)| range.contains(addr)),
+                "{range:#x?}"
+            );
+        }
+
+        // The result should be the same no matter which part of the
+        // function we start with.
+        assert_eq!(resolver.function_lines(cold.addr).unwrap(), lines);
+        assert_eq!(resolver.function_lines(0).unwrap(), Vec::new());
+    }
+
+    /// Check that we can look up the line a function is defined at.
+    #[test]
+    fn function_decl_line_lookup() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+        let opts = FindAddrOpts::default();
+
+        for (name, line) in [("factorial", 8), ("main", 50)] {
+            let syms = resolver.parser.find_addr(name, &opts).unwrap();
+            let sym = &syms[0];
+            // Any address within the function should do.
+            let addr = sym.addr + sym.size as Addr / 2;
+            let (path, decl_line) = resolver.function_decl_line(addr).unwrap().unwrap();
+            assert!(path.ends_with("test-stable-addresses.c"), "{path:?}");
+            assert_eq!(decl_line, line);
+
+            // The line table should agree with the declaration.
+            let loc = resolver.units.find_location(sym.addr).unwrap().unwrap();
+            assert_eq!(loc.line, Some(line));
+        }
+
+        assert_eq!(resolver.function_decl_line(0).unwrap(), None);
+    }
+
+    /// Check that we infer the extent of a function lacking address
+    /// range attributes from the location lists of its parameters.
+    #[test]
+    fn location_list_function_range() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-loclist-range.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+        let opts = FindAddrOpts::default();
+        let syms = resolver.parser.find_addr("loclisted", &opts).unwrap();
+        let func = &syms[0];
+
+        let last = func.addr + func.size as Addr - 1;
+        for addr in [func.addr, last] {
+            let sym = resolver.find_sym(addr).unwrap().unwrap();
+            assert_eq!(sym.name, "loclisted");
+            assert_eq!(sym.addr, func.addr);
+            assert_eq!(sym.size, Some(func.size));
+        }
+        assert!(resolver.find_sym(last + 1).unwrap().is_none());
+
+        // A function without any hint as to its extent is not reported.
+        let syms = resolver.find_addr("rangeless", &opts).unwrap();
+        assert_eq!(syms, Vec::new());
+    }
+
+    /// Check that we can retrieve the parameters of a function along
+    /// with their types and locations.
+    #[test]
+    fn local_variables() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let resolver = DwarfResolver::open(bin_name.as_ref(), true).unwrap();
+
+        // `main`.
+        let vars = resolver.locals(0x2000004).unwrap();
+        assert_eq!(vars.len(), 2, "{vars:?}");
+        assert_eq!(vars[0].name.as_deref(), Some("argc"));
+        assert_eq!(vars[0].type_name.as_deref(), Some("int"));
+        assert!(vars[0].param);
+        // Variables of an unoptimized build live on the stack and are
+        // addressed relative to the frame base.
+        assert_eq!(
+            vars[0].location.as_deref().unwrap().first(),
+            Some(&gimli::DW_OP_fbreg.0)
+        );
+        assert_eq!(vars[1].name.as_deref(), Some("argv"));
+        assert_eq!(vars[1].type_name.as_deref(), Some("const char **"));
+        assert!(vars[1].param);
+        assert!(vars[1].location.is_some());
+
+        // `factorial`.
+        let vars = resolver.locals(0x2000100).unwrap();
+        assert_eq!(vars.len(), 1, "{vars:?}");
+        assert_eq!(vars[0].name.as_deref(), Some("n"));
+        assert_eq!(vars[0].type_name.as_deref(), Some("unsigned int"));
+
+        // No function covers the address.
+        let vars = resolver.locals(0xffffffff).unwrap();
+        assert!(vars.is_empty(), "{vars:?}");
+    }
 }
@@ -35,6 +35,7 @@ use super::location::LocationRangeUnitIter;
 use super::reader::R;
 
 
+#[derive(Clone)]
 pub(super) struct UnitRange {
     pub unit_id: usize,
     pub max_end: u64,
@@ -63,8 +64,6 @@ impl<'dwarf> Unit<'dwarf> {
         }
     }
 
-    #[cfg(test)]
-    #[cfg(feature = "nightly")]
     pub(super) fn parse_functions<'unit>(
         &'unit self,
         sections: &gimli::Dwarf<R<'dwarf>>,
@@ -128,24 +127,54 @@ impl<'dwarf> Unit<'dwarf> {
             .get_or_try_init(|| Functions::parse(unit, sections))
     }
 
+    /// Find the function containing `probe`, along with the address
+    /// range of the function that covers it.
     pub(super) fn find_function(
         &self,
         probe: u64,
         sections: &gimli::Dwarf<R<'dwarf>>,
-    ) -> Result<Option<&Function<'dwarf>>, gimli::Error> {
+    ) -> Result<Option<(&Function<'dwarf>, gimli::Range)>, gimli::Error> {
         let unit = &self.dw_unit;
         let functions = self.parse_functions_dwarf_and_unit(unit, sections)?;
         let function = match functions.find_address(probe) {
             Some(address) => {
-                let function_index = functions.addresses[address].function;
-                let function = &functions.functions[function_index];
-                Some(function)
+                let address = &functions.addresses[address];
+                let function = &functions.functions[address.function];
+                Some((function, address.range))
             }
             None => None,
         };
         Ok(function)
     }
 
+    /// Find all address ranges of the function containing `probe`,
+    /// sorted by start address.
+    ///
+    /// An empty list is returned if no function contains `probe`.
+    pub(super) fn find_function_ranges(
+        &self,
+        probe: u64,
+        sections: &gimli::Dwarf<R<'dwarf>>,
+    ) -> Result<Vec<gimli::Range>, gimli::Error> {
+        let unit = &self.dw_unit;
+        let functions = self.parse_functions_dwarf_and_unit(unit, sections)?;
+        let ranges = match functions.find_address(probe) {
+            Some(address) => {
+                let function = functions.addresses[address].function;
+                let mut ranges = functions
+                    .addresses
+                    .iter()
+                    .filter(|address| address.function == function)
+                    .map(|address| address.range)
+                    .collect::<Vec<_>>();
+                let () = ranges.sort_by_key(|range| range.begin);
+                ranges
+            }
+            None => Vec::new(),
+        };
+        Ok(ranges)
+    }
+
     pub(super) fn find_name<'slf>(
         &'slf self,
         name: &str,
@@ -25,18 +25,29 @@
 // > IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // > DEALINGS IN THE SOFTWARE.
 
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt as _;
+use std::path::Path;
+use std::path::PathBuf;
+
 use crate::log::warn;
 use crate::once::OnceCell;
+use crate::symbolize::LineFlags;
 use crate::ErrorExt as _;
 use crate::Result;
 
+use super::function::parse_unit_inlined_functions;
 use super::function::Function;
 use super::lines::Lines;
 use super::location::Location;
+use super::location::LocationRangeUnitIter;
 use super::range::RangeAttributes;
 use super::reader::R;
 use super::unit::Unit;
 use super::unit::UnitRange;
+use super::vars::parse_vars;
+use super::vars::Variable;
 
 
 fn format_offset(offset: gimli::UnitSectionOffset<usize>) -> String {
@@ -51,28 +62,132 @@ fn format_offset(offset: gimli::UnitSectionOffset<usize>) -> String {
 }
 
 
+/// Parse all address ranges contained in `.debug_aranges`, along with
+/// the offset of the unit they belong to.
+///
+/// The result is sorted by unit offset.
+fn parse_aranges(
+    sections: &gimli::Dwarf<R<'_>>,
+) -> Result<Vec<(gimli::DebugInfoOffset<usize>, gimli::Range)>, gimli::Error> {
+    let mut aranges = Vec::new();
+    let mut headers = sections.debug_aranges.headers();
+    while let Some(header) = headers.next()? {
+        let mut entries = header.entries();
+        while let Some(arange) = entries.next()? {
+            if arange.length() != 0 {
+                let () = aranges.push((header.debug_info_offset(), arange.range()));
+            }
+        }
+    }
+    let () = aranges.sort_by_key(|(offset, _range)| *offset);
+    Ok(aranges)
+}
+
+
+/// Sort the provided unit ranges by start address and calculate their
+/// `max_end` attributes.
+fn sort_unit_ranges(unit_ranges: &mut [UnitRange]) {
+    let () = unit_ranges.sort_by_key(|i| i.range.begin);
+
+    let mut max = 0;
+    for i in unit_ranges.iter_mut() {
+        max = max.max(i.range.end);
+        i.max_end = max;
+    }
+}
+
+
+/// Find the unit ranges in `unit_ranges` (which have to be sorted) that
+/// overlap with the range of addresses given.
+///
+/// The range is [low, high) (ie, the upper bound is exclusive).
+fn find_unit_ranges(
+    unit_ranges: &[UnitRange],
+    probe_low: u64,
+    probe_high: u64,
+) -> impl Iterator<Item = &UnitRange> {
+    // First up find the position in the array which could have our function
+    // address.
+    let pos = match unit_ranges.binary_search_by_key(&probe_high, |i| i.range.begin) {
+        // Although unlikely, we could find an exact match.
+        Ok(i) => i + 1,
+        // No exact match was found, but this probe would fit at slot `i`.
+        // This means that slot `i` is bigger than `probe`, along with all
+        // indices greater than `i`, so we need to search all previous
+        // entries.
+        Err(i) => i,
+    };
+
+    // Once we have our index we iterate backwards from that position
+    // looking for a matching CU.
+    unit_ranges[..pos]
+        .iter()
+        .rev()
+        .take_while(move |i| {
+            // We know that this CU's start is beneath the probe already because
+            // of our sorted array.
+            debug_assert!(i.range.begin <= probe_high);
+
+            // Each entry keeps track of the maximum end address seen so far,
+            // starting from the beginning of the array of unit ranges. We're
+            // iterating in reverse so if our probe is beyond the maximum range
+            // of this entry, then it's guaranteed to not fit in any prior
+            // entries, so we break out.
+            probe_low < i.max_end
+        })
+        .filter(move |i| {
+            // If this CU doesn't actually contain this address, move to the
+            // next CU.
+            probe_low < i.range.end && probe_high > i.range.begin
+        })
+}
+
+
+/// An index from source file base names to the units and file table
+/// indices referencing them.
+type FileIndex<'dwarf> = HashMap<&'dwarf OsStr, Vec<(usize, u64)>>;
+
+
+/// An inlined function instance: its name, the address ranges it
+/// covers, and the location of its call site.
+type UnitInlineInstance<'dwarf, 'unit> = (
+    Option<&'dwarf str>,
+    Vec<gimli::Range>,
+    Option<Location<'unit>>,
+);
+
+
 pub(crate) struct Units<'dwarf> {
     /// The DWARF data.
     dwarf: gimli::Dwarf<R<'dwarf>>,
     /// The ranges of the units encountered.
     unit_ranges: Box<[UnitRange]>,
+    /// The unit ranges as reported by `.debug_aranges`, if any.
+    ///
+    /// These are consulted first when looking up the units for an
+    /// address.
+    arange_ranges: Box<[UnitRange]>,
     /// All units along with meta-data.
     units: Box<[Unit<'dwarf>]>,
+    /// The lazily created index of source files, used for mapping
+    /// source locations back to addresses.
+    file_index: OnceCell<FileIndex<'dwarf>>,
 }
 
 impl<'dwarf> Units<'dwarf> {
     pub(crate) fn parse(sections: gimli::Dwarf<R<'dwarf>>) -> Result<Self> {
-        // Find all the references to compilation units in .debug_aranges.
+        // Find all the compilation unit ranges in .debug_aranges.
         // Note that we always also iterate through all of .debug_info to
         // find compilation units, because .debug_aranges may be missing some.
-        let mut aranges = Vec::new();
-        let mut headers = sections.debug_aranges.headers();
-        while let Some(header) = headers.next()? {
-            aranges.push((header.debug_info_offset(), header.offset()));
-        }
-        aranges.sort_by_key(|i| i.0);
+        // The section is purely an optimization and so we ignore it if
+        // it is malformed.
+        let aranges = parse_aranges(&sections).unwrap_or_else(|err| {
+            warn!("failed to parse .debug_aranges section; ignoring: {err}");
+            Vec::new()
+        });
 
         let mut unit_ranges = Vec::new();
+        let mut arange_ranges = Vec::new();
         let mut res_units = Vec::new();
         let mut units = sections.units();
         while let Some(header) = units.next()? {
@@ -148,31 +263,25 @@ impl<'dwarf> Units<'dwarf> {
                 // .debug_aranges must be used before DW_AT_low_pc/DW_AT_high_pc because
                 // it has been observed on macOS that DW_AT_ranges was not emitted even for
                 // discontiguous CUs.
-                let i = match ranges.ranges_offset {
-                    Some(_) => None,
-                    None => aranges.binary_search_by_key(&offset, |x| x.0).ok(),
-                };
-                if let Some(mut i) = i {
-                    // There should be only one set per CU, but in practice multiple
-                    // sets have been observed. This is probably a compiler bug, but
-                    // either way we need to handle it.
-                    while i > 0 && aranges[i - 1].0 == offset {
-                        i -= 1;
-                    }
-                    for (_, aranges_offset) in aranges[i..].iter().take_while(|x| x.0 == offset) {
-                        let aranges_header = sections.debug_aranges.header(*aranges_offset)?;
-                        let mut aranges = aranges_header.entries();
-                        while let Some(arange) = aranges.next()? {
-                            if arange.length() != 0 {
-                                unit_ranges.push(UnitRange {
-                                    range: arange.range(),
-                                    unit_id,
-                                    max_end: 0,
-                                });
-                                have_unit_range = true;
-                            }
-                        }
-                    }
+                //
+                // There should be only one set per CU, but in practice multiple
+                // sets have been observed. This is probably a compiler bug, but
+                // either way we need to handle it.
+                let start =
+                    aranges.partition_point(|(arange_offset, _range)| *arange_offset < offset);
+                let unit_aranges = aranges[start..]
+                    .iter()
+                    .take_while(|(arange_offset, _range)| *arange_offset == offset)
+                    .map(|(_offset, range)| UnitRange {
+                        range: *range,
+                        unit_id,
+                        max_end: 0,
+                    })
+                    .collect::<Vec<_>>();
+
+                if ranges.ranges_offset.is_none() && !unit_aranges.is_empty() {
+                    let () = unit_ranges.extend(unit_aranges.iter().cloned());
+                    have_unit_range = true;
                 } else {
                     have_unit_range |= ranges.for_each_range(&sections, &dw_unit, |range| {
                         unit_ranges.push(UnitRange {
@@ -182,6 +291,7 @@ impl<'dwarf> Units<'dwarf> {
                         });
                     })?;
                 }
+                let () = arange_ranges.extend(unit_aranges);
             }
 
             let lines = OnceCell::new();
@@ -209,21 +319,16 @@ impl<'dwarf> Units<'dwarf> {
             res_units.push(Unit::new(dw_unit, lang, lines))
         }
 
-        // Sort this for faster lookups.
-        unit_ranges.sort_by_key(|i| i.range.begin);
-
-        // Calculate the `max_end` field now that we've determined the order of
-        // CUs.
-        let mut max = 0;
-        for i in unit_ranges.iter_mut() {
-            max = max.max(i.range.end);
-            i.max_end = max;
-        }
+        // Sort these for faster lookups.
+        let () = sort_unit_ranges(&mut unit_ranges);
+        let () = sort_unit_ranges(&mut arange_ranges);
 
         let slf = Self {
             dwarf: sections,
             unit_ranges: unit_ranges.into_boxed_slice(),
+            arange_ranges: arange_ranges.into_boxed_slice(),
             units: res_units.into_boxed_slice(),
+            file_index: OnceCell::new(),
         };
         Ok(slf)
     }
@@ -238,75 +343,53 @@ impl<'dwarf> Units<'dwarf> {
     /// Consequently we return an iterator for all CUs which may contain the
     /// address, and the caller must check if there is actually a function or
     /// location in the CU for that address.
-    fn find_units(&self, probe: u64) -> impl Iterator<Item = &Unit<'dwarf>> {
-        self.find_units_range(probe, probe + 1)
-            .map(|(unit, _range)| unit)
-    }
-
-    /// Finds the CUs covering the range of addresses given.
     ///
-    /// The range is [low, high) (ie, the upper bound is exclusive). This can return multiple
-    /// ranges for the same unit.
-    #[inline]
-    fn find_units_range(
-        &self,
-        probe_low: u64,
-        probe_high: u64,
-    ) -> impl Iterator<Item = (&Unit<'dwarf>, &gimli::Range)> {
-        // First up find the position in the array which could have our function
-        // address.
-        let pos = match self
-            .unit_ranges
-            .binary_search_by_key(&probe_high, |i| i.range.begin)
-        {
-            // Although unlikely, we could find an exact match.
-            Ok(i) => i + 1,
-            // No exact match was found, but this probe would fit at slot `i`.
-            // This means that slot `i` is bigger than `probe`, along with all
-            // indices greater than `i`, so we need to search all previous
-            // entries.
-            Err(i) => i,
-        };
+    /// Units covering the address according to `.debug_aranges` are
+    /// reported first, followed by any other candidates.
+    fn find_units(&self, probe: u64) -> impl Iterator<Item = &Unit<'dwarf>> {
+        let probe_high = probe.saturating_add(1);
+        let aranged = find_unit_ranges(&self.arange_ranges, probe, probe_high);
+        let other = find_unit_ranges(&self.unit_ranges, probe, probe_high).filter(move |i| {
+            find_unit_ranges(&self.arange_ranges, probe, probe_high)
+                .all(|arange| arange.unit_id != i.unit_id)
+        });
 
-        // Once we have our index we iterate backwards from that position
-        // looking for a matching CU.
-        self.unit_ranges[..pos]
-            .iter()
-            .rev()
-            .take_while(move |i| {
-                // We know that this CU's start is beneath the probe already because
-                // of our sorted array.
-                debug_assert!(i.range.begin <= probe_high);
-
-                // Each entry keeps track of the maximum end address seen so far,
-                // starting from the beginning of the array of unit ranges. We're
-                // iterating in reverse so if our probe is beyond the maximum range
-                // of this entry, then it's guaranteed to not fit in any prior
-                // entries, so we break out.
-                probe_low < i.max_end
-            })
-            .filter_map(move |i| {
-                // If this CU doesn't actually contain this address, move to the
-                // next CU.
-                if probe_low >= i.range.end || probe_high <= i.range.begin {
-                    return None
-                }
-                Some((&self.units[i.unit_id], &i.range))
-            })
+        aranged.chain(other).map(|i| &self.units[i.unit_id])
     }
 
+    /// Find the function containing `probe`.
+    ///
+    /// Besides the function itself, the address range of the function
+    /// covering `probe` is reported, which for functions with
+    /// non-contiguous ranges is not necessarily the function's primary
+    /// one.
+    #[allow(clippy::type_complexity)]
     pub fn find_function(
         &self,
         probe: u64,
-    ) -> Result<Option<(&Function<'dwarf>, Option<gimli::DwLang>)>, gimli::Error> {
+    ) -> Result<Option<(&Function<'dwarf>, gimli::Range, Option<gimli::DwLang>)>, gimli::Error>
+    {
         for unit in self.find_units(probe) {
-            if let Some(function) = unit.find_function(probe, &self.dwarf)? {
-                return Ok(Some((function, unit.language())))
+            if let Some((function, range)) = unit.find_function(probe, &self.dwarf)? {
+                return Ok(Some((function, range, unit.language())))
             }
         }
         Ok(None)
     }
 
+    /// Find the parameters and local variables of the function
+    /// containing `probe` that are in scope at this address.
+    ///
+    /// An empty list is returned if no function contains `probe`.
+    pub fn find_vars(&self, probe: u64) -> Result<Vec<Variable<'dwarf>>, gimli::Error> {
+        for unit in self.find_units(probe) {
+            if let Some((function, _range)) = unit.find_function(probe, &self.dwarf)? {
+                return parse_vars(function.dw_die_offset, unit.dw_unit(), &self.dwarf, probe)
+            }
+        }
+        Ok(Vec::new())
+    }
+
     /// Find the list of inlined functions that contain `probe`.
     pub fn find_inlined_functions<'slf>(
         &'slf self,
@@ -320,7 +403,7 @@ impl<'dwarf> Units<'dwarf> {
         gimli::Error,
     > {
         for unit in self.find_units(probe) {
-            if let Some(function) = unit.find_function(probe, &self.dwarf)? {
+            if let Some((function, _range)) = unit.find_function(probe, &self.dwarf)? {
                 let inlined_fns = function.parse_inlined_functions(unit.dw_unit(), &self.dwarf)?;
                 let iter = inlined_fns.find_inlined_functions(probe).map(|inlined_fn| {
                     let name = inlined_fn
@@ -331,12 +414,14 @@ impl<'dwarf> Units<'dwarf> {
 
                     let code_info = if let Some(call_file) = inlined_fn.call_file {
                         if let Some(lines) = unit.parse_lines(&self.dwarf)? {
-                            if let Some((dir, file)) = lines.files.get(call_file as usize) {
+                            if let Some((dir, file, md5)) = lines.files.get(call_file as usize) {
                                 let code_info = Location {
                                     dir,
                                     file,
                                     line: Some(inlined_fn.call_line),
                                     column: Some(inlined_fn.call_column),
+                                    md5: *md5,
+                                    flags: None,
                                 };
                                 Some(code_info)
                             } else {
@@ -359,6 +444,83 @@ impl<'dwarf> Units<'dwarf> {
         Ok(None)
     }
 
+    /// Iterate over all inlined function instances in all units.
+    ///
+    /// Units are processed lazily, one at a time, and the inlined
+    /// functions of a unit are not retained after they have been
+    /// reported. Note, though, that the line information of units,
+    /// which is consulted for call site locations, stays cached.
+    pub fn all_inlined<'slf>(
+        &'slf self,
+    ) -> impl Iterator<Item = Result<UnitInlineInstance<'dwarf, 'slf>, gimli::Error>> + 'slf {
+        self.units.iter().flat_map(move |unit| {
+            let (inlined, err) = match self.unit_inlined(unit) {
+                Ok(inlined) => (inlined, None),
+                Err(err) => (Vec::new(), Some(Err(err))),
+            };
+            inlined.into_iter().map(Ok).chain(err)
+        })
+    }
+
+    /// Check whether any unit contains `DW_TAG_inlined_subroutine`
+    /// DIEs.
+    ///
+    /// Units are scanned only up to the first such DIE.
+    pub fn has_inlined(&self) -> Result<bool, gimli::Error> {
+        for unit in self.units.iter() {
+            let mut entries = unit.dw_unit().entries_raw(None)?;
+            while !entries.is_empty() {
+                if let Some(abbrev) = entries.read_abbreviation()? {
+                    if abbrev.tag() == gimli::DW_TAG_inlined_subroutine {
+                        return Ok(true)
+                    }
+                    let () = entries.skip_attributes(abbrev.attributes())?;
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    fn unit_inlined<'slf>(
+        &'slf self,
+        unit: &'slf Unit<'dwarf>,
+    ) -> Result<Vec<UnitInlineInstance<'dwarf, 'slf>>, gimli::Error> {
+        let inlined_fns = parse_unit_inlined_functions(unit.dw_unit(), &self.dwarf)?;
+        inlined_fns
+            .into_iter()
+            .map(|(inlined_fn, ranges)| {
+                let name = inlined_fn.name.map(|name| name.to_string()).transpose()?;
+                let call_site = if let Some(call_file) = inlined_fn.call_file {
+                    if let Some(lines) = unit.parse_lines(&self.dwarf)? {
+                        if let Some((dir, file, md5)) = lines.files.get(call_file as usize) {
+                            let location = Location {
+                                dir,
+                                file,
+                                line: (inlined_fn.call_line != 0).then_some(inlined_fn.call_line),
+                                column: (inlined_fn.call_column != 0)
+                                    .then_some(inlined_fn.call_column),
+                                md5: *md5,
+                                flags: None,
+                            };
+                            Some(location)
+                        } else {
+                            warn!(
+                                "encountered invalid inlined function `call_file` index ({call_file}); ignoring..."
+                            );
+                            None
+                        }
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                };
+
+                Ok((name, ranges, call_site))
+            })
+            .collect()
+    }
+
     /// Find the source file and line corresponding to the given virtual memory address.
     pub fn find_location(&self, probe: u64) -> Result<Option<Location<'_>>, gimli::Error> {
         for unit in self.find_units(probe) {
@@ -369,6 +531,292 @@ impl<'dwarf> Units<'dwarf> {
         Ok(None)
     }
 
+    /// Iterate over the line table rows of all units.
+    ///
+    /// Each row is reported with its start address and length. Units
+    /// are processed one at a time, in the order in which they appear
+    /// in the file.
+    pub fn all_locations<'slf>(
+        &'slf self,
+    ) -> Box<dyn Iterator<Item = Result<(u64, u64, Location<'slf>), gimli::Error>> + 'slf> {
+        let iter = self.units.iter().flat_map(move |unit| {
+            let (locations, err) = match LocationRangeUnitIter::new(unit, &self.dwarf, 0, u64::MAX)
+            {
+                Ok(Some(iter)) => (iter.collect(), None),
+                Ok(None) => (Vec::new(), None),
+                Err(err) => (Vec::new(), Some(Err(err))),
+            };
+            locations.into_iter().map(Ok).chain(err)
+        });
+        Box::new(iter)
+    }
+
+    /// Find the start addresses of all line table rows overlapping the
+    /// address range `probe_low..probe_high`.
+    ///
+    /// The first reported row may start before `probe_low`, if it
+    /// covers it.
+    pub fn find_line_addrs(
+        &self,
+        probe_low: u64,
+        probe_high: u64,
+    ) -> Result<Vec<u64>, gimli::Error> {
+        for unit in self.find_units(probe_low) {
+            if let Some(iter) =
+                LocationRangeUnitIter::new(unit, &self.dwarf, probe_low, probe_high)?
+            {
+                let addrs = iter.map(|(addr, _len, _loc)| addr).collect::<Vec<_>>();
+                if !addrs.is_empty() {
+                    return Ok(addrs)
+                }
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    /// Find the line table rows covering the function containing
+    /// `probe`.
+    ///
+    /// All of the function's address ranges are taken into account.
+    /// Each row is reported with its start address (clamped to the
+    /// range it belongs to), the path of its source file, and its line
+    /// number. Rows not attributed to any line are skipped.
+    pub fn find_function_lines(
+        &self,
+        probe: u64,
+    ) -> Result<Vec<(u64, PathBuf, u32)>, gimli::Error> {
+        for unit in self.find_units(probe) {
+            let ranges = unit.find_function_ranges(probe, &self.dwarf)?;
+            if ranges.is_empty() {
+                continue
+            }
+
+            let mut lines = Vec::new();
+            for range in ranges {
+                if let Some(iter) =
+                    LocationRangeUnitIter::new(unit, &self.dwarf, range.begin, range.end)?
+                {
+                    let rows = iter.filter_map(|(addr, _len, loc)| {
+                        let line = loc.line?;
+                        Some((addr.max(range.begin), loc.dir.join(loc.file), line))
+                    });
+                    let () = lines.extend(rows);
+                }
+            }
+            return Ok(lines)
+        }
+        Ok(Vec::new())
+    }
+
+    /// Find the source location of the entry of the function
+    /// containing `probe`.
+    ///
+    /// The function's `DW_AT_decl_file` and `DW_AT_decl_line`
+    /// attributes are used if present. Otherwise the line table row
+    /// covering the function's entry address is consulted.
+    pub fn find_function_decl_line(
+        &self,
+        probe: u64,
+    ) -> Result<Option<(PathBuf, u32)>, gimli::Error> {
+        for unit in self.find_units(probe) {
+            if let Some((function, range)) = unit.find_function(probe, &self.dwarf)? {
+                if let (Some(decl_file), Some(decl_line)) = (function.decl_file, function.decl_line)
+                {
+                    if let Some(lines) = unit.parse_lines(&self.dwarf)? {
+                        if let Some((dir, file, _md5)) = lines.files.get(decl_file as usize) {
+                            return Ok(Some((dir.join(file), decl_line)))
+                        }
+                        warn!(
+                            "encountered invalid function `decl_file` index ({decl_file}); ignoring..."
+                        );
+                    }
+                }
+
+                let entry = function.range.unwrap_or(range).begin;
+                let decl = unit
+                    .find_location(entry, &self.dwarf)?
+                    .and_then(|loc| Some((loc.dir.join(loc.file), loc.line?)));
+                return Ok(decl)
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find the address of the first line table row within
+    /// `probe_low..probe_high` that marks the end of a function
+    /// prologue.
+    pub fn find_prologue_end(
+        &self,
+        probe_low: u64,
+        probe_high: u64,
+    ) -> Result<Option<u64>, gimli::Error> {
+        for unit in self.find_units(probe_low) {
+            if let Some(mut iter) =
+                LocationRangeUnitIter::new(unit, &self.dwarf, probe_low, probe_high)?
+            {
+                let addr = iter.find_map(|(addr, _len, loc)| {
+                    let prologue_end = loc
+                        .flags
+                        .map_or(false, |flags| flags.contains(LineFlags::PROLOGUE_END));
+                    (addr >= probe_low && prologue_end).then_some(addr)
+                });
+                if addr.is_some() {
+                    return Ok(addr)
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find all named functions whose primary address range starts
+    /// within `probe_low..probe_high`.
+    pub fn find_functions_in_range(
+        &self,
+        probe_low: u64,
+        probe_high: u64,
+    ) -> Result<Vec<&Function<'dwarf>>, gimli::Error> {
+        let mut functions = Vec::new();
+        for unit in self.units.iter() {
+            let funcs = unit.parse_functions(&self.dwarf)?;
+            let funcs = funcs.functions.iter().filter(|function| {
+                function.name.is_some()
+                    && function.range.as_ref().map_or(false, |range| {
+                        probe_low <= range.begin && range.begin < probe_high
+                    })
+            });
+            let () = functions.extend(funcs);
+        }
+        Ok(functions)
+    }
+
+    /// Find all source files referenced by the line program of the unit
+    /// covering `probe`.
+    ///
+    /// Paths are resolved against the unit's compilation directory.
+    /// Duplicate entries (e.g., the primary source file being listed at
+    /// index 0 as well as 1 in DWARF 5) are removed, while the order of
+    /// first appearance is retained.
+    pub fn find_source_files(&self, probe: u64) -> Result<Vec<PathBuf>, gimli::Error> {
+        for unit in self.find_units(probe) {
+            let lines = match unit.parse_lines(&self.dwarf)? {
+                Some(lines) => lines,
+                None => continue,
+            };
+            let comp_dir = unit
+                .dw_unit()
+                .comp_dir
+                .as_ref()
+                .map(|dir| Path::new(OsStr::from_bytes(dir.slice())))
+                .unwrap_or_else(|| Path::new(""));
+
+            let mut files = Vec::<PathBuf>::with_capacity(lines.files.len());
+            for (dir, file, _md5) in lines.files.iter() {
+                // DWARF versions prior to 5 have no file at index 0 and
+                // we use an empty placeholder instead.
+                if file.is_empty() {
+                    continue
+                }
+                // Directory index 0 refers to the compilation directory,
+                // but we represent it as empty.
+                let path = if dir.as_os_str().is_empty() {
+                    comp_dir.join(file)
+                } else {
+                    dir.join(file)
+                };
+                if !files.contains(&path) {
+                    let () = files.push(path);
+                }
+            }
+            return Ok(files)
+        }
+        Ok(Vec::new())
+    }
+
+    /// Find the name (`DW_AT_name`) of the unit covering `probe`.
+    ///
+    /// Relative names are resolved against the unit's compilation
+    /// directory.
+    pub fn find_unit_name(&self, probe: u64) -> Result<Option<PathBuf>, gimli::Error> {
+        for unit in self.find_units(probe) {
+            let dw_unit = unit.dw_unit();
+            if let Some(name) = &dw_unit.name {
+                let name = Path::new(OsStr::from_bytes(name.slice()));
+                let path = match &dw_unit.comp_dir {
+                    Some(dir) => Path::new(OsStr::from_bytes(dir.slice())).join(name),
+                    None => name.to_path_buf(),
+                };
+                return Ok(Some(path))
+            }
+        }
+        Ok(None)
+    }
+
+    fn create_file_index(&self) -> Result<FileIndex<'dwarf>, gimli::Error> {
+        let mut index = FileIndex::new();
+        for (unit_id, unit) in self.units.iter().enumerate() {
+            if let Some(lines) = unit.parse_lines(&self.dwarf)? {
+                for (file_idx, (_dir, file, _md5)) in lines.files.iter().enumerate() {
+                    if let Some(name) = Path::new(*file).file_name() {
+                        let () = index
+                            .entry(name)
+                            .or_default()
+                            .push((unit_id, file_idx as u64));
+                    }
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    /// Find the addresses of all line table rows attributed to `line`
+    /// in source file `file`.
+    ///
+    /// Files are matched if their path ends with `file`. Only if no such
+    /// file exists do we fall back to matching just the base name.
+    pub fn find_line(&self, file: &Path, line: u32) -> Result<Vec<u64>, gimli::Error> {
+        let index = self
+            .file_index
+            .get_or_try_init(|| self.create_file_index())?;
+        let candidates = match file.file_name().and_then(|name| index.get(name)) {
+            Some(candidates) => candidates,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut matches = Vec::new();
+        let mut fallback = Vec::new();
+        for (unit_id, file_idx) in candidates {
+            let unit = &self.units[*unit_id];
+            let lines = match unit.parse_lines(&self.dwarf)? {
+                Some(lines) => lines,
+                None => continue,
+            };
+            // SANITY: The index was created from this very file table.
+            let (dir, name, _md5) = &lines.files[*file_idx as usize];
+            let addrs = if dir.join(name).ends_with(file) {
+                &mut matches
+            } else {
+                &mut fallback
+            };
+
+            let rows = lines
+                .sequences
+                .iter()
+                .flat_map(|sequence| sequence.rows.iter())
+                .filter(|row| row.file_index == *file_idx && row.line == line)
+                .map(|row| row.address);
+            let () = addrs.extend(rows);
+        }
+
+        let mut addrs = if matches.is_empty() {
+            fallback
+        } else {
+            matches
+        };
+        let () = addrs.sort_unstable();
+        let () = addrs.dedup();
+        Ok(addrs)
+    }
+
     pub fn find_name<'s, 'slf: 's>(
         &'slf self,
         name: &'s str,
@@ -480,6 +928,35 @@ mod tests {
         }
     }
 
+    /// Check that we use `.debug_aranges` for unit lookup, if present,
+    /// and gracefully ignore it if it is malformed.
+    #[test]
+    fn aranges_lookup() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-dwarf-v4.bin");
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+        let mut load_section = |section| reader::load_section(&parser, section);
+        let dwarf = Dwarf::<R>::load(&mut load_section).unwrap();
+        let units = Units::parse(dwarf).unwrap();
+        assert!(!units.arange_ranges.is_empty());
+
+        let func = units.find_name("fibonacci").next().unwrap().unwrap();
+        let addr = func.range.as_ref().unwrap().begin;
+        let loc = units.find_location(addr).unwrap().unwrap();
+        assert_eq!(loc.file, OsStr::new("test-exe.c"));
+
+        let mut dwarf = Dwarf::<R>::load(&mut load_section).unwrap();
+        dwarf.debug_aranges = gimli::DebugAranges::new(&[0xff; 13], Default::default());
+        let units = Units::parse(dwarf).unwrap();
+        assert!(units.arange_ranges.is_empty());
+
+        // We should still be able to look up data by scanning units.
+        let loc = units.find_location(addr).unwrap().unwrap();
+        assert_eq!(loc.file, OsStr::new("test-exe.c"));
+        assert_eq!(loc.line.unwrap(), 8);
+    }
+
     /// Check that we fail to find any data for an address not
     /// represented.
     #[test]
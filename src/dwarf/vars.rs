@@ -0,0 +1,242 @@
+use gimli::Error;
+
+use super::range::RangeAttributes;
+use super::reader::R;
+
+
+/// The maximum nesting depth of lexical blocks that we support.
+const MAX_BLOCK_DEPTH: usize = 256;
+/// The maximum number of type DIEs we follow when naming a type.
+const MAX_TYPE_DEPTH: usize = 16;
+
+
+type Entry<'abbrev, 'unit, 'dwarf> = gimli::DebuggingInformationEntry<'abbrev, 'unit, R<'dwarf>>;
+
+
+/// A variable or formal parameter of a function, as described by a
+/// `DW_TAG_variable` or `DW_TAG_formal_parameter` DIE.
+pub(super) struct Variable<'dwarf> {
+    /// The variable's name, if present.
+    pub(super) name: Option<R<'dwarf>>,
+    /// A C style rendering of the variable's type, if present.
+    pub(super) type_name: Option<String>,
+    /// The raw DWARF expression describing the variable's location at
+    /// the address of interest, if known.
+    pub(super) location: Option<R<'dwarf>>,
+    /// Whether the variable is a formal parameter.
+    pub(super) param: bool,
+}
+
+
+/// Render the name of the type referenced by the `DW_AT_type` attribute
+/// value `value`.
+fn type_name<'dwarf>(
+    value: gimli::AttributeValue<R<'dwarf>>,
+    unit: &gimli::Unit<R<'dwarf>>,
+    sections: &gimli::Dwarf<R<'dwarf>>,
+    recursion_limit: usize,
+) -> Result<Option<String>, Error> {
+    let offset = match value {
+        gimli::AttributeValue::UnitRef(offset) if recursion_limit > 0 => offset,
+        // TODO: Need to handle `AttributeValue::DebugInfoRef` and
+        //       `AttributeValue::DebugInfoRefSup`.
+        _ => return Ok(None),
+    };
+
+    let entry = unit.entry(offset)?;
+    let inner = || -> Result<String, Error> {
+        let name = match entry.attr_value(gimli::DW_AT_type)? {
+            Some(value) => type_name(value, unit, sections, recursion_limit - 1)?,
+            None => None,
+        };
+        Ok(name.unwrap_or_else(|| "void".to_string()))
+    };
+
+    let name = match entry.tag() {
+        gimli::DW_TAG_pointer_type => {
+            let inner = inner()?;
+            if inner.ends_with('*') {
+                format!("{inner}*")
+            } else {
+                format!("{inner} *")
+            }
+        }
+        gimli::DW_TAG_reference_type => format!("{} &", inner()?),
+        gimli::DW_TAG_rvalue_reference_type => format!("{} &&", inner()?),
+        gimli::DW_TAG_const_type => format!("const {}", inner()?),
+        gimli::DW_TAG_volatile_type => format!("volatile {}", inner()?),
+        gimli::DW_TAG_array_type => format!("{}[]", inner()?),
+        _ => match entry.attr_value(gimli::DW_AT_name)? {
+            Some(value) => match sections.attr_string(unit, value) {
+                Ok(name) => name.to_string_lossy().into_owned(),
+                Err(_) => return Ok(None),
+            },
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(name))
+}
+
+
+/// Check whether the lexical block described by `entry` covers
+/// `probe`.
+///
+/// Blocks without address information are considered to cover all
+/// addresses of the enclosing scope.
+fn block_covers<'dwarf>(
+    entry: &Entry<'_, '_, 'dwarf>,
+    unit: &gimli::Unit<R<'dwarf>>,
+    sections: &gimli::Dwarf<R<'dwarf>>,
+    probe: u64,
+) -> Result<bool, Error> {
+    let mut ranges = RangeAttributes::default();
+    let mut attrs = entry.attrs();
+    while let Some(attr) = attrs.next()? {
+        match attr.name() {
+            gimli::DW_AT_low_pc => match attr.value() {
+                gimli::AttributeValue::Addr(val) => ranges.low_pc = Some(val),
+                gimli::AttributeValue::DebugAddrIndex(index) => {
+                    ranges.low_pc = Some(sections.address(unit, index)?);
+                }
+                _ => {}
+            },
+            gimli::DW_AT_high_pc => match attr.value() {
+                gimli::AttributeValue::Addr(val) => ranges.high_pc = Some(val),
+                gimli::AttributeValue::DebugAddrIndex(index) => {
+                    ranges.high_pc = Some(sections.address(unit, index)?);
+                }
+                gimli::AttributeValue::Udata(val) => ranges.size = Some(val),
+                _ => {}
+            },
+            gimli::DW_AT_ranges => {
+                ranges.ranges_offset = sections.attr_ranges_offset(unit, attr.value())?;
+            }
+            _ => {}
+        }
+    }
+
+    let mut covers = false;
+    let added = ranges.for_each_range(sections, unit, |range| {
+        covers |= range.begin <= probe && probe < range.end;
+    })?;
+    Ok(covers || !added)
+}
+
+
+impl<'dwarf> Variable<'dwarf> {
+    fn parse(
+        entry: &Entry<'_, '_, 'dwarf>,
+        unit: &gimli::Unit<R<'dwarf>>,
+        sections: &gimli::Dwarf<R<'dwarf>>,
+        probe: u64,
+    ) -> Result<Self, Error> {
+        let mut name = None;
+        let mut type_ = None;
+        let mut location = None;
+        let mut origin = None;
+
+        let mut attrs = entry.attrs();
+        while let Some(attr) = attrs.next()? {
+            match attr.name() {
+                gimli::DW_AT_name => name = sections.attr_string(unit, attr.value()).ok(),
+                gimli::DW_AT_type => type_ = Some(attr.value()),
+                gimli::DW_AT_abstract_origin => origin = Some(attr.value()),
+                gimli::DW_AT_location => match attr.value() {
+                    gimli::AttributeValue::Exprloc(expr) => location = Some(expr.0),
+                    value => {
+                        if let Some(offset) = sections.attr_locations_offset(unit, value)? {
+                            let mut locations = sections.locations(unit, offset)?;
+                            while let Some(entry) = locations.next()? {
+                                if entry.range.begin <= probe && probe < entry.range.end {
+                                    location = Some(entry.data.0);
+                                    break
+                                }
+                            }
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        // Concrete instances of variables (e.g., in out-of-line copies
+        // of inlined functions) typically only carry a location and
+        // refer to the abstract instance for everything else.
+        if let Some(gimli::AttributeValue::UnitRef(offset)) = origin {
+            let origin = unit.entry(offset)?;
+            if name.is_none() {
+                name = match origin.attr_value(gimli::DW_AT_name)? {
+                    Some(value) => sections.attr_string(unit, value).ok(),
+                    None => None,
+                };
+            }
+            if type_.is_none() {
+                type_ = origin.attr_value(gimli::DW_AT_type)?;
+            }
+        }
+
+        let type_name = match type_ {
+            Some(value) => type_name(value, unit, sections, MAX_TYPE_DEPTH)?,
+            None => None,
+        };
+
+        Ok(Self {
+            name,
+            type_name,
+            location,
+            param: entry.tag() == gimli::DW_TAG_formal_parameter,
+        })
+    }
+}
+
+
+/// Collect the variables declared by the children of `node` that are
+/// in scope at `probe`.
+fn parse_children<'dwarf>(
+    node: gimli::EntriesTreeNode<'_, '_, '_, R<'dwarf>>,
+    unit: &gimli::Unit<R<'dwarf>>,
+    sections: &gimli::Dwarf<R<'dwarf>>,
+    probe: u64,
+    vars: &mut Vec<Variable<'dwarf>>,
+    block_depth: usize,
+) -> Result<(), Error> {
+    let mut children = node.children();
+    while let Some(child) = children.next()? {
+        let entry = child.entry();
+        match entry.tag() {
+            gimli::DW_TAG_formal_parameter | gimli::DW_TAG_variable => {
+                let var = Variable::parse(entry, unit, sections, probe)?;
+                let () = vars.push(var);
+            }
+            gimli::DW_TAG_lexical_block
+                if block_depth < MAX_BLOCK_DEPTH && block_covers(entry, unit, sections, probe)? =>
+            {
+                let () = parse_children(child, unit, sections, probe, vars, block_depth + 1)?;
+            }
+            // Nested functions and inlined subroutines have variables
+            // of their own, which we do not report.
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+
+/// Collect the parameters and local variables of the function
+/// described by the DIE at `dw_die_offset` that are in scope at
+/// `probe`.
+///
+/// Variables are reported in the order in which they appear in the
+/// debug information.
+pub(super) fn parse_vars<'dwarf>(
+    dw_die_offset: gimli::UnitOffset<<R<'dwarf> as gimli::Reader>::Offset>,
+    unit: &gimli::Unit<R<'dwarf>>,
+    sections: &gimli::Dwarf<R<'dwarf>>,
+    probe: u64,
+) -> Result<Vec<Variable<'dwarf>>, Error> {
+    let mut tree = unit.entries_tree(Some(dw_die_offset))?;
+    let root = tree.root()?;
+    let mut vars = Vec::new();
+    let () = parse_children(root, unit, sections, probe, &mut vars, 0)?;
+    Ok(vars)
+}
@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use crate::Result;
+
+use super::types::ELF_NOTE_OS_FREEBSD;
+use super::types::ELF_NOTE_OS_GNU;
+use super::types::ELF_NOTE_OS_LINUX;
+use super::types::ELF_NOTE_OS_SOLARIS2;
+use super::ElfParser;
+
+
+/// The operating system an ELF file targets, as recorded in its
+/// `.note.ABI-tag` note.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum AbiTagOs {
+    /// Linux.
+    Linux,
+    /// GNU/Hurd.
+    Hurd,
+    /// Solaris.
+    Solaris,
+    /// FreeBSD.
+    FreeBsd,
+    /// An operating system not known to us, along with its raw value.
+    Unknown(u32),
+}
+
+impl From<u32> for AbiTagOs {
+    fn from(os: u32) -> Self {
+        match os {
+            ELF_NOTE_OS_LINUX => Self::Linux,
+            ELF_NOTE_OS_GNU => Self::Hurd,
+            ELF_NOTE_OS_SOLARIS2 => Self::Solaris,
+            ELF_NOTE_OS_FREEBSD => Self::FreeBsd,
+            os => Self::Unknown(os),
+        }
+    }
+}
+
+
+/// The contents of an ELF file's `.note.ABI-tag` note, describing the
+/// operating system and its minimum version that the file targets.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AbiTag {
+    /// The targeted operating system.
+    pub os: AbiTagOs,
+    /// The minimum (kernel) version required, as major, minor, and
+    /// patch level.
+    pub version: (u32, u32, u32),
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// Read the operating system and minimum version targeted by the ELF
+/// file at the given path, as recorded in its `.note.ABI-tag` note.
+///
+/// [`None`] is returned for files without such a note, e.g., shared
+/// objects or binaries not linked against a C runtime.
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("test-no-debug.bin");
+/// use blazesym::helper::AbiTagOs;
+///
+/// if let Some(tag) = blazesym::helper::read_elf_abi_tag(&path).unwrap() {
+///     if tag.os == AbiTagOs::Linux {
+///         let (major, minor, patch) = tag.version;
+///         println!("requires Linux {major}.{minor}.{patch} or newer");
+///     }
+/// }
+/// ```
+pub fn read_elf_abi_tag<P>(path: &P) -> Result<Option<AbiTag>>
+where
+    P: AsRef<Path>,
+{
+    let parser = ElfParser::open(path.as_ref())?;
+    parser.abi_tag()
+}
@@ -0,0 +1,265 @@
+use crate::log::warn;
+use crate::util::ReadRaw as _;
+use crate::Error;
+#[cfg(any(feature = "zlib", feature = "zstd"))]
+use crate::ErrorExt as _;
+use crate::IntoError as _;
+use crate::Result;
+
+use super::types::Elf64_Chdr;
+use super::types::ELFCOMPRESS_ZLIB;
+use super::types::ELFCOMPRESS_ZSTD;
+use super::types::SHF_COMPRESSED;
+
+
+/// The magic that GNU style compressed sections (`.zdebug_*`) start
+/// with.
+const GNU_ZLIB_MAGIC: &[u8] = b"ZLIB";
+
+
+/// Decompress zlib compressed `data` that is expected to inflate to
+/// `size` bytes.
+#[cfg(feature = "zlib")]
+fn decompress_zlib(data: &[u8], size: u64) -> Result<Vec<u8>> {
+    use miniz_oxide::inflate::decompress_to_vec_zlib_with_limit;
+
+    let size = usize::try_from(size)
+        .map_err(Error::with_invalid_data)
+        .context("decompressed section size is too large")?;
+    let decompressed = decompress_to_vec_zlib_with_limit(data, size)
+        .map_err(Error::with_invalid_data)
+        .context("failed to decompress section data")?;
+    if decompressed.len() != size {
+        return Err(Error::with_invalid_data(format!(
+            "decompressed section has unexpected size ({} vs. {size})",
+            decompressed.len()
+        )))
+    }
+    Ok(decompressed)
+}
+
+#[cfg(not(feature = "zlib"))]
+fn decompress_zlib(_data: &[u8], _size: u64) -> Result<Vec<u8>> {
+    Err(Error::with_unsupported(
+        "zlib compressed sections are unsupported; `zlib` feature is not enabled",
+    ))
+}
+
+
+/// Decompress zstd compressed `data` that is expected to decompress to
+/// `size` bytes.
+#[cfg(feature = "zstd")]
+fn decompress_zstd(data: &[u8], size: u64) -> Result<Vec<u8>> {
+    use std::io::Read as _;
+
+    use ruzstd::StreamingDecoder;
+
+    let size = usize::try_from(size)
+        .map_err(Error::with_invalid_data)
+        .context("decompressed section size is too large")?;
+    let decoder = StreamingDecoder::new(data)
+        .map_err(Error::with_invalid_data)
+        .context("failed to read zstd frame header")?;
+    let mut decompressed = Vec::with_capacity(size);
+    // Read at most one byte more than expected, which is sufficient
+    // for detecting a size mismatch.
+    let _count = decoder
+        .take(size as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(Error::with_invalid_data)
+        .context("failed to decompress section data")?;
+    if decompressed.len() != size {
+        return Err(Error::with_invalid_data(format!(
+            "decompressed section has unexpected size ({} vs. {size})",
+            decompressed.len()
+        )))
+    }
+    Ok(decompressed)
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress_zstd(_data: &[u8], _size: u64) -> Result<Vec<u8>> {
+    Err(Error::with_unsupported(
+        "zstd compressed sections are unsupported; `zstd` feature is not enabled",
+    ))
+}
+
+
+/// Decompress the data of a section, if it is compressed.
+///
+/// Sections flagged as `SHF_COMPRESSED` are expected to start with an
+/// `Elf64_Chdr` header and may be compressed using either zlib or zstd. Sections for which `gnu` is set (typically
+/// `.zdebug_*` ones) are expected to use the older GNU format, which
+/// starts with a `ZLIB` magic followed by the big endian uncompressed
+/// size. If the latter header is not present, the section data is
+/// treated as uncompressed.
+///
+/// `None` is returned if the section data is not compressed.
+pub(crate) fn decompress_section(
+    mut data: &[u8],
+    sh_flags: u64,
+    gnu: bool,
+) -> Result<Option<Vec<u8>>> {
+    if sh_flags & SHF_COMPRESSED != 0 {
+        let chdr = data
+            .read_pod::<Elf64_Chdr>()
+            .ok_or_invalid_data(|| "failed to read Elf64_Chdr")?;
+        match chdr.ch_type {
+            ELFCOMPRESS_ZLIB => decompress_zlib(data, chdr.ch_size).map(Some),
+            ELFCOMPRESS_ZSTD => decompress_zstd(data, chdr.ch_size).map(Some),
+            ty => Err(Error::with_unsupported(format!(
+                "ELF section compression type {ty} is unsupported"
+            ))),
+        }
+    } else if gnu {
+        if let Some(mut data) = data.strip_prefix(GNU_ZLIB_MAGIC) {
+            let size = data
+                .read_u64()
+                .ok_or_invalid_data(|| "failed to read GNU compressed section size")?;
+            let size = u64::from_be_bytes(size.to_ne_bytes());
+            decompress_zlib(data, size).map(Some)
+        } else {
+            warn!("GNU compressed section lacks compression header; treating as uncompressed");
+            Ok(None)
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::mem::size_of;
+    use std::slice;
+
+    use test_log::test;
+
+    use crate::ErrorKind;
+
+
+    /// Check that uncompressed section data is reported as such.
+    #[test]
+    fn uncompressed_section() {
+        let data = b"some data";
+        assert_eq!(decompress_section(data, 0, false).unwrap(), None);
+        // A GNU style section without a header is treated as
+        // uncompressed as well.
+        assert_eq!(decompress_section(data, 0, true).unwrap(), None);
+    }
+
+    /// Check that we report an error on unsupported compression types.
+    #[test]
+    fn unsupported_compression() {
+        let chdr = Elf64_Chdr {
+            ch_type: 42,
+            ch_reserved: 0,
+            ch_size: 42,
+            ch_addralign: 1,
+        };
+        // SAFETY: `Elf64_Chdr` is a plain old data type.
+        let data = unsafe {
+            slice::from_raw_parts(
+                (&chdr as *const Elf64_Chdr).cast::<u8>(),
+                size_of::<Elf64_Chdr>(),
+            )
+        };
+        let err = decompress_section(data, SHF_COMPRESSED, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let err = decompress_section(&data[..4], SHF_COMPRESSED, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    /// Check that we can decompress sections in both the standard and
+    /// the GNU format.
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn zlib_decompression() {
+        use miniz_oxide::deflate::compress_to_vec_zlib;
+
+        let uncompressed = b"some reasonably compressible data data data data".as_slice();
+        let compressed = compress_to_vec_zlib(uncompressed, 6);
+
+        let chdr = Elf64_Chdr {
+            ch_type: ELFCOMPRESS_ZLIB,
+            ch_reserved: 0,
+            ch_size: uncompressed.len() as _,
+            ch_addralign: 1,
+        };
+        // SAFETY: `Elf64_Chdr` is a plain old data type.
+        let header = unsafe {
+            slice::from_raw_parts(
+                (&chdr as *const Elf64_Chdr).cast::<u8>(),
+                size_of::<Elf64_Chdr>(),
+            )
+        };
+        let data = [header, &compressed].concat();
+        let decompressed = decompress_section(&data, SHF_COMPRESSED, false).unwrap();
+        assert_eq!(decompressed.as_deref(), Some(uncompressed));
+
+        let size = (uncompressed.len() as u64).to_be_bytes();
+        let data = [GNU_ZLIB_MAGIC, &size, &compressed].concat();
+        let decompressed = decompress_section(&data, 0, true).unwrap();
+        assert_eq!(decompressed.as_deref(), Some(uncompressed));
+
+        // A size mismatch should be reported as an error.
+        let size = (uncompressed.len() as u64 + 1).to_be_bytes();
+        let data = [GNU_ZLIB_MAGIC, &size, &compressed].concat();
+        let err = decompress_section(&data, 0, true).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    /// Check that we can decompress zstd compressed sections.
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_decompression() {
+        let uncompressed = b"some data stored in a raw zstd block".as_slice();
+        let len = uncompressed.len();
+        // A single segment frame with a one byte content size, followed
+        // by a single raw (i.e., uncompressed) block.
+        let frame_header = [0x28, 0xb5, 0x2f, 0xfd, 0x20, len as u8];
+        let block_header = ((len << 3) | 1).to_le_bytes();
+        let compressed = [&frame_header[..], &block_header[..3], uncompressed].concat();
+
+        let chdr = Elf64_Chdr {
+            ch_type: ELFCOMPRESS_ZSTD,
+            ch_reserved: 0,
+            ch_size: len as _,
+            ch_addralign: 1,
+        };
+        // SAFETY: `Elf64_Chdr` is a plain old data type.
+        let header = unsafe {
+            slice::from_raw_parts(
+                (&chdr as *const Elf64_Chdr).cast::<u8>(),
+                size_of::<Elf64_Chdr>(),
+            )
+        };
+        let data = [header, &compressed].concat();
+        let decompressed = decompress_section(&data, SHF_COMPRESSED, false).unwrap();
+        assert_eq!(decompressed.as_deref(), Some(uncompressed));
+
+        // A size mismatch should be reported as an error.
+        let chdr = Elf64_Chdr {
+            ch_size: len as u64 - 1,
+            ..chdr
+        };
+        // SAFETY: `Elf64_Chdr` is a plain old data type.
+        let header = unsafe {
+            slice::from_raw_parts(
+                (&chdr as *const Elf64_Chdr).cast::<u8>(),
+                size_of::<Elf64_Chdr>(),
+            )
+        };
+        let data = [header, &compressed].concat();
+        let err = decompress_section(&data, SHF_COMPRESSED, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        // So should garbage data.
+        let data = [header, b"not zstd"].concat();
+        let err = decompress_section(&data, SHF_COMPRESSED, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
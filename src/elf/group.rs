@@ -0,0 +1,54 @@
+use std::path::Path;
+
+use crate::Result;
+
+use super::ElfParser;
+
+
+/// A section group, as described by an `SHT_GROUP` section.
+///
+/// Section groups are most prominently used for COMDAT sections, i.e.,
+/// sections (such as the ones of template instantiations) that the
+/// linker deduplicates across object files.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SectionGroup {
+    /// The index of the `SHT_GROUP` section describing the group.
+    pub section: usize,
+    /// The group's signature, i.e., the name of the symbol identifying
+    /// it.
+    pub signature: String,
+    /// The group's flags (`GRP_*`).
+    pub flags: u32,
+    /// The indices of the sections that are members of the group.
+    pub members: Vec<usize>,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// Read the section groups of the ELF file at the given path, in the
+/// order in which they appear in the file.
+///
+/// Section indices are indices into the file's section header table,
+/// as reported by tools such as `readelf`.
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("test-section-groups.bin");
+/// const GRP_COMDAT: u32 = 0x1;
+///
+/// let groups = blazesym::helper::read_elf_section_groups(&path).unwrap();
+/// for group in groups.iter().filter(|group| group.flags & GRP_COMDAT != 0) {
+///     println!("COMDAT group {}: {:?}", group.signature, group.members);
+/// }
+/// ```
+pub fn read_elf_section_groups<P>(path: &P) -> Result<Vec<SectionGroup>>
+where
+    P: AsRef<Path>,
+{
+    let parser = ElfParser::open(path.as_ref())?;
+    parser.section_groups()
+}
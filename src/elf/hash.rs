@@ -0,0 +1,268 @@
+use crate::util::ReadRaw as _;
+use crate::IntoError as _;
+use crate::Result;
+
+
+/// Calculate the GNU style hash of a symbol name, as used by
+/// `.gnu_hash` sections.
+fn gnu_hash(name: &[u8]) -> u32 {
+    name.iter().fold(5381u32, |h, c| {
+        h.wrapping_mul(33).wrapping_add(u32::from(*c))
+    })
+}
+
+/// Calculate the System V style hash of a symbol name, as used by
+/// `.hash` sections.
+fn sysv_hash(name: &[u8]) -> u32 {
+    name.iter().fold(0u32, |h, c| {
+        let h = (h << 4).wrapping_add(u32::from(*c));
+        let g = h & 0xf0000000;
+        (h ^ (g >> 24)) & !g
+    })
+}
+
+/// Read the `idx`-th `u32` of `data`.
+fn read_u32_at(data: &[u8], idx: usize) -> Option<u32> {
+    data.get(idx.checked_mul(4)?..)?.read_u32()
+}
+
+
+/// Look up the symbol `name` in the GNU hash table `table`.
+///
+/// `check` is invoked with the symbol table index of each candidate
+/// and is expected to report whether it refers to the symbol in
+/// question. The index of the first such symbol is returned.
+pub(crate) fn gnu_hash_lookup<F>(table: &[u8], name: &str, mut check: F) -> Result<Option<usize>>
+where
+    F: FnMut(usize) -> Result<bool>,
+{
+    let mut data = table;
+    let nbuckets = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read GNU hash table bucket count")?;
+    let symoffset = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read GNU hash table symbol offset")?;
+    let bloom_size = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read GNU hash table bloom filter size")?;
+    let bloom_shift = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read GNU hash table bloom filter shift")?;
+
+    if nbuckets == 0 || bloom_size == 0 {
+        return Ok(None)
+    }
+
+    let hash = gnu_hash(name.as_bytes());
+    // The bloom filter consists of 64 bit words for ELF64.
+    let word_idx = (hash / 64 % bloom_size) as usize;
+    let word = data
+        .get(word_idx * 8..)
+        .and_then(|mut bloom| bloom.read_u64())
+        .ok_or_invalid_data(|| "failed to read GNU hash table bloom filter")?;
+    let mask = (1u64 << (hash % 64)) | (1u64 << (hash.checked_shr(bloom_shift).unwrap_or(0) % 64));
+    if word & mask != mask {
+        return Ok(None)
+    }
+
+    let buckets = data
+        .get(bloom_size as usize * 8..)
+        .ok_or_invalid_data(|| "GNU hash table is truncated")?;
+    let chains = buckets
+        .get(nbuckets as usize * 4..)
+        .ok_or_invalid_data(|| "GNU hash table is truncated")?;
+    let symoffset = symoffset as usize;
+    let mut idx = read_u32_at(buckets, (hash % nbuckets) as usize)
+        .ok_or_invalid_data(|| "failed to read GNU hash table bucket")? as usize;
+    if idx < symoffset {
+        return Ok(None)
+    }
+
+    loop {
+        let chain_hash = read_u32_at(chains, idx - symoffset)
+            .ok_or_invalid_data(|| "failed to read GNU hash table chain")?;
+        // The least significant bit of each chain entry marks the end
+        // of the chain and is not part of the hash.
+        if hash | 1 == chain_hash | 1 && check(idx)? {
+            return Ok(Some(idx))
+        }
+        if chain_hash & 1 != 0 {
+            break Ok(None)
+        }
+        idx += 1;
+    }
+}
+
+/// Look up the symbol `name` in the System V hash table `table`.
+///
+/// `check` is invoked with the symbol table index of each candidate
+/// and is expected to report whether it refers to the symbol in
+/// question. The index of the first such symbol is returned.
+pub(crate) fn sysv_hash_lookup<F>(table: &[u8], name: &str, mut check: F) -> Result<Option<usize>>
+where
+    F: FnMut(usize) -> Result<bool>,
+{
+    let mut data = table;
+    let nbucket = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read hash table bucket count")?;
+    let nchain = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read hash table chain count")?;
+
+    if nbucket == 0 {
+        return Ok(None)
+    }
+
+    let chains = data
+        .get(nbucket as usize * 4..)
+        .ok_or_invalid_data(|| "hash table is truncated")?;
+    let hash = sysv_hash(name.as_bytes());
+    let mut idx = read_u32_at(data, (hash % nbucket) as usize)
+        .ok_or_invalid_data(|| "failed to read hash table bucket")?;
+
+    // A well-formed chain visits each symbol at most once. Bounding the
+    // walk protects us from looping forever on corrupted data.
+    for _ in 0..nchain {
+        // Index 0 (`STN_UNDEF`) terminates the chain.
+        if idx == 0 {
+            break
+        }
+        if check(idx as usize)? {
+            return Ok(Some(idx as usize))
+        }
+        idx = read_u32_at(chains, idx as usize)
+            .ok_or_invalid_data(|| "failed to read hash table chain")?;
+    }
+    Ok(None)
+}
+
+
+/// Determine the number of symbols in the symbol table that the GNU
+/// hash table `table` belongs to.
+///
+/// The table does not state this number explicitly. Rather, it is
+/// inferred from the end of the chain of the highest populated bucket.
+pub(crate) fn gnu_hash_sym_count(table: &[u8]) -> Result<usize> {
+    let mut data = table;
+    let nbuckets = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read GNU hash table bucket count")?;
+    let symoffset = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read GNU hash table symbol offset")?;
+    let bloom_size = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read GNU hash table bloom filter size")?;
+    let _bloom_shift = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read GNU hash table bloom filter shift")?;
+
+    let buckets = data
+        .get(bloom_size as usize * 8..)
+        .ok_or_invalid_data(|| "GNU hash table is truncated")?;
+    let last = (0..nbuckets as usize).try_fold(0, |last, idx| {
+        let bucket = read_u32_at(buckets, idx)
+            .ok_or_invalid_data(|| "failed to read GNU hash table bucket")?;
+        Result::<_>::Ok(last.max(bucket as usize))
+    })?;
+    // Symbols below `symoffset` are not covered by the hash table, but
+    // they are part of the symbol table nevertheless.
+    if last < symoffset as usize {
+        return Ok(symoffset as usize)
+    }
+
+    let chains = buckets
+        .get(nbuckets as usize * 4..)
+        .ok_or_invalid_data(|| "GNU hash table is truncated")?;
+    let mut idx = last;
+    loop {
+        let chain_hash = read_u32_at(chains, idx - symoffset as usize)
+            .ok_or_invalid_data(|| "failed to read GNU hash table chain")?;
+        if chain_hash & 1 != 0 {
+            break Ok(idx + 1)
+        }
+        idx += 1;
+    }
+}
+
+/// Determine the number of symbols in the symbol table that the System
+/// V hash table `table` belongs to.
+pub(crate) fn sysv_hash_sym_count(table: &[u8]) -> Result<usize> {
+    // There is one chain entry per symbol.
+    let nchain =
+        read_u32_at(table, 1).ok_or_invalid_data(|| "failed to read hash table chain count")?;
+    Ok(nchain as usize)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+
+    /// Check that we calculate symbol name hashes correctly.
+    #[test]
+    fn hashing() {
+        assert_eq!(gnu_hash(b""), 5381);
+        assert_eq!(gnu_hash(b"printf"), 0x156b2bb8);
+        assert_eq!(sysv_hash(b""), 0);
+        assert_eq!(sysv_hash(b"printf"), 0x077905a6);
+    }
+
+    /// Check that lookups in empty hash tables come up empty.
+    #[test]
+    fn empty_table_lookup() {
+        let table = [0u8; 16];
+        let result = gnu_hash_lookup(&table, "printf", |_| unreachable!()).unwrap();
+        assert_eq!(result, None);
+
+        let table = [0u8; 8];
+        let result = sysv_hash_lookup(&table, "printf", |_| unreachable!()).unwrap();
+        assert_eq!(result, None);
+    }
+
+    /// Check that we infer the number of symbols covered by hash tables
+    /// correctly.
+    #[test]
+    fn sym_counting() {
+        #[rustfmt::skip]
+        let words = [
+            // Bucket count, symbol offset, bloom filter size & shift.
+            1u32, 2, 1, 0,
+            // Bloom filter (a single 64 bit word).
+            0, 0,
+            // The only bucket.
+            2,
+            // Chains for symbols 2 to 4.
+            0x10, 0x20, 0x31,
+        ];
+        let table = words
+            .iter()
+            .flat_map(|word| word.to_ne_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(gnu_hash_sym_count(&table).unwrap(), 5);
+        assert!(gnu_hash_sym_count(&table[..table.len() - 4]).is_err());
+
+        let table = [1u32, 7, 0]
+            .iter()
+            .flat_map(|word| word.to_ne_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(sysv_hash_sym_count(&table).unwrap(), 7);
+    }
+
+    /// Make sure that we error out on truncated hash tables.
+    #[test]
+    fn truncated_table_lookup() {
+        let result = gnu_hash_lookup(&[0u8; 4], "printf", |_| Ok(true));
+        assert!(result.is_err());
+
+        // A single bucket, but no data for it.
+        let table = [1u8, 0, 0, 0, 1, 0, 0, 0];
+        let result = sysv_hash_lookup(&table, "printf", |_| Ok(true));
+        assert!(result.is_err());
+    }
+}
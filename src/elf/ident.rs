@@ -0,0 +1,105 @@
+use std::path::Path;
+
+use crate::Result;
+
+use super::ElfParser;
+
+
+/// Identification information of an ELF file, as recorded in its
+/// header.
+///
+/// Values are reported as-is, i.e., in terms of the constants defined
+/// by the ELF specification.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ElfIdent {
+    /// The class of the file (`ELFCLASS*`), i.e., whether it is a 32
+    /// or 64 bit object.
+    pub class: u8,
+    /// The data encoding of the file (`ELFDATA*`), i.e., whether it is
+    /// a little or big endian object.
+    pub endianness: u8,
+    /// The architecture the file targets (`EM_*`).
+    pub machine: u16,
+    /// The OS/ABI the file targets (`ELFOSABI_*`), e.g., System V,
+    /// GNU/Linux, or FreeBSD.
+    pub os_abi: u8,
+    /// The version of the ABI the file targets.
+    ///
+    /// The interpretation of this value depends on the
+    /// [OS/ABI][Self::os_abi].
+    pub abi_version: u8,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// Read the identification information of the ELF file at the given
+/// path.
+///
+/// This information allows for rejecting or special casing files
+/// targeting an unexpected architecture before attempting
+/// symbolization.
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("libtest-so.so");
+/// const EM_X86_64: u16 = 62;
+///
+/// let ident = blazesym::helper::read_elf_ident(&path).unwrap();
+/// if ident.machine != EM_X86_64 {
+///     println!("{} is not an x86-64 binary", path.display());
+/// }
+/// ```
+pub fn read_elf_ident<P>(path: &P) -> Result<ElfIdent>
+where
+    P: AsRef<Path>,
+{
+    let parser = ElfParser::open(path.as_ref())?;
+    let ident = ElfIdent {
+        class: parser.class()?,
+        endianness: parser.endianness()?,
+        machine: parser.machine()?,
+        os_abi: parser.os_abi()?,
+        abi_version: parser.abi_version()?,
+        _non_exhaustive: (),
+    };
+    Ok(ident)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::super::types::ELFCLASS64;
+    use super::super::types::ELFOSABI_GNU;
+    use super::super::types::ELFOSABI_SYSV;
+
+    use crate::ErrorKind;
+
+
+    /// Check that we can read the identification information of an ELF
+    /// file.
+    #[test]
+    fn ident_reading() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let ident = read_elf_ident(&path).unwrap();
+        assert_eq!(ident.class, ELFCLASS64);
+        assert!(
+            [ELFOSABI_SYSV, ELFOSABI_GNU].contains(&ident.os_abi),
+            "{ident:?}"
+        );
+        assert_eq!(ident.abi_version, 0);
+
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("does-not-exist");
+        let err = read_elf_ident(&path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+}
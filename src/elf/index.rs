@@ -0,0 +1,284 @@
+//! Serialization of parsed ELF symbol tables into compact indices.
+//!
+//! Sorting the symbol table of a large binary by address and by name
+//! is costly. An index captures the outcome of this work so that it
+//! can be stored on disk and reused later on.
+//!
+//! # Format
+//!
+//! The current format version is 1. All integers are stored in native
+//! byte order.
+//!
+//! | Offset | Size        | Contents                                       |
+//! |--------|-------------|------------------------------------------------|
+//! | 0      | 8           | Magic: `BLZSYMIX`                              |
+//! | 8      | 4           | Format version                                 |
+//! | 12     | 4           | Length of the build ID (`n`)                   |
+//! | 16     | 8           | ELF file modification time: seconds since epoch|
+//! | 24     | 4           | ELF file modification time: nanoseconds       |
+//! | 28     | 4           | Number of symbols (`count`)                    |
+//! | 32     | `n`         | Build ID, zero padded to a multiple of four    |
+//! | ...    | 4 * `count` | Symbol table indices, in address order         |
+//! | ...    | 4 * `count` | Indices into the address ordered list, in name order |
+//!
+//! An index is only valid for the ELF file matching both the build ID
+//! and the modification time it was created for. Indices of a
+//! different format version are considered stale.
+//!
+//! Indices are stored in files named
+//! `<build ID>-<seconds>.<nanoseconds>.symidx`, based on the same
+//! build ID and modification time, so that indices for multiple copies
+//! of a binary can coexist.
+
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::util::hex_encode;
+use crate::util::ReadRaw as _;
+use crate::Error;
+use crate::ErrorExt as _;
+use crate::IntoError as _;
+use crate::Result;
+
+
+/// The magic that symbol indices start with.
+const MAGIC: &[u8; 8] = b"BLZSYMIX";
+/// The version of the index format.
+const VERSION: u32 = 1;
+
+
+/// The key identifying the ELF file that a symbol index belongs to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SymIndexKey<'id> {
+    /// The build ID of the file.
+    pub build_id: &'id [u8],
+    /// The modification time of the file.
+    pub mtime: SystemTime,
+}
+
+impl SymIndexKey<'_> {
+    /// Retrieve the modification time as seconds and nanoseconds since
+    /// the Unix epoch.
+    fn mtime_parts(&self) -> (u64, u32) {
+        // Times before the epoch are not something we expect to see in
+        // practice. Map them all to the epoch itself, which still
+        // results in a key that is checked consistently.
+        let duration = self.mtime.duration_since(UNIX_EPOCH).unwrap_or_default();
+        (duration.as_secs(), duration.subsec_nanos())
+    }
+
+    /// Construct the name of the file storing the index for the ELF
+    /// file identified by this key.
+    pub fn file_name(&self) -> String {
+        let (secs, nsecs) = self.mtime_parts();
+        format!("{}-{secs}.{nsecs:09}.symidx", hex_encode(self.build_id))
+    }
+}
+
+
+/// Calculate the number of padding bytes following a build ID of
+/// `len` bytes.
+fn build_id_padding(len: usize) -> usize {
+    (4 - len % 4) % 4
+}
+
+/// Convert a list of indices into their serialized representation.
+fn write_indices(data: &mut Vec<u8>, indices: &[usize]) -> Result<()> {
+    for idx in indices {
+        let idx = u32::try_from(*idx)
+            .map_err(Error::with_unsupported)
+            .context("symbol index does not fit into 32 bit")?;
+        let () = data.extend_from_slice(&idx.to_ne_bytes());
+    }
+    Ok(())
+}
+
+/// Read `count` indices smaller than `bound` from `data`, without
+/// copying them.
+fn read_indices<'data>(data: &mut &'data [u8], count: usize, bound: usize) -> Result<&'data [u32]> {
+    let indices = data
+        .read_pod_slice_ref::<u32>(count)
+        .ok_or_invalid_data(|| "failed to read symbol index entries")?;
+    if let Some(idx) = indices.iter().find(|idx| **idx as usize >= bound) {
+        return Err(Error::with_invalid_data(format!(
+            "symbol index entry {idx} is out of bounds"
+        )))
+    }
+    Ok(indices)
+}
+
+
+/// Serialize a symbol index for the file identified by `key`.
+///
+/// `by_addr` contains symbol table indices in address order, while
+/// `by_name` contains indices into `by_addr`, in name order.
+pub(crate) fn serialize(
+    key: &SymIndexKey<'_>,
+    by_addr: &[usize],
+    by_name: &[usize],
+) -> Result<Vec<u8>> {
+    debug_assert_eq!(by_addr.len(), by_name.len());
+
+    let build_id_len = u32::try_from(key.build_id.len())
+        .map_err(Error::with_unsupported)
+        .context("build ID is too long")?;
+    let count = u32::try_from(by_addr.len())
+        .map_err(Error::with_unsupported)
+        .context("too many symbols")?;
+    let (secs, nsecs) = key.mtime_parts();
+    let padding = build_id_padding(key.build_id.len());
+
+    let mut data =
+        Vec::with_capacity(32 + key.build_id.len() + padding + (by_addr.len() + by_name.len()) * 4);
+    let () = data.extend_from_slice(MAGIC);
+    let () = data.extend_from_slice(&VERSION.to_ne_bytes());
+    let () = data.extend_from_slice(&build_id_len.to_ne_bytes());
+    let () = data.extend_from_slice(&secs.to_ne_bytes());
+    let () = data.extend_from_slice(&nsecs.to_ne_bytes());
+    let () = data.extend_from_slice(&count.to_ne_bytes());
+    let () = data.extend_from_slice(key.build_id);
+    let () = data.extend(std::iter::repeat(0).take(padding));
+    let () = write_indices(&mut data, by_addr)?;
+    let () = write_indices(&mut data, by_name)?;
+    Ok(data)
+}
+
+/// Deserialize a symbol index, checking that it belongs to the file
+/// identified by `key` and a symbol table containing `count` symbols.
+///
+/// `None` is returned if the index is stale, i.e., does not match
+/// `key` or `count` or uses a different format version. The returned
+/// lists have the same meaning as the arguments to [`serialize`] and
+/// reference `data` directly. The name ordered list always makes up
+/// the tail of `data`.
+pub(crate) fn deserialize<'data>(
+    mut data: &'data [u8],
+    key: &SymIndexKey<'_>,
+    count: usize,
+) -> Result<Option<(&'data [u32], &'data [u32])>> {
+    let magic = data
+        .read_slice(MAGIC.len())
+        .ok_or_invalid_data(|| "failed to read symbol index magic")?;
+    if magic != MAGIC {
+        return Err(Error::with_invalid_data("symbol index magic is invalid"))
+    }
+
+    let version = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read symbol index version")?;
+    if version != VERSION {
+        return Ok(None)
+    }
+
+    let build_id_len = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read symbol index build ID length")?;
+    let secs = data
+        .read_u64()
+        .ok_or_invalid_data(|| "failed to read symbol index modification time")?;
+    let nsecs = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read symbol index modification time")?;
+    let sym_count = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read symbol index symbol count")?;
+    let build_id = data
+        .read_slice(build_id_len as usize)
+        .ok_or_invalid_data(|| "failed to read symbol index build ID")?;
+    let _padding = data
+        .read_slice(build_id_padding(build_id.len()))
+        .ok_or_invalid_data(|| "symbol index is truncated")?;
+
+    if build_id != key.build_id || (secs, nsecs) != key.mtime_parts() || sym_count as usize != count
+    {
+        return Ok(None)
+    }
+
+    let by_addr = read_indices(&mut data, count, count)?;
+    let by_name = read_indices(&mut data, count, count)?;
+    if !data.is_empty() {
+        return Err(Error::with_invalid_data("symbol index has trailing data"))
+    }
+    Ok(Some((by_addr, by_name)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use test_log::test;
+
+
+    /// Check that we can serialize and deserialize a symbol index.
+    #[test]
+    fn serialization_roundtrip() {
+        let key = SymIndexKey {
+            build_id: &[0xde, 0xad, 0xbe, 0xef, 0x42],
+            mtime: UNIX_EPOCH + Duration::new(1337, 42),
+        };
+        let by_addr = [2, 0, 1];
+        let by_name = [1, 2, 0];
+        let data = serialize(&key, &by_addr, &by_name).unwrap();
+        let (addrs, names) = deserialize(&data, &key, 3).unwrap().unwrap();
+        assert_eq!(addrs, [2, 0, 1]);
+        assert_eq!(names, [1, 2, 0]);
+
+        // A mismatch in the key or symbol count renders the index
+        // stale.
+        let other = SymIndexKey {
+            build_id: &[0xde, 0xad, 0xbe, 0xef],
+            ..key
+        };
+        assert_eq!(deserialize(&data, &other, 3).unwrap(), None);
+        let other = SymIndexKey {
+            mtime: UNIX_EPOCH + Duration::new(1337, 43),
+            ..key
+        };
+        assert_eq!(deserialize(&data, &other, 3).unwrap(), None);
+        assert_eq!(deserialize(&data, &key, 4).unwrap(), None);
+    }
+
+    /// Check that index file names reflect the full key.
+    #[test]
+    fn index_file_name() {
+        let key = SymIndexKey {
+            build_id: &[0xde, 0xad, 0xbe, 0xef],
+            mtime: UNIX_EPOCH + Duration::new(1337, 42),
+        };
+        assert_eq!(key.file_name(), "deadbeef-1337.000000042.symidx");
+
+        let other = SymIndexKey {
+            mtime: UNIX_EPOCH + Duration::new(1337, 43),
+            ..key
+        };
+        assert_ne!(other.file_name(), key.file_name());
+    }
+
+    /// Make sure that we reject malformed symbol indices.
+    #[test]
+    fn malformed_deserialization() {
+        let key = SymIndexKey {
+            build_id: &[1, 2, 3, 4],
+            mtime: UNIX_EPOCH,
+        };
+        let data = serialize(&key, &[0, 1], &[1, 0]).unwrap();
+
+        let err = deserialize(b"NOTANIDX", &key, 2).unwrap_err();
+        assert!(err.to_string().contains("magic"), "{err}");
+        let result = deserialize(&data[..data.len() - 1], &key, 2);
+        assert!(result.is_err());
+        let mut extended = data.clone();
+        let () = extended.push(0);
+        let err = deserialize(&extended, &key, 2).unwrap_err();
+        assert!(err.to_string().contains("trailing"), "{err}");
+
+        let mut corrupted = data.clone();
+        let last = corrupted.len() - 4;
+        let () = corrupted[last..].copy_from_slice(&2u32.to_ne_bytes());
+        let err = deserialize(&corrupted, &key, 2).unwrap_err();
+        assert!(err.to_string().contains("out of bounds"), "{err}");
+    }
+}
@@ -1,10 +1,25 @@
+pub(crate) mod abi;
 mod backend;
+mod compress;
+pub(crate) mod group;
+mod hash;
+pub(crate) mod ident;
+mod index;
+mod overlay;
 mod parser;
+pub(crate) mod property;
+pub(crate) mod recover;
+pub(crate) mod reloc;
 #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
 mod resolver;
+pub(crate) mod segment;
+mod source;
 #[allow(dead_code, non_camel_case_types)]
 pub(crate) mod types;
 
 pub(crate) use backend::ElfBackend;
+pub(crate) use index::SymIndexKey;
+pub(crate) use overlay::OverlayResolver;
 pub(crate) use parser::ElfParser;
 pub(crate) use resolver::ElfResolver;
+pub use source::ElfSymSource;
@@ -0,0 +1,144 @@
+use std::ops::Range;
+
+use crate::inspect::FindAddrOpts;
+use crate::inspect::SymInfo;
+use crate::symbolize::AddrCodeInfo;
+use crate::symbolize::IntSym;
+use crate::symbolize::SrcLang;
+use crate::Addr;
+use crate::Error;
+use crate::Result;
+use crate::SymResolver;
+
+use super::types::Elf64_Shdr;
+use super::types::SHF_ALLOC;
+use super::types::STT_FUNC;
+use super::ElfResolver;
+use super::ElfSymSource;
+
+
+/// A symbol resolver scoping lookups of an [`ElfResolver`] to a single
+/// overlay.
+///
+/// Firmware making use of overlays (or bank switching) links multiple
+/// sections to the same virtual address range, with only one of them
+/// being mapped at any given time. Addresses in such a range are
+/// ambiguous and can only be attributed to a symbol once it is known
+/// which overlay was mapped.
+///
+/// All sections overlapping with the overlay's sections, but not being
+/// part of it, are considered excluded. Symbols defined in an excluded
+/// section are never reported. DWARF does not convey section
+/// membership, so for addresses covered by an excluded section only
+/// the ELF symbol tables are consulted and no source code information
+/// is reported. All other addresses are resolved as usual.
+#[derive(Debug)]
+pub(crate) struct OverlayResolver<'res> {
+    /// The resolver for the ELF file at large.
+    resolver: &'res ElfResolver,
+    /// The indices of excluded sections.
+    excluded: Vec<usize>,
+    /// The address ranges covered by excluded sections.
+    ranges: Vec<Range<Addr>>,
+}
+
+impl<'res> OverlayResolver<'res> {
+    /// Create a new `OverlayResolver` for the overlay comprised of the
+    /// sections with the provided names.
+    pub(crate) fn new(resolver: &'res ElfResolver, sections: &[String]) -> Result<Self> {
+        let parser = resolver.parser();
+        let shdrs = parser.section_headers()?;
+
+        let range = |shdr: &Elf64_Shdr| shdr.sh_addr..shdr.sh_addr.saturating_add(shdr.sh_size);
+
+        let mut overlay = Vec::with_capacity(sections.len());
+        for name in sections {
+            let idx = parser.find_section(name)?.ok_or_else(|| {
+                Error::with_not_found(format!(
+                    "overlay section `{name}` not found in {}",
+                    resolver.file_name().display()
+                ))
+            })?;
+            let () = overlay.push((idx, range(&shdrs[idx])));
+        }
+
+        let mut excluded = Vec::new();
+        let mut ranges = Vec::new();
+        for (idx, shdr) in shdrs.iter().enumerate() {
+            if shdr.sh_flags & SHF_ALLOC == 0
+                || overlay.iter().any(|(overlay_idx, _)| *overlay_idx == idx)
+            {
+                continue
+            }
+
+            let range = range(shdr);
+            let overlaps = overlay
+                .iter()
+                .any(|(_, other)| range.start < other.end && other.start < range.end);
+            if overlaps {
+                let () = excluded.push(idx);
+                let () = ranges.push(range);
+            }
+        }
+
+        Ok(Self {
+            resolver,
+            excluded,
+            ranges,
+        })
+    }
+
+    /// Check whether `addr` is covered by an excluded section and,
+    /// hence, ambiguous for all but ELF symbol table lookups.
+    fn is_ambiguous(&self, addr: Addr) -> bool {
+        self.ranges.iter().any(|range| range.contains(&addr))
+    }
+}
+
+impl SymResolver for OverlayResolver<'_> {
+    fn find_sym(&self, addr: Addr) -> Result<Option<IntSym<'_>>> {
+        if !self.is_ambiguous(addr) {
+            return self.resolver.find_sym(addr)
+        }
+
+        for parser in self.resolver.sym_parsers() {
+            if let Some((name, addr, size)) =
+                parser.find_sym_excluding(addr, STT_FUNC, &self.excluded)?
+            {
+                let sym = IntSym {
+                    name,
+                    addr,
+                    size: Some(size),
+                    lang: SrcLang::Unknown,
+                };
+                return Ok(Some(sym))
+            }
+        }
+        Ok(None)
+    }
+
+    fn find_sym_in(&self, addr: Addr, sources: &[ElfSymSource]) -> Result<Option<IntSym<'_>>> {
+        if !self.is_ambiguous(addr) {
+            return self.resolver.find_sym_in(addr, sources)
+        }
+        // Only the ELF symbol table can be consulted for ambiguous
+        // addresses to begin with.
+        self.find_sym(addr)
+    }
+
+    fn find_addr(&self, name: &str, opts: &FindAddrOpts) -> Result<Vec<SymInfo<'_>>> {
+        self.resolver.find_addr(name, opts)
+    }
+
+    fn find_code_info(
+        &self,
+        addr: Addr,
+        inlined_fns: bool,
+        line_only: bool,
+    ) -> Result<Option<AddrCodeInfo<'_>>> {
+        if self.is_ambiguous(addr) {
+            return Ok(None)
+        }
+        self.resolver.find_code_info(addr, inlined_fns, line_only)
+    }
+}
@@ -1,18 +1,26 @@
 use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::fs;
 use std::fs::File;
+use std::io;
 use std::mem;
 use std::ops::Deref as _;
 use std::path::Path;
+use std::process;
 
 use crate::inspect::FindAddrOpts;
 use crate::inspect::SymInfo;
 use crate::inspect::SymType;
+use crate::log::warn;
 use crate::mmap::Mmap;
 use crate::once::OnceCell;
+use crate::util::edit_distance_bounded;
 use crate::util::find_match_or_lower_bound_by_key;
+use crate::util::ReadAt;
 use crate::util::ReadRaw as _;
 use crate::Addr;
 use crate::Error;
@@ -20,16 +28,124 @@ use crate::ErrorExt as _;
 use crate::IntoError as _;
 use crate::Result;
 
+use super::abi::AbiTag;
+use super::abi::AbiTagOs;
+use super::compress::decompress_section;
+use super::group::SectionGroup;
+use super::hash::gnu_hash_lookup;
+use super::hash::gnu_hash_sym_count;
+use super::hash::sysv_hash_lookup;
+use super::hash::sysv_hash_sym_count;
+use super::index::deserialize as deserialize_sym_index;
+use super::index::serialize as serialize_sym_index;
+use super::index::SymIndexKey;
+use super::property::parse_gnu_property_notes;
+use super::property::GnuProperties;
+use super::recover::RecoveredTable;
+use super::reloc::relocate;
+use super::reloc::RelocInfo;
+use super::segment::TlsTemplate;
+use super::types::Elf64_Dyn;
 use super::types::Elf64_Ehdr;
+use super::types::Elf64_Nhdr;
 use super::types::Elf64_Phdr;
+use super::types::Elf64_Rel;
+use super::types::Elf64_Rela;
 use super::types::Elf64_Shdr;
 use super::types::Elf64_Sym;
+use super::types::DT_GNU_HASH;
+use super::types::DT_HASH;
+use super::types::DT_NULL;
+use super::types::DT_STRSZ;
+use super::types::DT_STRTAB;
+use super::types::DT_SYMENT;
+use super::types::DT_SYMTAB;
+use super::types::EI_ABIVERSION;
+use super::types::EI_CLASS;
+use super::types::EI_DATA;
+use super::types::EI_OSABI;
+use super::types::EM_AARCH64;
+use super::types::EM_X86_64;
+use super::types::NT_GNU_ABI_TAG;
+use super::types::NT_GO_BUILD_ID;
+use super::types::PF_X;
 use super::types::PN_XNUM;
+use super::types::PT_DYNAMIC;
+use super::types::PT_GNU_STACK;
 use super::types::PT_LOAD;
+use super::types::PT_TLS;
+use super::types::SHF_ALLOC;
+use super::types::SHF_COMPRESSED;
+use super::types::SHN_ABS;
+use super::types::SHN_COMMON;
+use super::types::SHN_LORESERVE;
 use super::types::SHN_UNDEF;
 use super::types::SHN_XINDEX;
+use super::types::SHT_DYNSYM;
+use super::types::SHT_GNU_HASH;
+use super::types::SHT_GROUP;
+use super::types::SHT_HASH;
+use super::types::SHT_NOBITS;
+use super::types::SHT_NOTE;
+use super::types::SHT_REL;
+use super::types::SHT_RELA;
+use super::types::SHT_SYMTAB;
+use super::types::STB_GLOBAL;
+use super::types::STB_LOCAL;
+use super::types::STB_WEAK;
 use super::types::STT_FUNC;
+use super::types::STT_TLS;
+
+
+/// Determine the size of the entries of the symbol table described by
+/// `shdr`.
+///
+/// Symbol tables may use entries larger than `Elf64_Sym`, in which case
+/// the standard fields are located at the front of each entry.
+fn sym_entsize(shdr: &Elf64_Shdr) -> Result<usize> {
+    let size = mem::size_of::<Elf64_Sym>();
+    match shdr.sh_type {
+        SHT_SYMTAB | SHT_DYNSYM if shdr.sh_entsize != 0 => {
+            let entsize = usize::try_from(shdr.sh_entsize)
+                .ok()
+                .filter(|entsize| *entsize >= size)
+                .ok_or_invalid_data(|| {
+                    format!("symbol table entry size ({}) is invalid", shdr.sh_entsize)
+                })?;
+            Ok(entsize)
+        }
+        _ => Ok(size),
+    }
+}
+
 
+/// Determine the (aligned) virtual address of the lowest loadable
+/// segment described by `phdrs`.
+fn load_base(phdrs: &[Elf64_Phdr]) -> Option<Addr> {
+    phdrs
+        .iter()
+        .filter(|phdr| phdr.p_type == PT_LOAD)
+        .map(|phdr| {
+            if phdr.p_align.is_power_of_two() {
+                phdr.p_vaddr & !(phdr.p_align - 1)
+            } else {
+                phdr.p_vaddr
+            }
+        })
+        .min()
+}
+
+/// Read the symbols contained in the raw symbol table `data`, with
+/// entries of size `entsize`.
+fn read_syms(data: &[u8], entsize: usize) -> Result<Vec<&Elf64_Sym>> {
+    data.chunks(entsize)
+        .map(|mut entry| {
+            entry
+                .read_pod_ref::<Elf64_Sym>()
+                .ok_or_invalid_data(|| "failed to read symbol table contents")
+        })
+        .collect()
+}
 
 fn symbol_name<'mmap>(strtab: &'mmap [u8], sym: &Elf64_Sym) -> Result<&'mmap str> {
     let name = strtab
@@ -44,6 +160,14 @@ fn symbol_name<'mmap>(strtab: &'mmap [u8], sym: &Elf64_Sym) -> Result<&'mmap str
     Ok(name)
 }
 
+/// Check whether `sym` has an address that it can be looked up by.
+///
+/// Undefined symbols have no address at all, while the value of a
+/// `SHN_COMMON` symbol is its alignment constraint.
+fn has_addr(sym: &Elf64_Sym) -> bool {
+    sym.st_shndx != SHN_UNDEF && sym.st_shndx != SHN_COMMON
+}
+
 fn find_sym<'mmap>(
     symtab: &[&Elf64_Sym],
     strtab: &'mmap [u8],
@@ -64,9 +188,14 @@ fn find_sym<'mmap>(
                 // In ELF, a symbol size of 0 indicates "no size or an unknown
                 // size" (see elf(5)). We take our changes and report these on a
                 // best-effort basis.
+                // Note that `sym.st_value <= addr` at this point, so the
+                // subtraction below cannot underflow.
+                // TLS symbols are never reported, as their values are
+                // not addresses.
                 if sym.type_() == st_type
-                    && sym.st_shndx != SHN_UNDEF
-                    && (sym.st_size == 0 || addr < sym.st_value + sym.st_size)
+                    && st_type != STT_TLS
+                    && has_addr(sym)
+                    && (sym.st_size == 0 || addr - sym.st_value < sym.st_size)
                 {
                     let name = symbol_name(strtab, sym)?;
                     let addr = sym.st_value as Addr;
@@ -79,6 +208,176 @@ fn find_sym<'mmap>(
     }
 }
 
+/// Determine the end addresses of all symbols in the address sorted
+/// `symtab`, in the same order.
+///
+/// A non-zero `st_size` takes precedence. For symbols without a size,
+/// the start address of the next higher symbol in the same section is
+/// used instead.
+fn sym_ends(symtab: &[&Elf64_Sym]) -> Box<[Option<Addr>]> {
+    let mut ends = vec![None; symtab.len()];
+    // For each section, the start address of the symbol visited last
+    // along with the next higher start address following it.
+    let mut next = HashMap::<u16, (Addr, Option<Addr>)>::new();
+
+    for (idx, sym) in symtab.iter().enumerate().rev() {
+        let start = sym.st_value as Addr;
+        let higher = match next.get(&sym.st_shndx) {
+            Some((last, _)) if *last > start => Some(*last),
+            Some((_, higher)) => *higher,
+            None => None,
+        };
+        let _prev = next.insert(sym.st_shndx, (start, higher));
+
+        if !has_addr(sym) {
+            continue
+        }
+        ends[idx] = if sym.st_size != 0 {
+            start.checked_add(sym.st_size)
+        } else {
+            higher
+        };
+    }
+    ends.into_boxed_slice()
+}
+
+/// Find the symbol of type `st_type` containing `addr`, disregarding
+/// all symbols defined in one of the sections with the indices
+/// `excluded`.
+///
+/// The closest symbol preceding `addr` that is not excluded is
+/// reported if it contains `addr` (or has no size).
+fn find_sym_excluding<'mmap>(
+    symtab: &[&Elf64_Sym],
+    strtab: &'mmap [u8],
+    addr: Addr,
+    st_type: u8,
+    excluded: &[usize],
+) -> Result<Option<(&'mmap str, Addr, usize)>> {
+    let end = symtab.partition_point(|sym| (sym.st_value as Addr) <= addr);
+    let sym = symtab[..end].iter().rev().find(|sym| {
+        sym.type_() == st_type
+            && st_type != STT_TLS
+            && has_addr(sym)
+            && !excluded.contains(&usize::from(sym.st_shndx))
+    });
+
+    match sym {
+        Some(sym) if sym.st_size == 0 || addr - sym.st_value < sym.st_size => {
+            let name = symbol_name(strtab, sym)?;
+            let addr = sym.st_value as Addr;
+            let size = usize::try_from(sym.st_size).unwrap_or(usize::MAX);
+            Ok(Some((name, addr, size)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Find the symbol of type `st_type` starting exactly at `addr`.
+fn find_sym_exact<'mmap>(
+    symtab: &[&Elf64_Sym],
+    strtab: &'mmap [u8],
+    addr: Addr,
+    st_type: u8,
+) -> Result<Option<(&'mmap str, Addr, usize)>> {
+    let idx = symtab.partition_point(|sym| (sym.st_value as Addr) < addr);
+    let sym = symtab[idx..]
+        .iter()
+        .take_while(|sym| sym.st_value as Addr == addr)
+        .find(|sym| sym.type_() == st_type && st_type != STT_TLS && has_addr(sym));
+
+    match sym {
+        Some(sym) => {
+            let name = symbol_name(strtab, sym)?;
+            let size = usize::try_from(sym.st_size).unwrap_or(usize::MAX);
+            Ok(Some((name, addr, size)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Rank a symbol binding, with lower values being preferable.
+///
+/// Global symbols come before weak ones, which come before local ones.
+fn binding_rank(binding: u8) -> u8 {
+    match binding {
+        STB_GLOBAL => 0,
+        STB_WEAK => 1,
+        STB_LOCAL => 2,
+        _ => 3,
+    }
+}
+
+
+/// Find all symbols of type `st_type` that contain `addr` or start
+/// exactly at it.
+///
+/// The end of symbols without a size is determined as per
+/// [`sym_ends`]. If it cannot be determined, such a symbol is only
+/// considered if it is one of the closest ones preceding `addr`, just
+/// as [`find_sym`] would.
+///
+/// Symbols are reported in order of preference:
+/// - symbols starting closer to `addr` come first
+/// - among symbols starting at the same address, ones with a size come
+///   before ones without
+/// - after that, global symbols come before weak ones, which come
+///   before local ones
+///
+/// Symbols for which all of the above is equal are reported in symbol
+/// table order.
+fn find_syms<'mmap>(
+    symtab: &[&Elf64_Sym],
+    ends: &[Option<Addr>],
+    strtab: &'mmap [u8],
+    addr: Addr,
+    st_type: u8,
+) -> Result<Vec<(&'mmap str, Addr, usize)>> {
+    if st_type == STT_TLS {
+        return Ok(Vec::new())
+    }
+
+    // All candidates start at or before `addr`. Because symbols with a
+    // size may span arbitrarily large ranges, we have no choice but to
+    // check all of them.
+    let end = symtab.partition_point(|sym| (sym.st_value as Addr) <= addr);
+    let closest = end.checked_sub(1).map(|idx| symtab[idx].st_value);
+
+    let mut syms = symtab[..end]
+        .iter()
+        .enumerate()
+        .filter(|(idx, sym)| {
+            if sym.type_() != st_type || !has_addr(sym) {
+                return false
+            }
+            if sym.st_value as Addr == addr {
+                return true
+            }
+            match ends[*idx] {
+                Some(end) => addr < end,
+                None => sym.st_size == 0 && Some(sym.st_value) == closest,
+            }
+        })
+        .map(|(_idx, sym)| *sym)
+        .collect::<Vec<_>>();
+
+    let () = syms.sort_by_key(|sym| {
+        (
+            Reverse(sym.st_value),
+            sym.st_size == 0,
+            binding_rank(sym.binding()),
+        )
+    });
+
+    syms.into_iter()
+        .map(|sym| {
+            let name = symbol_name(strtab, sym)?;
+            let size = usize::try_from(sym.st_size).unwrap_or(usize::MAX);
+            Ok((name, sym.st_value as Addr, size))
+        })
+        .collect()
+}
+
 
 #[derive(Clone, Copy, Debug)]
 struct EhdrExt<'mmap> {
@@ -93,6 +392,29 @@ struct EhdrExt<'mmap> {
 }
 
 
+/// A symbol table in address order, along with its string table.
+type DynSymtab<'mmap> = (Box<[&'mmap Elf64_Sym]>, &'mmap [u8]);
+
+
+/// Symbol related tables located via the dynamic segment.
+#[derive(Debug, Default)]
+struct DynamicTables<'mmap> {
+    /// The symbol table (`DT_SYMTAB`), along with its entry size.
+    symtab: Option<(&'mmap [u8], usize)>,
+    /// The string table (`DT_STRTAB`).
+    strtab: Option<&'mmap [u8]>,
+    /// Whether the size of the symbol table was inferred from the
+    /// System V hash table (`DT_HASH`).
+    hash: bool,
+    /// Whether the size of the symbol table was inferred from the GNU
+    /// hash table (`DT_GNU_HASH`).
+    gnu_hash: bool,
+}
+
+
+/// Decompressed section data, indexed by section.
+type DecompressedSections = Box<[OnceCell<Option<Box<[u8]>>>]>;
+
 struct Cache<'mmap> {
     /// A slice of the raw ELF data that we are about to parse.
     elf_data: &'mmap [u8],
@@ -104,9 +426,33 @@ struct Cache<'mmap> {
     /// The cached ELF program headers.
     phdrs: OnceCell<&'mmap [Elf64_Phdr]>,
     symtab: OnceCell<Box<[&'mmap Elf64_Sym]>>, // in address order
+    /// The cached end addresses of the symbols in `symtab`, in the
+    /// same order.
+    sym_ends: OnceCell<Box<[Option<Addr>]>>,
     /// The cached ELF string table.
     strtab: OnceCell<&'mmap [u8]>,
     str2symtab: OnceCell<Box<[(&'mmap str, usize)]>>, // strtab offset to symtab in the dictionary order
+    /// The name ordered part of a memory mapped symbol index that
+    /// `str2symtab` is to be initialized from, if any.
+    sym_index: OnceCell<Mmap>,
+    /// The cached decompressed section data. `None` indicates that a
+    /// section turned out not to be compressed.
+    decompressed: OnceCell<DecompressedSections>,
+    /// The load addresses of sections of a relocatable file, keyed by
+    /// section name, if provided.
+    section_addrs: Option<HashMap<String, Addr>>,
+    /// The cached relocated section data. `None` indicates that a
+    /// section has no relocations.
+    relocated: OnceCell<DecompressedSections>,
+    /// Whether to parse leniently, recovering whatever information is
+    /// available from a potentially corrupted image.
+    lenient: bool,
+    /// The cached symbol related tables referenced by the dynamic
+    /// segment.
+    dynamic: OnceCell<Option<DynamicTables<'mmap>>>,
+    /// The cached `.dynsym` section, if it is not the symbol table in
+    /// use.
+    dynsym: OnceCell<Option<DynSymtab<'mmap>>>,
 }
 
 impl<'mmap> Cache<'mmap> {
@@ -119,8 +465,16 @@ impl<'mmap> Cache<'mmap> {
             shstrtab: OnceCell::new(),
             phdrs: OnceCell::new(),
             symtab: OnceCell::new(),
+            sym_ends: OnceCell::new(),
             strtab: OnceCell::new(),
             str2symtab: OnceCell::new(),
+            sym_index: OnceCell::new(),
+            decompressed: OnceCell::new(),
+            section_addrs: None,
+            relocated: OnceCell::new(),
+            lenient: false,
+            dynamic: OnceCell::new(),
+            dynsym: OnceCell::new(),
         }
     }
 
@@ -141,6 +495,100 @@ impl<'mmap> Cache<'mmap> {
         Ok(data)
     }
 
+    /// Retrieve the data for the ELF section at index `idx`,
+    /// decompressing it if necessary.
+    fn decompressed_section_data(&self, idx: usize) -> Result<&[u8]> {
+        let shdrs = self.ensure_shdrs()?;
+        let data = self.section_data(idx)?;
+        // SANITY: `section_data` would have failed for an invalid
+        //         index.
+        let shdr = &shdrs[idx];
+        let gnu = self.section_name(idx)?.starts_with(".zdebug_");
+        if shdr.sh_flags & SHF_COMPRESSED == 0 && !gnu {
+            return Ok(data)
+        }
+
+        let cells = self
+            .decompressed
+            .get_or_init(|| shdrs.iter().map(|_| OnceCell::new()).collect());
+        let decompressed = cells[idx].get_or_try_init(|| {
+            let decompressed = decompress_section(data, shdr.sh_flags, gnu)
+                .with_context(|| format!("failed to decompress ELF section {idx}"))?;
+            Result::<_, Error>::Ok(decompressed.map(Vec::into_boxed_slice))
+        })?;
+        Ok(decompressed.as_deref().unwrap_or(data))
+    }
+
+    /// Retrieve the data for the ELF section at index `idx`,
+    /// decompressing it and applying relocations, if necessary.
+    ///
+    /// Relocations are only applied if section load addresses were
+    /// provided.
+    fn relocated_section_data(&self, idx: usize) -> Result<&[u8]> {
+        let data = self.decompressed_section_data(idx)?;
+        let section_addrs = match &self.section_addrs {
+            Some(section_addrs) => section_addrs,
+            None => return Ok(data),
+        };
+
+        let shdrs = self.ensure_shdrs()?;
+        let cells = self
+            .relocated
+            .get_or_init(|| shdrs.iter().map(|_| OnceCell::new()).collect());
+        let relocated = cells[idx].get_or_try_init(|| {
+            let rela_idx = shdrs
+                .iter()
+                .position(|shdr| shdr.sh_type == SHT_RELA && shdr.sh_info as usize == idx);
+            let rela_idx = match rela_idx {
+                Some(rela_idx) => rela_idx,
+                None => return Ok(None),
+            };
+            // SANITY: We just found the section at this index.
+            let rela_shdr = &shdrs[rela_idx];
+            let relas = self.section_data(rela_idx)?;
+            let symtab = self.section_data(rela_shdr.sh_link as usize)?;
+            // SANITY: `section_data` would have failed for an invalid
+            //         index.
+            let entsize = sym_entsize(&shdrs[rela_shdr.sh_link as usize])?;
+            let machine = self.ensure_ehdr()?.ehdr.e_machine;
+
+            let sym_value = |sym_idx: u32| -> Result<Option<Addr>> {
+                let sym = symtab
+                    .get(sym_idx as usize * entsize..)
+                    .and_then(|mut data| data.read_pod_ref::<Elf64_Sym>())
+                    .ok_or_invalid_data(|| {
+                        format!("relocation references invalid symbol #{sym_idx}")
+                    })?;
+
+                let base = match sym.st_shndx {
+                    SHN_ABS => 0,
+                    SHN_UNDEF => return Ok(None),
+                    shndx if shndx >= SHN_LORESERVE => return Ok(None),
+                    shndx => {
+                        let shndx = usize::from(shndx);
+                        let shdr = shdrs.get(shndx).ok_or_invalid_data(|| {
+                            format!("symbol #{sym_idx} references invalid section #{shndx}")
+                        })?;
+                        match section_addrs.get(self.section_name(shndx)?) {
+                            Some(addr) => *addr,
+                            // Sections not loaded at runtime, such as
+                            // debug sections, are never placed and
+                            // addressed relative to their start.
+                            None if shdr.sh_flags & SHF_ALLOC == 0 => 0,
+                            None => return Ok(None),
+                        }
+                    }
+                };
+                Ok(Some(base.wrapping_add(sym.st_value)))
+            };
+
+            let relocated = relocate(data, relas, machine, sym_value)
+                .with_context(|| format!("failed to relocate ELF section {idx}"))?;
+            Result::<_, Error>::Ok(Some(relocated))
+        })?;
+        Ok(relocated.as_deref().unwrap_or(data))
+    }
+
     /// Read the very first section header.
     ///
     /// ELF contains a couple of clauses that special case data ranges
@@ -177,14 +625,23 @@ impl<'mmap> Cache<'mmap> {
         // or equal to SHN_LORESERVE, e_shnum holds the value zero and the real
         // number of entries in the section header table is held in the sh_size
         // member of the initial entry in section header table."
-        let shnum = if ehdr.e_shnum == 0 {
-            let shdr = self.read_first_shdr(ehdr)?;
-            usize::try_from(shdr.sh_size).ok().ok_or_invalid_data(|| {
-                format!(
-                    "ELF file contains unsupported number of sections ({})",
-                    shdr.sh_size
-                )
-            })?
+        let shnum = if ehdr.e_shoff == 0 {
+            // An offset of zero indicates that there is no section
+            // header table.
+            0
+        } else if ehdr.e_shnum == 0 {
+            match self.read_first_shdr(ehdr) {
+                Ok(shdr) => usize::try_from(shdr.sh_size).ok().ok_or_invalid_data(|| {
+                    format!(
+                        "ELF file contains unsupported number of sections ({})",
+                        shdr.sh_size
+                    )
+                })?,
+                // Section headers may have been overwritten or not be
+                // part of a memory image at all.
+                Err(_) if self.lenient => 0,
+                Err(err) => return Err(err),
+            }
         } else {
             ehdr.e_shnum.into()
         };
@@ -215,6 +672,19 @@ impl<'mmap> Cache<'mmap> {
     }
 
     fn parse_shdrs(&self) -> Result<&'mmap [Elf64_Shdr]> {
+        match self.read_shdrs() {
+            Ok(shdrs) => Ok(shdrs),
+            // A memory image may well lack section headers or contain
+            // garbage in their place. Just act as if there were none.
+            Err(err) if self.lenient => {
+                warn!("ignoring unusable section headers: {err}");
+                Ok(&[])
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn read_shdrs(&self) -> Result<&'mmap [Elf64_Shdr]> {
         let ehdr = self.ensure_ehdr()?;
         let shdrs = self
             .elf_data
@@ -222,6 +692,26 @@ impl<'mmap> Cache<'mmap> {
             .ok_or_invalid_data(|| "Elf64_Ehdr::e_shoff is invalid")?
             .read_pod_slice_ref::<Elf64_Shdr>(ehdr.shnum)
             .ok_or_invalid_data(|| "failed to read Elf64_Shdr")?;
+
+        // Validate section bounds upfront, so that a single corrupt
+        // section header is reported as such instead of surfacing as
+        // some obscure read failure down the line.
+        let len = self.elf_data.len() as u64;
+        for (idx, shdr) in shdrs.iter().enumerate() {
+            // `SHT_NOBITS` sections (e.g., `.bss`) occupy no space in
+            // the file.
+            if shdr.sh_type == SHT_NOBITS {
+                continue
+            }
+
+            let end = shdr.sh_offset.checked_add(shdr.sh_size);
+            if !matches!(end, Some(end) if end <= len) {
+                return Err(Error::with_invalid_data(format!(
+                    "ELF section #{idx} (offset {:#x}, size {:#x}) exceeds file size ({len:#x})",
+                    shdr.sh_offset, shdr.sh_size
+                )))
+            }
+        }
         Ok(shdrs)
     }
 
@@ -309,8 +799,10 @@ impl<'mmap> Cache<'mmap> {
     ///
     /// This function return the index of the section if found.
     fn find_section(&self, name: &str) -> Result<Option<usize>> {
-        let ehdr = self.ensure_ehdr()?;
-        for i in 1..ehdr.shnum {
+        // Section headers may have been discarded in lenient mode, so
+        // don't rely on the count reported by the ELF header.
+        let shdrs = self.ensure_shdrs()?;
+        for i in 1..shdrs.len() {
             if self.section_name(i)? == name {
                 return Ok(Some(i))
             }
@@ -318,38 +810,258 @@ impl<'mmap> Cache<'mmap> {
         Ok(None)
     }
 
-    fn parse_symtab(&self) -> Result<Box<[&'mmap Elf64_Sym]>> {
-        let idx = if let Some(idx) = self.find_section(".symtab")? {
-            idx
-        } else if let Some(idx) = self.find_section(".dynsym")? {
+    /// Translate the virtual address `addr` into an offset into the
+    /// ELF data.
+    ///
+    /// If `image` is `true`, the data are assumed to be laid out as a
+    /// memory image, i.e., as mapped by the loader. Otherwise the file
+    /// layout as described by the program headers is used.
+    fn addr_offset(&self, addr: Addr, image: bool) -> Result<Option<usize>> {
+        let phdrs = self.ensure_phdrs()?;
+        let offset = if image {
+            load_base(phdrs).and_then(|base| addr.checked_sub(base))
+        } else {
+            phdrs.iter().find_map(|phdr| {
+                let offset = addr.checked_sub(phdr.p_vaddr)?;
+                (phdr.p_type == PT_LOAD && offset < phdr.p_filesz)
+                    .then(|| phdr.p_offset.checked_add(offset))
+                    .flatten()
+            })
+        };
+        let offset = offset
+            .and_then(|offset| usize::try_from(offset).ok())
+            .filter(|offset| *offset < self.elf_data.len());
+        Ok(offset)
+    }
+
+    /// Find the entries of the dynamic segment (`PT_DYNAMIC`).
+    ///
+    /// The second member of the returned tuple indicates whether the
+    /// data are laid out as a memory image.
+    fn find_dynamic(&self) -> Result<Option<(&'mmap [Elf64_Dyn], bool)>> {
+        let phdrs = self.ensure_phdrs()?;
+        let phdr = match phdrs.iter().find(|phdr| phdr.p_type == PT_DYNAMIC) {
+            Some(phdr) => phdr,
+            None => return Ok(None),
+        };
+        let count =
+            usize::try_from(phdr.p_filesz).unwrap_or(usize::MAX) / mem::size_of::<Elf64_Dyn>();
+
+        // In a memory image the dynamic segment is located at its
+        // virtual address, while in a file it is at its file offset.
+        // We don't know what we are dealing with, so try both.
+        let candidates = [
+            (self.addr_offset(phdr.p_vaddr, true)?, true),
+            (usize::try_from(phdr.p_offset).ok(), false),
+        ];
+        for (offset, image) in candidates {
+            let entries = offset
+                .and_then(|offset| self.elf_data.get(offset..))
+                .and_then(|mut data| data.read_pod_slice_ref::<Elf64_Dyn>(count));
+            if let Some(entries) = entries {
+                // Only accept data that plausibly constitute the
+                // dynamic segment.
+                let valid = entries
+                    .iter()
+                    .take_while(|entry| entry.d_tag != DT_NULL)
+                    .any(|entry| entry.d_tag == DT_SYMTAB);
+                if valid {
+                    return Ok(Some((entries, image)))
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn parse_dynamic_tables(&self) -> Result<Option<DynamicTables<'mmap>>> {
+        let (entries, image) = match self.find_dynamic()? {
+            Some(dynamic) => dynamic,
+            None => return Ok(None),
+        };
+
+        let mut symtab = None;
+        let mut strtab = None;
+        let mut strsz = None;
+        let mut syment = None;
+        let mut hash = None;
+        let mut gnu_hash = None;
+        for entry in entries {
+            match entry.d_tag {
+                DT_NULL => break,
+                DT_SYMTAB => symtab = Some(entry.d_val),
+                DT_STRTAB => strtab = Some(entry.d_val),
+                DT_STRSZ => strsz = Some(entry.d_val),
+                DT_SYMENT => syment = Some(entry.d_val),
+                DT_HASH => hash = Some(entry.d_val),
+                DT_GNU_HASH => gnu_hash = Some(entry.d_val),
+                _ => (),
+            }
+        }
+
+        let data_at = |addr| -> Result<Option<&'mmap [u8]>> {
+            let data = self
+                .addr_offset(addr, image)?
+                .and_then(|offset| self.elf_data.get(offset..));
+            Ok(data)
+        };
+
+        let mut tables = DynamicTables::default();
+        if let Some(addr) = strtab {
+            tables.strtab = data_at(addr)?.map(|data| match strsz {
+                Some(size) => &data[..data.len().min(size as usize)],
+                None => data,
+            });
+        }
+
+        let entsize = syment
+            .and_then(|size| usize::try_from(size).ok())
+            .filter(|size| *size >= mem::size_of::<Elf64_Sym>())
+            .unwrap_or(mem::size_of::<Elf64_Sym>());
+        // The symbol table's size is not recorded anywhere. It has to
+        // be inferred from one of the hash tables instead.
+        let mut count = None;
+        if let Some(table) = gnu_hash.map(data_at).transpose()?.flatten() {
+            match gnu_hash_sym_count(table) {
+                Ok(n) => {
+                    count = Some(n);
+                    tables.gnu_hash = true;
+                }
+                Err(err) => warn!("failed to recover GNU hash table: {err}"),
+            }
+        }
+        if count.is_none() {
+            if let Some(table) = hash.map(data_at).transpose()?.flatten() {
+                match sysv_hash_sym_count(table) {
+                    Ok(n) => {
+                        count = Some(n);
+                        tables.hash = true;
+                    }
+                    Err(err) => warn!("failed to recover hash table: {err}"),
+                }
+            }
+        }
+        if count.is_none() {
+            // As a last resort, rely on the string table conventionally
+            // directly following the symbol table.
+            count = symtab
+                .zip(strtab)
+                .and_then(|(symtab, strtab)| strtab.checked_sub(symtab))
+                .map(|size| usize::try_from(size).unwrap_or(usize::MAX) / entsize);
+        }
+
+        if let (Some(addr), Some(count)) = (symtab, count) {
+            tables.symtab = data_at(addr)?
+                .and_then(|data| data.get(..count.checked_mul(entsize)?))
+                .map(|data| (data, entsize));
+        }
+        Ok(Some(tables))
+    }
+
+    fn ensure_dynamic_tables(&self) -> Result<Option<&DynamicTables<'mmap>>> {
+        let tables = self
+            .dynamic
+            .get_or_try_init(|| self.parse_dynamic_tables())?
+            .as_ref();
+        Ok(tables)
+    }
+
+    /// Check whether symbols should be recovered based on the dynamic
+    /// segment, because no usable symbol table section is available.
+    ///
+    /// That is only ever the case when parsing leniently.
+    fn use_dynamic_syms(&self) -> bool {
+        if !self.lenient {
+            return false
+        }
+
+        match self.find_symtab_section() {
+            Ok(Some(idx)) => self.section_data(idx).is_err(),
+            Ok(None) | Err(_) => true,
+        }
+    }
+
+    /// Find the index of the section containing the symbol table to
+    /// use.
+    ///
+    /// `.symtab` is preferred over `.dynsym`, as it generally is a
+    /// superset of the latter. Neither may be present: fully static
+    /// binaries (e.g., linked against musl) lack `.dynsym`, while
+    /// stripped ones lack `.symtab`.
+    fn find_symtab_section(&self) -> Result<Option<usize>> {
+        let shdrs = self.ensure_shdrs()?;
+        let find = |sh_type| shdrs.iter().position(|shdr| shdr.sh_type == sh_type);
+        Ok(find(SHT_SYMTAB).or_else(|| find(SHT_DYNSYM)))
+    }
+
+    /// Retrieve the raw data of the symbol table in use, along with
+    /// the size of each of its entries.
+    fn symtab_data(&self) -> Result<Option<(&'mmap [u8], usize)>> {
+        if self.use_dynamic_syms() {
+            let symtab = self
+                .ensure_dynamic_tables()?
+                .and_then(|tables| tables.symtab);
+            return Ok(symtab)
+        }
+
+        let idx = if let Some(idx) = self.find_symtab_section()? {
             idx
         } else {
-            // Neither symbol table exists. Fake an empty one.
-            return Ok(Box::default())
+            return Ok(None)
         };
-        let mut symtab = self.section_data(idx)?;
+        self.symtab_section_data(idx).map(Some)
+    }
 
-        if symtab.len() % mem::size_of::<Elf64_Sym>() != 0 {
+    /// Retrieve the raw data of the symbol table contained in the
+    /// section at index `idx`, along with the size of each of its
+    /// entries.
+    fn symtab_section_data(&self, idx: usize) -> Result<(&'mmap [u8], usize)> {
+        let symtab = self.section_data(idx)?;
+        // SANITY: `section_data` would have failed for an invalid index.
+        let entsize = sym_entsize(&self.ensure_shdrs()?[idx])?;
+
+        if symtab.len() % entsize != 0 {
             return Err(Error::with_invalid_data(
                 "size of symbol table section is invalid",
             ))
         }
+        Ok((symtab, entsize))
+    }
+
+    /// Read the symbol table in use, in its original order.
+    fn read_symtab(&self) -> Result<Vec<&'mmap Elf64_Sym>> {
+        match self.symtab_data()? {
+            Some((symtab, entsize)) => read_syms(symtab, entsize),
+            // Neither symbol table exists. Fake an empty one.
+            None => Ok(Vec::new()),
+        }
+    }
 
-        let count = symtab.len() / mem::size_of::<Elf64_Sym>();
-        let mut symtab = symtab
-            .read_pod_slice_ref::<Elf64_Sym>(count)
-            .ok_or_invalid_data(|| "failed to read symbol table contents")?
-            .iter()
-            .collect::<Vec<&Elf64_Sym>>()
-            .into_boxed_slice();
-        // Order symbols by address and those with equal address descending by
-        // size.
-        let () = symtab.sort_by(|sym1, sym2| {
+    /// Read the symbol table contained in the section at index `idx`,
+    /// in its original order.
+    fn read_symtab_section(&self, idx: usize) -> Result<Vec<&'mmap Elf64_Sym>> {
+        let (symtab, entsize) = self.symtab_section_data(idx)?;
+        read_syms(symtab, entsize)
+    }
+
+    /// Determine the order of the symbols in `syms` by address, with
+    /// those with equal address ordered descending by size.
+    fn sym_addr_order(syms: &[&Elf64_Sym]) -> Vec<usize> {
+        let mut order = (0..syms.len()).collect::<Vec<_>>();
+        let () = order.sort_by(|idx1, idx2| {
+            let (sym1, sym2) = (syms[*idx1], syms[*idx2]);
             sym1.st_value
                 .cmp(&sym2.st_value)
                 .then_with(|| sym1.st_size.cmp(&sym2.st_size).reverse())
         });
+        order
+    }
 
+    fn parse_symtab(&self) -> Result<Box<[&'mmap Elf64_Sym]>> {
+        let syms = self.read_symtab()?;
+        let symtab = Self::sym_addr_order(&syms)
+            .into_iter()
+            .map(|idx| syms[idx])
+            .collect();
         Ok(symtab)
     }
 
@@ -358,10 +1070,49 @@ impl<'mmap> Cache<'mmap> {
         Ok(symtab)
     }
 
+    fn ensure_sym_ends(&self) -> Result<&[Option<Addr>]> {
+        let ends = self
+            .sym_ends
+            .get_or_try_init(|| self.ensure_symtab().map(sym_ends))?
+            .deref();
+        Ok(ends)
+    }
+
+    /// Retrieve the string table belonging to the symbol table in use
+    /// (see [`Cache::find_symtab_section`]).
     fn parse_strtab(&self) -> Result<&'mmap [u8]> {
-        let strtab = if let Some(idx) = self.find_section(".strtab")? {
-            self.section_data(idx)?
-        } else if let Some(idx) = self.find_section(".dynstr")? {
+        if self.use_dynamic_syms() {
+            let strtab = self
+                .ensure_dynamic_tables()?
+                .and_then(|tables| tables.strtab)
+                .unwrap_or(&[]);
+            return Ok(strtab)
+        }
+
+        let idx = if let Some(idx) = self.find_symtab_section()? {
+            idx
+        } else {
+            return Ok(&[])
+        };
+        self.symtab_section_strtab(idx)
+    }
+
+    /// Retrieve the string table belonging to the symbol table
+    /// contained in the section at index `idx`.
+    fn symtab_section_strtab(&self, idx: usize) -> Result<&'mmap [u8]> {
+        let shdr = self
+            .ensure_shdrs()?
+            .get(idx)
+            .ok_or_invalid_input(|| format!("ELF section index ({idx}) out of bounds"))?;
+        // The string table is referenced by the symbol table's `sh_link`.
+        // Fall back to looking it up by name if the link is missing.
+        let strtab_idx = match shdr.sh_link {
+            0 if shdr.sh_type == SHT_SYMTAB => self.find_section(".strtab")?,
+            0 => self.find_section(".dynstr")?,
+            link => Some(link as usize),
+        };
+
+        let strtab = if let Some(idx) = strtab_idx {
             self.section_data(idx)?
         } else {
             &[]
@@ -373,7 +1124,53 @@ impl<'mmap> Cache<'mmap> {
         self.strtab.get_or_try_init(|| self.parse_strtab()).copied()
     }
 
+    fn parse_dynsym(&self) -> Result<Option<DynSymtab<'mmap>>> {
+        let shdrs = self.ensure_shdrs()?;
+        let idx = match shdrs.iter().position(|shdr| shdr.sh_type == SHT_DYNSYM) {
+            Some(idx) => idx,
+            None => return Ok(None),
+        };
+
+        let syms = self.read_symtab_section(idx)?;
+        let symtab = Self::sym_addr_order(&syms)
+            .into_iter()
+            .map(|idx| syms[idx])
+            .collect();
+        let strtab = self.symtab_section_strtab(idx)?;
+        Ok(Some((symtab, strtab)))
+    }
+
+    /// Retrieve the dynamic symbol table (`.dynsym`) in address order,
+    /// along with its string table, if present.
+    fn ensure_dynsym(&self) -> Result<Option<(&[&'mmap Elf64_Sym], &'mmap [u8])>> {
+        // If `.dynsym` is the symbol table in use anyway, there is no
+        // point in parsing it a second time.
+        if !self.use_dynamic_syms() {
+            if let Some(idx) = self.find_symtab_section()? {
+                if self.ensure_shdrs()?[idx].sh_type == SHT_DYNSYM {
+                    let symtab = self.ensure_symtab()?;
+                    let strtab = self.ensure_strtab()?;
+                    return Ok(Some((symtab, strtab)))
+                }
+            }
+        }
+
+        let dynsym = self
+            .dynsym
+            .get_or_try_init(|| self.parse_dynsym())?
+            .as_ref()
+            .map(|(symtab, strtab)| (symtab.deref(), *strtab));
+        Ok(dynsym)
+    }
+
     fn parse_str2symtab(&self) -> Result<Box<[(&'mmap str, usize)]>> {
+        if let Some(by_name) = self.sym_index.get() {
+            match self.str2symtab_from_index(by_name) {
+                Ok(str2symtab) => return Ok(str2symtab),
+                Err(err) => warn!("ignoring invalid symbol index: {err}"),
+            }
+        }
+
         let strtab = self.ensure_strtab()?;
         let symtab = self.ensure_symtab()?;
 
@@ -405,6 +1202,100 @@ impl<'mmap> Cache<'mmap> {
             .deref();
         Ok(str2symtab)
     }
+
+    /// Create a symbol index capturing the order of the symbol table,
+    /// for the file identified by `key`.
+    fn create_sym_index(&self, key: &SymIndexKey<'_>) -> Result<Vec<u8>> {
+        let syms = self.read_symtab()?;
+        let by_addr = Self::sym_addr_order(&syms);
+        let by_name = self
+            .ensure_str2symtab()?
+            .iter()
+            .map(|(_name, idx)| *idx)
+            .collect::<Vec<_>>();
+        serialize_sym_index(key, &by_addr, &by_name)
+    }
+
+    /// Initialize the symbol table caches from the serialized symbol
+    /// index `index`.
+    ///
+    /// The address ordered symbol table is set up right away, by
+    /// looking up symbols directly in the raw symbol table data. The
+    /// name ordered part of the index is kept mapped and only consulted
+    /// once it is actually needed (see [`Cache::parse_str2symtab`]).
+    ///
+    /// `false` is returned if the index does not belong to the file
+    /// identified by `key` or was created for a different symbol table.
+    fn load_sym_index(&self, index: Mmap, key: &SymIndexKey<'_>) -> Result<bool> {
+        let (data, entsize) = self.symtab_data()?.unwrap_or_default();
+        let count = data.len().checked_div(entsize).unwrap_or(0);
+        let (by_addr, by_name) = match deserialize_sym_index(&index, key, count)? {
+            Some(orders) => orders,
+            None => return Ok(false),
+        };
+
+        let symtab = by_addr
+            .iter()
+            .map(|idx| {
+                data.get(*idx as usize * entsize..)
+                    .and_then(|mut entry| entry.read_pod_ref::<Elf64_Sym>())
+                    .ok_or_invalid_data(|| "failed to read symbol table contents")
+            })
+            .collect::<Result<Box<[_]>>>()?;
+        // A bogus index must not break our lookups, which rely on the
+        // symbols being sorted.
+        if !symtab
+            .windows(2)
+            .all(|syms| syms[0].st_value <= syms[1].st_value)
+        {
+            return Err(Error::with_invalid_data(
+                "symbol index is not sorted by address",
+            ))
+        }
+
+        // The name ordered indices make up the tail of the index.
+        let len = index.len() as u64;
+        let by_name_len = mem::size_of_val(by_name) as u64;
+        // SANITY: The name ordered indices are contained in `index`.
+        let by_name = index.constrain(len - by_name_len..len).unwrap();
+
+        // The cache may have been initialized already, in which case
+        // there is nothing to be gained from the index.
+        if self.symtab.set(symtab).is_ok() {
+            let _result = self.sym_index.set(by_name);
+        }
+        Ok(true)
+    }
+
+    /// Create the name ordered symbol index from `by_name`, the
+    /// name ordered part of a previously loaded symbol index.
+    fn str2symtab_from_index(&self, mut by_name: &[u8]) -> Result<Box<[(&'mmap str, usize)]>> {
+        let strtab = self.ensure_strtab()?;
+        let symtab = self.ensure_symtab()?;
+        let by_name = by_name
+            .read_pod_slice_ref::<u32>(symtab.len())
+            .ok_or_invalid_data(|| "failed to read symbol index name order")?;
+
+        let str2symtab = by_name
+            .iter()
+            .map(|idx| {
+                let idx = *idx as usize;
+                let sym = symtab
+                    .get(idx)
+                    .ok_or_invalid_data(|| format!("symbol index entry {idx} is out of bounds"))?;
+                Ok((symbol_name(strtab, sym)?, idx))
+            })
+            .collect::<Result<Box<[_]>>>()?;
+        if !str2symtab
+            .windows(2)
+            .all(|entries| entries[0].0 <= entries[1].0)
+        {
+            return Err(Error::with_invalid_data(
+                "symbol index is not sorted by name",
+            ))
+        }
+        Ok(str2symtab)
+    }
 }
 
 impl Debug for Cache<'_> {
@@ -448,6 +1339,20 @@ impl ElfParser {
         parser
     }
 
+    /// Create an `ElfParser` for an object of `len` bytes whose
+    /// contents are provided by `reader`.
+    ///
+    /// This constructor allows for parsing ELF data that is not
+    /// present on the local file system. Note that the parser operates
+    /// on a contiguous in-memory representation of the object, meaning
+    /// that its contents are retrieved in their entirety up front.
+    pub(crate) fn from_reader<R>(reader: &R, len: u64) -> Result<ElfParser>
+    where
+        R: ReadAt + ?Sized,
+    {
+        Mmap::builder().read(reader, len).map(Self::from_mmap)
+    }
+
     /// Create an `ElfParser` for a path.
     pub fn open(filename: &Path) -> Result<ElfParser> {
         let file = File::open(filename)?;
@@ -464,6 +1369,82 @@ impl ElfParser {
         self.cache.section_data(idx)
     }
 
+    /// Set the load addresses of the sections of a relocatable
+    /// (`ET_REL`) file, keyed by section name.
+    ///
+    /// Once set, [`relocated_section_data`][Self::relocated_section_data]
+    /// applies the relocations targeting a section to its data. The
+    /// addresses of sections that are not loaded at runtime (such as
+    /// debug sections) do not need to be provided.
+    pub(crate) fn set_section_addrs(&mut self, section_addrs: HashMap<String, Addr>) {
+        self.cache.section_addrs = Some(section_addrs);
+        // Relocated data may have been computed based on different
+        // addresses already.
+        self.cache.relocated = OnceCell::new();
+    }
+
+    /// Enable or disable lenient parsing.
+    ///
+    /// In lenient mode the parser tolerates the absence of usable
+    /// section headers, as is common for ELF images carved from a
+    /// memory snapshot. Symbols are then recovered from the tables
+    /// referenced by the dynamic segment (`PT_DYNAMIC`), with the
+    /// data being interpreted either as a memory image or as a file.
+    /// Use [`recovered_tables`][Self::recovered_tables] to check which
+    /// tables could be recovered.
+    pub(crate) fn set_lenient(&mut self, lenient: bool) {
+        // Anything parsed so far may have been affected by the mode.
+        self.cache = Cache {
+            lenient,
+            section_addrs: self.cache.section_addrs.take(),
+            ..Cache::new(self.cache.elf_data)
+        };
+    }
+
+    /// Report the tables that could be recovered from the ELF data.
+    ///
+    /// This function is mostly meaningful in [lenient
+    /// mode][Self::set_lenient], where symbol information may have been
+    /// reconstructed from whatever survived.
+    pub(crate) fn recovered_tables(&self) -> Result<Vec<RecoveredTable>> {
+        let mut tables = Vec::new();
+        if !self.cache.ensure_shdrs()?.is_empty() {
+            let () = tables.push(RecoveredTable::SectionHeaders);
+        }
+
+        if self.cache.use_dynamic_syms() {
+            if let Some(dynamic) = self.cache.ensure_dynamic_tables()? {
+                let () = tables.push(RecoveredTable::Dynamic);
+                if dynamic.symtab.is_some() {
+                    let () = tables.push(RecoveredTable::DynSymtab);
+                }
+                if dynamic.strtab.is_some() {
+                    let () = tables.push(RecoveredTable::DynStrtab);
+                }
+                if dynamic.hash {
+                    let () = tables.push(RecoveredTable::Hash);
+                }
+                if dynamic.gnu_hash {
+                    let () = tables.push(RecoveredTable::GnuHash);
+                }
+            }
+        } else if self.cache.find_symtab_section()?.is_some() {
+            let () = tables.push(RecoveredTable::SymtabSection);
+        }
+        Ok(tables)
+    }
+
+    /// Retrieve the data corresponding to the ELF section at index
+    /// `idx`, transparently decompressing it if it is compressed and
+    /// applying relocations if [section
+    /// addresses][Self::set_section_addrs] were set.
+    ///
+    /// Both sections flagged as `SHF_COMPRESSED` and GNU style
+    /// compressed `.zdebug_*` sections are supported.
+    pub(crate) fn relocated_section_data(&self, idx: usize) -> Result<&[u8]> {
+        self.cache.relocated_section_data(idx)
+    }
+
     /// Find the section of a given name.
     ///
     /// This function return the index of the section if found.
@@ -472,21 +1453,604 @@ impl ElfParser {
         Ok(index)
     }
 
-    pub fn find_sym(&self, addr: Addr, st_type: u8) -> Result<Option<(&str, Addr, usize)>> {
-        let strtab = self.cache.ensure_strtab()?;
-        let symtab = self.cache.ensure_symtab()?;
+    /// Eagerly parse and cache the symbol and string tables.
+    pub(crate) fn parse_syms(&self) -> Result<()> {
+        let _symtab = self.cache.ensure_symtab()?;
+        let _strtab = self.cache.ensure_strtab()?;
+        Ok(())
+    }
 
-        find_sym(symtab, strtab, addr, st_type)
+    /// Write an index of the parsed symbol table to `path`, for the
+    /// file identified by `key`.
+    ///
+    /// The index can be used to speed up symbol table parsing for the
+    /// same file later on (see [`load_sym_index`][Self::load_sym_index]).
+    /// The file is replaced atomically.
+    pub(crate) fn write_sym_index(&self, path: &Path, key: &SymIndexKey<'_>) -> Result<()> {
+        let data = self.cache.create_sym_index(key)?;
+        let mut tmp = path.as_os_str().to_os_string();
+        let () = tmp.push(format!(".{}.tmp", process::id()));
+        let () = fs::write(&tmp, data)?;
+        let result = fs::rename(&tmp, path);
+        if result.is_err() {
+            let _result = fs::remove_file(&tmp);
+        }
+        let () = result?;
+        Ok(())
     }
 
-    /// Calculate the file offset of the given symbol.
+    /// Initialize the parsed symbol table from the index at `path`,
+    /// as previously created by
+    /// [`write_sym_index`][Self::write_sym_index].
+    ///
+    /// `false` is returned if no index exists at `path` or if it is
+    /// stale, i.e., it was not created for the file identified by `key`.
+    pub(crate) fn load_sym_index(&self, path: &Path, key: &SymIndexKey<'_>) -> Result<bool> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err.into()),
+        };
+        let mmap = Mmap::map(&file)?;
+        self.cache.load_sym_index(mmap, key)
+    }
+
+    pub fn find_sym(&self, addr: Addr, st_type: u8) -> Result<Option<(&str, Addr, usize)>> {
+        let strtab = self.cache.ensure_strtab()?;
+        let symtab = self.cache.ensure_symtab()?;
+
+        find_sym(symtab, strtab, addr, st_type)
+    }
+
+    /// Find the symbol of type `st_type` containing `addr` in the
+    /// dynamic symbol table (`.dynsym`).
+    ///
+    /// Contrary to [`find_sym`][Self::find_sym], which consults
+    /// `.symtab` if present, only `.dynsym` is searched. `None` is
+    /// returned if the file does not have a `.dynsym` section.
+    pub(crate) fn find_dynsym(
+        &self,
+        addr: Addr,
+        st_type: u8,
+    ) -> Result<Option<(&str, Addr, usize)>> {
+        match self.cache.ensure_dynsym()? {
+            Some((symtab, strtab)) => find_sym(symtab, strtab, addr, st_type),
+            None => Ok(None),
+        }
+    }
+
+    /// Find the symbol of type `st_type` whose start address is exactly
+    /// `addr`, consulting only the dynamic symbol table (`.dynsym`).
+    pub(crate) fn find_dynsym_exact(
+        &self,
+        addr: Addr,
+        st_type: u8,
+    ) -> Result<Option<(&str, Addr, usize)>> {
+        match self.cache.ensure_dynsym()? {
+            Some((symtab, strtab)) => find_sym_exact(symtab, strtab, addr, st_type),
+            None => Ok(None),
+        }
+    }
+
+    /// Find the symbol of type `st_type` whose start address is exactly
+    /// `addr`.
+    pub(crate) fn find_sym_exact(
+        &self,
+        addr: Addr,
+        st_type: u8,
+    ) -> Result<Option<(&str, Addr, usize)>> {
+        let strtab = self.cache.ensure_strtab()?;
+        let symtab = self.cache.ensure_symtab()?;
+
+        find_sym_exact(symtab, strtab, addr, st_type)
+    }
+
+    /// Find the symbol of type `st_type` containing `addr`, ignoring
+    /// symbols defined in any of the sections with the indices
+    /// `excluded`.
+    pub(crate) fn find_sym_excluding(
+        &self,
+        addr: Addr,
+        st_type: u8,
+        excluded: &[usize],
+    ) -> Result<Option<(&str, Addr, usize)>> {
+        let strtab = self.cache.ensure_strtab()?;
+        let symtab = self.cache.ensure_symtab()?;
+
+        find_sym_excluding(symtab, strtab, addr, st_type, excluded)
+    }
+
+    /// Find all symbols of type `st_type` that contain `addr` or start
+    /// exactly at it, in order of preference.
+    ///
+    /// Contrary to [`ElfParser::find_sym`], this method has to consider
+    /// all symbols preceding `addr` and is correspondingly more
+    /// expensive.
+    pub(crate) fn find_syms(&self, addr: Addr, st_type: u8) -> Result<Vec<(&str, Addr, usize)>> {
+        let strtab = self.cache.ensure_strtab()?;
+        let symtab = self.cache.ensure_symtab()?;
+        let ends = self.cache.ensure_sym_ends()?;
+
+        find_syms(symtab, ends, strtab, addr, st_type)
+    }
+
+    /// Find the allocated section containing the virtual address
+    /// `addr`.
+    ///
+    /// This function returns the index of the section if found.
+    pub(crate) fn find_section_by_addr(&self, addr: Addr) -> Result<Option<usize>> {
+        let shdrs = self.section_headers()?;
+        let idx = shdrs.iter().position(|shdr| {
+            shdr.sh_flags & SHF_ALLOC != 0
+                && addr >= shdr.sh_addr
+                && addr - shdr.sh_addr < shdr.sh_size
+        });
+        Ok(idx)
+    }
+
+    /// Check whether the file contains any defined function symbols.
+    pub(crate) fn has_func_syms(&self) -> Result<bool> {
+        let symtab = self.cache.ensure_symtab()?;
+        let has_syms = symtab
+            .iter()
+            .any(|sym| sym.type_() == STT_FUNC && sym.st_shndx != SHN_UNDEF);
+        Ok(has_syms)
+    }
+
+    /// Find the symbol of type `st_type` in the section at index
+    /// `shndx` that contains `offset`.
+    ///
+    /// This method is meant to be used with relocatable (`ET_REL`)
+    /// files, in which symbol values are relative to the start of the
+    /// section they reside in.
+    pub(crate) fn find_section_sym(
+        &self,
+        shndx: usize,
+        offset: u64,
+        st_type: u8,
+    ) -> Result<Option<(&str, Addr, usize)>> {
+        let strtab = self.cache.ensure_strtab()?;
+        let symtab = self.cache.ensure_symtab()?;
+
+        // Symbols are sorted by value. Search backwards so that we
+        // report the closest symbol in case of ones without size.
+        let sym = symtab.iter().rev().find(|sym| {
+            usize::from(sym.st_shndx) == shndx
+                && sym.type_() == st_type
+                && sym.st_value <= offset
+                && (sym.st_size == 0 || offset - sym.st_value < sym.st_size)
+        });
+        match sym {
+            Some(sym) => {
+                let name = symbol_name(strtab, sym)?;
+                let addr = sym.st_value as Addr;
+                let size = usize::try_from(sym.st_size).unwrap_or(usize::MAX);
+                Ok(Some((name, addr, size)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieve the ELF file's header.
+    pub(crate) fn elf_header(&self) -> Result<&Elf64_Ehdr> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.ehdr)
+    }
+
+    /// Retrieve the address of the ELF file's entry point (`e_entry`).
+    ///
+    /// For executables (`ET_EXEC`) this is the absolute address at which
+    /// execution starts. For position independent executables and shared
+    /// objects (`ET_DYN`) it is a file virtual address, i.e., it is
+    /// relative to the file's load base and the load bias has to be
+    /// added to get the runtime address. A value of zero indicates that
+    /// the file has no entry point.
+    pub(crate) fn entry(&self) -> Result<Addr> {
+        let ehdr = self.elf_header()?;
+        Ok(ehdr.e_entry)
+    }
+
+    /// Retrieve the type of the ELF file (`ET_*`).
+    pub(crate) fn elf_type(&self) -> Result<u16> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.ehdr.e_type)
+    }
+
+    /// Retrieve the architecture the ELF file targets (`EM_*`).
+    pub(crate) fn machine(&self) -> Result<u16> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.ehdr.e_machine)
+    }
+
+    /// Retrieve the class of the ELF file (`ELFCLASS*`), i.e., whether
+    /// it is a 32 or 64 bit object.
+    ///
+    /// Note that only 64 bit objects are properly supported for the
+    /// time being.
+    pub(crate) fn class(&self) -> Result<u8> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.ehdr.e_ident[EI_CLASS])
+    }
+
+    /// Retrieve the data encoding of the ELF file (`ELFDATA*`), i.e.,
+    /// whether it is a little or big endian object.
+    pub(crate) fn endianness(&self) -> Result<u8> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.ehdr.e_ident[EI_DATA])
+    }
+
+    /// Retrieve the OS/ABI the ELF file targets (`ELFOSABI_*`), e.g.,
+    /// System V, GNU/Linux, or FreeBSD.
+    pub(crate) fn os_abi(&self) -> Result<u8> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.ehdr.e_ident[EI_OSABI])
+    }
+
+    /// Retrieve the version of the ABI the ELF file targets.
+    ///
+    /// The interpretation of this value depends on the
+    /// [OS/ABI][Self::os_abi].
+    pub(crate) fn abi_version(&self) -> Result<u8> {
+        let ehdr = self.cache.ensure_ehdr()?;
+        Ok(ehdr.ehdr.e_ident[EI_ABIVERSION])
+    }
+
+    /// Retrieve the control flow protection features recorded in the
+    /// file's `NT_GNU_PROPERTY_TYPE_0` note(s), if any.
+    pub(crate) fn gnu_properties(&self) -> Result<GnuProperties> {
+        let e_machine = self.machine()?;
+        let mut props = GnuProperties::default();
+        let shdrs = self.section_headers()?;
+        for (idx, shdr) in shdrs.iter().enumerate() {
+            if shdr.sh_type != SHT_NOTE {
+                continue
+            }
+            // Notes in 64 bit objects are typically aligned to four
+            // bytes, but property notes use eight.
+            let align = if shdr.sh_addralign == 8 { 8 } else { 4 };
+            let data = self.section_data(idx)?;
+            let () = parse_gnu_property_notes(data, align, e_machine, &mut props)
+                .with_context(|| format!("failed to parse notes in section {idx}"))?;
+        }
+        Ok(props)
+    }
+
+    /// Retrieve the build ID recorded by the Go toolchain in the
+    /// `.note.go.buildid` note, if any.
+    ///
+    /// `None` is reported for binaries not produced by Go.
+    pub(crate) fn go_build_id(&self) -> Result<Option<String>> {
+        let idx = match self.find_section(".note.go.buildid")? {
+            Some(idx) => idx,
+            None => return Ok(None),
+        };
+
+        let mut notes = self.section_data(idx)?;
+        while !notes.is_empty() {
+            let nhdr = notes
+                .read_pod::<Elf64_Nhdr>()
+                .ok_or_invalid_data(|| "failed to read Go build ID note header")?;
+            // Both name and descriptor are padded to four bytes.
+            let namesz = nhdr.n_namesz as usize;
+            let name = notes
+                .read_slice(namesz)
+                .ok_or_invalid_data(|| "failed to read Go build ID note name")?;
+            let _padding = notes.read_slice(namesz.wrapping_neg() % 4);
+            let descsz = nhdr.n_descsz as usize;
+            let desc = notes
+                .read_slice(descsz)
+                .ok_or_invalid_data(|| "failed to read Go build ID note descriptor")?;
+            // The very last note may not be padded, so be lenient.
+            let _padding = notes.read_slice((descsz.wrapping_neg() % 4).min(notes.len()));
+
+            if nhdr.n_type == NT_GO_BUILD_ID && name == b"Go\0\0" {
+                let id = String::from_utf8(desc.to_vec())
+                    .map_err(Error::with_invalid_data)
+                    .context("Go build ID is not valid UTF-8")?;
+                return Ok(Some(id))
+            }
+        }
+        Ok(None)
+    }
+
+    /// Retrieve the operating system and minimum version the file
+    /// targets, as recorded in its `.note.ABI-tag` note, if any.
+    ///
+    /// `None` is reported for binaries without such a note, e.g.,
+    /// shared objects or ones not linked against a C runtime.
+    pub(crate) fn abi_tag(&self) -> Result<Option<AbiTag>> {
+        let idx = match self.find_section(".note.ABI-tag")? {
+            Some(idx) => idx,
+            None => return Ok(None),
+        };
+
+        let mut notes = self.section_data(idx)?;
+        while !notes.is_empty() {
+            let nhdr = notes
+                .read_pod::<Elf64_Nhdr>()
+                .ok_or_invalid_data(|| "failed to read ABI tag note header")?;
+            // Both name and descriptor are padded to four bytes.
+            let namesz = nhdr.n_namesz as usize;
+            let name = notes
+                .read_slice(namesz)
+                .ok_or_invalid_data(|| "failed to read ABI tag note name")?;
+            let _padding = notes.read_slice(namesz.wrapping_neg() % 4);
+            let descsz = nhdr.n_descsz as usize;
+            let mut desc = notes
+                .read_slice(descsz)
+                .ok_or_invalid_data(|| "failed to read ABI tag note descriptor")?;
+            // The very last note may not be padded, so be lenient.
+            let _padding = notes.read_slice((descsz.wrapping_neg() % 4).min(notes.len()));
+
+            if nhdr.n_type == NT_GNU_ABI_TAG && name == b"GNU\0" {
+                let mut words = [0; 4];
+                for word in &mut words {
+                    *word = desc
+                        .read_u32()
+                        .ok_or_invalid_data(|| "ABI tag note descriptor is truncated")?;
+                }
+                let [os, major, minor, patch] = words;
+                let tag = AbiTag {
+                    os: AbiTagOs::from(os),
+                    version: (major, minor, patch),
+                    _non_exhaustive: (),
+                };
+                return Ok(Some(tag))
+            }
+        }
+        Ok(None)
+    }
+
+    /// Map the address of a procedure linkage table (PLT) entry to the
+    /// address of the global offset table (GOT) slot it jumps through.
+    ///
+    /// Only the lazy binding PLT layouts of x86-64 and AArch64 are
+    /// understood, for which the N-th PLT entry corresponds to the N-th
+    /// relocation in `.rela.plt`. `None` is returned if `addr` does not
+    /// fall into a PLT entry.
+    pub(crate) fn plt_got_slot(&self, addr: Addr) -> Result<Option<Addr>> {
+        // The size of the PLT header and of each subsequent entry.
+        let (hdr_size, entry_size) = match self.machine()? {
+            EM_X86_64 => (16, 16),
+            EM_AARCH64 => (32, 16),
+            _ => return Ok(None),
+        };
+
+        let shdrs = self.section_headers()?;
+        let mut index = None;
+        // If present, `.plt.sec` contains the entries that are actually
+        // called and it does not have a header.
+        for (name, hdr_size) in [(".plt.sec", 0), (".plt", hdr_size)] {
+            if let Some(idx) = self.find_section(name)? {
+                let shdr = &shdrs[idx];
+                if addr >= shdr.sh_addr.saturating_add(hdr_size)
+                    && addr - shdr.sh_addr < shdr.sh_size
+                {
+                    index = Some((addr - shdr.sh_addr - hdr_size) / entry_size);
+                    break
+                }
+            }
+        }
+
+        let index = match index {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let idx = match self.find_section(".rela.plt")? {
+            Some(idx) => idx,
+            None => return Ok(None),
+        };
+        let mut relas = self.section_data(idx)?;
+        let offset = usize::try_from(index)
+            .ok()
+            .and_then(|index| index.checked_mul(mem::size_of::<Elf64_Rela>()));
+        let rela = offset
+            .and_then(|offset| {
+                let _skipped = relas.read_slice(offset)?;
+                relas.read_pod::<Elf64_Rela>()
+            })
+            .ok_or_invalid_data(|| format!("PLT entry {index} has no associated relocation"))?;
+        Ok(Some(rela.r_offset))
+    }
+
+    /// Find the names of defined symbols that are similar to, but
+    /// different from, `name`.
+    ///
+    /// Names are considered similar if their edit distance to `name`
+    /// is at most `max_distance`. At most `max_count` names are
+    /// reported, closest first.
+    pub(crate) fn find_similar_names(
+        &self,
+        name: &str,
+        max_distance: usize,
+        max_count: usize,
+    ) -> Result<Vec<&str>> {
+        let symtab = self.cache.ensure_symtab()?;
+        let str2symtab = self.cache.ensure_str2symtab()?;
+
+        let mut similar = Vec::new();
+        for (other, idx) in str2symtab {
+            // `str2symtab` is sorted by name and we only want to
+            // consider each name once.
+            if similar.last().map(|(_, last)| last) == Some(other) {
+                continue
+            }
+
+            let sym = symtab
+                .get(*idx)
+                .ok_or_invalid_input(|| format!("symbol table index ({idx}) out of bounds"))?;
+            if sym.st_shndx == SHN_UNDEF || other.is_empty() {
+                continue
+            }
+
+            match edit_distance_bounded(name.as_bytes(), other.as_bytes(), max_distance) {
+                Some(0) | None => (),
+                Some(distance) => {
+                    let () = similar.push((distance, *other));
+                }
+            }
+        }
+
+        let () = similar.sort();
+        let similar = similar
+            .into_iter()
+            .take(max_count)
+            .map(|(_distance, name)| name)
+            .collect();
+        Ok(similar)
+    }
+
+    /// Retrieve all section groups (`SHT_GROUP` sections) along with
+    /// their members, in the order in which they appear in the file.
+    pub(crate) fn section_groups(&self) -> Result<Vec<SectionGroup>> {
+        let shdrs = self.section_headers()?;
+        let mut groups = Vec::new();
+        for (idx, shdr) in shdrs.iter().enumerate() {
+            if shdr.sh_type != SHT_GROUP {
+                continue
+            }
+
+            let mut data = self.section_data(idx)?;
+            if data.len() % mem::size_of::<u32>() != 0 {
+                return Err(Error::with_invalid_data(format!(
+                    "size of section group {idx} is invalid"
+                )))
+            }
+            let flags = data
+                .read_u32()
+                .ok_or_invalid_data(|| format!("section group {idx} lacks flags"))?;
+            let mut members = Vec::with_capacity(data.len() / mem::size_of::<u32>());
+            while let Some(member) = data.read_u32() {
+                let () = members.push(member as usize);
+            }
+
+            // The group's signature is the name of the symbol with
+            // index `sh_info` in the symbol table referenced by
+            // `sh_link`.
+            let symtab_idx = shdr.sh_link as usize;
+            let symtab = self.section_data(symtab_idx)?;
+            // SANITY: `section_data` would have failed for an invalid
+            //         index.
+            let symtab_shdr = &shdrs[symtab_idx];
+            let entsize = sym_entsize(symtab_shdr)?;
+            let sym = symtab
+                .get(shdr.sh_info as usize * entsize..)
+                .and_then(|mut data| data.read_pod_ref::<Elf64_Sym>())
+                .ok_or_invalid_data(|| format!("section group {idx} references invalid symbol"))?;
+            let strtab = self.section_data(symtab_shdr.sh_link as usize)?;
+            let signature = symbol_name(strtab, sym)?;
+
+            let group = SectionGroup {
+                section: idx,
+                signature: signature.to_string(),
+                flags,
+                members,
+                _non_exhaustive: (),
+            };
+            let () = groups.push(group);
+        }
+        Ok(groups)
+    }
+
+    /// Find all relocations referencing the symbol with the given name,
+    /// in the order in which they appear in the file.
+    ///
+    /// All `SHT_RELA` and `SHT_REL` sections are considered, with
+    /// symbols being looked up in the symbol table each of them
+    /// references (typically `.symtab` or `.dynsym`).
+    pub(crate) fn relocations_for(&self, sym_name: &str) -> Result<Vec<RelocInfo>> {
+        let shdrs = self.section_headers()?;
+        let mut relocs = Vec::new();
+
+        for (idx, shdr) in shdrs.iter().enumerate() {
+            if !matches!(shdr.sh_type, SHT_RELA | SHT_REL) || shdr.sh_link == 0 {
+                continue
+            }
+
+            let symtab_idx = shdr.sh_link as usize;
+            let symtab = self.section_data(symtab_idx)?;
+            // SANITY: `section_data` would have failed for an invalid
+            //         index.
+            let symtab_shdr = &shdrs[symtab_idx];
+            let entsize = sym_entsize(symtab_shdr)?;
+            let strtab = self.section_data(symtab_shdr.sh_link as usize)?;
+
+            let mut data = self.section_data(idx)?;
+            loop {
+                let (sym_idx, reloc) = if shdr.sh_type == SHT_RELA {
+                    match data.read_pod::<Elf64_Rela>() {
+                        Some(rela) => (
+                            rela.sym(),
+                            RelocInfo {
+                                section: idx,
+                                offset: rela.r_offset,
+                                type_: rela.type_(),
+                                addend: Some(rela.r_addend),
+                                _non_exhaustive: (),
+                            },
+                        ),
+                        None => break,
+                    }
+                } else {
+                    match data.read_pod::<Elf64_Rel>() {
+                        Some(rel) => (
+                            rel.sym(),
+                            RelocInfo {
+                                section: idx,
+                                offset: rel.r_offset,
+                                type_: rel.type_(),
+                                addend: None,
+                                _non_exhaustive: (),
+                            },
+                        ),
+                        None => break,
+                    }
+                };
+
+                // Symbol index zero denotes relocations not referencing
+                // any symbol (e.g., `R_X86_64_RELATIVE` ones).
+                if sym_idx == 0 {
+                    continue
+                }
+
+                let sym = symtab
+                    .get(sym_idx as usize * entsize..)
+                    .and_then(|mut data| data.read_pod_ref::<Elf64_Sym>())
+                    .ok_or_invalid_data(|| {
+                        format!("relocation in section {idx} references invalid symbol #{sym_idx}")
+                    })?;
+                if symbol_name(strtab, sym)? == sym_name {
+                    let () = relocs.push(reloc);
+                }
+            }
+        }
+        Ok(relocs)
+    }
+
+    /// Retrieve the name of the section at index `idx`.
+    pub(crate) fn section_name(&self, idx: usize) -> Result<&str> {
+        self.cache.section_name(idx)
+    }
+
+    /// Calculate the file offset of the given symbol.
+    ///
+    /// The value of an absolute (`SHN_ABS`) symbol is not tied to any
+    /// section and is mapped via the program headers instead. Symbols
+    /// with other special section indices (e.g., `SHN_COMMON`) have no
+    /// file offset.
     ///
     /// # Notes
     /// It is the caller's responsibility to ensure that the symbol's section
     /// index is not `SHN_UNDEF`.
-    fn file_offset(&self, shdrs: &[Elf64_Shdr], sym: &Elf64_Sym) -> Result<u64> {
+    fn file_offset(&self, shdrs: &[Elf64_Shdr], sym: &Elf64_Sym) -> Result<Option<u64>> {
         debug_assert_ne!(sym.st_shndx, SHN_UNDEF);
 
+        match sym.st_shndx {
+            SHN_ABS => return self.find_file_offset(sym.st_value),
+            shndx if shndx >= SHN_LORESERVE => return Ok(None),
+            _ => (),
+        }
+
         let section = shdrs
             .get(usize::from(sym.st_shndx))
             .ok_or_invalid_input(|| {
@@ -495,7 +2059,17 @@ impl ElfParser {
                     sym.st_shndx, sym.st_value
                 )
             })?;
-        Ok(sym.st_value - section.sh_addr + section.sh_offset)
+        let offset = sym
+            .st_value
+            .checked_sub(section.sh_addr)
+            .and_then(|offset| offset.checked_add(section.sh_offset))
+            .ok_or_invalid_data(|| {
+                format!(
+                    "symbol at {:#x} is not contained in its section ({})",
+                    sym.st_value, sym.st_shndx
+                )
+            })?;
+        Ok(Some(offset))
     }
 
     pub(crate) fn find_addr<'slf>(
@@ -507,39 +2081,228 @@ impl ElfParser {
             return Err(Error::with_unsupported("Not implemented"))
         }
 
-        let shdrs = self.cache.ensure_shdrs()?;
-        let symtab = self.cache.ensure_symtab()?;
         let str2symtab = self.cache.ensure_str2symtab()?;
-
         let r = find_match_or_lower_bound_by_key(str2symtab, name, |&(name, _i)| name);
         match r {
-            Some(idx) => {
-                let mut found = vec![];
-                for (name_visit, sym_i) in str2symtab.iter().skip(idx) {
-                    if *name_visit != name {
-                        break
+            Some(idx) => self.syms_named(&str2symtab[idx..], name, opts),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Find the addresses of a list of symbols, given their names.
+    ///
+    /// This method is semantically equivalent to invoking
+    /// [`find_addr`][Self::find_addr] for each of the names, but it
+    /// processes them in a single sweep over the symbol table. Results
+    /// are reported in the order of `names`, with an empty list for
+    /// each name that could not be found.
+    pub(crate) fn find_addrs<'slf>(
+        &'slf self,
+        names: &[&str],
+        opts: &FindAddrOpts,
+    ) -> Result<Vec<Vec<SymInfo<'slf>>>> {
+        if let SymType::Variable = opts.sym_type {
+            return Err(Error::with_unsupported("Not implemented"))
+        }
+
+        let str2symtab = self.cache.ensure_str2symtab()?;
+        let mut sorted = names.iter().enumerate().collect::<Vec<_>>();
+        let () = sorted.sort_by_key(|(_idx, name)| **name);
+
+        let mut results = vec![Vec::new(); names.len()];
+        // The symbol table index is in dictionary order, as are the
+        // names we search for. Hence, each search can start where the
+        // previous one left off.
+        let mut start = 0;
+        for (idx, name) in sorted {
+            let str2symtab = &str2symtab[start..];
+            let offset = str2symtab.partition_point(|(name_visit, _i)| name_visit < name);
+            let () = start += offset;
+            results[idx] = self.syms_named(&str2symtab[offset..], name, opts)?;
+        }
+        Ok(results)
+    }
+
+    /// Look up `name` in the hash table of the dynamic symbol table, if
+    /// any.
+    ///
+    /// On success, the index of the dynamic symbol table section is
+    /// reported alongside whether a defined symbol was found. `.gnu_hash`
+    /// is preferred over `.hash` if both are present.
+    fn hash_lookup(&self, name: &str) -> Result<Option<(usize, bool)>> {
+        let shdrs = self.cache.ensure_shdrs()?;
+        let find = |sh_type| shdrs.iter().position(|shdr| shdr.sh_type == sh_type);
+        let (hash_idx, gnu) = match find(SHT_GNU_HASH) {
+            Some(idx) => (idx, true),
+            None => match find(SHT_HASH) {
+                Some(idx) => (idx, false),
+                None => return Ok(None),
+            },
+        };
+
+        // SANITY: `position` reported a valid index.
+        let dynsym_idx = shdrs[hash_idx].sh_link as usize;
+        let dynsym_shdr = match shdrs.get(dynsym_idx) {
+            Some(shdr) if shdr.sh_type == SHT_DYNSYM => shdr,
+            _ => return Ok(None),
+        };
+        let table = self.cache.section_data(hash_idx)?;
+        let dynsym = self.cache.section_data(dynsym_idx)?;
+        let strtab = self.cache.section_data(dynsym_shdr.sh_link as usize)?;
+        let entsize = sym_entsize(dynsym_shdr)?;
+
+        let check = |sym_idx: usize| -> Result<bool> {
+            let sym = sym_idx
+                .checked_mul(entsize)
+                .and_then(|offset| dynsym.get(offset..))
+                .and_then(|mut data| data.read_pod::<Elf64_Sym>())
+                .ok_or_invalid_data(|| format!("dynamic symbol index ({sym_idx}) out of bounds"))?;
+            Ok(sym.st_shndx != SHN_UNDEF && symbol_name(strtab, &sym)? == name)
+        };
+
+        let found = if gnu {
+            gnu_hash_lookup(table, name, check)?
+        } else {
+            sysv_hash_lookup(table, name, check)?
+        };
+        Ok(Some((dynsym_idx, found.is_some())))
+    }
+
+    /// Check whether the file defines a symbol named `name`.
+    ///
+    /// This method is cheaper than [`find_addr`][Self::find_addr] if
+    /// no information about the symbol is needed: if present, the
+    /// dynamic symbol hash table is consulted first, which does not
+    /// require indexing the entire symbol table.
+    pub(crate) fn has_sym(&self, name: &str) -> Result<bool> {
+        if let Some((dynsym_idx, found)) = self.hash_lookup(name)? {
+            if found {
+                return Ok(true)
+            }
+            // The hash table only covers dynamic symbols. Its verdict
+            // is final only if those are all we would search otherwise.
+            if self.cache.find_symtab_section()? == Some(dynsym_idx) {
+                return Ok(false)
+            }
+        }
+
+        let opts = FindAddrOpts {
+            limit: Some(1),
+            ..Default::default()
+        };
+        let syms = self.find_addr(name, &opts)?;
+        Ok(!syms.is_empty())
+    }
+
+    /// Resolve the section filter of `opts` to a section index.
+    ///
+    /// If the requested section does not exist, `SHN_UNDEF` is
+    /// reported, which no reportable symbol is associated with.
+    fn section_filter(&self, opts: &FindAddrOpts) -> Result<Option<usize>> {
+        let idx = match &opts.section {
+            Some(name) => Some(
+                self.find_section(name)?
+                    .unwrap_or_else(|| usize::from(SHN_UNDEF)),
+            ),
+            None => None,
+        };
+        Ok(idx)
+    }
+
+    /// Collect all symbols named `name` from the name sorted symbol
+    /// index `str2symtab`, which is expected to start with the first
+    /// candidate.
+    fn syms_named<'slf>(
+        &'slf self,
+        str2symtab: &[(&'slf str, usize)],
+        name: &str,
+        opts: &FindAddrOpts,
+    ) -> Result<Vec<SymInfo<'slf>>> {
+        let shdrs = self.cache.ensure_shdrs()?;
+        let symtab = self.cache.ensure_symtab()?;
+        let ends = self.cache.ensure_sym_ends()?;
+        let section = self.section_filter(opts)?;
+
+        let mut found = vec![];
+        // The rank of each symbol in `found`, as used for picking among
+        // duplicates. Lower is better.
+        let mut ranks = vec![];
+        for (name_visit, sym_i) in str2symtab {
+            if *name_visit != name {
+                break
+            }
+            let sym_ref = &symtab
+                .get(*sym_i)
+                .ok_or_invalid_input(|| format!("symbol table index ({sym_i}) out of bounds"))?;
+            let is_tls = sym_ref.type_() == STT_TLS;
+            let skip = match opts.sym_type {
+                SymType::Function => is_tls,
+                SymType::Tls => !is_tls,
+                SymType::Unknown | SymType::Variable => false,
+            };
+            let skip = skip || section.map_or(false, |idx| usize::from(sym_ref.st_shndx) != idx);
+            if sym_ref.st_shndx != SHN_UNDEF && !skip {
+                // The value of a TLS symbol is an offset into the
+                // TLS block and not an address. As such, it does
+                // not map to any file offset either.
+                let (sym_type, file_offset) = if is_tls {
+                    (SymType::Tls, None)
+                } else {
+                    let file_offset = opts
+                        .offset_in_file
+                        .then(|| self.file_offset(shdrs, sym_ref))
+                        .transpose()?
+                        .flatten();
+                    (SymType::Function, file_offset)
+                };
+
+                let sym = SymInfo {
+                    name: Cow::Borrowed(name_visit),
+                    demangled_name: None,
+                    // The value of a common symbol is its alignment
+                    // and not an address; it has yet to be allocated.
+                    addr: if sym_ref.st_shndx == SHN_COMMON {
+                        0
+                    } else {
+                        sym_ref.st_value as Addr
+                    },
+                    size: sym_ref.st_size as usize,
+                    end: ends[*sym_i],
+                    sym_type,
+                    file_offset,
+                    obj_file_name: None,
+                };
+                let rank = (sym.size == 0, binding_rank(sym_ref.binding()));
+
+                let dup = if opts.dedup {
+                    found
+                        .iter()
+                        .position(|other: &SymInfo<'_>| other.addr == sym.addr)
+                } else {
+                    None
+                };
+                match dup {
+                    Some(idx) if rank < ranks[idx] => {
+                        found[idx] = sym;
+                        ranks[idx] = rank;
                     }
-                    let sym_ref = &symtab.get(*sym_i).ok_or_invalid_input(|| {
-                        format!("symbol table index ({sym_i}) out of bounds")
-                    })?;
-                    if sym_ref.st_shndx != SHN_UNDEF {
-                        found.push(SymInfo {
-                            name: Cow::Borrowed(name_visit),
-                            addr: sym_ref.st_value as Addr,
-                            size: sym_ref.st_size as usize,
-                            sym_type: SymType::Function,
-                            file_offset: opts
-                                .offset_in_file
-                                .then(|| self.file_offset(shdrs, sym_ref))
-                                .transpose()?,
-                            obj_file_name: None,
-                        });
+                    Some(_) => (),
+                    None if opts.limit.map_or(false, |limit| found.len() >= limit) => {
+                        // With deduplication enabled later symbols may
+                        // still replace ones we already found, so we
+                        // can only stop early without it.
+                        if !opts.dedup {
+                            break
+                        }
+                    }
+                    None => {
+                        let () = found.push(sym);
+                        let () = ranks.push(rank);
                     }
                 }
-                Ok(found)
             }
-            None => Ok(vec![]),
         }
+        Ok(found)
     }
 
     /// Perform an operation on each symbol.
@@ -552,10 +2315,12 @@ impl ElfParser {
         }
 
         let shdrs = self.cache.ensure_shdrs()?;
+        let section = self.section_filter(opts)?;
 
         let mut i = 0;
         loop {
             let symtab = self.cache.ensure_symtab()?;
+            let ends = self.cache.ensure_sym_ends()?;
             let str2symtab = self.cache.ensure_str2symtab()?;
 
             if i >= str2symtab.len() {
@@ -566,16 +2331,22 @@ impl ElfParser {
             let sym = &symtab
                 .get(*idx)
                 .ok_or_invalid_input(|| format!("symbol table index ({idx}) out of bounds"))?;
-            if sym.type_() == STT_FUNC && sym.st_shndx != SHN_UNDEF {
+            if sym.type_() == STT_FUNC
+                && sym.st_shndx != SHN_UNDEF
+                && section.map_or(true, |idx| usize::from(sym.st_shndx) == idx)
+            {
                 let sym_info = SymInfo {
                     name: Cow::Borrowed(name),
+                    demangled_name: None,
                     addr: sym.st_value as Addr,
                     size: sym.st_size as usize,
+                    end: ends[*idx],
                     sym_type: SymType::Function,
                     file_offset: opts
                         .offset_in_file
                         .then(|| self.file_offset(shdrs, sym))
-                        .transpose()?,
+                        .transpose()?
+                        .flatten(),
                     obj_file_name: None,
                 };
                 r = f(r, &sym_info)
@@ -585,19 +2356,78 @@ impl ElfParser {
         }
     }
 
+    /// Determine the virtual address at which the file's lowest loadable
+    /// segment starts, rounded down to its alignment.
+    ///
+    /// Segments are mapped at page granularity, so this is the address
+    /// corresponding to the start of the file's first mapping.
+    pub(crate) fn load_base(&self) -> Result<Option<Addr>> {
+        let phdrs = self.program_headers()?;
+        Ok(load_base(phdrs))
+    }
+
+    /// Check whether the file requests an executable stack.
+    ///
+    /// The information is conveyed by the flags of the `PT_GNU_STACK`
+    /// program header. `None` is returned if the file does not contain
+    /// such a program header, in which case the stack is typically
+    /// executable by default on most architectures.
+    pub(crate) fn exec_stack(&self) -> Result<Option<bool>> {
+        let phdrs = self.program_headers()?;
+        let exec = phdrs
+            .iter()
+            .find(|phdr| phdr.p_type == PT_GNU_STACK)
+            .map(|phdr| phdr.p_flags & PF_X != 0);
+        Ok(exec)
+    }
+
+    /// Retrieve the TLS initialization image of the file.
+    ///
+    /// TLS symbol values are offsets into the image. `None` is returned
+    /// if the file does not contain a `PT_TLS` program header.
+    pub(crate) fn tls_template(&self) -> Result<Option<TlsTemplate>> {
+        let phdrs = self.program_headers()?;
+        let template = phdrs
+            .iter()
+            .find(|phdr| phdr.p_type == PT_TLS)
+            .map(|phdr| TlsTemplate {
+                addr: phdr.p_vaddr as Addr,
+                file_offset: phdr.p_offset,
+                file_size: phdr.p_filesz,
+                mem_size: phdr.p_memsz,
+                align: phdr.p_align,
+                _non_exhaustive: (),
+            });
+        Ok(template)
+    }
+
     /// Find the file offset of the symbol at address `addr`.
+    ///
+    /// If multiple `PT_LOAD` segments contain `addr` (which some
+    /// linkers and post-processing tools produce), an executable
+    /// segment is preferred over a non-executable one. Among equally
+    /// qualified segments the one with the smallest memory size, i.e.,
+    /// the tightest fit, is used, and remaining ties are broken by
+    /// program header order.
     // If possible, use the constant-time [`file_offset`][Self::file_offset]
     // method instead.
     pub(crate) fn find_file_offset(&self, addr: Addr) -> Result<Option<u64>> {
         let phdrs = self.program_headers()?;
-        let offset = phdrs.iter().find_map(|phdr| {
-            if phdr.p_type == PT_LOAD {
-                if (phdr.p_vaddr..phdr.p_vaddr + phdr.p_memsz).contains(&addr) {
-                    return Some(addr - phdr.p_vaddr + phdr.p_offset)
+        let offset = phdrs
+            .iter()
+            .filter_map(|phdr| {
+                if phdr.p_type == PT_LOAD {
+                    let offset = addr.checked_sub(phdr.p_vaddr)?;
+                    if offset < phdr.p_memsz {
+                        return Some((phdr, offset))
+                    }
                 }
-            }
-            None
-        });
+                None
+            })
+            // `min_by_key` reports the first of multiple equal
+            // elements, preserving program header order for ties.
+            .min_by_key(|(phdr, _offset)| (phdr.p_flags & PF_X == 0, phdr.p_memsz))
+            .and_then(|(phdr, offset)| offset.checked_add(phdr.p_offset));
         Ok(offset)
     }
 
@@ -645,20 +2475,379 @@ impl ElfParser {
 mod tests {
     use super::*;
 
+    use super::super::types::ELFCLASS64;
+    use super::super::types::ELFDATA2LSB;
+    use super::super::types::ELFDATA2MSB;
+    use super::super::types::ELFOSABI_GNU;
+    use super::super::types::ELFOSABI_SYSV;
+    use super::super::types::EM_RISCV;
+    use super::super::types::GRP_COMDAT;
     use super::super::types::SHN_LORESERVE;
+    use super::super::types::STT_OBJECT;
+
+    use crate::ErrorKind;
 
     use std::env;
     use std::env::current_exe;
+    use std::fs::read;
     use std::io::Seek as _;
     use std::io::Write as _;
     use std::mem::size_of;
     use std::slice;
+    use std::time::Duration;
+    use std::time::UNIX_EPOCH;
 
+    use tempfile::tempdir;
     use tempfile::tempfile;
 
     use test_log::test;
 
 
+    /// Make sure that `ElfParser` objects can be sent to other threads,
+    /// as is necessary for parsing files concurrently.
+    #[test]
+    fn send() {
+        fn test<T: Send>() {}
+
+        let () = test::<ElfParser>();
+    }
+
+    /// Check that we can read the build ID of a Go binary.
+    #[test]
+    fn go_build_id_reading() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-go-buildid.bin");
+        let parser = ElfParser::open(&path).unwrap();
+        let id = parser.go_build_id().unwrap();
+        assert_eq!(
+            id.as_deref(),
+            Some(
+                "abcdefghijklmnopqrst/uvwxyz0123456789ABC/DEFGHIJKLMNOPQRSTUVW/XYZabcdefghijklmnop"
+            )
+        );
+
+        // Binaries not produced by Go do not carry a Go build ID.
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let parser = ElfParser::open(&path).unwrap();
+        assert_eq!(parser.go_build_id().unwrap(), None);
+    }
+
+    /// Check that we can read the ABI tag of an ELF file.
+    #[test]
+    fn abi_tag_reading() {
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+        let parser = ElfParser::open(&data.join("test-no-debug.bin")).unwrap();
+        let tag = parser.abi_tag().unwrap().unwrap();
+        assert_eq!(tag.os, AbiTagOs::Linux);
+        // The exact version depends on the C runtime and architecture,
+        // but anything supported is at least 2.6.
+        assert!(tag.version >= (2, 6, 0), "{tag:?}");
+
+        // Shared objects and binaries built without a C runtime don't
+        // carry the note.
+        for file in ["libtest-so.so", "test-stable-addresses.bin"] {
+            let parser = ElfParser::open(&data.join(file)).unwrap();
+            assert_eq!(parser.abi_tag().unwrap(), None);
+        }
+    }
+
+    /// Check that we can determine whether a file requests an
+    /// executable stack.
+    #[test]
+    fn exec_stack_reading() {
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+
+        let parser = ElfParser::open(&data.join("libtest-so.so")).unwrap();
+        assert_eq!(parser.exec_stack().unwrap(), Some(false));
+
+        let parser = ElfParser::open(&data.join("libtest-so-execstack.so")).unwrap();
+        assert_eq!(parser.exec_stack().unwrap(), Some(true));
+
+        // Our assembly based test file lacks a `.note.GNU-stack`
+        // section, so no `PT_GNU_STACK` program header is emitted.
+        let parser = ElfParser::open(&data.join("test-prologue.bin")).unwrap();
+        assert_eq!(parser.exec_stack().unwrap(), None);
+    }
+
+    /// Check that we can read the TLS initialization image of a file.
+    #[test]
+    fn tls_template_reading() {
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+
+        let parser = ElfParser::open(&data.join("libtest-tls.so")).unwrap();
+        let template = parser.tls_template().unwrap().unwrap();
+        let tdata = parser.find_section(".tdata").unwrap().unwrap();
+        let tdata = &parser.section_headers().unwrap()[tdata];
+        assert_eq!(template.addr, tdata.sh_addr);
+        assert_eq!(template.file_offset, tdata.sh_offset);
+        // `tls_counter` is initialized while `tls_zeroed` lives in
+        // `.tbss` and only takes up memory.
+        assert_eq!(template.file_size, 4);
+        assert!(template.mem_size > template.file_size, "{template:?}");
+        assert_eq!(template.align, 8);
+
+        let parser = ElfParser::open(&data.join("libtest-so.so")).unwrap();
+        assert_eq!(parser.tls_template().unwrap(), None);
+    }
+
+    /// Check that we can determine whether a file defines a symbol.
+    #[test]
+    fn symbol_existence() {
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+
+        for file in ["libtest-so.so", "libtest-so-sysv-hash.so"] {
+            let parser = ElfParser::open(&data.join(file)).unwrap();
+            assert!(parser.hash_lookup("the_answer").unwrap().unwrap().1);
+            assert!(parser.has_sym("the_answer").unwrap());
+            // `frame_dummy` is a local symbol and, hence, not covered
+            // by the hash table.
+            assert!(!parser.hash_lookup("frame_dummy").unwrap().unwrap().1);
+            assert!(parser.has_sym("frame_dummy").unwrap());
+            // Undefined symbols don't count.
+            assert!(!parser.has_sym("__cxa_finalize").unwrap());
+            assert!(!parser.has_sym("does_not_exist").unwrap());
+        }
+
+        let parser = ElfParser::open(&data.join("libtest-so-dynsym-only.so")).unwrap();
+        assert!(parser.has_sym("the_answer").unwrap());
+        assert!(!parser.has_sym("frame_dummy").unwrap());
+
+        // Relocatable objects lack a hash table altogether.
+        let parser = ElfParser::open(&data.join("test-stable-addresses-rel.bin")).unwrap();
+        assert_eq!(parser.hash_lookup("factorial").unwrap(), None);
+        assert!(parser.has_sym("factorial").unwrap());
+    }
+
+    /// Check that we can look up symbols in `.dynsym` specifically.
+    #[test]
+    fn dynsym_lookup() {
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+        let opts = FindAddrOpts::default();
+
+        for file in ["libtest-so.so", "libtest-so-dynsym-only.so"] {
+            let parser = ElfParser::open(&data.join(file)).unwrap();
+            let sym = &parser.find_addr("the_answer", &opts).unwrap()[0];
+            let (name, addr, _size) = parser.find_dynsym(sym.addr, STT_FUNC).unwrap().unwrap();
+            assert_eq!(name, "the_answer");
+            assert_eq!(addr, sym.addr);
+        }
+
+        // Local symbols are only part of `.symtab`.
+        let parser = ElfParser::open(&data.join("libtest-so.so")).unwrap();
+        let sym = &parser.find_addr("frame_dummy", &opts).unwrap()[0];
+        assert_ne!(parser.find_sym(sym.addr, STT_FUNC).unwrap(), None);
+        assert_eq!(parser.find_dynsym(sym.addr, STT_FUNC).unwrap(), None);
+
+        // A fully static binary lacks `.dynsym`.
+        let parser = ElfParser::open(&data.join("test-stable-addresses.bin")).unwrap();
+        assert_eq!(parser.find_dynsym(0x2000100, STT_FUNC).unwrap(), None);
+    }
+
+    /// Check that a lenient parser can recover symbols from an ELF
+    /// image carved from memory, lacking section headers.
+    #[test]
+    fn lenient_memory_image_parsing() {
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+
+        for (file, hash) in [
+            ("libtest-so.so", RecoveredTable::GnuHash),
+            ("libtest-so-sysv-hash.so", RecoveredTable::Hash),
+        ] {
+            let path = data.join(file);
+            let original = ElfParser::open(&path).unwrap();
+            let mut parser = ElfParser::open(&path).unwrap();
+            let () = parser.set_lenient(true);
+            assert_eq!(
+                parser.recovered_tables().unwrap(),
+                vec![
+                    RecoveredTable::SectionHeaders,
+                    RecoveredTable::SymtabSection
+                ]
+            );
+
+            // Lay out the loadable segments as the loader would.
+            let contents = fs::read(&path).unwrap();
+            let base = original.load_base().unwrap().unwrap();
+            let loads = original
+                .program_headers()
+                .unwrap()
+                .iter()
+                .filter(|phdr| phdr.p_type == PT_LOAD)
+                .collect::<Vec<_>>();
+            let size = loads
+                .iter()
+                .map(|phdr| phdr.p_vaddr + phdr.p_memsz - base)
+                .max()
+                .unwrap();
+            let mut image = vec![0; size as usize];
+            for phdr in loads {
+                let start = (phdr.p_vaddr - base) as usize;
+                let offset = phdr.p_offset as usize;
+                let size = phdr.p_filesz as usize;
+                let () =
+                    image[start..start + size].copy_from_slice(&contents[offset..offset + size]);
+            }
+            // Section headers are not loaded and whatever is at their
+            // supposed location in the image is garbage.
+            // `e_shoff` is located at offset 0x28 of the ELF header.
+            let () = image[0x28..0x30].copy_from_slice(&(size * 2).to_ne_bytes());
+
+            let mut parser = ElfParser::from_reader(&image[..], image.len() as u64).unwrap();
+            let opts = FindAddrOpts::default();
+            assert!(parser.find_addr("the_answer", &opts).is_err());
+
+            let () = parser.set_lenient(true);
+            assert_eq!(
+                parser.recovered_tables().unwrap(),
+                vec![
+                    RecoveredTable::Dynamic,
+                    RecoveredTable::DynSymtab,
+                    RecoveredTable::DynStrtab,
+                    hash
+                ]
+            );
+
+            let addrs = |parser: &ElfParser| {
+                parser
+                    .find_addr("the_answer", &opts)
+                    .unwrap()
+                    .iter()
+                    .map(|sym| (sym.addr, sym.size))
+                    .collect::<Vec<_>>()
+            };
+            let expected = addrs(&original);
+            assert_eq!(expected.len(), 1);
+            assert_eq!(addrs(&parser), expected);
+
+            let (addr, _size) = expected[0];
+            assert_eq!(
+                parser.find_sym(addr, STT_FUNC).unwrap(),
+                original.find_sym(addr, STT_FUNC).unwrap()
+            );
+        }
+    }
+
+    /// Check that we can persist the parsed symbol table in an index
+    /// and restore it from there.
+    #[test]
+    fn sym_index_roundtrip() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let dir = tempdir().unwrap();
+        let index = dir.path().join("test.symidx");
+        let key = SymIndexKey {
+            build_id: b"build-id",
+            mtime: UNIX_EPOCH + Duration::from_secs(42),
+        };
+
+        let parser = ElfParser::open(&path).unwrap();
+        assert!(!parser.load_sym_index(&index, &key).unwrap());
+        let () = parser.write_sym_index(&index, &key).unwrap();
+
+        let indexed = ElfParser::open(&path).unwrap();
+        assert!(indexed.load_sym_index(&index, &key).unwrap());
+        // The name ordered part of the index is only used lazily.
+        assert!(indexed.cache.sym_index.get().is_some());
+        assert!(indexed.cache.str2symtab.get().is_none());
+        let syms = |parser: &ElfParser| {
+            parser
+                .cache
+                .ensure_symtab()
+                .unwrap()
+                .iter()
+                .map(|sym| (sym.st_name, sym.st_value, sym.st_size))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(syms(&indexed), syms(&parser));
+        assert_eq!(
+            indexed.cache.ensure_str2symtab().unwrap(),
+            parser.cache.ensure_str2symtab().unwrap()
+        );
+        let opts = FindAddrOpts::default();
+        assert_eq!(
+            indexed.find_addr("factorial", &opts).unwrap(),
+            parser.find_addr("factorial", &opts).unwrap()
+        );
+
+        // An index created for a different file should not be used.
+        let other = SymIndexKey {
+            mtime: UNIX_EPOCH + Duration::from_secs(43),
+            ..key
+        };
+        let parser = ElfParser::open(&path).unwrap();
+        assert!(!parser.load_sym_index(&index, &other).unwrap());
+    }
+
+    /// Check that we handle symbols with the special `SHN_ABS` and
+    /// `SHN_COMMON` section indices correctly.
+    #[test]
+    fn special_section_index_syms() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-special-shndx.bin");
+        let parser = ElfParser::open(&path).unwrap();
+        let opts = FindAddrOpts {
+            offset_in_file: true,
+            ..Default::default()
+        };
+
+        // The value of an absolute symbol is its address.
+        let syms = parser.find_addr("abs_sym", &opts).unwrap();
+        assert_eq!(syms.len(), 1);
+        assert_eq!(syms[0].addr, 0x10);
+        assert_eq!(syms[0].file_offset, None);
+        let (name, addr, _size) = parser.find_sym(0x10, STT_FUNC).unwrap().unwrap();
+        assert_eq!(name, "abs_sym");
+        assert_eq!(addr, 0x10);
+
+        // The value of a common symbol is its alignment, which must not
+        // be mistaken for an address.
+        let syms = parser.find_addr("common_sym", &opts).unwrap();
+        assert_eq!(syms.len(), 1);
+        assert_eq!(syms[0].addr, 0);
+        assert_eq!(syms[0].size, 64);
+        assert_eq!(syms[0].end, None);
+        assert_eq!(syms[0].file_offset, None);
+        for addr in [0x20, 0x21, 0x5f] {
+            assert_eq!(parser.find_sym(addr, STT_OBJECT).unwrap(), None);
+            assert_eq!(parser.find_sym_exact(addr, STT_OBJECT).unwrap(), None);
+        }
+    }
+
+    /// Check that we can find the relocations referencing a symbol.
+    #[test]
+    fn symbol_relocations() {
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+
+        // `factorial` is called from three different sections of this
+        // relocatable object.
+        let parser = ElfParser::open(&data.join("test-stable-addresses-rel.bin")).unwrap();
+        let relocs = parser.relocations_for("factorial").unwrap();
+        let sections = relocs
+            .iter()
+            .map(|reloc| parser.section_name(reloc.section).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sections,
+            [".rela.text", ".rela.text.factorial", ".rela.text.inline"]
+        );
+        assert!(relocs.iter().all(|reloc| reloc.addend.is_some()));
+
+        assert_eq!(parser.relocations_for("does_not_exist").unwrap(), []);
+
+        // Symbols of shared objects are referenced by dynamic
+        // relocations.
+        let parser = ElfParser::open(&data.join("libtest-so.so")).unwrap();
+        let relocs = parser.relocations_for("__cxa_finalize").unwrap();
+        assert_eq!(relocs.len(), 1);
+        assert_eq!(parser.section_name(relocs[0].section).unwrap(), ".rela.dyn");
+        assert_eq!(relocs[0].addend, Some(0));
+    }
+
     /// Check that our `ElfParser` can handle more than 0xff00 section
     /// headers and more than 0xffff program headers properly.
     #[test]
@@ -731,6 +2920,264 @@ mod tests {
         assert_eq!(ehdr.phnum, usize::try_from(PHNUM).unwrap());
     }
 
+    /// Check that `find_file_offset` deterministically picks a segment
+    /// when multiple `PT_LOAD` segments overlap.
+    #[test]
+    fn overlapping_load_segments() {
+        #[repr(C)]
+        struct Elf {
+            ehdr: Elf64_Ehdr,
+            phdrs: [Elf64_Phdr; 4],
+            shdrs: [Elf64_Shdr; 1],
+        }
+
+        let load = |p_flags, p_offset, p_vaddr, p_memsz| Elf64_Phdr {
+            p_type: PT_LOAD,
+            p_flags,
+            p_offset,
+            p_vaddr,
+            p_paddr: p_vaddr,
+            p_filesz: p_memsz,
+            p_memsz,
+            p_align: 0x1000,
+        };
+
+        let elf = Elf {
+            ehdr: Elf64_Ehdr {
+                e_ident: [127, 69, 76, 70, 2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+                e_type: 3,
+                e_machine: 62,
+                e_version: 1,
+                e_entry: 0,
+                e_phoff: size_of::<Elf64_Ehdr>() as _,
+                e_shoff: (size_of::<Elf64_Ehdr>() + 4 * size_of::<Elf64_Phdr>()) as _,
+                e_flags: 0,
+                e_ehsize: 64,
+                e_phentsize: 56,
+                e_phnum: 4,
+                e_shentsize: 64,
+                e_shnum: 1,
+                e_shstrndx: 0,
+            },
+            phdrs: [
+                // A large read-only segment spanning everything else.
+                load(0, 0x1000, 0x1000, 0x8000),
+                // A smaller read-only segment.
+                load(0, 0x10000, 0x4000, 0x1000),
+                // Two executable segments that overlap with each other.
+                load(PF_X, 0x20000, 0x2000, 0x2000),
+                load(PF_X, 0x30000, 0x2000, 0x2000),
+            ],
+            shdrs: [Elf64_Shdr {
+                sh_name: 0,
+                sh_type: 0,
+                sh_flags: 0,
+                sh_addr: 0,
+                sh_offset: 0,
+                sh_size: 0,
+                sh_link: 0,
+                sh_info: 0,
+                sh_addralign: 0,
+                sh_entsize: 0,
+            }],
+        };
+
+        let mut file = tempfile().unwrap();
+        let dump =
+            unsafe { slice::from_raw_parts((&elf as *const Elf).cast::<u8>(), size_of::<Elf>()) };
+        let () = file.write_all(dump).unwrap();
+        let () = file.rewind().unwrap();
+
+        let parser = ElfParser::open_file(&file).unwrap();
+        // Only covered by the large segment.
+        assert_eq!(parser.find_file_offset(0x1010).unwrap(), Some(0x1010));
+        // The executable segment listed first wins over everything
+        // else.
+        assert_eq!(parser.find_file_offset(0x2010).unwrap(), Some(0x20010));
+        // Among non-executable segments the tightest fit wins.
+        assert_eq!(parser.find_file_offset(0x4010).unwrap(), Some(0x10010));
+        assert_eq!(parser.find_file_offset(0x5010).unwrap(), Some(0x5010));
+        assert_eq!(parser.find_file_offset(0x9000).unwrap(), None);
+    }
+
+    /// Check that we reject section headers referencing data past the
+    /// end of the file.
+    #[test]
+    fn out_of_bounds_section() {
+        #[repr(C)]
+        struct Elf {
+            ehdr: Elf64_Ehdr,
+            shdrs: [Elf64_Shdr; 3],
+        }
+
+        let shdr = |sh_type, sh_offset, sh_size| Elf64_Shdr {
+            sh_name: 0,
+            sh_type,
+            sh_flags: 0,
+            sh_addr: 0,
+            sh_offset,
+            sh_size,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 0,
+            sh_entsize: 0,
+        };
+
+        let mut elf = Elf {
+            ehdr: Elf64_Ehdr {
+                e_ident: [127, 69, 76, 70, 2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+                e_type: 3,
+                e_machine: 62,
+                e_version: 1,
+                e_entry: 0,
+                e_phoff: 0,
+                e_shoff: size_of::<Elf64_Ehdr>() as _,
+                e_flags: 0,
+                e_ehsize: 64,
+                e_phentsize: 56,
+                e_phnum: 0,
+                e_shentsize: 64,
+                e_shnum: 3,
+                e_shstrndx: 0,
+            },
+            shdrs: [
+                shdr(0, 0, 0),
+                // `SHT_NOBITS` sections are not backed by file data.
+                shdr(SHT_NOBITS, 0x10000, 0x1000),
+                shdr(1, 0x40, 0x10),
+            ],
+        };
+
+        let parse = |elf: &Elf| {
+            let mut file = tempfile().unwrap();
+            let dump = unsafe {
+                slice::from_raw_parts((elf as *const Elf).cast::<u8>(), size_of::<Elf>())
+            };
+            let () = file.write_all(dump).unwrap();
+            let () = file.rewind().unwrap();
+            let parser = ElfParser::open_file(&file).unwrap();
+            parser.section_headers().map(<[_]>::len)
+        };
+
+        assert_eq!(parse(&elf).unwrap(), 3);
+
+        elf.shdrs[2].sh_offset = 0x10000;
+        let err = parse(&elf).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().contains("section #2"), "{err}");
+
+        elf.shdrs[2].sh_offset = u64::MAX;
+        let err = parse(&elf).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    /// Check that we honor the entry size of symbol tables using
+    /// padded entries.
+    #[test]
+    fn padded_symbol_table_entries() {
+        #[repr(C)]
+        struct PaddedSym {
+            sym: Elf64_Sym,
+            pad: [u8; 8],
+        }
+
+        #[repr(C)]
+        struct Elf {
+            ehdr: Elf64_Ehdr,
+            shdrs: [Elf64_Shdr; 4],
+            syms: [PaddedSym; 3],
+            shstrtab: [u8; 27],
+            strtab: [u8; 9],
+        }
+
+        let syms_off = size_of::<Elf64_Ehdr>() + 4 * size_of::<Elf64_Shdr>();
+        let shstrtab_off = syms_off + 3 * size_of::<PaddedSym>();
+        let strtab_off = shstrtab_off + 27;
+
+        let shdr = |sh_name, sh_type, sh_offset: usize, sh_size, sh_link, sh_entsize| Elf64_Shdr {
+            sh_name,
+            sh_type,
+            sh_flags: 0,
+            sh_addr: 0,
+            sh_offset: sh_offset as _,
+            sh_size,
+            sh_link,
+            sh_info: 0,
+            sh_addralign: 0,
+            sh_entsize,
+        };
+        let sym = |st_name, st_value, st_size| PaddedSym {
+            sym: Elf64_Sym {
+                st_name,
+                st_info: (STB_GLOBAL << 4) | STT_FUNC,
+                st_other: 0,
+                st_shndx: 1,
+                st_value,
+                st_size,
+            },
+            // Fill the padding with garbage that would be misinterpreted
+            // if we were to use the wrong stride.
+            pad: [0xff; 8],
+        };
+
+        let mut elf = Elf {
+            ehdr: Elf64_Ehdr {
+                e_ident: [127, 69, 76, 70, 2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+                e_type: 3,
+                e_machine: 62,
+                e_version: 1,
+                e_entry: 0,
+                e_phoff: 0,
+                e_shoff: size_of::<Elf64_Ehdr>() as _,
+                e_flags: 0,
+                e_ehsize: 64,
+                e_phentsize: 56,
+                e_phnum: 0,
+                e_shentsize: 64,
+                e_shnum: 4,
+                e_shstrndx: 1,
+            },
+            shdrs: [
+                shdr(0, 0, 0, 0, 0, 0),
+                shdr(1, 3, shstrtab_off, 27, 0, 0),
+                shdr(
+                    11,
+                    SHT_SYMTAB,
+                    syms_off,
+                    3 * size_of::<PaddedSym>() as u64,
+                    3,
+                    size_of::<PaddedSym>() as _,
+                ),
+                shdr(19, 3, strtab_off, 9, 0, 0),
+            ],
+            syms: [sym(0, 0, 0), sym(1, 0x1000, 0x10), sym(5, 0x2000, 0x20)],
+            shstrtab: *b"\0.shstrtab\0.symtab\0.strtab\0",
+            strtab: *b"\0foo\0bar\0",
+        };
+
+        let parse = |elf: &Elf| {
+            let mut file = tempfile().unwrap();
+            let dump = unsafe {
+                slice::from_raw_parts((elf as *const Elf).cast::<u8>(), size_of::<Elf>())
+            };
+            let () = file.write_all(dump).unwrap();
+            let () = file.rewind().unwrap();
+            ElfParser::open_file(&file).unwrap()
+        };
+
+        let parser = parse(&elf);
+        let (name, addr, size) = parser.find_sym(0x1008, STT_FUNC).unwrap().unwrap();
+        assert_eq!((name, addr, size), ("foo", 0x1000, 0x10));
+        let (name, addr, size) = parser.find_sym(0x2010, STT_FUNC).unwrap().unwrap();
+        assert_eq!((name, addr, size), ("bar", 0x2000, 0x20));
+
+        // Entries smaller than `Elf64_Sym` are invalid.
+        elf.shdrs[2].sh_entsize = 16;
+        let parser = parse(&elf);
+        let err = parser.find_sym(0x1008, STT_FUNC).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
     /// Test that our `ElfParser` can handle a `shstrndx` larger than
     /// 0xff00.
     #[test]
@@ -788,6 +3235,35 @@ mod tests {
         assert_eq!(shstrndx, SHSTRNDX.into());
     }
 
+    /// Check that we can look up symbols in files containing only one
+    /// of `.symtab` and `.dynsym`.
+    #[test]
+    fn single_symbol_table() {
+        // A fully static binary lacks `.dynsym`.
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-static.bin");
+        let parser = ElfParser::open(&path).unwrap();
+        assert_eq!(parser.find_section(".dynsym").unwrap(), None);
+        let (name, addr, _size) = parser.find_sym(0x2000100, STT_FUNC).unwrap().unwrap();
+        assert_eq!((name, addr), ("factorial", 0x2000100));
+
+        // A stripped shared object only has `.dynsym`.
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so-dynsym-only.so");
+        let parser = ElfParser::open(&path).unwrap();
+        assert_eq!(parser.find_section(".symtab").unwrap(), None);
+        let opts = FindAddrOpts {
+            sym_type: SymType::Function,
+            ..Default::default()
+        };
+        let syms = parser.find_addr("the_answer", &opts).unwrap();
+        assert_eq!(syms.len(), 1);
+        let (name, addr, _size) = parser.find_sym(syms[0].addr, STT_FUNC).unwrap().unwrap();
+        assert_eq!((name, addr), ("the_answer", syms[0].addr));
+    }
+
 
     #[test]
     fn test_elf64_parser() {
@@ -867,6 +3343,7 @@ mod tests {
         let sym = &syms[0];
         assert_eq!(sym.name, "factorial");
         assert_eq!(sym.addr, 0x2000100);
+        assert_eq!(sym.end, Some(sym.addr + sym.size as Addr));
 
         let syms = parser.find_addr("factorial_wrapper", &opts).unwrap();
         assert_eq!(syms.len(), 2);
@@ -875,6 +3352,265 @@ mod tests {
         assert_ne!(syms[0].addr, syms[1].addr);
     }
 
+    /// Check that we can deduplicate symbols sharing name and address.
+    #[test]
+    fn lookup_symbol_dedup() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-dup-sym.bin");
+
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+        let opts = FindAddrOpts::default();
+        let syms = parser.find_addr("factorial", &opts).unwrap();
+        assert_eq!(syms.len(), 2, "{syms:?}");
+        assert_eq!(syms[0].addr, syms[1].addr);
+
+        let opts = FindAddrOpts {
+            dedup: true,
+            ..Default::default()
+        };
+        let syms = parser.find_addr("factorial", &opts).unwrap();
+        assert_eq!(syms.len(), 1, "{syms:?}");
+        // The global symbol, which has a size, should be preferred.
+        assert_eq!(syms[0].addr, 0x2000100);
+        assert_ne!(syms[0].size, 0);
+
+        // Symbols with the same name at distinct addresses are retained.
+        let syms = parser.find_addr("factorial_wrapper", &opts).unwrap();
+        assert_eq!(syms.len(), 2, "{syms:?}");
+    }
+
+    /// Check that we can cap the number of symbols reported for a name.
+    #[test]
+    fn lookup_symbol_limit() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-dup-sym.bin");
+
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+        let opts = FindAddrOpts::default();
+        let all = parser.find_addr("factorial_wrapper", &opts).unwrap();
+        assert_eq!(all.len(), 2, "{all:?}");
+
+        let opts = FindAddrOpts {
+            limit: Some(1),
+            ..Default::default()
+        };
+        let syms = parser.find_addr("factorial_wrapper", &opts).unwrap();
+        assert_eq!(syms, all[..1]);
+
+        let opts = FindAddrOpts {
+            limit: Some(0),
+            ..Default::default()
+        };
+        let syms = parser.find_addr("factorial_wrapper", &opts).unwrap();
+        assert_eq!(syms, Vec::new());
+
+        // Deduplication still picks the most informative symbol when
+        // a limit is in place.
+        let opts = FindAddrOpts {
+            dedup: true,
+            limit: Some(1),
+            ..Default::default()
+        };
+        let syms = parser.find_addr("factorial", &opts).unwrap();
+        assert_eq!(syms.len(), 1, "{syms:?}");
+        assert_eq!(syms[0].addr, 0x2000100);
+        assert_ne!(syms[0].size, 0);
+    }
+
+    /// Check that looking up multiple symbols at once yields the same
+    /// results as looking them up one by one.
+    #[test]
+    fn lookup_symbols_batched() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-no-dwarf.bin");
+
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+        let opts = FindAddrOpts::default();
+        let names = [
+            "main",
+            "factorial_wrapper",
+            "does-not-exist",
+            "factorial",
+            "main",
+            "",
+        ];
+        let results = parser.find_addrs(&names, &opts).unwrap();
+        assert_eq!(results.len(), names.len());
+        for (name, syms) in names.iter().zip(&results) {
+            assert_eq!(syms, &parser.find_addr(name, &opts).unwrap());
+        }
+
+        assert_eq!(results[0].len(), 1);
+        assert_eq!(results[1].len(), 2);
+        assert!(results[2].is_empty());
+        assert_eq!(results[3][0].addr, 0x2000100);
+        assert_eq!(results[0], results[4]);
+    }
+
+    /// Check that we only report symbols starting exactly at the
+    /// provided address when asking for an exact match.
+    #[test]
+    fn lookup_symbol_exact() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-no-dwarf.bin");
+
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+        let (name, addr, _size) = parser.find_sym_exact(0x2000100, STT_FUNC).unwrap().unwrap();
+        assert_eq!(name, "factorial");
+        assert_eq!(addr, 0x2000100);
+
+        // The address is contained in `factorial`, but it is not its
+        // start.
+        let (name, ..) = parser.find_sym(0x2000101, STT_FUNC).unwrap().unwrap();
+        assert_eq!(name, "factorial");
+        let result = parser.find_sym_exact(0x2000101, STT_FUNC).unwrap();
+        assert_eq!(result, None);
+
+        let result = parser.find_sym_exact(0x0, STT_FUNC).unwrap();
+        assert_eq!(result, None);
+        let result = parser.find_sym_exact(u64::MAX, STT_FUNC).unwrap();
+        assert_eq!(result, None);
+    }
+
+    /// Check that we can parse an ELF file provided by a reader.
+    #[test]
+    fn from_reader() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let file = File::open(&path).unwrap();
+        let len = file.metadata().unwrap().len();
+        let parser = ElfParser::from_reader(&file, len).unwrap();
+        let (name, addr, _size) = parser.find_sym(0x2000100, STT_FUNC).unwrap().unwrap();
+        assert_eq!(name, "factorial");
+        assert_eq!(addr, 0x2000100);
+
+        let data = read(&path).unwrap();
+        let parser = ElfParser::from_reader(data.as_slice(), len).unwrap();
+        let (name, ..) = parser.find_sym(0x2000100, STT_FUNC).unwrap().unwrap();
+        assert_eq!(name, "factorial");
+
+        // Reporting a length larger than the data available should
+        // result in an error.
+        let err = ElfParser::from_reader(data.as_slice(), len + 1).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    /// Check that we can enumerate section groups and their members.
+    #[test]
+    fn section_group_enumeration() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-section-groups.bin");
+        let parser = ElfParser::open(&path).unwrap();
+        let groups = parser.section_groups().unwrap();
+        assert_eq!(groups.len(), 2, "{groups:?}");
+
+        let group = &groups[0];
+        assert_eq!(group.signature, "comdat_fn");
+        assert_eq!(group.flags, GRP_COMDAT);
+        let members = group
+            .members
+            .iter()
+            .map(|idx| parser.section_name(*idx).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(members, [".text.comdat_fn", ".data.comdat_fn"]);
+        assert_eq!(parser.section_name(group.section).unwrap(), ".group");
+
+        let group = &groups[1];
+        assert_eq!(group.signature, "group_fn");
+        assert_eq!(group.flags, 0);
+        assert_eq!(group.members.len(), 1);
+        assert_eq!(
+            parser.section_name(group.members[0]).unwrap(),
+            ".text.group_fn"
+        );
+
+        // Files without section groups are reported as such.
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let parser = ElfParser::open(&path).unwrap();
+        assert_eq!(parser.section_groups().unwrap(), Vec::new());
+    }
+
+    /// Check that we can query basic properties of the target
+    /// architecture of an ELF file.
+    #[test]
+    fn architecture_properties() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+
+        let machine = parser.machine().unwrap();
+        if cfg!(target_arch = "x86_64") {
+            assert_eq!(machine, EM_X86_64);
+        } else if cfg!(target_arch = "aarch64") {
+            assert_eq!(machine, EM_AARCH64);
+        } else if cfg!(target_arch = "riscv64") {
+            assert_eq!(machine, EM_RISCV);
+        }
+        assert_eq!(parser.class().unwrap(), ELFCLASS64);
+
+        let endianness = parser.endianness().unwrap();
+        if cfg!(target_endian = "little") {
+            assert_eq!(endianness, ELFDATA2LSB);
+        } else {
+            assert_eq!(endianness, ELFDATA2MSB);
+        }
+
+        let os_abi = parser.os_abi().unwrap();
+        assert!([ELFOSABI_SYSV, ELFOSABI_GNU].contains(&os_abi), "{os_abi}");
+        assert_eq!(parser.abi_version().unwrap(), 0);
+    }
+
+    /// Check that TLS symbols are reported as such when looked up by
+    /// name, but never when looking up an address.
+    #[test]
+    fn tls_symbols() {
+        let bin_name = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-tls.so");
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+
+        let opts = FindAddrOpts {
+            offset_in_file: true,
+            ..Default::default()
+        };
+        let syms = parser.find_addr("tls_counter", &opts).unwrap();
+        assert_eq!(syms.len(), 1, "{syms:?}");
+        let sym = &syms[0];
+        assert_eq!(sym.sym_type, SymType::Tls);
+        assert_eq!(sym.size, 4);
+        assert_eq!(sym.file_offset, None);
+
+        let tls_addr = sym.addr;
+        let syms = parser.find_addr("tls_zeroed", &opts).unwrap();
+        assert_eq!(syms[0].sym_type, SymType::Tls);
+
+        // Explicitly asking for functions should not yield TLS symbols.
+        let opts = FindAddrOpts {
+            sym_type: SymType::Function,
+            ..Default::default()
+        };
+        let syms = parser.find_addr("tls_counter", &opts).unwrap();
+        assert_eq!(syms, Vec::new());
+        let syms = parser.find_addr("tls_increment", &opts).unwrap();
+        assert_eq!(syms[0].sym_type, SymType::Function);
+
+        // The TLS offset must not be attributed to the TLS symbol, not
+        // even when explicitly asking for this symbol type.
+        let result = parser.find_sym(tls_addr, STT_TLS).unwrap();
+        assert_eq!(result, None);
+        let result = parser.find_sym_exact(tls_addr, STT_TLS).unwrap();
+        assert_eq!(result, None);
+    }
+
     /// Make sure that we do not report a symbol if there is no conceivable
     /// match.
     #[test]
@@ -968,4 +3704,133 @@ mod tests {
         test(&symtab);
         test(&symtab[0..2]);
     }
+
+    /// Check that we determine symbol end addresses correctly.
+    #[test]
+    fn symbol_end_calculation() {
+        let sym = |st_shndx, st_value, st_size| Elf64_Sym {
+            st_name: 0,
+            st_info: 0x12,
+            st_other: 0,
+            st_shndx,
+            st_value,
+            st_size,
+        };
+        let syms = [
+            sym(0xe, 0x1000, 0x0),
+            sym(0xe, 0x1000, 0x10),
+            sym(0xf, 0x1008, 0x0),
+            sym(0xe, 0x1020, 0x0),
+            sym(0xe, 0x1030, 0x8),
+            sym(0xe, 0x1040, 0x0),
+        ];
+        let symtab = syms.iter().collect::<Vec<_>>();
+        let ends = sym_ends(&symtab);
+        assert_eq!(ends.len(), symtab.len());
+
+        // Symbols without size end where the next symbol in the same
+        // section starts, ignoring ones at the same address.
+        assert_eq!(ends[0], Some(0x1020));
+        // A size takes precedence over the next symbol.
+        assert_eq!(ends[1], Some(0x1010));
+        assert_eq!(ends[2], None);
+        assert_eq!(ends[3], Some(0x1030));
+        assert_eq!(ends[4], Some(0x1038));
+        assert_eq!(ends[5], None);
+
+        assert!(sym_ends(&[]).is_empty());
+    }
+
+    /// Check that we report all symbols covering an address, in order
+    /// of preference.
+    #[test]
+    fn lookup_all_symbols() {
+        let strtab = b"\x00outer\x00inner\x00weak\x00local\x00label\x00next\x00";
+        let sym = |st_name, st_info, st_value, st_size| Elf64_Sym {
+            st_name,
+            st_info,
+            st_other: 0,
+            st_shndx: 0xe,
+            st_value,
+            st_size,
+        };
+        let syms = [
+            Elf64_Sym {
+                st_name: 0,
+                st_info: 0,
+                st_other: 0,
+                st_shndx: 0,
+                st_value: 0,
+                st_size: 0,
+            },
+            sym(1, 0x12, 0x1000, 0x100),
+            sym(18, 0x02, 0x1010, 0x10),
+            sym(13, 0x22, 0x1010, 0x10),
+            sym(7, 0x12, 0x1010, 0x10),
+            sym(24, 0x12, 0x1018, 0x0),
+            sym(30, 0x12, 0x1080, 0x10),
+        ];
+        let symtab = syms.iter().collect::<Vec<_>>();
+
+        let names = |addr| {
+            find_syms(&symtab, &sym_ends(&symtab), strtab, addr, STT_FUNC)
+                .unwrap()
+                .into_iter()
+                .map(|(name, ..)| name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(0x1018),
+            vec!["label", "inner", "weak", "local", "outer"]
+        );
+        assert_eq!(names(0x1010), vec!["inner", "weak", "local", "outer"]);
+        // `label` ends where `next` starts.
+        assert_eq!(names(0x1030), vec!["label", "outer"]);
+        assert_eq!(names(0x1090), vec!["outer"]);
+        assert_eq!(names(0x1100), Vec::<&str>::new());
+        assert_eq!(names(0x1), Vec::<&str>::new());
+        // We never report TLS symbols.
+        let syms = find_syms(&symtab, &sym_ends(&symtab), strtab, 0x1018, STT_TLS).unwrap();
+        assert!(syms.is_empty());
+    }
+
+    /// Check that we handle malformed symbols whose extent overflows the
+    /// address space gracefully.
+    #[test]
+    fn lookup_symbol_with_overflowing_size() {
+        let strtab = b"\x00overflow\x00";
+        let symtab = [&Elf64_Sym {
+            st_name: 0x1,
+            st_info: 0x12,
+            st_other: 0x0,
+            st_shndx: 0xf,
+            st_value: 0xfffffffffffffff0,
+            st_size: 0x100,
+        }];
+
+        let result = find_sym(&symtab, strtab, 0xfffffffffffffff8, STT_FUNC)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result, ("overflow", 0xfffffffffffffff0, 0x100));
+
+        let shdrs = (0..16)
+            .map(|_| Elf64_Shdr {
+                sh_name: 0,
+                sh_type: 0,
+                sh_flags: 0,
+                sh_addr: 0xfffffffffffffff8,
+                sh_offset: 0,
+                sh_size: 0,
+                sh_link: 0,
+                sh_info: 0,
+                sh_addralign: 0,
+                sh_entsize: 0,
+            })
+            .collect::<Vec<_>>();
+        let bin_name = env::current_exe().unwrap();
+        let parser = ElfParser::open(bin_name.as_ref()).unwrap();
+        let err = parser.file_offset(&shdrs, symtab[0]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
 }
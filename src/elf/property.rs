@@ -0,0 +1,227 @@
+use std::path::Path;
+
+use crate::util::ReadRaw as _;
+use crate::IntoError as _;
+use crate::Result;
+
+use super::types::Elf64_Nhdr;
+use super::types::EM_AARCH64;
+use super::types::EM_X86_64;
+use super::types::GNU_PROPERTY_AARCH64_FEATURE_1_AND;
+use super::types::GNU_PROPERTY_AARCH64_FEATURE_1_BTI;
+use super::types::GNU_PROPERTY_AARCH64_FEATURE_1_PAC;
+use super::types::GNU_PROPERTY_X86_FEATURE_1_AND;
+use super::types::GNU_PROPERTY_X86_FEATURE_1_IBT;
+use super::types::GNU_PROPERTY_X86_FEATURE_1_SHSTK;
+use super::types::NT_GNU_PROPERTY_TYPE_0;
+use super::ElfParser;
+
+
+/// Control flow protection related features an ELF file was built
+/// with, as recorded in its `NT_GNU_PROPERTY_TYPE_0` note.
+///
+/// All features are reported as disabled if the file does not contain
+/// such a note.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GnuProperties {
+    /// Whether x86 indirect branch tracking (Intel CET IBT) is enabled.
+    pub x86_ibt: bool,
+    /// Whether x86 shadow stacks (Intel CET SHSTK) are enabled.
+    pub x86_shstk: bool,
+    /// Whether AArch64 branch target identification (BTI) is enabled.
+    pub aarch64_bti: bool,
+    /// Whether AArch64 pointer authentication (PAC) is enabled.
+    pub aarch64_pac: bool,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// Skip the padding needed to advance from offset `offset` to the next
+/// multiple of `align`.
+fn skip_padding(data: &mut &[u8], offset: usize, align: usize) {
+    let padding = (align - offset % align) % align;
+    // The very last item may not be padded, so be lenient.
+    let _padding = data.read_slice(padding.min(data.len()));
+}
+
+/// Parse the properties of an `NT_GNU_PROPERTY_TYPE_0` note descriptor,
+/// merging them into `props`.
+fn parse_property_desc(mut desc: &[u8], e_machine: u16, props: &mut GnuProperties) -> Result<()> {
+    while !desc.is_empty() {
+        let pr_type = desc
+            .read_u32()
+            .ok_or_invalid_data(|| "failed to read GNU property type")?;
+        let pr_datasz = desc
+            .read_u32()
+            .ok_or_invalid_data(|| "failed to read GNU property data size")?;
+        let mut data = desc
+            .read_slice(pr_datasz as usize)
+            .ok_or_invalid_data(|| "failed to read GNU property data")?;
+        // Property data are padded to eight bytes in 64 bit objects.
+        let () = skip_padding(&mut desc, pr_datasz as usize, 8);
+
+        match (e_machine, pr_type) {
+            (EM_X86_64, GNU_PROPERTY_X86_FEATURE_1_AND) => {
+                let features = data
+                    .read_u32()
+                    .ok_or_invalid_data(|| "failed to read x86 feature property")?;
+                props.x86_ibt = features & GNU_PROPERTY_X86_FEATURE_1_IBT != 0;
+                props.x86_shstk = features & GNU_PROPERTY_X86_FEATURE_1_SHSTK != 0;
+            }
+            (EM_AARCH64, GNU_PROPERTY_AARCH64_FEATURE_1_AND) => {
+                let features = data
+                    .read_u32()
+                    .ok_or_invalid_data(|| "failed to read AArch64 feature property")?;
+                props.aarch64_bti = features & GNU_PROPERTY_AARCH64_FEATURE_1_BTI != 0;
+                props.aarch64_pac = features & GNU_PROPERTY_AARCH64_FEATURE_1_PAC != 0;
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Parse all `NT_GNU_PROPERTY_TYPE_0` notes contained in the note
+/// section data `data`, which has an alignment of `align`.
+pub(super) fn parse_gnu_property_notes(
+    data: &[u8],
+    align: usize,
+    e_machine: u16,
+    props: &mut GnuProperties,
+) -> Result<()> {
+    let mut notes = data;
+    while !notes.is_empty() {
+        let nhdr = notes
+            .read_pod::<Elf64_Nhdr>()
+            .ok_or_invalid_data(|| "failed to read ELF note header")?;
+        let name = notes
+            .read_slice(nhdr.n_namesz as usize)
+            .ok_or_invalid_data(|| "failed to read ELF note name")?;
+        // Padding is relative to the start of the section.
+        let offset = data.len() - notes.len();
+        let () = skip_padding(&mut notes, offset, align);
+        let desc = notes
+            .read_slice(nhdr.n_descsz as usize)
+            .ok_or_invalid_data(|| "failed to read ELF note descriptor")?;
+        let offset = data.len() - notes.len();
+        let () = skip_padding(&mut notes, offset, align);
+
+        if nhdr.n_type == NT_GNU_PROPERTY_TYPE_0 && name == b"GNU\0" {
+            let () = parse_property_desc(desc, e_machine, props)?;
+        }
+    }
+    Ok(())
+}
+
+
+/// Read the control flow protection related features of the ELF file
+/// at the given path, as recorded in its `NT_GNU_PROPERTY_TYPE_0` note.
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("libtest-so.so");
+/// let props = blazesym::helper::read_elf_gnu_properties(&path).unwrap();
+/// if !props.x86_ibt {
+///     println!("{} was not built with IBT enabled", path.display());
+/// }
+/// ```
+pub fn read_elf_gnu_properties<P>(path: &P) -> Result<GnuProperties>
+where
+    P: AsRef<Path>,
+{
+    let parser = ElfParser::open(path.as_ref())?;
+    parser.gnu_properties()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ErrorKind;
+
+
+    /// Create a `NT_GNU_PROPERTY_TYPE_0` note containing a single
+    /// property with the provided type and 32 bit value.
+    fn make_note(pr_type: u32, value: u32) -> Vec<u8> {
+        let mut desc = Vec::new();
+        let () = desc.extend_from_slice(&pr_type.to_ne_bytes());
+        let () = desc.extend_from_slice(&4u32.to_ne_bytes());
+        let () = desc.extend_from_slice(&value.to_ne_bytes());
+        // Padding.
+        let () = desc.extend_from_slice(&[0; 4]);
+
+        let mut note = Vec::new();
+        let () = note.extend_from_slice(&4u32.to_ne_bytes());
+        let () = note.extend_from_slice(&(desc.len() as u32).to_ne_bytes());
+        let () = note.extend_from_slice(&NT_GNU_PROPERTY_TYPE_0.to_ne_bytes());
+        let () = note.extend_from_slice(b"GNU\0");
+        let () = note.extend_from_slice(&desc);
+        note
+    }
+
+
+    /// Check that we can parse x86 and AArch64 feature properties.
+    #[test]
+    fn gnu_property_parsing() {
+        let note = make_note(
+            GNU_PROPERTY_X86_FEATURE_1_AND,
+            GNU_PROPERTY_X86_FEATURE_1_IBT | GNU_PROPERTY_X86_FEATURE_1_SHSTK,
+        );
+        let mut props = GnuProperties::default();
+        let () = parse_gnu_property_notes(&note, 8, EM_X86_64, &mut props).unwrap();
+        assert!(props.x86_ibt);
+        assert!(props.x86_shstk);
+        assert!(!props.aarch64_bti);
+
+        // Property types are architecture specific and should be
+        // ignored for other architectures.
+        let mut props = GnuProperties::default();
+        let () = parse_gnu_property_notes(&note, 8, EM_AARCH64, &mut props).unwrap();
+        assert_eq!(props, GnuProperties::default());
+
+        let mut notes = make_note(
+            GNU_PROPERTY_AARCH64_FEATURE_1_AND,
+            GNU_PROPERTY_AARCH64_FEATURE_1_BTI,
+        );
+        let () = notes.extend(make_note(0xdeadbeef, 0));
+        let mut props = GnuProperties::default();
+        let () = parse_gnu_property_notes(&notes, 8, EM_AARCH64, &mut props).unwrap();
+        assert!(props.aarch64_bti);
+        assert!(!props.aarch64_pac);
+        assert!(!props.x86_ibt);
+    }
+
+    /// Make sure that we fail gracefully on truncated notes.
+    #[test]
+    fn gnu_property_parsing_truncated() {
+        let note = make_note(
+            GNU_PROPERTY_X86_FEATURE_1_AND,
+            GNU_PROPERTY_X86_FEATURE_1_IBT,
+        );
+        // Cut into the note descriptor.
+        let mut props = GnuProperties::default();
+        let err = parse_gnu_property_notes(&note[..26], 8, EM_X86_64, &mut props).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    /// Check that we can read GNU properties from an actual ELF file.
+    #[test]
+    fn gnu_property_reading() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let _props = read_elf_gnu_properties(&path).unwrap();
+
+        // A file without any note sections has no features enabled.
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-rel.bin");
+        let props = read_elf_gnu_properties(&path).unwrap();
+        assert_eq!(props, GnuProperties::default());
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use crate::Result;
+
+use super::ElfParser;
+
+
+/// A table that could be recovered by a lenient parser.
+///
+/// Tables are reported by [`read_elf_recovered_tables`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum RecoveredTable {
+    /// The section header table.
+    SectionHeaders,
+    /// A symbol table section (`.symtab` or `.dynsym`), as referenced
+    /// by the section headers.
+    SymtabSection,
+    /// The dynamic segment (`PT_DYNAMIC`).
+    Dynamic,
+    /// The dynamic symbol table (`DT_SYMTAB`).
+    DynSymtab,
+    /// The dynamic string table (`DT_STRTAB`).
+    DynStrtab,
+    /// The System V hash table (`DT_HASH`), used for sizing the
+    /// dynamic symbol table.
+    Hash,
+    /// The GNU hash table (`DT_GNU_HASH`), used for sizing the dynamic
+    /// symbol table.
+    GnuHash,
+}
+
+
+/// Parse the ELF file at the given path leniently and report the tables
+/// that could be recovered from it.
+///
+/// Lenient parsing tolerates the absence of usable section headers, as
+/// is common for ELF images carved from a memory snapshot. Symbols are
+/// then recovered from the tables referenced by the dynamic segment
+/// (`PT_DYNAMIC`). The same mode can be enabled for symbolization via
+/// [`Builder::enable_lenient_elf_parsing`][crate::symbolize::Builder::enable_lenient_elf_parsing].
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("libtest-so.so");
+/// use blazesym::helper::RecoveredTable;
+///
+/// let tables = blazesym::helper::read_elf_recovered_tables(&path).unwrap();
+/// // An intact file provides all information via its section headers.
+/// assert_eq!(
+///     tables,
+///     [RecoveredTable::SectionHeaders, RecoveredTable::SymtabSection]
+/// );
+/// ```
+pub fn read_elf_recovered_tables<P>(path: &P) -> Result<Vec<RecoveredTable>>
+where
+    P: AsRef<Path>,
+{
+    let mut parser = ElfParser::open(path.as_ref())?;
+    let () = parser.set_lenient(true);
+    parser.recovered_tables()
+}
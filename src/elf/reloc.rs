@@ -0,0 +1,226 @@
+use std::path::Path;
+
+use crate::util::ReadRaw as _;
+use crate::Addr;
+use crate::Error;
+use crate::IntoError as _;
+use crate::Result;
+
+use super::types::Elf64_Rela;
+use super::types::EM_AARCH64;
+use super::types::EM_X86_64;
+use super::types::R_AARCH64_ABS32;
+use super::types::R_AARCH64_ABS64;
+use super::types::R_AARCH64_NONE;
+use super::types::R_X86_64_32;
+use super::types::R_X86_64_32S;
+use super::types::R_X86_64_64;
+use super::types::R_X86_64_NONE;
+use super::ElfParser;
+
+
+/// A relocation entry, as contained in an `SHT_RELA` or `SHT_REL`
+/// section.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RelocInfo {
+    /// The index of the relocation section containing the entry.
+    pub section: usize,
+    /// The location at which the relocation is applied: a section
+    /// offset for relocatable objects and a virtual address otherwise.
+    pub offset: u64,
+    /// The architecture specific relocation type (`R_*`).
+    pub type_: u32,
+    /// The constant addend, if the entry carries an explicit one (i.e.,
+    /// if it is contained in an `SHT_RELA` section).
+    pub addend: Option<i64>,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// Determine the width, in bytes, of the value patched by a
+/// relocation of type `r_type` on architecture `machine`.
+///
+/// `None` is returned for relocations that do not have any effect.
+fn reloc_width(machine: u16, r_type: u32) -> Result<Option<usize>> {
+    let width = match (machine, r_type) {
+        (EM_X86_64, R_X86_64_NONE) | (EM_AARCH64, R_AARCH64_NONE) => None,
+        (EM_X86_64, R_X86_64_64) | (EM_AARCH64, R_AARCH64_ABS64) => Some(8),
+        (EM_X86_64, R_X86_64_32 | R_X86_64_32S) | (EM_AARCH64, R_AARCH64_ABS32) => Some(4),
+        _ => {
+            return Err(Error::with_unsupported(format!(
+                "relocation type {r_type} for machine {machine} is unsupported"
+            )))
+        }
+    };
+    Ok(width)
+}
+
+
+/// Apply the `Elf64_Rela` relocations contained in `relas` to a copy of
+/// the section data `data`.
+///
+/// `sym_value` is used to look up the value of the symbol with the
+/// provided index, i.e., its address after the containing section got
+/// placed. Relocations referencing symbols for which `None` is reported
+/// (e.g., because they are undefined) are skipped.
+///
+/// Only absolute relocations, as found in debug sections, are
+/// supported.
+pub(crate) fn relocate<F>(
+    data: &[u8],
+    mut relas: &[u8],
+    machine: u16,
+    mut sym_value: F,
+) -> Result<Box<[u8]>>
+where
+    F: FnMut(u32) -> Result<Option<Addr>>,
+{
+    let mut relocated = data.to_vec().into_boxed_slice();
+
+    while !relas.is_empty() {
+        let rela = relas
+            .read_pod::<Elf64_Rela>()
+            .ok_or_invalid_data(|| "failed to read Elf64_Rela")?;
+        let width = match reloc_width(machine, rela.type_())? {
+            Some(width) => width,
+            None => continue,
+        };
+        let value = match sym_value(rela.sym())? {
+            Some(value) => value.wrapping_add(rela.r_addend as u64),
+            None => continue,
+        };
+
+        let target = usize::try_from(rela.r_offset)
+            .ok()
+            .and_then(|offset| relocated.get_mut(offset..offset.checked_add(width)?))
+            .ok_or_invalid_data(|| {
+                format!(
+                    "relocation offset {:#x} is out of bounds of section",
+                    rela.r_offset
+                )
+            })?;
+        match width {
+            4 => target.copy_from_slice(&(value as u32).to_ne_bytes()),
+            8 => target.copy_from_slice(&value.to_ne_bytes()),
+            _ => unreachable!(),
+        }
+    }
+    Ok(relocated)
+}
+
+
+/// Find all relocations referencing the symbol with the given name in
+/// the ELF file at the given path, in the order in which they appear in
+/// the file.
+///
+/// All `SHT_RELA` and `SHT_REL` sections are considered, with symbols
+/// being looked up in the symbol table each of them references
+/// (typically `.symtab` or `.dynsym`). Section indices are indices into
+/// the file's section header table, as reported by tools such as
+/// `readelf`.
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("libtest-so.so");
+/// let relocs = blazesym::helper::read_elf_relocations_for(&path, "__cxa_finalize").unwrap();
+/// for reloc in relocs {
+///     println!("__cxa_finalize referenced at {:#x}", reloc.offset);
+/// }
+/// ```
+pub fn read_elf_relocations_for<P>(path: &P, sym_name: &str) -> Result<Vec<RelocInfo>>
+where
+    P: AsRef<Path>,
+{
+    let parser = ElfParser::open(path.as_ref())?;
+    parser.relocations_for(sym_name)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::mem::size_of_val;
+    use std::slice;
+
+    use test_log::test;
+
+    use crate::ErrorKind;
+
+
+    /// Convert a slice of `Elf64_Rela` objects into its raw bytes.
+    fn rela_bytes(relas: &[Elf64_Rela]) -> &[u8] {
+        let len = size_of_val(relas);
+        // SAFETY: `Elf64_Rela` is a plain old data type.
+        unsafe { slice::from_raw_parts(relas.as_ptr().cast::<u8>(), len) }
+    }
+
+    /// Check that we can apply absolute relocations.
+    #[test]
+    fn relocation() {
+        let data = [0u8; 16];
+        let relas = [
+            Elf64_Rela {
+                r_offset: 0,
+                r_info: (1 << 32) | u64::from(R_X86_64_64),
+                r_addend: 0x10,
+            },
+            Elf64_Rela {
+                r_offset: 8,
+                r_info: (2 << 32) | u64::from(R_X86_64_32),
+                r_addend: 0x20,
+            },
+            // A relocation referencing an undefined symbol is skipped.
+            Elf64_Rela {
+                r_offset: 12,
+                r_info: (3 << 32) | u64::from(R_X86_64_32),
+                r_addend: 0x30,
+            },
+        ];
+        let sym_value = |idx| match idx {
+            1 => Ok(Some(0xffffffffc0001000)),
+            2 => Ok(Some(0)),
+            _ => Ok(None),
+        };
+
+        let relocated = relocate(&data, rela_bytes(&relas), EM_X86_64, sym_value).unwrap();
+        assert_eq!(
+            relocated[0..8],
+            0xffffffffc0001010u64.to_ne_bytes(),
+            "{relocated:x?}"
+        );
+        assert_eq!(relocated[8..12], 0x20u32.to_ne_bytes(), "{relocated:x?}");
+        assert_eq!(relocated[12..16], [0; 4], "{relocated:x?}");
+    }
+
+    /// Check that we report errors on unsupported or malformed
+    /// relocations.
+    #[test]
+    fn invalid_relocation() {
+        let data = [0u8; 8];
+        let sym_value = |_idx| Ok(Some(0));
+
+        let relas = [Elf64_Rela {
+            r_offset: 4,
+            r_info: (1 << 32) | u64::from(R_X86_64_64),
+            r_addend: 0,
+        }];
+        let err = relocate(&data, rela_bytes(&relas), EM_X86_64, sym_value).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let relas = [Elf64_Rela {
+            r_offset: 0,
+            r_info: (1 << 32) | 2,
+            r_addend: 0,
+        }];
+        let err = relocate(&data, rela_bytes(&relas), EM_X86_64, sym_value).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let err = relocate(&data, &[0; 3], EM_X86_64, sym_value).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
@@ -2,22 +2,61 @@ use std::borrow::Cow;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::mem;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
+use std::ptr;
 use std::rc::Rc;
+#[cfg(feature = "stats")]
+use std::sync::atomic::AtomicU64;
 
+use crate::inspect::AddrStatus;
 use crate::inspect::FindAddrOpts;
+use crate::inspect::InlineInstance;
 use crate::inspect::SymInfo;
+use crate::inspect::SymType;
+use crate::inspect::VarInfo;
+#[cfg(feature = "dwarf")]
+use crate::log::warn;
 use crate::symbolize::AddrCodeInfo;
+use crate::symbolize::CodeInfo;
 use crate::symbolize::IntSym;
 use crate::symbolize::SrcLang;
+#[cfg(feature = "stats")]
+use crate::symbolize::StatsCounters;
 use crate::Addr;
+use crate::IntoError as _;
 use crate::Result;
 use crate::SymResolver;
 
+use super::types::SHF_ALLOC;
+use super::types::SHF_EXECINSTR;
 use super::types::STT_FUNC;
 use super::ElfBackend;
 use super::ElfParser;
+use super::ElfSymSource;
+
+
+/// Address ranges of sections in a runtime file along with the start
+/// address of the same section in the corresponding debug file.
+type SectionMap = Box<[(Range<Addr>, Addr)]>;
+
+
+/// Determine the order in which to consult symbol sources for address
+/// based lookups, given the preferred source `sym_source`.
+fn sym_source_order(sym_source: ElfSymSource) -> &'static [ElfSymSource] {
+    match sym_source {
+        ElfSymSource::Dwarf => &[ElfSymSource::Dwarf, ElfSymSource::Symtab],
+        ElfSymSource::Symtab => &[ElfSymSource::Symtab, ElfSymSource::Dwarf],
+        ElfSymSource::Dynsym => &[
+            ElfSymSource::Dynsym,
+            ElfSymSource::Dwarf,
+            ElfSymSource::Symtab,
+        ],
+    }
+}
+
 
 /// The symbol resolver for a single ELF file.
 ///
@@ -30,18 +69,123 @@ use super::ElfParser;
 /// it's loaded address.
 pub struct ElfResolver {
     backend: ElfBackend,
+    /// The parser for the ELF file that gets loaded at runtime, if
+    /// symbols and debug information are provided by a separate debug
+    /// file (represented by `backend`).
+    runtime_parser: Option<Rc<ElfParser>>,
+    /// The address ranges of sections in the runtime file along with
+    /// the start address of the same section in the debug file, for
+    /// all sections whose addresses differ between the two.
+    section_map: SectionMap,
     file_name: PathBuf,
+    /// The source of symbols to prefer for address based lookups.
+    sym_source: ElfSymSource,
+    /// Whether to report source code information for addresses in a
+    /// function's prologue as of the end of said prologue.
+    #[cfg(feature = "dwarf")]
+    skip_prologue: bool,
+    /// Counters for statistics about the work performed, if any.
+    #[cfg(feature = "stats")]
+    stats: Option<Rc<StatsCounters>>,
 }
 
 impl ElfResolver {
     pub(crate) fn with_backend(file_name: &Path, backend: ElfBackend) -> Result<ElfResolver> {
         Ok(ElfResolver {
             backend,
+            runtime_parser: None,
+            section_map: Box::default(),
             file_name: file_name.to_path_buf(),
+            sym_source: ElfSymSource::default(),
+            #[cfg(feature = "dwarf")]
+            skip_prologue: false,
+            #[cfg(feature = "stats")]
+            stats: None,
         })
     }
 
+    /// Create an `ElfResolver` for an ELF file (represented by
+    /// `parser`) that has its symbols and debug information split off
+    /// into a separate debug file (represented by `debug_backend`).
+    ///
+    /// Symbols are looked up in the debug file first, falling back to
+    /// the ones of the runtime file (typically only `.dynsym`).
+    ///
+    /// The runtime file is the authority on addresses. Should sections
+    /// of the debug file reside at different addresses (e.g., because
+    /// the runtime file got prelinked after the debug file was split
+    /// off), addresses are translated between the two.
+    pub(crate) fn with_debug_backend(
+        file_name: &Path,
+        parser: Rc<ElfParser>,
+        debug_backend: ElfBackend,
+    ) -> Result<ElfResolver> {
+        let debug_parser = match &debug_backend {
+            #[cfg(feature = "dwarf")]
+            ElfBackend::Dwarf(dwarf) => dwarf.parser(),
+            ElfBackend::Elf(parser) => parser,
+        };
+        let section_map = map_sections(&parser, debug_parser)?;
+
+        Ok(ElfResolver {
+            backend: debug_backend,
+            runtime_parser: Some(parser),
+            section_map,
+            file_name: file_name.to_path_buf(),
+            sym_source: ElfSymSource::default(),
+            #[cfg(feature = "dwarf")]
+            skip_prologue: false,
+            #[cfg(feature = "stats")]
+            stats: None,
+        })
+    }
+
+    /// Set the source of symbols to prefer for address based lookups.
+    ///
+    /// Name based lookups are governed by [`FindAddrOpts::sym_source`]
+    /// instead.
+    pub(crate) fn set_sym_source(&mut self, sym_source: ElfSymSource) {
+        self.sym_source = sym_source;
+    }
+
+    /// Set whether source code information for addresses in a
+    /// function's prologue is to be reported as of the end of the
+    /// prologue.
+    #[cfg(feature = "dwarf")]
+    pub(crate) fn set_skip_prologue(&mut self, skip_prologue: bool) {
+        self.skip_prologue = skip_prologue;
+    }
+
+    /// Set the counters to account work performed by this resolver to.
+    #[cfg(feature = "stats")]
+    pub(crate) fn set_stats(&mut self, stats: Rc<StatsCounters>) {
+        self.stats = Some(stats);
+    }
+
+    /// Increment the counter selected by `f`, if statistics are being
+    /// gathered.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn count<F>(&self, f: F)
+    where
+        F: FnOnce(&StatsCounters) -> &AtomicU64,
+    {
+        if let Some(stats) = &self.stats {
+            let () = StatsCounters::inc(f(stats));
+        }
+    }
+
+    /// Retrieve the parser for the ELF file as it is loaded at runtime.
+    ///
+    /// Any address mapping (e.g., of file offsets to virtual addresses)
+    /// should happen based on this file.
     pub(crate) fn parser(&self) -> &Rc<ElfParser> {
+        self.runtime_parser
+            .as_ref()
+            .unwrap_or_else(|| self.backend_parser())
+    }
+
+    fn backend_parser(&self) -> &Rc<ElfParser> {
         match &self.backend {
             #[cfg(feature = "dwarf")]
             ElfBackend::Dwarf(dwarf) => dwarf.parser(),
@@ -49,79 +193,868 @@ impl ElfResolver {
         }
     }
 
+    /// Retrieve all parsers to consult for ELF symbols, in order of
+    /// preference.
+    pub(super) fn sym_parsers(&self) -> impl Iterator<Item = &Rc<ElfParser>> {
+        [Some(self.backend_parser()), self.runtime_parser.as_ref()]
+            .into_iter()
+            .flatten()
+    }
+
+    /// Translate `addr`, an address in the runtime file, into the
+    /// address space of the debug file.
+    fn debug_addr(&self, addr: Addr) -> Addr {
+        self.section_map
+            .iter()
+            .find(|(range, _)| range.contains(&addr))
+            .map_or(addr, |(range, debug_start)| {
+                addr - range.start + debug_start
+            })
+    }
+
+    /// Translate `addr`, an address in the debug file, into the address
+    /// space of the runtime file.
+    fn runtime_addr(&self, addr: Addr) -> Addr {
+        self.section_map
+            .iter()
+            .find(|(range, debug_start)| {
+                addr >= *debug_start && addr - debug_start < range.end - range.start
+            })
+            .map_or(addr, |(range, debug_start)| {
+                addr - debug_start + range.start
+            })
+    }
+
+    /// Translate `addr`, an address in the runtime file, into the
+    /// address space of `parser`.
+    fn parser_addr(&self, parser: &ElfParser, addr: Addr) -> Addr {
+        if ptr::eq(parser, &**self.backend_parser()) {
+            self.debug_addr(addr)
+        } else {
+            addr
+        }
+    }
+
+    /// Translate `addr`, an address in the address space of `parser`,
+    /// into the address space of the runtime file.
+    fn parser_runtime_addr(&self, parser: &ElfParser, addr: Addr) -> Addr {
+        if ptr::eq(parser, &**self.backend_parser()) {
+            self.runtime_addr(addr)
+        } else {
+            addr
+        }
+    }
+
+    /// Translate the address of a symbol found in the debug file into
+    /// the address space of the runtime file.
+    fn runtime_sym_info(&self, sym: &mut SymInfo<'_>) {
+        let addr = self.runtime_addr(sym.addr);
+        sym.end = sym
+            .end
+            .map(|end| end.wrapping_sub(sym.addr).wrapping_add(addr));
+        sym.addr = addr;
+    }
+
+    /// Eagerly parse the symbol tables of all relevant parsers, so that
+    /// subsequent lookups do not have to.
+    pub(crate) fn warm_up(&self) -> Result<()> {
+        for parser in self.sym_parsers() {
+            let () = parser.parse_syms()?;
+        }
+        Ok(())
+    }
+
+    /// Retrieve all function symbols from the ELF symbol tables, sorted
+    /// by address.
+    ///
+    /// If multiple symbols start at the same address, only the largest
+    /// one is reported.
+    pub(crate) fn func_syms(&self) -> Result<Vec<SymInfo<'static>>> {
+        let opts = FindAddrOpts {
+            sym_type: SymType::Function,
+            ..Default::default()
+        };
+        let mut syms = Vec::new();
+        for parser in self.sym_parsers() {
+            let is_debug = ptr::eq(&**parser, &**self.backend_parser());
+            syms = parser.for_each_sym(&opts, syms, |mut syms, sym| {
+                let mut sym = sym.to_owned();
+                if is_debug {
+                    let () = self.runtime_sym_info(&mut sym);
+                }
+                let () = syms.push(sym);
+                syms
+            })?;
+        }
+
+        let () = syms.sort_by(|sym1, sym2| {
+            sym1.addr
+                .cmp(&sym2.addr)
+                .then_with(|| sym2.size.cmp(&sym1.size))
+        });
+        let () = syms.dedup_by_key(|sym| sym.addr);
+        Ok(syms)
+    }
+
+    /// Retrieve all symbols starting within `start..end`, sorted by
+    /// address.
+    ///
+    /// Symbols from DWARF debug information and the ELF symbol tables
+    /// are merged, with the source configured in `opts` taking
+    /// precedence for symbols sharing name and address.
+    pub(crate) fn syms_in_range<'slf>(
+        &'slf self,
+        start: Addr,
+        end: Addr,
+        opts: &FindAddrOpts,
+    ) -> Result<Vec<SymInfo<'slf>>> {
+        let range = start..end;
+
+        #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+        let dwarf_syms = |syms: &mut Vec<SymInfo<'slf>>| -> Result<()> {
+            #[cfg(feature = "dwarf")]
+            if let ElfBackend::Dwarf(dwarf) = &self.backend {
+                let debug_range = self.debug_addr(start)..self.debug_addr(end);
+                let mut dwarf_syms = dwarf.syms_in_range(debug_range, opts)?;
+                let () = dwarf_syms
+                    .iter_mut()
+                    .for_each(|sym| self.runtime_sym_info(sym));
+                let () = dwarf_syms.retain(|sym| range.contains(&sym.addr));
+                let () = self.retain_in_section(&mut dwarf_syms, opts)?;
+                let () = syms.extend(dwarf_syms);
+            }
+            Ok(())
+        };
+
+        let elf_syms = |syms: &mut Vec<SymInfo<'slf>>| -> Result<()> {
+            for parser in self.sym_parsers() {
+                let is_debug = ptr::eq(&**parser, &**self.backend_parser());
+                *syms = parser.for_each_sym(opts, mem::take(syms), |mut syms, sym| {
+                    let mut sym = sym.to_owned();
+                    if is_debug {
+                        let () = self.runtime_sym_info(&mut sym);
+                    }
+                    if range.contains(&sym.addr) {
+                        let () = syms.push(sym);
+                    }
+                    syms
+                })?;
+            }
+            Ok(())
+        };
+
+        let mut syms = Vec::new();
+        match opts.sym_source {
+            ElfSymSource::Dwarf => {
+                let () = dwarf_syms(&mut syms)?;
+                let () = elf_syms(&mut syms)?;
+            }
+            ElfSymSource::Symtab | ElfSymSource::Dynsym => {
+                let () = elf_syms(&mut syms)?;
+                let () = dwarf_syms(&mut syms)?;
+            }
+        }
+
+        // The sort is stable, so the first of multiple equal symbols
+        // stems from the preferred source.
+        let () = syms.sort_by(|sym1, sym2| {
+            sym1.addr
+                .cmp(&sym2.addr)
+                .then_with(|| sym1.name.cmp(&sym2.name))
+        });
+        let () = syms.dedup_by(|sym2, sym1| sym1.addr == sym2.addr && sym1.name == sym2.name);
+        let () = self.finalize_syms(&mut syms, opts)?;
+        Ok(syms)
+    }
+
+    /// Invoke `f` for each DWARF line table row, with the address range
+    /// it covers and its source location.
+    ///
+    /// Rows are not reported in any particular order. Nothing is
+    /// reported if no DWARF information is available.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables, unused_mut))]
+    pub(crate) fn for_each_line<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(Range<Addr>, &CodeInfo<'_>),
+    {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            for result in dwarf.all_lines() {
+                let (range, code_info) = result?;
+                let start = self.runtime_addr(range.start);
+                let end = start.saturating_add(range.end - range.start);
+                let () = f(start..end, &code_info);
+            }
+        }
+        Ok(())
+    }
+
+    /// Perform an operation on each inlined function instance described
+    /// by the file's DWARF debug information.
+    ///
+    /// Nothing is reported if no DWARF information is available.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables, unused_mut))]
+    pub(crate) fn for_each_inlined<F, R>(&self, r: R, mut f: F) -> Result<R>
+    where
+        F: FnMut(R, &InlineInstance<'_>) -> R,
+    {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            let mut r = r;
+            for result in dwarf.all_inlined() {
+                let mut instance = result?;
+                let () = instance.ranges.iter_mut().for_each(|range| {
+                    let start = self.runtime_addr(range.start);
+                    *range = start..start.saturating_add(range.end - range.start);
+                });
+                r = f(r, &instance);
+            }
+            return Ok(r)
+        }
+        Ok(r)
+    }
+
+    /// Find the addresses attributed to the given source code line.
+    ///
+    /// The returned addresses are sorted. An empty list is reported if
+    /// no DWARF information is available.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+    pub(crate) fn find_line(&self, file: &Path, line: u32) -> Result<Vec<Addr>> {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            let mut addrs = dwarf
+                .find_line(file, line)?
+                .into_iter()
+                .map(|addr| self.runtime_addr(addr))
+                .collect::<Vec<_>>();
+            let () = addrs.sort_unstable();
+            return Ok(addrs)
+        }
+        Ok(Vec::new())
+    }
+
+    /// Retrieve the paths of all source files referenced by the DWARF
+    /// compilation unit covering `addr`.
+    ///
+    /// An empty list is reported if no DWARF information is available.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+    pub(crate) fn source_files(&self, addr: Addr) -> Result<Vec<PathBuf>> {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            return dwarf.source_files(self.debug_addr(addr))
+        }
+        Ok(Vec::new())
+    }
+
+    /// Retrieve the parameters and local variables of the function
+    /// covering `addr` that are in scope at this address.
+    ///
+    /// An empty list is reported if no DWARF information is available.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+    pub(crate) fn locals(&self, addr: Addr) -> Result<Vec<VarInfo<'_>>> {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            return dwarf.locals(self.debug_addr(addr))
+        }
+        Ok(Vec::new())
+    }
+
+    /// Retrieve the source location of every DWARF line table row of
+    /// the function containing `addr`, sorted by address.
+    ///
+    /// An empty list is reported if no DWARF information is available.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+    pub(crate) fn function_lines(&self, addr: Addr) -> Result<Vec<(Addr, PathBuf, u32)>> {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            let mut lines = dwarf
+                .function_lines(self.debug_addr(addr))?
+                .into_iter()
+                .map(|(addr, path, line)| (self.runtime_addr(addr), path, line))
+                .collect::<Vec<_>>();
+            let () = lines.sort_by_key(|(addr, ..)| *addr);
+            return Ok(lines)
+        }
+        Ok(Vec::new())
+    }
+
+    /// Retrieve the source location of the entry of the function
+    /// containing `addr`.
+    ///
+    /// `None` is reported if no DWARF information is available.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+    pub(crate) fn function_decl_line(&self, addr: Addr) -> Result<Option<(PathBuf, u32)>> {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            return dwarf.function_decl_line(self.debug_addr(addr))
+        }
+        Ok(None)
+    }
+
+    /// Retrieve the name of the DWARF compilation unit covering `addr`.
+    ///
+    /// `None` is reported if no DWARF information is available.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+    pub(crate) fn unit_name(&self, addr: Addr) -> Result<Option<PathBuf>> {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            return dwarf.unit_name(self.debug_addr(addr))
+        }
+        Ok(None)
+    }
+
+    /// Retrieve the start addresses of all DWARF line table rows
+    /// overlapping `range`.
+    ///
+    /// An empty list is reported if no DWARF information is available.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+    pub(crate) fn line_addrs(&self, range: Range<Addr>) -> Result<Vec<Addr>> {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            let range = self.debug_addr(range.start)..self.debug_addr(range.end);
+            let addrs = dwarf.line_addrs(range)?;
+            return Ok(addrs
+                .into_iter()
+                .map(|addr| self.runtime_addr(addr))
+                .collect())
+        }
+        Ok(Vec::new())
+    }
+
+    /// Find the symbol containing the file's entry point (typically
+    /// `_start`).
+    ///
+    /// The entry point is a file virtual address and so is the reported
+    /// symbol's address. `None` is returned if the file has no entry
+    /// point or no symbol covers it. Symbol sources are consulted as
+    /// per `sym_source`.
+    pub(crate) fn find_entry_sym(&self, sym_source: ElfSymSource) -> Result<Option<IntSym<'_>>> {
+        let entry = self.parser().entry()?;
+        if entry == 0 {
+            return Ok(None)
+        }
+        self.find_sym_in(entry, sym_source_order(sym_source))
+    }
+
+    /// Find the function containing `addr` using only the ELF symbol
+    /// tables.
+    ///
+    /// Contrary to [`find_sym`][SymResolver::find_sym], this method
+    /// never consults DWARF debug information and the configured
+    /// [`ElfSymSource`] is disregarded. The lookup is a binary search
+    /// over the sorted symbol start addresses, making for predictable
+    /// low latency, e.g., for sampling profilers that defer full
+    /// symbolization.
+    pub(crate) fn find_func_fast(&self, addr: Addr) -> Result<Option<IntSym<'_>>> {
+        for parser in self.sym_parsers() {
+            let probe = self.parser_addr(parser, addr);
+            if let Some((name, addr, size)) = parser.find_sym(probe, STT_FUNC)? {
+                // ELF does not carry any source code language information.
+                let lang = SrcLang::Unknown;
+                let sym = IntSym {
+                    name,
+                    addr: self.parser_runtime_addr(parser, addr),
+                    size: Some(size),
+                    lang,
+                };
+                return Ok(Some(sym))
+            }
+        }
+        Ok(None)
+    }
+
+    /// Check whether the file provides information about inlined
+    /// functions.
+    ///
+    /// That is the case if DWARF debug information is in use and it
+    /// contains at least one `DW_TAG_inlined_subroutine` DIE. Callers
+    /// can use this method to determine upfront whether requesting
+    /// inlined functions can have any effect. Failure to parse debug
+    /// information is treated as absence of inline information.
+    pub(crate) fn has_inline_info(&self) -> bool {
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            return dwarf.has_inlined_fns().unwrap_or_else(|err| {
+                warn!(
+                    "failed to check {} for inline information: {err}",
+                    self.file_name.display()
+                );
+                false
+            })
+        }
+        false
+    }
+
+    /// Explain whether and why `addr` can or cannot be symbolized.
+    ///
+    /// This method is meant for diagnosing coverage gaps, for when
+    /// [`find_sym`][SymResolver::find_sym] does not report a symbol.
+    pub(crate) fn explain_addr(&self, addr: Addr) -> Result<AddrStatus> {
+        if self.find_sym(addr)?.is_some() {
+            return Ok(AddrStatus::Resolvable)
+        }
+
+        // Section headers of a separate debug file may not reflect
+        // those of the file loaded at runtime, so we consult the
+        // latter.
+        let parser = self.parser();
+        let idx = match parser.find_section_by_addr(addr)? {
+            Some(idx) => idx,
+            None => return Ok(AddrStatus::OutOfRange),
+        };
+        let shdr = parser
+            .section_headers()?
+            .get(idx)
+            .ok_or_invalid_data(|| format!("failed to retrieve ELF section header #{idx}"))?;
+        if shdr.sh_flags & SHF_EXECINSTR == 0 {
+            return Ok(AddrStatus::InDataSection)
+        }
+
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            let parser = dwarf.parser();
+            if parser.find_section(".debug_info")?.is_some()
+                || parser.find_section(".zdebug_info")?.is_some()
+            {
+                return Ok(AddrStatus::Uncovered)
+            }
+        }
+
+        for parser in self.sym_parsers() {
+            if parser.has_func_syms()? {
+                return Ok(AddrStatus::Uncovered)
+            }
+        }
+        Ok(AddrStatus::NoSymbols)
+    }
+
+    /// Find the function symbol starting exactly at `addr`.
+    ///
+    /// Contrary to [`find_sym`][SymResolver::find_sym], no symbol is
+    /// reported for an address merely contained in a symbol. Symbol
+    /// sources are consulted in the same order as by
+    /// [`find_sym`][SymResolver::find_sym] for `sym_source`.
+    pub(crate) fn find_sym_exact(
+        &self,
+        addr: Addr,
+        sym_source: ElfSymSource,
+    ) -> Result<Option<IntSym<'_>>> {
+        self.find_sym_exact_in(addr, sym_source_order(sym_source))
+    }
+
+    /// Find the function symbol starting exactly at `addr`, consulting
+    /// only the symbol sources `sources`, in the given order.
+    pub(crate) fn find_sym_exact_in(
+        &self,
+        addr: Addr,
+        sources: &[ElfSymSource],
+    ) -> Result<Option<IntSym<'_>>> {
+        let find_dwarf_sym = || -> Result<Option<IntSym<'_>>> {
+            #[cfg(feature = "dwarf")]
+            if let ElfBackend::Dwarf(dwarf) = &self.backend {
+                if let Some(mut sym) = dwarf.find_sym(self.debug_addr(addr))? {
+                    sym.addr = self.runtime_addr(sym.addr);
+                    if sym.addr == addr {
+                        return Ok(Some(sym))
+                    }
+                }
+            }
+            Ok(None)
+        };
+
+        let find_elf_sym = |dynsym: bool| -> Result<Option<IntSym<'_>>> {
+            for parser in self.sym_parsers() {
+                let probe = self.parser_addr(parser, addr);
+                let sym = if dynsym {
+                    parser.find_dynsym_exact(probe, STT_FUNC)?
+                } else {
+                    parser.find_sym_exact(probe, STT_FUNC)?
+                };
+                if let Some((name, addr, size)) = sym {
+                    let sym = IntSym {
+                        name,
+                        addr: self.parser_runtime_addr(parser, addr),
+                        size: Some(size),
+                        lang: SrcLang::Unknown,
+                    };
+                    return Ok(Some(sym))
+                }
+            }
+            Ok(None)
+        };
+
+        for source in sources {
+            let sym = match source {
+                ElfSymSource::Dwarf => find_dwarf_sym()?,
+                ElfSymSource::Symtab => find_elf_sym(false)?,
+                ElfSymSource::Dynsym => find_elf_sym(true)?,
+            };
+            if sym.is_some() {
+                return Ok(sym)
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find all symbols covering the given address.
+    ///
+    /// The symbol reported by DWARF, if any, and ELF symbols as
+    /// reported by [`ElfParser::find_syms`] are ordered as per the
+    /// preferred source `sym_source`. ELF symbols from the debug file
+    /// come before those from the runtime file. Symbols with the same
+    /// name and address are only reported once.
+    pub(crate) fn find_syms_at(
+        &self,
+        addr: Addr,
+        sym_source: ElfSymSource,
+    ) -> Result<Vec<IntSym<'_>>> {
+        fn push_unique<'sym>(syms: &mut Vec<IntSym<'sym>>, sym: IntSym<'sym>) {
+            if !syms
+                .iter()
+                .any(|other| other.name == sym.name && other.addr == sym.addr)
+            {
+                let () = syms.push(sym);
+            }
+        }
+
+        #[cfg_attr(not(feature = "dwarf"), allow(unused_mut))]
+        let mut dwarf_syms = Vec::<IntSym<'_>>::new();
+        #[cfg(feature = "dwarf")]
+        if let ElfBackend::Dwarf(dwarf) = &self.backend {
+            if let Some(mut sym) = dwarf.find_sym(self.debug_addr(addr))? {
+                sym.addr = self.runtime_addr(sym.addr);
+                let () = dwarf_syms.push(sym);
+            }
+        }
+
+        let mut elf_syms = Vec::<IntSym<'_>>::new();
+        for parser in self.sym_parsers() {
+            let probe = self.parser_addr(parser, addr);
+            for (name, addr, size) in parser.find_syms(probe, STT_FUNC)? {
+                let sym = IntSym {
+                    name,
+                    addr: self.parser_runtime_addr(parser, addr),
+                    size: Some(size),
+                    lang: SrcLang::Unknown,
+                };
+                let () = elf_syms.push(sym);
+            }
+        }
+
+        let (first, second) = match sym_source {
+            ElfSymSource::Dwarf => (dwarf_syms, elf_syms),
+            ElfSymSource::Symtab | ElfSymSource::Dynsym => (elf_syms, dwarf_syms),
+        };
+
+        let mut syms = Vec::with_capacity(first.len() + second.len());
+        for sym in first.into_iter().chain(second) {
+            let () = push_unique(&mut syms, sym);
+        }
+        Ok(syms)
+    }
+
+    /// Fill in file specific information in symbols found by name.
+    fn finalize_syms<'slf>(
+        &'slf self,
+        syms: &mut [SymInfo<'slf>],
+        opts: &FindAddrOpts,
+    ) -> Result<()> {
+        for sym in syms.iter_mut() {
+            if let Some(parser) = &self.runtime_parser {
+                // Section offsets in a separate debug file are not
+                // meaningful, so we have to consult the runtime file.
+                if opts.offset_in_file && sym.sym_type != SymType::Tls {
+                    sym.file_offset = parser.find_file_offset(sym.addr)?;
+                }
+            }
+            sym.obj_file_name = Some(Cow::Borrowed(&self.file_name));
+        }
+        Ok(())
+    }
+
+    /// Remove all symbols not residing in the section requested by
+    /// `opts`, if any.
+    ///
+    /// DWARF does not convey section membership, so symbols are
+    /// attributed to a section based on their address.
+    #[cfg(feature = "dwarf")]
+    fn retain_in_section(&self, syms: &mut Vec<SymInfo<'_>>, opts: &FindAddrOpts) -> Result<()> {
+        if let Some(name) = &opts.section {
+            let parser = self.parser();
+            let range = match parser.find_section(name)? {
+                Some(idx) => {
+                    let shdr = &parser.section_headers()?[idx];
+                    shdr.sh_addr..shdr.sh_addr.saturating_add(shdr.sh_size)
+                }
+                None => 0..0,
+            };
+            let () = syms.retain(|sym| range.contains(&sym.addr));
+        }
+        Ok(())
+    }
+
+    /// Check whether the ELF file defines a symbol named `name`.
+    ///
+    /// Contrary to [`SymResolver::find_addr`], only ELF symbol tables
+    /// are consulted and no information about the symbol is gathered,
+    /// making this check comparatively cheap.
+    pub(crate) fn has_sym(&self, name: &str) -> Result<bool> {
+        for parser in self.sym_parsers() {
+            if parser.has_sym(name)? {
+                return Ok(true)
+            }
+        }
+        Ok(false)
+    }
+
     /// Retrieve the path to the ELF file represented by this resolver.
     pub(crate) fn file_name(&self) -> &Path {
         &self.file_name
     }
 }
 
+/// Determine the sections of the runtime file (represented by `parser`)
+/// whose addresses differ from those of the same sections in the debug
+/// file (represented by `debug_parser`).
+fn map_sections(parser: &ElfParser, debug_parser: &ElfParser) -> Result<SectionMap> {
+    let shdrs = parser.section_headers()?;
+    let debug_shdrs = debug_parser.section_headers()?;
+
+    let mut map = Vec::new();
+    for (idx, shdr) in shdrs.iter().enumerate() {
+        if shdr.sh_flags & SHF_ALLOC == 0 || shdr.sh_size == 0 {
+            continue
+        }
+
+        let name = parser.section_name(idx)?;
+        let debug_shdr = match debug_parser.find_section(name)? {
+            Some(debug_idx) => &debug_shdrs[debug_idx],
+            None => continue,
+        };
+        if debug_shdr.sh_addr != shdr.sh_addr {
+            let range = shdr.sh_addr..shdr.sh_addr.saturating_add(shdr.sh_size);
+            let () = map.push((range, debug_shdr.sh_addr));
+        }
+    }
+    Ok(map.into_boxed_slice())
+}
+
 impl SymResolver for ElfResolver {
     #[cfg_attr(feature = "tracing", crate::log::instrument(fields(addr = format_args!("{addr:#x}"))))]
     fn find_sym(&self, addr: Addr) -> Result<Option<IntSym<'_>>> {
-        #[cfg(feature = "dwarf")]
-        if let ElfBackend::Dwarf(dwarf) = &self.backend {
-            if let Some(sym) = dwarf.find_sym(addr)? {
-                return Ok(Some(sym))
+        // DWARF and the ELF symbol table do not necessarily contain the
+        // same set of symbols, so we consult them in the configured
+        // order.
+        self.find_sym_in(addr, sym_source_order(self.sym_source))
+    }
+
+    fn find_sym_in(&self, addr: Addr, sources: &[ElfSymSource]) -> Result<Option<IntSym<'_>>> {
+        #[cfg(feature = "stats")]
+        let () = self.count(|stats| &stats.sym_lookups);
+
+        let find_dwarf_sym = || -> Result<Option<IntSym<'_>>> {
+            #[cfg(feature = "dwarf")]
+            if let ElfBackend::Dwarf(dwarf) = &self.backend {
+                if let Some(mut sym) = dwarf.find_sym(self.debug_addr(addr))? {
+                    #[cfg(feature = "stats")]
+                    let () = self.count(|stats| &stats.dwarf_hits);
+                    sym.addr = self.runtime_addr(sym.addr);
+                    return Ok(Some(sym))
+                }
             }
-        }
+            Ok(None)
+        };
 
-        let parser = self.parser();
-        if let Some((name, addr, size)) = parser.find_sym(addr, STT_FUNC)? {
-            // ELF does not carry any source code language information.
-            let lang = SrcLang::Unknown;
-            // We found the address in ELF.
-            // TODO: Long term we probably want a different heuristic here, as
-            //       there can be valid differences between the two formats
-            //       (e.g., DWARF could contain more symbols).
-            let sym = IntSym {
-                name,
-                addr,
-                size: Some(size),
-                lang,
-            };
-            Ok(Some(sym))
-        } else {
+        let find_elf_sym = || -> Result<Option<IntSym<'_>>> {
+            let sym = self.find_func_fast(addr)?;
+            #[cfg(feature = "stats")]
+            if sym.is_some() {
+                let () = self.count(|stats| &stats.elf_fallbacks);
+            }
+            Ok(sym)
+        };
+
+        let find_dynsym_sym = || -> Result<Option<IntSym<'_>>> {
+            for parser in self.sym_parsers() {
+                let probe = self.parser_addr(parser, addr);
+                if let Some((name, addr, size)) = parser.find_dynsym(probe, STT_FUNC)? {
+                    #[cfg(feature = "stats")]
+                    let () = self.count(|stats| &stats.elf_fallbacks);
+                    let sym = IntSym {
+                        name,
+                        addr: self.parser_runtime_addr(parser, addr),
+                        size: Some(size),
+                        lang: SrcLang::Unknown,
+                    };
+                    return Ok(Some(sym))
+                }
+            }
             Ok(None)
+        };
+
+        for source in sources {
+            let sym = match source {
+                ElfSymSource::Dwarf => find_dwarf_sym()?,
+                ElfSymSource::Symtab => find_elf_sym()?,
+                ElfSymSource::Dynsym => find_dynsym_sym()?,
+            };
+            if sym.is_some() {
+                return Ok(sym)
+            }
         }
+        Ok(None)
     }
 
     fn find_addr<'slf>(&'slf self, name: &str, opts: &FindAddrOpts) -> Result<Vec<SymInfo<'slf>>> {
-        fn find_addr_impl<'slf>(
-            slf: &'slf ElfResolver,
-            name: &str,
-            opts: &FindAddrOpts,
-        ) -> Result<Vec<SymInfo<'slf>>> {
+        let find_dwarf_addr = || -> Result<Vec<SymInfo<'slf>>> {
             #[cfg(feature = "dwarf")]
-            if let ElfBackend::Dwarf(dwarf) = &slf.backend {
-                let syms = dwarf.find_addr(name, opts)?;
+            if let ElfBackend::Dwarf(dwarf) = &self.backend {
+                let mut syms = dwarf.find_addr(name, opts)?;
+                let () = syms.iter_mut().for_each(|sym| self.runtime_sym_info(sym));
+                let () = self.retain_in_section(&mut syms, opts)?;
+                if let Some(limit) = opts.limit {
+                    let () = syms.truncate(limit);
+                }
+                return Ok(syms)
+            }
+            Ok(Vec::new())
+        };
+
+        let find_elf_addr = || -> Result<Vec<SymInfo<'slf>>> {
+            for parser in self.sym_parsers() {
+                let mut syms = parser.find_addr(name, opts)?;
                 if !syms.is_empty() {
+                    if ptr::eq(&**parser, &**self.backend_parser()) {
+                        let () = syms.iter_mut().for_each(|sym| self.runtime_sym_info(sym));
+                    }
                     return Ok(syms)
                 }
             }
+            Ok(Vec::new())
+        };
 
-            let parser = slf.parser();
-            let syms = parser.find_addr(name, opts)?;
-            Ok(syms)
+        let mut syms = match opts.sym_source {
+            ElfSymSource::Dwarf => match find_dwarf_addr()? {
+                syms if syms.is_empty() => find_elf_addr()?,
+                syms => syms,
+            },
+            ElfSymSource::Symtab | ElfSymSource::Dynsym => match find_elf_addr()? {
+                syms if syms.is_empty() => find_dwarf_addr()?,
+                syms => syms,
+            },
+        };
+        let () = self.finalize_syms(&mut syms, opts)?;
+        Ok(syms)
+    }
+
+    /// Find information about a list of symbols given their names.
+    ///
+    /// Contrary to the default implementation, ELF symbol tables are
+    /// searched in a single sweep for all names.
+    fn find_addrs<'slf>(
+        &'slf self,
+        names: &[&str],
+        opts: &FindAddrOpts,
+    ) -> Result<Vec<Vec<SymInfo<'slf>>>> {
+        let mut results = vec![Vec::new(); names.len()];
+
+        let find_dwarf_addrs = |results: &mut [Vec<SymInfo<'slf>>]| -> Result<()> {
+            #[cfg(feature = "dwarf")]
+            if let ElfBackend::Dwarf(dwarf) = &self.backend {
+                for (syms, name) in results.iter_mut().zip(names) {
+                    if syms.is_empty() {
+                        *syms = dwarf.find_addr(name, opts)?;
+                        let () = syms.iter_mut().for_each(|sym| self.runtime_sym_info(sym));
+                        let () = self.retain_in_section(syms, opts)?;
+                        if let Some(limit) = opts.limit {
+                            let () = syms.truncate(limit);
+                        }
+                    }
+                }
+            }
+            Ok(())
+        };
+
+        let find_elf_addrs = |results: &mut [Vec<SymInfo<'slf>>]| -> Result<()> {
+            for parser in self.sym_parsers() {
+                let (indices, missing) = results
+                    .iter()
+                    .zip(names)
+                    .enumerate()
+                    .filter_map(|(idx, (syms, name))| syms.is_empty().then_some((idx, *name)))
+                    .unzip::<_, _, Vec<_>, Vec<_>>();
+                if missing.is_empty() {
+                    break
+                }
+
+                let is_debug = ptr::eq(&**parser, &**self.backend_parser());
+                let found = parser.find_addrs(&missing, opts)?;
+                for (idx, mut syms) in indices.into_iter().zip(found) {
+                    if is_debug {
+                        let () = syms.iter_mut().for_each(|sym| self.runtime_sym_info(sym));
+                    }
+                    results[idx] = syms;
+                }
+            }
+            Ok(())
+        };
+
+        match opts.sym_source {
+            ElfSymSource::Dwarf => {
+                let () = find_dwarf_addrs(&mut results)?;
+                let () = find_elf_addrs(&mut results)?;
+            }
+            ElfSymSource::Symtab | ElfSymSource::Dynsym => {
+                let () = find_elf_addrs(&mut results)?;
+                let () = find_dwarf_addrs(&mut results)?;
+            }
         }
 
-        let mut syms = find_addr_impl(self, name, opts)?;
-        let () = syms
-            .iter_mut()
-            .for_each(|sym| sym.obj_file_name = Some(Cow::Borrowed(&self.file_name)));
-        Ok(syms)
+        for syms in results.iter_mut() {
+            let () = self.finalize_syms(syms, opts)?;
+        }
+        Ok(results)
     }
 
     #[cfg(feature = "dwarf")]
-    fn find_code_info(&self, addr: Addr, inlined_fns: bool) -> Result<Option<AddrCodeInfo<'_>>> {
+    fn find_code_info(
+        &self,
+        addr: Addr,
+        inlined_fns: bool,
+        line_only: bool,
+    ) -> Result<Option<AddrCodeInfo<'_>>> {
         if let ElfBackend::Dwarf(dwarf) = &self.backend {
-            dwarf.find_code_info(addr, inlined_fns)
+            let mut probe = self.debug_addr(addr);
+            if self.skip_prologue {
+                // Line tables may be present without DWARF function
+                // information, so fall back to ELF symbols for
+                // determining the function's extent.
+                let func = match dwarf.find_sym(probe)? {
+                    Some(sym) => Some((sym.addr, sym.size)),
+                    None => self
+                        .find_func_fast(addr)?
+                        .map(|sym| (self.debug_addr(sym.addr), sym.size)),
+                };
+                if let Some((start, Some(size))) = func {
+                    let end = start.saturating_add(size as Addr);
+                    if let Some(prologue_end) = dwarf.prologue_end(start..end)? {
+                        probe = probe.max(prologue_end);
+                    }
+                }
+            }
+            dwarf.find_code_info(probe, inlined_fns, line_only)
         } else {
             Ok(None)
         }
     }
 
     #[cfg(not(feature = "dwarf"))]
-    fn find_code_info(&self, addr: Addr, inlined_fns: bool) -> Result<Option<AddrCodeInfo<'_>>> {
+    fn find_code_info(
+        &self,
+        addr: Addr,
+        inlined_fns: bool,
+        line_only: bool,
+    ) -> Result<Option<AddrCodeInfo<'_>>> {
         Ok(None)
     }
 }
@@ -141,6 +1074,8 @@ impl Debug for ElfResolver {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "dwarf")]
+    use std::ffi::OsStr;
     use std::path::Path;
 
     #[cfg(feature = "dwarf")]
@@ -172,6 +1107,406 @@ mod tests {
         }
     }
 
+    /// Check that `find_sym_exact` only reports symbols starting at
+    /// the provided address, for both the ELF and the DWARF backend.
+    #[test]
+    fn exact_symbol_lookup() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let mut backends = vec![ElfBackend::Elf(parser.clone())];
+        #[cfg(feature = "dwarf")]
+        {
+            let dwarf = DwarfResolver::from_parser(parser, true).unwrap();
+            let () = backends.push(ElfBackend::Dwarf(Rc::new(dwarf)));
+        }
+
+        for backend in backends {
+            let resolver = ElfResolver::with_backend(&path, backend).unwrap();
+            let sym = resolver
+                .find_sym_exact(0x2000100, ElfSymSource::default())
+                .unwrap()
+                .unwrap();
+            assert_eq!(sym.name, "factorial");
+            assert_eq!(sym.addr, 0x2000100);
+
+            let sym = resolver
+                .find_sym_exact(0x2000101, ElfSymSource::default())
+                .unwrap();
+            assert!(sym.is_none());
+        }
+    }
+
+    /// Check that `find_sym_exact_in` only consults the provided
+    /// symbol sources.
+    #[test]
+    fn exact_symbol_lookup_sources() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let resolver = ElfResolver::with_backend(&path, ElfBackend::Elf(parser.clone())).unwrap();
+
+        let opts = FindAddrOpts {
+            sym_type: SymType::Function,
+            ..Default::default()
+        };
+        // `the_answer` is exported and, hence, part of `.dynsym`, while
+        // `frame_dummy` is a local symbol only present in `.symtab`.
+        let exported = parser.find_addr("the_answer", &opts).unwrap()[0].addr;
+        let local = parser.find_addr("frame_dummy", &opts).unwrap()[0].addr;
+
+        for sources in [
+            &[ElfSymSource::Dynsym][..],
+            &[ElfSymSource::Symtab],
+            &[ElfSymSource::Dynsym, ElfSymSource::Symtab],
+        ] {
+            let sym = resolver
+                .find_sym_exact_in(exported, sources)
+                .unwrap()
+                .unwrap();
+            assert_eq!(sym.name, "the_answer", "{sources:?}");
+        }
+
+        let sym = resolver
+            .find_sym_exact_in(local, &[ElfSymSource::Dynsym])
+            .unwrap();
+        assert_eq!(sym, None);
+        let sym = resolver
+            .find_sym_exact_in(local, &[ElfSymSource::Dynsym, ElfSymSource::Symtab])
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "frame_dummy");
+        let sym = resolver
+            .find_sym_exact(local, ElfSymSource::Symtab)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "frame_dummy");
+        assert_eq!(resolver.find_sym_exact_in(exported, &[]).unwrap(), None);
+    }
+
+    /// Check that we honor the configured preference of symbol
+    /// sources.
+    #[cfg(feature = "dwarf")]
+    #[test]
+    fn sym_source_preference() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let dwarf = DwarfResolver::from_parser(parser, true).unwrap();
+        let mut resolver =
+            ElfResolver::with_backend(&path, ElfBackend::Dwarf(Rc::new(dwarf))).unwrap();
+
+        // Only DWARF knows about the source language of a symbol, which
+        // allows us to tell the sources apart.
+        let sym = resolver.find_sym(0x2000100).unwrap().unwrap();
+        assert_eq!(sym.name, "factorial");
+        assert_eq!(sym.lang, SrcLang::C);
+        let syms = resolver
+            .find_syms_at(0x2000100, ElfSymSource::Dwarf)
+            .unwrap();
+        assert_eq!(syms.len(), 1);
+        assert_eq!(syms[0].lang, SrcLang::C);
+
+        let () = resolver.set_sym_source(ElfSymSource::Symtab);
+        let sym = resolver.find_sym(0x2000100).unwrap().unwrap();
+        assert_eq!(sym.name, "factorial");
+        assert_eq!(sym.lang, SrcLang::Unknown);
+        let sym = resolver
+            .find_sym_exact(0x2000100, ElfSymSource::Symtab)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.lang, SrcLang::Unknown);
+        let syms = resolver
+            .find_syms_at(0x2000100, ElfSymSource::Symtab)
+            .unwrap();
+        assert_eq!(syms.len(), 1);
+        assert_eq!(syms[0].lang, SrcLang::Unknown);
+
+        // Name based lookups are configured per request.
+        for sym_source in [ElfSymSource::Dwarf, ElfSymSource::Symtab] {
+            let opts = FindAddrOpts {
+                sym_source,
+                ..Default::default()
+            };
+            let syms = resolver.find_addr("factorial", &opts).unwrap();
+            assert_eq!(syms.len(), 1);
+            assert_eq!(syms[0].addr, 0x2000100);
+
+            let syms = resolver.find_addrs(&["factorial", "main"], &opts).unwrap();
+            assert_eq!(syms.len(), 2);
+            assert_eq!(syms[0][0].addr, 0x2000100);
+            assert_eq!(syms[1][0].addr, 0x2000000);
+        }
+    }
+
+    /// Check that `find_func_fast` reports the function containing an
+    /// address based on ELF symbols only.
+    #[test]
+    fn fast_func_lookup() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let mut backends = vec![ElfBackend::Elf(parser.clone())];
+        #[cfg(feature = "dwarf")]
+        {
+            let dwarf = DwarfResolver::from_parser(parser, true).unwrap();
+            let () = backends.push(ElfBackend::Dwarf(Rc::new(dwarf)));
+        }
+
+        for backend in backends {
+            let resolver = ElfResolver::with_backend(&path, backend).unwrap();
+            for addr in [0x2000100, 0x2000101] {
+                let sym = resolver.find_func_fast(addr).unwrap().unwrap();
+                assert_eq!(sym.name, "factorial");
+                assert_eq!(sym.addr, 0x2000100);
+                assert_eq!(sym.size, Some(0x2b));
+                assert_eq!(sym.lang, SrcLang::Unknown);
+            }
+
+            assert!(resolver.find_func_fast(0x0).unwrap().is_none());
+        }
+    }
+
+    /// Check that `find_syms_at` reports all symbols covering an
+    /// address, without duplicates.
+    #[test]
+    fn all_symbols_lookup() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let mut backends = vec![ElfBackend::Elf(parser.clone())];
+        #[cfg(feature = "dwarf")]
+        {
+            let dwarf = DwarfResolver::from_parser(parser, true).unwrap();
+            let () = backends.push(ElfBackend::Dwarf(Rc::new(dwarf)));
+        }
+
+        for backend in backends {
+            let resolver = ElfResolver::with_backend(&path, backend).unwrap();
+            for addr in [0x2000100, 0x2000101] {
+                let syms = resolver
+                    .find_syms_at(addr, ElfSymSource::default())
+                    .unwrap();
+                assert_eq!(syms.len(), 1);
+                assert_eq!(syms[0].name, "factorial");
+                assert_eq!(syms[0].addr, 0x2000100);
+            }
+
+            let syms = resolver.find_syms_at(0x0, ElfSymSource::default()).unwrap();
+            assert!(syms.is_empty());
+        }
+    }
+
+    /// Check that addresses are translated between a runtime file and
+    /// its debug file, if their section addresses differ.
+    #[test]
+    fn split_debug_divergent_addrs() {
+        let dir = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+        let path = dir.join("libtest-so-split-shifted.so");
+        let debug_path = dir.join("libtest-so-split-dwarf-debug.so");
+
+        let debug_parser = Rc::new(ElfParser::open(&debug_path).unwrap());
+        let mut backends = vec![ElfBackend::Elf(debug_parser.clone())];
+        #[cfg(feature = "dwarf")]
+        {
+            let dwarf = DwarfResolver::from_parser(debug_parser, true).unwrap();
+            let () = backends.push(ElfBackend::Dwarf(Rc::new(dwarf)));
+        }
+
+        for backend in backends {
+            let parser = Rc::new(ElfParser::open(&path).unwrap());
+            let resolver = ElfResolver::with_debug_backend(&path, parser, backend).unwrap();
+
+            // `the_hidden_answer` is only present in the debug file,
+            // which has it at 0x10f9.
+            let sym = resolver.find_sym(0x400010fa).unwrap().unwrap();
+            assert_eq!(sym.name, "the_hidden_answer");
+            assert_eq!(sym.addr, 0x400010f9);
+
+            let sym = resolver
+                .find_sym_exact(0x400010f9, ElfSymSource::default())
+                .unwrap()
+                .unwrap();
+            assert_eq!(sym.name, "the_hidden_answer");
+
+            let opts = FindAddrOpts {
+                sym_type: SymType::Function,
+                ..Default::default()
+            };
+            let syms = resolver.find_addr("the_hidden_answer", &opts).unwrap();
+            assert_eq!(syms.len(), 1);
+            assert_eq!(syms[0].addr, 0x400010f9);
+
+            #[cfg(feature = "dwarf")]
+            if let ElfBackend::Dwarf(..) = resolver.backend {
+                let info = resolver
+                    .find_code_info(0x400010fa, false, false)
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(info.direct.1.file, OsStr::new("test-so-split.c"));
+            }
+        }
+    }
+
+    /// Check that we can list all symbols in an address range.
+    #[test]
+    fn range_symbols_listing() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let mut backends = vec![ElfBackend::Elf(parser.clone())];
+        #[cfg(feature = "dwarf")]
+        {
+            let dwarf = DwarfResolver::from_parser(parser, true).unwrap();
+            let () = backends.push(ElfBackend::Dwarf(Rc::new(dwarf)));
+        }
+
+        for backend in backends {
+            let resolver = ElfResolver::with_backend(&path, backend).unwrap();
+            for sym_source in [ElfSymSource::Dwarf, ElfSymSource::Symtab] {
+                let opts = FindAddrOpts {
+                    sym_type: SymType::Function,
+                    sym_source,
+                    ..Default::default()
+                };
+                let syms = resolver
+                    .syms_in_range(0x2000000, 0x2000101, &opts)
+                    .unwrap()
+                    .into_iter()
+                    .map(|sym| (sym.name.to_string(), sym.addr))
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    syms,
+                    vec![
+                        ("main".to_string(), 0x2000000),
+                        ("factorial_wrapper".to_string(), 0x2000034),
+                        ("foo".to_string(), 0x2000045),
+                        ("factorial_wrapper".to_string(), 0x2000056),
+                        ("factorial".to_string(), 0x2000100),
+                    ]
+                );
+
+                // The end of the range is exclusive.
+                let syms = resolver.syms_in_range(0x2000001, 0x2000034, &opts).unwrap();
+                assert!(syms.is_empty(), "{syms:?}");
+            }
+        }
+    }
+
+    /// Check that we can detect whether a file contains inline
+    /// information.
+    #[test]
+    fn inline_info_detection() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let resolver = ElfResolver::with_backend(&path, ElfBackend::Elf(parser.clone())).unwrap();
+        assert!(!resolver.has_inline_info());
+
+        #[cfg(feature = "dwarf")]
+        {
+            let dwarf = DwarfResolver::from_parser(parser, true).unwrap();
+            let backend = ElfBackend::Dwarf(Rc::new(dwarf));
+            let resolver = ElfResolver::with_backend(&path, backend).unwrap();
+            assert!(resolver.has_inline_info());
+
+            let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+                .join("data")
+                .join("test-stable-addresses-no-dwarf.bin");
+            let parser = Rc::new(ElfParser::open(&path).unwrap());
+            let dwarf = DwarfResolver::from_parser(parser, true).unwrap();
+            let backend = ElfBackend::Dwarf(Rc::new(dwarf));
+            let resolver = ElfResolver::with_backend(&path, backend).unwrap();
+            assert!(!resolver.has_inline_info());
+        }
+    }
+
+    /// Check that we can explain why addresses can or cannot be
+    /// symbolized.
+    #[test]
+    fn addr_explanation() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-no-dwarf.bin");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let resolver = ElfResolver::with_backend(&path, ElfBackend::Elf(parser)).unwrap();
+        assert_eq!(
+            resolver.explain_addr(0x2000100).unwrap(),
+            AddrStatus::Resolvable
+        );
+        assert_eq!(resolver.explain_addr(0x0).unwrap(), AddrStatus::OutOfRange);
+        // The padding between `factorial_wrapper` and `factorial` is
+        // not covered by any symbol.
+        assert_eq!(
+            resolver.explain_addr(0x20000f0).unwrap(),
+            AddrStatus::Uncovered
+        );
+
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-no-debug.bin");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let idx = parser.find_section(".rodata").unwrap().unwrap();
+        let addr = parser.section_headers().unwrap()[idx].sh_addr;
+        let resolver = ElfResolver::with_backend(&path, ElfBackend::Elf(parser)).unwrap();
+        assert_eq!(
+            resolver.explain_addr(addr).unwrap(),
+            AddrStatus::InDataSection
+        );
+
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-stripped.bin");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let resolver = ElfResolver::with_backend(&path, ElfBackend::Elf(parser)).unwrap();
+        assert_eq!(
+            resolver.explain_addr(0x2000100).unwrap(),
+            AddrStatus::NoSymbols
+        );
+    }
+
+    /// Check that we can look up the symbol at a file's entry point.
+    #[test]
+    fn entry_symbol_lookup() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-no-debug.bin");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        let entry = parser.entry().unwrap();
+        assert_ne!(entry, 0);
+
+        let resolver = ElfResolver::with_backend(&path, ElfBackend::Elf(parser)).unwrap();
+        let sym = resolver
+            .find_entry_sym(ElfSymSource::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(sym.name, "_start");
+        assert_eq!(sym.addr, entry);
+
+        // Shared objects typically have no entry point.
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let parser = Rc::new(ElfParser::open(&path).unwrap());
+        assert_eq!(parser.entry().unwrap(), 0);
+        let resolver = ElfResolver::with_backend(&path, ElfBackend::Elf(parser)).unwrap();
+        assert!(resolver
+            .find_entry_sym(ElfSymSource::default())
+            .unwrap()
+            .is_none());
+    }
+
     /// Check that we fail finding an offset for an address not
     /// representing a symbol in an ELF file.
     #[test]
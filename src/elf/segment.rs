@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use crate::Addr;
+use crate::Result;
+
+use super::ElfParser;
+
+
+/// The thread-local storage (TLS) initialization image of a file, as
+/// described by its `PT_TLS` program header.
+///
+/// The image consists of the initialized data (`.tdata`) followed by
+/// zero-initialized memory (`.tbss`).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TlsTemplate {
+    /// The virtual address of the image.
+    pub addr: Addr,
+    /// The offset of the initialized data in the file.
+    pub file_offset: u64,
+    /// The size of the initialized data, i.e., the part of the image
+    /// backed by the file.
+    pub file_size: u64,
+    /// The size of the entire image in memory, including
+    /// zero-initialized data.
+    pub mem_size: u64,
+    /// The required alignment of the image.
+    pub align: u64,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// Check whether the ELF file at the given path requests an executable
+/// stack.
+///
+/// The information is conveyed by the flags of the `PT_GNU_STACK`
+/// program header. [`None`] is returned if the file does not contain
+/// such a program header, in which case the stack is typically
+/// executable by default on most architectures.
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("libtest-so.so");
+/// let exec_stack = blazesym::helper::read_elf_exec_stack(&path).unwrap();
+/// if exec_stack != Some(false) {
+///     println!("{} may run with an executable stack", path.display());
+/// }
+/// ```
+pub fn read_elf_exec_stack<P>(path: &P) -> Result<Option<bool>>
+where
+    P: AsRef<Path>,
+{
+    let parser = ElfParser::open(path.as_ref())?;
+    parser.exec_stack()
+}
+
+
+/// Read the thread-local storage (TLS) initialization image of the ELF
+/// file at the given path.
+///
+/// Values of TLS symbols (as reported with
+/// [`SymType::Tls`][crate::inspect::SymType::Tls]) are offsets into
+/// this image. [`None`] is returned if the file does not contain a
+/// `PT_TLS` program header.
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("libtest-tls.so");
+/// let template = blazesym::helper::read_elf_tls_template(&path)
+///     .unwrap()
+///     .unwrap();
+/// assert!(template.file_size <= template.mem_size);
+/// ```
+pub fn read_elf_tls_template<P>(path: &P) -> Result<Option<TlsTemplate>>
+where
+    P: AsRef<Path>,
+{
+    let parser = ElfParser::open(path.as_ref())?;
+    parser.tls_template()
+}
@@ -0,0 +1,27 @@
+/// A source of symbol information in an ELF file.
+///
+/// ELF files may contain both DWARF debug information and an ELF symbol
+/// table (`.symtab` & `.dynsym`). The two do not necessarily contain
+/// the same set of symbols: DWARF may lack information about symbols
+/// for hand written assembly, for example, while the symbol table knows
+/// nothing about static functions that got optimized away. This type
+/// expresses which source to consult first; the other one is only used
+/// if the preferred one does not know about a symbol.
+///
+/// Source code location information is only ever read from DWARF.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ElfSymSource {
+    /// Prefer DWARF debug information, if available.
+    #[default]
+    Dwarf,
+    /// Prefer the ELF symbol table (`.symtab` or, if not present,
+    /// `.dynsym`).
+    Symtab,
+    /// Prefer the dynamic symbol table (`.dynsym`), which only
+    /// contains exported symbols.
+    ///
+    /// Only address based lookups distinguish between the two ELF
+    /// symbol tables. Otherwise this variant is equivalent to
+    /// [`Symtab`][ElfSymSource::Symtab].
+    Dynsym,
+}
@@ -1,14 +1,39 @@
 const EI_NIDENT: usize = 16;
 
+/// Index of the file class byte in `e_ident`.
+pub(crate) const EI_CLASS: usize = 4;
+/// Index of the data encoding byte in `e_ident`.
+pub(crate) const EI_DATA: usize = 5;
+/// Index of the OS/ABI identification byte in `e_ident`.
+pub(crate) const EI_OSABI: usize = 7;
+/// Index of the ABI version byte in `e_ident`.
+pub(crate) const EI_ABIVERSION: usize = 8;
+
+pub(crate) const ELFCLASS32: u8 = 1;
+pub(crate) const ELFCLASS64: u8 = 2;
+
+pub(crate) const ELFDATA2LSB: u8 = 1;
+pub(crate) const ELFDATA2MSB: u8 = 2;
+
+pub(crate) const ELFOSABI_SYSV: u8 = 0;
+pub(crate) const ELFOSABI_GNU: u8 = 3;
+pub(crate) const ELFOSABI_FREEBSD: u8 = 9;
+
 type Elf64_Addr = u64;
 type Elf64_Half = u16;
 type Elf64_Off = u64;
 type Elf64_Word = u32;
 type Elf64_Xword = u64;
+type Elf64_Sxword = i64;
 
+pub(crate) const ET_REL: u16 = 1;
 pub(crate) const ET_EXEC: u16 = 2;
 pub(crate) const ET_DYN: u16 = 3;
 
+pub(crate) const EM_X86_64: u16 = 62;
+pub(crate) const EM_AARCH64: u16 = 183;
+pub(crate) const EM_RISCV: u16 = 243;
+
 #[derive(Debug)]
 #[repr(C)]
 pub(crate) struct Elf64_Ehdr {
@@ -32,6 +57,10 @@ pub(crate) struct Elf64_Ehdr {
 unsafe impl crate::util::Pod for Elf64_Ehdr {}
 
 pub(crate) const PT_LOAD: u32 = 1;
+pub(crate) const PT_DYNAMIC: u32 = 2;
+pub(crate) const PT_PHDR: u32 = 6;
+pub(crate) const PT_TLS: u32 = 7;
+pub(crate) const PT_GNU_STACK: u32 = 0x6474e551;
 
 #[derive(Debug)]
 #[repr(C)]
@@ -51,6 +80,24 @@ unsafe impl crate::util::Pod for Elf64_Phdr {}
 
 pub(crate) const PF_X: Elf64_Word = 1;
 
+pub(crate) const DT_NULL: i64 = 0;
+pub(crate) const DT_HASH: i64 = 4;
+pub(crate) const DT_STRTAB: i64 = 5;
+pub(crate) const DT_SYMTAB: i64 = 6;
+pub(crate) const DT_STRSZ: i64 = 10;
+pub(crate) const DT_SYMENT: i64 = 11;
+pub(crate) const DT_GNU_HASH: i64 = 0x6ffffef5;
+
+#[derive(Debug)]
+#[repr(C)]
+pub(crate) struct Elf64_Dyn {
+    pub d_tag: Elf64_Sxword, /* Dynamic entry type */
+    pub d_val: Elf64_Xword,  /* Integer or address value */
+}
+
+// SAFETY: `Elf64_Dyn` is valid for any bit pattern.
+unsafe impl crate::util::Pod for Elf64_Dyn {}
+
 pub(crate) const PN_XNUM: u16 = 0xffff;
 
 #[derive(Debug)]
@@ -73,11 +120,48 @@ unsafe impl crate::util::Pod for Elf64_Shdr {}
 
 pub(crate) const SHN_UNDEF: u16 = 0;
 pub(crate) const SHN_LORESERVE: u16 = 0xff00;
+pub(crate) const SHN_ABS: u16 = 0xfff1;
+pub(crate) const SHN_COMMON: u16 = 0xfff2;
 pub(crate) const SHN_XINDEX: u16 = 0xffff;
 
+pub(crate) const SHT_SYMTAB: Elf64_Word = 2;
+pub(crate) const SHT_RELA: Elf64_Word = 4;
+pub(crate) const SHT_HASH: Elf64_Word = 5;
 pub(crate) const SHT_NOTE: Elf64_Word = 7;
+pub(crate) const SHT_NOBITS: Elf64_Word = 8;
+pub(crate) const SHT_REL: Elf64_Word = 9;
+pub(crate) const SHT_DYNSYM: Elf64_Word = 11;
+pub(crate) const SHT_GROUP: Elf64_Word = 17;
+pub(crate) const SHT_GNU_HASH: Elf64_Word = 0x6ffffff6;
+
+pub(crate) const SHF_ALLOC: Elf64_Xword = 0x2;
+pub(crate) const SHF_EXECINSTR: Elf64_Xword = 0x4;
+pub(crate) const SHF_COMPRESSED: Elf64_Xword = 0x800;
 
+pub(crate) const GRP_COMDAT: Elf64_Word = 0x1;
+
+#[derive(Debug)]
+#[repr(C)]
+pub(crate) struct Elf64_Chdr {
+    pub ch_type: Elf64_Word,       /* Compression format */
+    pub ch_reserved: Elf64_Word,   /* Reserved */
+    pub ch_size: Elf64_Xword,      /* Uncompressed data size */
+    pub ch_addralign: Elf64_Xword, /* Uncompressed data alignment */
+}
+
+// SAFETY: `Elf64_Chdr` is valid for any bit pattern.
+unsafe impl crate::util::Pod for Elf64_Chdr {}
+
+pub(crate) const ELFCOMPRESS_ZLIB: Elf64_Word = 1;
+pub(crate) const ELFCOMPRESS_ZSTD: Elf64_Word = 2;
+
+pub(crate) const STT_OBJECT: u8 = 1;
 pub(crate) const STT_FUNC: u8 = 2;
+pub(crate) const STT_TLS: u8 = 6;
+
+pub(crate) const STB_LOCAL: u8 = 0;
+pub(crate) const STB_GLOBAL: u8 = 1;
+pub(crate) const STB_WEAK: u8 = 2;
 
 #[derive(Clone, Debug)]
 #[repr(C)]
@@ -95,12 +179,89 @@ impl Elf64_Sym {
     pub fn type_(&self) -> u8 {
         self.st_info & 0xf
     }
+
+    /// Extract the symbols binding, typically represented by a STB_*
+    /// constant.
+    pub fn binding(&self) -> u8 {
+        self.st_info >> 4
+    }
 }
 
 // SAFETY: `Elf64_Sym` is valid for any bit pattern.
 unsafe impl crate::util::Pod for Elf64_Sym {}
 
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf64_Rel {
+    pub r_offset: Elf64_Addr, /* Location at which to apply the action */
+    pub r_info: Elf64_Xword,  /* Index and type of relocation */
+}
+
+impl Elf64_Rel {
+    /// Extract the index of the symbol the relocation refers to.
+    pub fn sym(&self) -> u32 {
+        (self.r_info >> 32) as u32
+    }
+
+    /// Extract the relocation type, typically represented by an
+    /// architecture specific R_* constant.
+    pub fn type_(&self) -> u32 {
+        (self.r_info & 0xffffffff) as u32
+    }
+}
+
+// SAFETY: `Elf64_Rel` is valid for any bit pattern.
+unsafe impl crate::util::Pod for Elf64_Rel {}
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct Elf64_Rela {
+    pub r_offset: Elf64_Addr,   /* Location at which to apply the action */
+    pub r_info: Elf64_Xword,    /* Index and type of relocation */
+    pub r_addend: Elf64_Sxword, /* Constant addend used to compute value */
+}
+
+impl Elf64_Rela {
+    /// Extract the index of the symbol the relocation refers to.
+    pub fn sym(&self) -> u32 {
+        (self.r_info >> 32) as u32
+    }
+
+    /// Extract the relocation type, typically represented by an
+    /// architecture specific R_* constant.
+    pub fn type_(&self) -> u32 {
+        (self.r_info & 0xffffffff) as u32
+    }
+}
+
+// SAFETY: `Elf64_Rela` is valid for any bit pattern.
+unsafe impl crate::util::Pod for Elf64_Rela {}
+
+pub(crate) const R_X86_64_NONE: u32 = 0;
+pub(crate) const R_X86_64_64: u32 = 1;
+pub(crate) const R_X86_64_32: u32 = 10;
+pub(crate) const R_X86_64_32S: u32 = 11;
+
+pub(crate) const R_AARCH64_NONE: u32 = 0;
+pub(crate) const R_AARCH64_ABS64: u32 = 257;
+pub(crate) const R_AARCH64_ABS32: u32 = 258;
+
+pub(crate) const NT_GNU_ABI_TAG: Elf64_Word = 1;
 pub(crate) const NT_GNU_BUILD_ID: Elf64_Word = 3;
+pub(crate) const NT_GO_BUILD_ID: Elf64_Word = 4;
+pub(crate) const NT_GNU_PROPERTY_TYPE_0: Elf64_Word = 5;
+
+pub(crate) const ELF_NOTE_OS_LINUX: Elf64_Word = 0;
+pub(crate) const ELF_NOTE_OS_GNU: Elf64_Word = 1;
+pub(crate) const ELF_NOTE_OS_SOLARIS2: Elf64_Word = 2;
+pub(crate) const ELF_NOTE_OS_FREEBSD: Elf64_Word = 3;
+
+pub(crate) const GNU_PROPERTY_AARCH64_FEATURE_1_AND: Elf64_Word = 0xc0000000;
+pub(crate) const GNU_PROPERTY_AARCH64_FEATURE_1_BTI: Elf64_Word = 1 << 0;
+pub(crate) const GNU_PROPERTY_AARCH64_FEATURE_1_PAC: Elf64_Word = 1 << 1;
+pub(crate) const GNU_PROPERTY_X86_FEATURE_1_AND: Elf64_Word = 0xc0000002;
+pub(crate) const GNU_PROPERTY_X86_FEATURE_1_IBT: Elf64_Word = 1 << 0;
+pub(crate) const GNU_PROPERTY_X86_FEATURE_1_SHSTK: Elf64_Word = 1 << 1;
 
 #[derive(Debug)]
 #[repr(C)]
@@ -1,11 +1,19 @@
 use std::ops::Range;
 
 use crate::util::ReadRaw as _;
+use crate::Error;
 use crate::ErrorExt as _;
 use crate::IntoError as _;
 use crate::Result;
 
 
+/// The maximum nesting depth of inlined functions that we support.
+///
+/// Inline information is parsed recursively and we have to guard
+/// against malformed data causing stack exhaustion.
+const MAX_INLINE_DEPTH: usize = 256;
+
+
 #[derive(Clone)]
 pub(super) struct InlineInfo {
     pub name: u32,
@@ -23,6 +31,21 @@ impl InlineInfo {
         base_addr: u64,
         lookup_addr: Option<u64>,
     ) -> Result<Option<InlineInfo>> {
+        Self::parse_impl(data, base_addr, lookup_addr, 0)
+    }
+
+    fn parse_impl(
+        data: &mut &[u8],
+        base_addr: u64,
+        lookup_addr: Option<u64>,
+        depth: usize,
+    ) -> Result<Option<InlineInfo>> {
+        if depth > MAX_INLINE_DEPTH {
+            return Err(Error::with_invalid_data(format!(
+                "inline information exceeds maximum nesting depth of {MAX_INLINE_DEPTH}"
+            )))
+        }
+
         let range_cnt = data
             .read_u64_leb128()
             .ok_or_invalid_data(|| "failed to read range count from inline information")?
@@ -117,11 +140,13 @@ impl InlineInfo {
             if ranges.is_empty() {
                 // This inlined function does not contain `lookup_addr`, no need
                 // to decode ranges, just skip.
-                while let Some(_child) = Self::parse(data, child_base_addr, None)? {
+                while let Some(_child) = Self::parse_impl(data, child_base_addr, None, depth + 1)? {
                     // Do nothing; we just skip the data.
                 }
             } else {
-                while let Some(child) = Self::parse(data, child_base_addr, lookup_addr)? {
+                while let Some(child) =
+                    Self::parse_impl(data, child_base_addr, lookup_addr, depth + 1)?
+                {
                     let () = children.push(child);
                 }
             }
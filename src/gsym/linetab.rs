@@ -113,12 +113,12 @@ pub fn run_op(
         }
         ADVANCE_PC => {
             let (adv, _bytes) = ops.read_u64_leb128()?;
-            row.addr += adv as Addr;
+            row.addr = row.addr.wrapping_add(adv as Addr);
             Some(RunResult::NewRow)
         }
         ADVANCE_LINE => {
             let (adv, _bytes) = ops.read_i64_leb128()?;
-            row.file_line = (row.file_line as i64 + adv) as u32;
+            row.file_line = (row.file_line as i64).wrapping_add(adv) as u32;
             Some(RunResult::Ok)
         }
         // Special operators.
@@ -131,16 +131,19 @@ pub fn run_op(
             let adjusted = (op - FIRST_SPECIAL) as i64;
             // The range of line number delta is from min_delta to max_delta,
             // including max_delta.
-            let range = header.max_delta - header.min_delta + 1;
+            let range = header
+                .max_delta
+                .checked_sub(header.min_delta)?
+                .checked_add(1)?;
             if range == 0 {
                 return None
             }
-            let line_delta = header.min_delta + (adjusted % range);
+            let line_delta = header.min_delta.wrapping_add(adjusted % range);
             let addr_delta = adjusted / range;
 
-            let file_line = row.file_line as i32 + line_delta as i32;
+            let file_line = (row.file_line as i32).wrapping_add(line_delta as i32);
             row.file_line = file_line as u32;
-            row.addr += addr_delta as Addr;
+            row.addr = row.addr.wrapping_add(addr_delta as Addr);
             Some(RunResult::NewRow)
         }
     }
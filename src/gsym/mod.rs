@@ -3,5 +3,7 @@ mod linetab;
 mod parser;
 mod resolver;
 mod types;
+mod writer;
 
 pub use resolver::GsymResolver;
+pub use writer::elf_to_gsym;
@@ -111,6 +111,10 @@ impl<'dat> GsymResolver<'dat> {
             file: Cow::Borrowed(OsStr::new(file)),
             line,
             column: None,
+            md5: None,
+            flags: None,
+            context: None,
+            file_exists: None,
             _non_exhaustive: (),
         };
         Ok(info)
@@ -205,7 +209,12 @@ impl SymResolver for GsymResolver<'_> {
     }
 
     #[cfg_attr(feature = "tracing", crate::log::instrument(skip(self), fields(file = debug(&self.file_name))))]
-    fn find_code_info(&self, addr: Addr, inlined_fns: bool) -> Result<Option<AddrCodeInfo<'_>>> {
+    fn find_code_info(
+        &self,
+        addr: Addr,
+        inlined_fns: bool,
+        line_only: bool,
+    ) -> Result<Option<AddrCodeInfo<'_>>> {
         let idx = match self.ctx.find_addr(addr) {
             Some(idx) => idx,
             None => return Ok(None),
@@ -221,7 +230,7 @@ impl SymResolver for GsymResolver<'_> {
             .ctx
             .addr_info(idx)
             .ok_or_invalid_data(|| format!("failed to read address info entry {idx}"))?;
-        if addr >= (symaddr + addrinfo.size as Addr) {
+        if addr >= symaddr.saturating_add(addrinfo.size as Addr) {
             return Ok(None)
         }
 
@@ -235,8 +244,8 @@ impl SymResolver for GsymResolver<'_> {
                         line_tab_info = self.parse_line_tab_info(addr_ent.data, symaddr, addr)?;
                     }
                 }
-                INFO_TYPE_INLINE_INFO if inlined_fns => {
-                    if inline_info.is_none() {
+                INFO_TYPE_INLINE_INFO => {
+                    if inlined_fns && !line_only && inline_info.is_none() {
                         let mut data = addr_ent.data;
                         inline_info = InlineInfo::parse(&mut data, symaddr, Some(addr))?;
                     }
@@ -373,14 +382,20 @@ mod tests {
 
         // `main` resides at address 0x2000000, and it's located at the given
         // line.
-        let info = resolver.find_code_info(0x2000000, true).unwrap().unwrap();
+        let info = resolver
+            .find_code_info(0x2000000, true, false)
+            .unwrap()
+            .unwrap();
         assert_eq!(info.direct.1.line, Some(50));
         assert_eq!(info.direct.1.file, OsStr::new("test-stable-addresses.c"));
         assert_eq!(info.inlined, Vec::new());
 
         // `factorial` resides at address 0x2000100, and it's located at the
         // given line.
-        let info = resolver.find_code_info(0x2000100, true).unwrap().unwrap();
+        let info = resolver
+            .find_code_info(0x2000100, true, false)
+            .unwrap()
+            .unwrap();
         assert_eq!(info.direct.1.line, Some(8));
         assert_eq!(info.direct.1.file, OsStr::new("test-stable-addresses.c"));
         assert_eq!(info.inlined, Vec::new());
@@ -392,7 +407,7 @@ mod tests {
         let sym = resolver.find_sym(addr).unwrap().unwrap();
         assert_eq!(sym.name, "factorial_inline_test");
 
-        let info = resolver.find_code_info(addr, true).unwrap().unwrap();
+        let info = resolver.find_code_info(addr, true, false).unwrap().unwrap();
         assert_eq!(info.direct.1.line, Some(32));
         assert_eq!(info.direct.1.file, OsStr::new("test-stable-addresses.c"));
         assert_eq!(info.inlined.len(), 2);
@@ -409,7 +424,10 @@ mod tests {
         assert_eq!(frame.file, OsStr::new("test-stable-addresses.c"));
         assert_eq!(frame.line, Some(21));
 
-        let info = resolver.find_code_info(addr, false).unwrap().unwrap();
+        let info = resolver
+            .find_code_info(addr, false, false)
+            .unwrap()
+            .unwrap();
         // Note that the line number reported without inline information is
         // different to that when using inlined function information, because in
         // Gsym this additional data is used to "refine" the result.
@@ -0,0 +1,641 @@
+//! Functionality for converting the symbol and source code information
+//! of an ELF file into the GSYM format.
+//!
+//! The produced file follows the layout understood by
+//! [`GsymContext::parse_header`][super::parser::GsymContext::parse_header]:
+//!
+//! ```text
+//! header | address table | address info offsets | file table |
+//! string table | address infos
+//! ```
+//!
+//! All multi-byte fixed width integers are stored in native byte order.
+
+use std::collections::HashMap;
+use std::fs;
+use std::mem::size_of;
+use std::ops::Range;
+use std::os::unix::ffi::OsStrExt as _;
+use std::path::Path;
+use std::rc::Rc;
+
+#[cfg(feature = "dwarf")]
+use crate::dwarf::DwarfResolver;
+use crate::elf::ElfBackend;
+use crate::elf::ElfParser;
+use crate::elf::ElfResolver;
+use crate::inspect::SymInfo;
+use crate::symbolize::CodeInfo;
+use crate::Addr;
+use crate::Error;
+use crate::ErrorExt as _;
+use crate::Result;
+use crate::SymResolver as _;
+
+use super::types::GSYM_MAGIC;
+use super::types::GSYM_VERSION;
+use super::types::INFO_TYPE_END_OF_LIST;
+use super::types::INFO_TYPE_INLINE_INFO;
+use super::types::INFO_TYPE_LINE_TABLE_INFO;
+
+
+/// End of the line table.
+const END_SEQUENCE: u8 = 0x00;
+/// Set the file index of the line table state machine.
+const SET_FILE: u8 = 0x01;
+/// Advance the address and push a row.
+const ADVANCE_PC: u8 = 0x02;
+/// Advance the line number.
+const ADVANCE_LINE: u8 = 0x03;
+/// The first special opcode.
+const FIRST_SPECIAL: u8 = 0x04;
+
+/// The smallest line delta representable by a special opcode.
+const MIN_LINE_DELTA: i64 = -4;
+/// The largest range of line deltas representable by special
+/// opcodes. Larger ranges leave little room for address deltas.
+const MAX_LINE_RANGE: i64 = 14;
+
+
+/// Append `value` to `data`, encoded as unsigned LEB128 value.
+fn write_u64_leb128(data: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0b0111_1111) as u8;
+        value >>= 7;
+        if value == 0 {
+            let () = data.push(byte);
+            break
+        }
+        let () = data.push(byte | 0b1000_0000);
+    }
+}
+
+/// Append `value` to `data`, encoded as signed LEB128 value.
+fn write_i64_leb128(data: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = (value & 0b0111_1111) as u8;
+        value >>= 7;
+        if (value == 0 && byte & 0b0100_0000 == 0) || (value == -1 && byte & 0b0100_0000 != 0) {
+            let () = data.push(byte);
+            break
+        }
+        let () = data.push(byte | 0b1000_0000);
+    }
+}
+
+/// Pad `data` with zeroes until its length is a multiple of `align`.
+fn pad(data: &mut Vec<u8>, align: usize) {
+    let len = (data.len() + align - 1) / align * align;
+    let () = data.resize(len, 0);
+}
+
+/// Convert `value` into a `u32`, as used by GSYM for most offsets and
+/// counts.
+fn to_u32(value: usize, what: &str) -> Result<u32> {
+    u32::try_from(value).map_err(|_| {
+        Error::with_unsupported(format!("{what} ({value}) cannot be represented in GSYM"))
+    })
+}
+
+
+/// The GSYM string table, deduplicating the strings added to it.
+struct StrTab {
+    data: Vec<u8>,
+    offsets: HashMap<Vec<u8>, u32>,
+}
+
+impl StrTab {
+    fn new() -> Self {
+        Self {
+            // Offset zero always refers to the empty string.
+            data: vec![0],
+            offsets: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, s: &[u8]) -> Result<u32> {
+        if s.is_empty() {
+            return Ok(0)
+        }
+
+        if let Some(offset) = self.offsets.get(s) {
+            return Ok(*offset)
+        }
+
+        let offset = to_u32(self.data.len(), "string table offset")?;
+        let () = self.data.extend_from_slice(s);
+        let () = self.data.push(0);
+        let _prev = self.offsets.insert(s.to_vec(), offset);
+        Ok(offset)
+    }
+}
+
+
+/// The GSYM file table, deduplicating the files added to it.
+struct FileTab {
+    files: Vec<(u32, u32)>,
+    indices: HashMap<(u32, u32), u32>,
+}
+
+impl FileTab {
+    fn new() -> Self {
+        Self {
+            // Index zero always refers to an invalid file.
+            files: vec![(0, 0)],
+            indices: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, strtab: &mut StrTab, code_info: &CodeInfo<'_>) -> Result<u32> {
+        let dir = code_info
+            .dir
+            .as_deref()
+            .map(|dir| dir.as_os_str().as_bytes())
+            .unwrap_or_default();
+        let dir = strtab.insert(dir)?;
+        let file = strtab.insert(code_info.file.as_bytes())?;
+
+        if let Some(idx) = self.indices.get(&(dir, file)) {
+            return Ok(*idx)
+        }
+
+        let idx = to_u32(self.files.len(), "file table index")?;
+        let () = self.files.push((dir, file));
+        let _prev = self.indices.insert((dir, file), idx);
+        Ok(idx)
+    }
+}
+
+
+/// A row of a GSYM line table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LineRow {
+    addr: Addr,
+    file: u32,
+    line: u32,
+}
+
+
+/// Encode the line table `rows` of the function starting at `base`.
+fn encode_line_table(rows: &[LineRow], base: Addr, data: &mut Vec<u8>) {
+    // SANITY: Callers only ever provide non-empty line tables.
+    let first_line = rows.first().unwrap().line;
+
+    let mut min_delta = 0;
+    let mut max_delta = 0;
+    let mut prev_line = first_line;
+    for row in rows {
+        let delta = i64::from(row.line) - i64::from(prev_line);
+        min_delta = min_delta.min(delta);
+        max_delta = max_delta.max(delta);
+        prev_line = row.line;
+    }
+    let min_delta = min_delta.max(MIN_LINE_DELTA);
+    let max_delta = max_delta.min(min_delta + MAX_LINE_RANGE);
+    let line_range = (max_delta - min_delta + 1) as u64;
+
+    let () = write_i64_leb128(data, min_delta);
+    let () = write_i64_leb128(data, max_delta);
+    let () = write_u64_leb128(data, u64::from(first_line));
+
+    let mut addr = base;
+    let mut file = 1;
+    let mut line = first_line;
+    for row in rows {
+        if row.file != file {
+            let () = data.push(SET_FILE);
+            let () = write_u64_leb128(data, u64::from(row.file));
+            file = row.file;
+        }
+
+        let line_delta = i64::from(row.line) - i64::from(line);
+        let addr_delta = row.addr - addr;
+        let special = (min_delta..=max_delta)
+            .contains(&line_delta)
+            .then(|| {
+                addr_delta
+                    .checked_mul(line_range)?
+                    .checked_add((line_delta - min_delta) as u64)?
+                    .checked_add(u64::from(FIRST_SPECIAL))
+            })
+            .flatten()
+            .and_then(|op| u8::try_from(op).ok());
+
+        if let Some(op) = special {
+            let () = data.push(op);
+        } else {
+            if line_delta != 0 {
+                let () = data.push(ADVANCE_LINE);
+                let () = write_i64_leb128(data, line_delta);
+            }
+            let () = data.push(ADVANCE_PC);
+            let () = write_u64_leb128(data, addr_delta);
+        }
+
+        addr = row.addr;
+        line = row.line;
+    }
+    let () = data.push(END_SEQUENCE);
+}
+
+
+/// An inlined function call, as seen from a single address.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct InlineFrame {
+    name: u32,
+    call_file: u32,
+    call_line: u32,
+}
+
+
+/// A node in the GSYM inline information tree.
+#[derive(Debug)]
+struct InlineNode {
+    frame: InlineFrame,
+    ranges: Vec<Range<Addr>>,
+    children: Vec<InlineNode>,
+}
+
+impl InlineNode {
+    fn new(frame: InlineFrame) -> Self {
+        Self {
+            frame,
+            ranges: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn add_range(&mut self, range: Range<Addr>) {
+        match self.ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => self.ranges.push(range),
+        }
+    }
+
+    /// Attribute `range` to the stack of inlined calls `frames`, from
+    /// outermost to innermost.
+    fn insert(&mut self, frames: &[InlineFrame], range: Range<Addr>) {
+        if let Some((frame, frames)) = frames.split_first() {
+            let child = match self.children.last_mut() {
+                Some(child) if child.frame == *frame => child,
+                _ => {
+                    let () = self.children.push(Self::new(*frame));
+                    // SANITY: We just pushed an element.
+                    self.children.last_mut().unwrap()
+                }
+            };
+            let () = child.add_range(range.clone());
+            let () = child.insert(frames, range);
+        }
+    }
+
+    fn encode(&self, base: Addr, data: &mut Vec<u8>) {
+        let () = write_u64_leb128(data, self.ranges.len() as u64);
+        for range in &self.ranges {
+            let () = write_u64_leb128(data, range.start - base);
+            let () = write_u64_leb128(data, range.end - range.start);
+        }
+        let () = data.push(u8::from(!self.children.is_empty()));
+        let () = data.extend_from_slice(&self.frame.name.to_ne_bytes());
+        let () = write_u64_leb128(data, u64::from(self.frame.call_file));
+        let () = write_u64_leb128(data, u64::from(self.frame.call_line));
+
+        if let Some(range) = self.ranges.first() {
+            if !self.children.is_empty() {
+                for child in &self.children {
+                    let () = child.encode(range.start, data);
+                }
+                // A range count of zero terminates the list of children.
+                let () = write_u64_leb128(data, 0);
+            }
+        }
+    }
+}
+
+
+/// Append an address data entry of type `typ` to `data`.
+fn write_addr_data(data: &mut Vec<u8>, typ: u32, payload: &[u8]) -> Result<()> {
+    let len = to_u32(payload.len(), "address data length")?;
+    let () = data.extend_from_slice(&typ.to_ne_bytes());
+    let () = data.extend_from_slice(&len.to_ne_bytes());
+    let () = data.extend_from_slice(payload);
+    Ok(())
+}
+
+
+/// Encode the address data (line table and inline information) of the
+/// function `sym`.
+fn encode_func(
+    resolver: &ElfResolver,
+    sym: &SymInfo<'_>,
+    range: Range<Addr>,
+    strtab: &mut StrTab,
+    filetab: &mut FileTab,
+) -> Result<Vec<u8>> {
+    let name = strtab.insert(sym.name.as_bytes())?;
+    let mut rows = Vec::<LineRow>::new();
+    let mut stacks = Vec::<(Addr, Vec<InlineFrame>)>::new();
+
+    for addr in resolver.line_addrs(range.clone())? {
+        // The first row may start before the function does.
+        let addr = addr.max(range.start);
+        if addr >= range.end {
+            continue
+        }
+
+        let info = match resolver.find_code_info(addr, true, false)? {
+            Some(info) => info,
+            None => continue,
+        };
+
+        // The location of the innermost inlined function, if any, is
+        // the one that the line table has to report. Each inlined
+        // function's call site is reported by its parent.
+        let location = info
+            .inlined
+            .last()
+            .and_then(|(_name, code_info)| code_info.as_ref())
+            .unwrap_or(&info.direct.1);
+        let row = LineRow {
+            addr,
+            file: filetab.insert(strtab, location)?,
+            line: location.line.unwrap_or(0),
+        };
+
+        match rows.last() {
+            Some(last) if last.file == row.file && last.line == row.line => (),
+            Some(last) if last.addr == row.addr => {
+                // SANITY: We just checked that there is a last row.
+                *rows.last_mut().unwrap() = row;
+            }
+            _ => rows.push(row),
+        }
+
+        let mut frames = Vec::with_capacity(info.inlined.len());
+        for (idx, (name, _code_info)) in info.inlined.iter().enumerate() {
+            let call_site = if idx == 0 {
+                Some(&info.direct.1)
+            } else {
+                info.inlined[idx - 1].1.as_ref()
+            };
+            let (call_file, call_line) = match call_site {
+                Some(call_site) => (
+                    filetab.insert(strtab, call_site)?,
+                    call_site.line.unwrap_or(0),
+                ),
+                None => (0, 0),
+            };
+            let frame = InlineFrame {
+                name: strtab.insert(name.as_bytes())?,
+                call_file,
+                call_line,
+            };
+            let () = frames.push(frame);
+        }
+
+        match stacks.last_mut() {
+            Some((last_addr, last_frames)) if *last_addr == addr => *last_frames = frames,
+            _ => stacks.push((addr, frames)),
+        }
+    }
+
+    let mut data = Vec::new();
+    if !rows.is_empty() {
+        let mut linetab = Vec::new();
+        let () = encode_line_table(&rows, range.start, &mut linetab);
+        let () = write_addr_data(&mut data, INFO_TYPE_LINE_TABLE_INFO, &linetab)?;
+    }
+
+    if stacks.iter().any(|(_addr, frames)| !frames.is_empty()) {
+        let mut root = InlineNode::new(InlineFrame {
+            name,
+            call_file: 0,
+            call_line: 0,
+        });
+        let () = root.add_range(range.clone());
+
+        for (idx, (addr, frames)) in stacks.iter().enumerate() {
+            let end = stacks
+                .get(idx + 1)
+                .map(|(next, _frames)| *next)
+                .unwrap_or(range.end);
+            let () = root.insert(frames, *addr..end);
+        }
+
+        let mut inline = Vec::new();
+        let () = root.encode(range.start, &mut inline);
+        let () = write_addr_data(&mut data, INFO_TYPE_INLINE_INFO, &inline)?;
+    }
+
+    let () = data.extend_from_slice(&INFO_TYPE_END_OF_LIST.to_ne_bytes());
+    let () = data.extend_from_slice(&0u32.to_ne_bytes());
+    Ok(data)
+}
+
+
+/// Write the symbols and source code information available to
+/// `resolver` to a GSYM file at `path`.
+///
+/// Functions are taken from the ELF symbol tables. Line information
+/// and inlined function information is added if the resolver has
+/// DWARF debug information available.
+pub(crate) fn write(resolver: &ElfResolver, path: &Path) -> Result<()> {
+    let syms = resolver.func_syms()?;
+    let base = syms.first().map(|sym| sym.addr).unwrap_or(0);
+
+    let mut strtab = StrTab::new();
+    let mut filetab = FileTab::new();
+    let mut funcs = Vec::with_capacity(syms.len());
+    for sym in syms {
+        let size = sym.end.unwrap_or(sym.addr).saturating_sub(sym.addr);
+        let size = u32::try_from(size).unwrap_or(u32::MAX);
+        let range = sym.addr..sym.addr + Addr::from(size);
+        let name = strtab.insert(sym.name.as_bytes())?;
+        let data = encode_func(resolver, &sym, range, &mut strtab, &mut filetab)
+            .with_context(|| format!("failed to encode function `{}`", sym.name))?;
+        let () = funcs.push((sym.addr - base, size, name, data));
+    }
+
+    let max_offset = funcs.last().map(|(offset, ..)| *offset).unwrap_or(0);
+    let addr_off_size = if max_offset <= u64::from(u8::MAX) {
+        1u8
+    } else if max_offset <= u64::from(u16::MAX) {
+        2
+    } else if max_offset <= u64::from(u32::MAX) {
+        4
+    } else {
+        8
+    };
+
+    let num_addrs = to_u32(funcs.len(), "number of functions")?;
+    let num_files = to_u32(filetab.files.len(), "number of files")?;
+    let strtab_size = to_u32(strtab.data.len(), "string table size")?;
+
+    let mut data = Vec::new();
+    let () = data.extend_from_slice(&GSYM_MAGIC.to_ne_bytes());
+    let () = data.extend_from_slice(&GSYM_VERSION.to_ne_bytes());
+    let () = data.push(addr_off_size);
+    // We do not store a UUID.
+    let () = data.push(0);
+    let () = data.extend_from_slice(&base.to_ne_bytes());
+    let () = data.extend_from_slice(&num_addrs.to_ne_bytes());
+    let strtab_offset_idx = data.len();
+    let () = data.extend_from_slice(&0u32.to_ne_bytes());
+    let () = data.extend_from_slice(&strtab_size.to_ne_bytes());
+    let () = data.extend_from_slice(&[0; 20]);
+
+    for (offset, ..) in &funcs {
+        let bytes = offset.to_ne_bytes();
+        let bytes = if cfg!(target_endian = "little") {
+            &bytes[..usize::from(addr_off_size)]
+        } else {
+            &bytes[bytes.len() - usize::from(addr_off_size)..]
+        };
+        let () = data.extend_from_slice(bytes);
+    }
+
+    let () = pad(&mut data, size_of::<u32>());
+    let info_offsets_idx = data.len();
+    let () = data.resize(info_offsets_idx + funcs.len() * size_of::<u32>(), 0);
+
+    let () = data.extend_from_slice(&num_files.to_ne_bytes());
+    for (dir, file) in &filetab.files {
+        let () = data.extend_from_slice(&dir.to_ne_bytes());
+        let () = data.extend_from_slice(&file.to_ne_bytes());
+    }
+
+    let strtab_offset = to_u32(data.len(), "string table offset")?;
+    let () = data[strtab_offset_idx..strtab_offset_idx + size_of::<u32>()]
+        .copy_from_slice(&strtab_offset.to_ne_bytes());
+    let () = data.extend_from_slice(&strtab.data);
+
+    for (idx, (_offset, size, name, info)) in funcs.iter().enumerate() {
+        let () = pad(&mut data, size_of::<u32>());
+        let info_offset = to_u32(data.len(), "address info offset")?;
+        let start = info_offsets_idx + idx * size_of::<u32>();
+        let () = data[start..start + size_of::<u32>()].copy_from_slice(&info_offset.to_ne_bytes());
+
+        let () = data.extend_from_slice(&size.to_ne_bytes());
+        let () = data.extend_from_slice(&name.to_ne_bytes());
+        let () = data.extend_from_slice(info);
+    }
+
+    let () = fs::write(path, data)
+        .with_context(|| format!("failed to write GSYM file `{}`", path.display()))?;
+    Ok(())
+}
+
+
+/// Convert the ELF file at `elf_path` into a GSYM file at `gsym_path`.
+///
+/// All function symbols of the ELF file's symbol tables are converted.
+/// If the file contains DWARF debug information (and the `dwarf`
+/// feature is enabled), source code location information, including
+/// information about inlined functions, is included as well. GSYM
+/// files can be symbolized via
+/// [`symbolize::Source::Gsym`][crate::symbolize::Source::Gsym].
+pub fn elf_to_gsym(elf_path: &Path, gsym_path: &Path) -> Result<()> {
+    let parser = Rc::new(ElfParser::open(elf_path)?);
+
+    #[cfg(feature = "dwarf")]
+    let backend = {
+        let debug_line_info = true;
+        let dwarf = DwarfResolver::from_parser(parser, debug_line_info)?;
+        ElfBackend::Dwarf(Rc::new(dwarf))
+    };
+    #[cfg(not(feature = "dwarf"))]
+    let backend = ElfBackend::Elf(parser);
+
+    let resolver = ElfResolver::with_backend(elf_path, backend)?;
+    write(&resolver, gsym_path)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::util::ReadRaw as _;
+
+    use super::super::linetab::run_op;
+    use super::super::linetab::LineTableHeader;
+    use super::super::linetab::LineTableRow;
+    use super::super::linetab::RunResult;
+
+    use test_log::test;
+
+
+    /// Check that our LEB128 encoding round trips through the decoding
+    /// logic.
+    #[test]
+    fn leb128_encoding() {
+        for value in [0, 1, 127, 128, 0x3fff, 0x4000, u64::MAX] {
+            let mut data = Vec::new();
+            let () = write_u64_leb128(&mut data, value);
+            assert_eq!(data.as_slice().read_u64_leb128().unwrap().0, value);
+        }
+
+        for value in [0, 1, -1, 63, 64, -64, -65, 0x7fff_ffff, -0x8000_0000] {
+            let mut data = Vec::new();
+            let () = write_i64_leb128(&mut data, value);
+            assert_eq!(data.as_slice().read_i64_leb128().unwrap().0, value);
+        }
+    }
+
+    /// Make sure that encoded line tables decode to the original rows.
+    #[test]
+    fn line_table_encoding() {
+        let base = 0x1000;
+        let rows = [
+            LineRow {
+                addr: 0x1000,
+                file: 1,
+                line: 10,
+            },
+            LineRow {
+                addr: 0x1004,
+                file: 1,
+                line: 11,
+            },
+            // A large address delta.
+            LineRow {
+                addr: 0x2000,
+                file: 1,
+                line: 9,
+            },
+            // A large negative line delta and a file change.
+            LineRow {
+                addr: 0x2001,
+                file: 2,
+                line: 1,
+            },
+            // A large positive line delta.
+            LineRow {
+                addr: 0x2002,
+                file: 2,
+                line: 1000,
+            },
+        ];
+
+        let mut data = Vec::new();
+        let () = encode_line_table(&rows, base, &mut data);
+
+        let mut data = data.as_slice();
+        let header = LineTableHeader::parse(&mut data).unwrap();
+        let mut row = LineTableRow::from_header(&header, base);
+        let mut decoded = Vec::new();
+        loop {
+            match run_op(&mut row, &header, &mut data).unwrap() {
+                RunResult::Ok => (),
+                RunResult::NewRow => decoded.push(LineRow {
+                    addr: row.addr,
+                    file: row.file_idx,
+                    line: row.file_line,
+                }),
+                RunResult::End => break,
+            }
+        }
+        assert_eq!(decoded, rows);
+        assert!(data.is_empty());
+    }
+}
@@ -1,6 +1,10 @@
+use std::borrow::Cow;
+use std::ops::Range;
 use std::path::Path;
+use std::path::PathBuf;
 use std::rc::Rc;
 
+use crate::demangle::maybe_demangle;
 #[cfg(feature = "dwarf")]
 use crate::dwarf::DwarfResolver;
 use crate::elf::ElfBackend;
@@ -8,14 +12,20 @@ use crate::elf::ElfParser;
 use crate::elf::ElfResolver;
 use crate::file_cache::FileCache;
 use crate::once::OnceCell;
+use crate::symbolize::IntSym;
+use crate::symbolize::SrcLang;
+use crate::Addr;
 use crate::Result;
 use crate::SymResolver;
 
 use super::source::Elf;
 use super::source::Source;
+use super::AddrStatus;
 use super::FindAddrOpts;
+use super::InlineInstance;
 use super::SymInfo;
 use super::SymType;
+use super::VarInfo;
 
 
 /// Resolver data associated with a specific source.
@@ -28,6 +38,36 @@ struct ResolverData {
 }
 
 
+/// Convert a function symbol found by address into a [`SymInfo`].
+fn func_sym_info(
+    sym: IntSym<'_>,
+    resolver: &ElfResolver,
+    demangle: bool,
+) -> Result<SymInfo<'static>> {
+    let IntSym {
+        name,
+        addr,
+        size,
+        lang,
+    } = sym;
+
+    let info = SymInfo {
+        name: Cow::Owned(name.to_string()),
+        demangled_name: demangle
+            .then(|| Cow::Owned(maybe_demangle(Cow::Borrowed(name), lang).into_owned())),
+        addr,
+        size: size.unwrap_or(0),
+        end: size
+            .filter(|size| *size != 0)
+            .map(|size| addr.saturating_add(size as Addr)),
+        sym_type: SymType::Function,
+        file_offset: resolver.parser().find_file_offset(addr)?,
+        obj_file_name: Some(Cow::Owned(resolver.file_name().to_path_buf())),
+    };
+    Ok(info)
+}
+
+
 /// An inspector of various "sources".
 ///
 /// Object of this type can be used to perform inspections of supported sources.
@@ -126,42 +166,526 @@ impl Inspector {
     /// given their names.
     ///
     /// # Notes
-    /// - no symbol name demangling is performed currently
+    /// - names are matched against raw (i.e., potentially mangled)
+    ///   symbol names; demangled names are only reported alongside, if
+    ///   requested via [`Elf::demangle`]
     pub fn lookup<'slf>(
         &'slf self,
         names: &[&str],
         src: &Source,
     ) -> Result<Vec<Vec<SymInfo<'slf>>>> {
-        let opts = FindAddrOpts {
-            offset_in_file: true,
-            sym_type: SymType::Unknown,
-        };
-
         match src {
             Source::Elf(Elf {
                 path,
                 debug_info,
+                sym_source,
+                section,
+                dedup,
+                demangle,
+                limit,
                 _non_exhaustive: (),
             }) => {
+                let opts = FindAddrOpts {
+                    offset_in_file: true,
+                    sym_type: SymType::Unknown,
+                    sym_source: *sym_source,
+                    section: section.clone(),
+                    dedup: *dedup,
+                    limit: *limit,
+                };
                 let resolver = self.elf_resolver(path, *debug_info)?;
-                let syms = names
-                    .iter()
-                    .map(|name| {
-                        resolver.find_addr(name, &opts).map(|syms| {
-                            // This dance including reallocation of the vector
-                            // is very unfortunate, but it's unclear how else to
-                            // make the borrow checker accept this code (modulo
-                            // `transmute`).
-                            syms.into_iter().map(|sym| sym.to_owned()).collect()
-                        })
+                let syms = resolver
+                    .find_addrs(names, &opts)?
+                    .into_iter()
+                    // This dance including reallocation of the vector
+                    // is very unfortunate, but it's unclear how else to
+                    // make the borrow checker accept this code (modulo
+                    // `transmute`).
+                    .map(|syms| {
+                        syms.into_iter()
+                            .map(|sym| {
+                                let mut sym = sym.to_owned();
+                                if *demangle {
+                                    // Neither ELF symbols nor the
+                                    // (unmangled) names of DWARF
+                                    // functions convey the source
+                                    // language, so we have to probe
+                                    // the supported schemes.
+                                    let name = maybe_demangle(sym.name.clone(), SrcLang::Unknown);
+                                    sym.demangled_name = Some(name);
+                                }
+                                sym
+                            })
+                            .collect()
                     })
-                    .collect::<Result<Vec<_>>>()?;
+                    .collect();
+
+                Ok(syms)
+            }
+        }
+    }
 
+    /// Check whether the source defines a symbol with the given name.
+    ///
+    /// Contrary to [`lookup`][Self::lookup], no information about the
+    /// symbol is gathered, making this check comparatively cheap.
+    /// Hash tables (`.gnu.hash` or `.hash`) are used where available.
+    ///
+    /// # Notes
+    /// - names are matched against raw (i.e., potentially mangled)
+    ///   symbol names
+    /// - for the [`Elf`](Source::Elf) source, only the ELF symbol
+    ///   tables are consulted, irrespective of
+    ///   [`debug_info`][Elf::debug_info]
+    pub fn has_sym(&self, name: &str, src: &Source) -> Result<bool> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver.has_sym(name)
+            }
+        }
+    }
+
+    /// Retrieve all symbols starting within `range`, sorted by address.
+    ///
+    /// `range` is expressed in file virtual addresses. Symbols from
+    /// DWARF debug information and the ELF symbol tables are merged,
+    /// with the configured [`sym_source`][Elf::sym_source] taking
+    /// precedence for symbols sharing name and address. Hence, at most
+    /// one symbol is reported for each name and address combination,
+    /// irrespective of [`dedup`][Elf::dedup].
+    ///
+    /// # Notes
+    /// - [`limit`][Elf::limit] is not honored
+    pub fn syms_in_range<'slf>(
+        &'slf self,
+        range: Range<Addr>,
+        src: &Source,
+    ) -> Result<Vec<SymInfo<'slf>>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source,
+                section,
+                dedup: _,
+                demangle,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let opts = FindAddrOpts {
+                    offset_in_file: true,
+                    sym_type: SymType::Unknown,
+                    sym_source: *sym_source,
+                    section: section.clone(),
+                    dedup: true,
+                    limit: None,
+                };
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                let syms = resolver
+                    .syms_in_range(range.start, range.end, &opts)?
+                    .into_iter()
+                    .map(|sym| {
+                        let mut sym = sym.to_owned();
+                        if *demangle {
+                            let name = maybe_demangle(sym.name.clone(), SrcLang::Unknown);
+                            sym.demangled_name = Some(name);
+                        }
+                        sym
+                    })
+                    .collect();
                 Ok(syms)
             }
         }
     }
 
+    /// Look up the function symbol starting exactly at `addr`.
+    ///
+    /// Contrary to symbolization, no symbol is reported for an address
+    /// that is merely contained in a function, making this method
+    /// suitable for, say, validating function entry instrumentation.
+    /// `addr` is a file virtual address.
+    pub fn find_sym_exact<'slf>(
+        &'slf self,
+        addr: Addr,
+        src: &Source,
+    ) -> Result<Option<SymInfo<'slf>>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source,
+                section: _,
+                dedup: _,
+                demangle,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver
+                    .find_sym_exact(addr, *sym_source)?
+                    .map(|sym| func_sym_info(sym, &resolver, *demangle))
+                    .transpose()
+            }
+        }
+    }
+
+    /// Look up all function symbols covering `addr`.
+    ///
+    /// Contrary to symbolization, which settles on a single symbol,
+    /// reported are all symbols whose range contains the address or
+    /// that start exactly at it, allowing callers to pick among aliases
+    /// and nested symbols themselves. Symbols are ordered by
+    /// preference, with those from the preferred
+    /// [`sym_source`][Elf::sym_source] first. `addr` is a file virtual
+    /// address.
+    pub fn find_syms_at<'slf>(&'slf self, addr: Addr, src: &Source) -> Result<Vec<SymInfo<'slf>>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source,
+                section: _,
+                dedup: _,
+                demangle,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver
+                    .find_syms_at(addr, *sym_source)?
+                    .into_iter()
+                    .map(|sym| func_sym_info(sym, &resolver, *demangle))
+                    .collect()
+            }
+        }
+    }
+
+    /// Look up the function containing `addr` using only the ELF symbol
+    /// tables.
+    ///
+    /// Contrary to symbolization, this method never consults DWARF
+    /// debug information and reports neither source code locations nor
+    /// inlined functions. The lookup is a binary search over the sorted
+    /// symbol start addresses, making for predictable low latency,
+    /// e.g., for sampling profilers that defer full symbolization.
+    /// `addr` is a file virtual address.
+    ///
+    /// # Notes
+    /// - the [`sym_source`][Elf::sym_source] preference is disregarded;
+    ///   `.symtab` is used if present and `.dynsym` otherwise
+    pub fn find_func_fast<'slf>(
+        &'slf self,
+        addr: Addr,
+        src: &Source,
+    ) -> Result<Option<SymInfo<'slf>>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver
+                    .find_func_fast(addr)?
+                    .map(|sym| func_sym_info(sym, &resolver, *demangle))
+                    .transpose()
+            }
+        }
+    }
+
+    /// Look up the function symbol containing the entry point of the
+    /// source (typically `_start`).
+    ///
+    /// For the [`Elf`](Source::Elf) source, the entry point is a file
+    /// virtual address, even for position independent executables.
+    /// `None` is returned if the file has no entry point (as is usually
+    /// the case for shared objects) or no symbol covers it.
+    pub fn find_entry_sym<'slf>(&'slf self, src: &Source) -> Result<Option<SymInfo<'slf>>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source,
+                section: _,
+                dedup: _,
+                demangle,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver
+                    .find_entry_sym(*sym_source)?
+                    .map(|sym| func_sym_info(sym, &resolver, *demangle))
+                    .transpose()
+            }
+        }
+    }
+
+    /// Explain whether and why `addr` can or cannot be symbolized.
+    ///
+    /// This method is meant for diagnosing coverage gaps, for when
+    /// symbolization does not report a symbol for an address. `addr` is
+    /// a file virtual address.
+    pub fn explain_addr(&self, addr: Addr, src: &Source) -> Result<AddrStatus> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver.explain_addr(addr)
+            }
+        }
+    }
+
+    /// Suggest names of symbols similar to `name`.
+    ///
+    /// This method is meant for providing "did you mean" style hints to
+    /// users, for example, after [`lookup`][Self::lookup] came up empty
+    /// because of a misspelled name. Similarity is determined based on
+    /// the edit distance between names, which is bounded to roughly a
+    /// third of the length of `name`, and at most five names are
+    /// reported, closest first. `name` itself is never reported.
+    ///
+    /// # Notes
+    /// - no symbol name demangling is performed currently
+    /// - for the [`Elf`](Source::Elf) source, only ELF symbols are
+    ///   considered (irrespective of the [`debug_info`][Elf::debug_info]
+    ///   configuration)
+    pub fn suggest(&self, name: &str, src: &Source) -> Result<Vec<String>> {
+        const MAX_SUGGESTIONS: usize = 5;
+
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let max_distance = (name.len() / 3).max(1);
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                let names = resolver
+                    .parser()
+                    .find_similar_names(name, max_distance, MAX_SUGGESTIONS)?
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
+                Ok(names)
+            }
+        }
+    }
+
+    /// Find the addresses attributed to a source code line.
+    ///
+    /// This is the inverse of the source code information reported
+    /// during symbolization, useful, for example, for setting
+    /// breakpoints. `file` is matched against the end of the paths of
+    /// source files and, if that yields no match, against their base
+    /// names. Reported addresses are sorted.
+    ///
+    /// # Notes
+    /// - for the [`Elf`](Source::Elf) source, addresses are only
+    ///   reported if [`debug_info`][Elf::debug_info] is enabled and the
+    ///   file contains DWARF debug information
+    pub fn find_line_addrs(&self, file: &Path, line: u32, src: &Source) -> Result<Vec<Addr>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver.find_line(file, line)
+            }
+        }
+    }
+
+    /// Retrieve the paths of all source files referenced by the
+    /// compilation unit covering `addr`.
+    ///
+    /// Paths are resolved against the unit's compilation directory.
+    /// `addr` is a file virtual address. An empty list is reported if
+    /// no compilation unit covers it.
+    ///
+    /// # Notes
+    /// - for the [`Elf`](Source::Elf) source, files are only reported
+    ///   if [`debug_info`][Elf::debug_info] is enabled and the file
+    ///   contains DWARF debug information
+    pub fn source_files(&self, addr: Addr, src: &Source) -> Result<Vec<PathBuf>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver.source_files(addr)
+            }
+        }
+    }
+
+    /// Retrieve the parameters and local variables of the function
+    /// covering `addr`.
+    ///
+    /// Only variables in scope at `addr` are reported, in the order in
+    /// which the debug information declares them. Variables of inlined
+    /// functions are not included. `addr` is a file virtual address.
+    /// Location expressions are reported in their raw DWARF encoding
+    /// and are left for the caller to evaluate.
+    ///
+    /// # Notes
+    /// - for the [`Elf`](Source::Elf) source, variables are only
+    ///   reported if [`debug_info`][Elf::debug_info] is enabled and the
+    ///   file contains DWARF debug information
+    pub fn locals(&self, addr: Addr, src: &Source) -> Result<Vec<VarInfo<'static>>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                let vars = resolver
+                    .locals(addr)?
+                    .iter()
+                    .map(VarInfo::to_owned)
+                    .collect();
+                Ok(vars)
+            }
+        }
+    }
+
+    /// Retrieve the source location of every line table row of the
+    /// function containing `addr`.
+    ///
+    /// Each entry consists of the start address of the row, the path to
+    /// the source file, and the line number. Entries are sorted by
+    /// address and cover all parts of non-contiguous functions (e.g.,
+    /// as produced by hot/cold splitting). `addr` is a file virtual
+    /// address. An empty list is returned if no function contains it.
+    ///
+    /// # Notes
+    /// - for the [`Elf`](Source::Elf) source, lines are only reported
+    ///   if [`debug_info`][Elf::debug_info] is enabled and the file
+    ///   contains DWARF debug information
+    pub fn function_lines(&self, addr: Addr, src: &Source) -> Result<Vec<(Addr, PathBuf, u32)>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver.function_lines(addr)
+            }
+        }
+    }
+
+    /// Retrieve the source location of the entry of the function
+    /// containing `addr`, i.e., the path to the source file and the
+    /// line the function is defined at.
+    ///
+    /// `addr` is a file virtual address and may refer to any part of
+    /// the function. `None` is returned if no function contains it or
+    /// if the function's entry is not attributed to any line.
+    ///
+    /// # Notes
+    /// - for the [`Elf`](Source::Elf) source, a location is only
+    ///   reported if [`debug_info`][Elf::debug_info] is enabled and the
+    ///   file contains DWARF debug information
+    pub fn function_decl_line(&self, addr: Addr, src: &Source) -> Result<Option<(PathBuf, u32)>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver.function_decl_line(addr)
+            }
+        }
+    }
+
+    /// Retrieve the name of the compilation unit covering `addr`.
+    ///
+    /// The name is the unit's primary source file, as opposed to any of
+    /// the files referenced by its line information, and it is resolved
+    /// against the unit's compilation directory. `addr` is a file
+    /// virtual address. `None` is returned if no compilation unit
+    /// covers it.
+    ///
+    /// # Notes
+    /// - for the [`Elf`](Source::Elf) source, a name is only reported
+    ///   if [`debug_info`][Elf::debug_info] is enabled and the file
+    ///   contains DWARF debug information
+    pub fn unit_name(&self, addr: Addr, src: &Source) -> Result<Option<PathBuf>> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver.unit_name(addr)
+            }
+        }
+    }
+
     /// Perform an operation on each symbol in the source.
     ///
     /// Symbols are reported in implementation defined order that should
@@ -184,11 +708,20 @@ impl Inspector {
             Source::Elf(Elf {
                 path,
                 debug_info,
+                sym_source,
+                section,
+                dedup,
+                demangle: _,
+                limit: _,
                 _non_exhaustive: (),
             }) => {
                 let opts = FindAddrOpts {
                     offset_in_file: true,
                     sym_type: SymType::Unknown,
+                    sym_source: *sym_source,
+                    section: section.clone(),
+                    dedup: *dedup,
+                    limit: None,
                 };
                 let resolver = self.elf_resolver(path, *debug_info)?;
                 let parser = resolver.parser();
@@ -196,6 +729,70 @@ impl Inspector {
             }
         }
     }
+
+    /// Check whether the source provides information about inlined
+    /// functions.
+    ///
+    /// Callers can use this method to determine upfront whether
+    /// requesting inlined functions during symbolization can have any
+    /// effect, e.g., to disable a corresponding option in a user
+    /// interface.
+    ///
+    /// # Notes
+    /// - for the [`Elf`](Source::Elf) source, inline information is
+    ///   only available if [`debug_info`][Elf::debug_info] is enabled
+    ///   and the file contains DWARF debug information describing at
+    ///   least one inlined function
+    pub fn has_inline_info(&self, src: &Source) -> Result<bool> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                Ok(resolver.has_inline_info())
+            }
+        }
+    }
+
+    /// Perform an operation on each inlined function instance in the
+    /// source.
+    ///
+    /// Instances are reported in implementation defined order that
+    /// should not be relied on. Compilation units are processed one at
+    /// a time, making this method suitable for large binaries with
+    /// plenty of inlined functions.
+    ///
+    /// # Notes
+    /// - for the [`Elf`](Source::Elf) source, inlined functions are only
+    ///   reported if [`debug_info`][Elf::debug_info] is enabled and the
+    ///   file contains DWARF debug information
+    pub fn for_each_inlined<F, R>(&self, src: &Source, r: R, f: F) -> Result<R>
+    where
+        F: FnMut(R, &InlineInstance<'_>) -> R,
+    {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_info,
+                sym_source: _,
+                section: _,
+                dedup: _,
+                demangle: _,
+                limit: _,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.elf_resolver(path, *debug_info)?;
+                resolver.for_each_inlined(r, f)
+            }
+        }
+    }
 }
 
 impl Default for Inspector {
@@ -19,15 +19,42 @@ mod inspector;
 mod source;
 
 use std::borrow::Cow;
+use std::ops::Range;
 use std::path::Path;
 
+use crate::symbolize::CodeInfo;
 use crate::Addr;
 
+pub use crate::elf::ElfSymSource;
 pub use inspector::Inspector;
 pub use source::Elf;
 pub use source::Source;
 
 
+/// An explanation of whether and why an address can or cannot be
+/// symbolized.
+///
+/// Explanations are reported by [`Inspector::explain_addr`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum AddrStatus {
+    /// A symbol covering the address was found.
+    Resolvable,
+    /// The address is not contained in any section loaded at runtime.
+    OutOfRange,
+    /// The address is contained in a section that does not contain
+    /// code, such as `.data` or `.rodata`.
+    InDataSection,
+    /// The address refers to code, but the file contains neither
+    /// function symbols nor DWARF debug information (e.g., because it
+    /// was stripped).
+    NoSymbols,
+    /// The address refers to code and symbols are present, but none of
+    /// them covers the address.
+    Uncovered,
+}
+
+
 /// The type of a symbol.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub enum SymType {
@@ -38,6 +65,12 @@ pub enum SymType {
     Function,
     /// The symbol is a variable.
     Variable,
+    /// The symbol is a thread-local variable.
+    ///
+    /// The address of such a symbol is an offset into the thread-local
+    /// storage block of the module defining it and not a virtual
+    /// address.
+    Tls,
 }
 
 
@@ -46,10 +79,26 @@ pub enum SymType {
 pub struct SymInfo<'src> {
     /// The name of the symbol; for example, a function name.
     pub name: Cow<'src, str>,
+    /// The demangled name of the symbol.
+    ///
+    /// Only reported if demangling was requested (e.g., via
+    /// [`Elf::demangle`]), in which case it is equal to
+    /// [`name`][Self::name] for symbols that are not mangled. `None`
+    /// otherwise.
+    pub demangled_name: Option<Cow<'src, str>>,
     /// Start address (the first byte) of the symbol.
     pub addr: Addr,
     /// The size of the symbol. The size of a function for example.
     pub size: usize,
+    /// The end address of the symbol (the first byte past it), if known.
+    ///
+    /// If the symbol has a non-zero [`size`][Self::size], the end is
+    /// always `addr + size`. Only for symbols without size information,
+    /// the end is derived from the start address of the next symbol, if
+    /// the source allows for determining it (e.g., for ELF symbols
+    /// residing in the same section or kernel symbols). Otherwise it
+    /// is `None`.
+    pub end: Option<Addr>,
     /// A function or a variable.
     pub sym_type: SymType,
     /// The offset in the object file.
@@ -65,8 +114,13 @@ impl SymInfo<'_> {
     pub fn to_owned(&self) -> SymInfo<'static> {
         SymInfo {
             name: Cow::Owned(self.name.to_string()),
+            demangled_name: self
+                .demangled_name
+                .as_deref()
+                .map(|name| Cow::Owned(name.to_string())),
             addr: self.addr,
             size: self.size,
+            end: self.end,
             sym_type: self.sym_type,
             file_offset: self.file_offset,
             obj_file_name: self
@@ -78,6 +132,67 @@ impl SymInfo<'_> {
 }
 
 
+/// An instance of an inlined function.
+///
+/// Instances are reported by [`Inspector::for_each_inlined`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct InlineInstance<'src> {
+    /// The name of the inlined function, typically inferred from its
+    /// abstract origin.
+    pub name: Option<&'src str>,
+    /// The address ranges covered by the inlined code.
+    pub ranges: Vec<Range<Addr>>,
+    /// The source code location of the call site, if known.
+    pub call_site: Option<CodeInfo<'src>>,
+    /// The struct is non-exhaustive and open to extension.
+    pub _non_exhaustive: (),
+}
+
+
+/// A parameter or local variable of a function.
+///
+/// Variables are reported by [`Inspector::locals`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct VarInfo<'src> {
+    /// The name of the variable, if present.
+    pub name: Option<Cow<'src, str>>,
+    /// A C style rendering of the variable's type (e.g.,
+    /// `const char *`), if known.
+    pub type_name: Option<String>,
+    /// The raw DWARF location expression of the variable at the
+    /// address of interest.
+    ///
+    /// `None` if the variable has no location at this address (e.g.,
+    /// because it got optimized out).
+    pub location: Option<Cow<'src, [u8]>>,
+    /// Whether the variable is a formal parameter of the function.
+    pub param: bool,
+    /// The struct is non-exhaustive and open to extension.
+    pub _non_exhaustive: (),
+}
+
+impl VarInfo<'_> {
+    /// Clone the object ensuring that references are converted to owned
+    /// objects.
+    #[inline]
+    pub fn to_owned(&self) -> VarInfo<'static> {
+        VarInfo {
+            name: self
+                .name
+                .as_deref()
+                .map(|name| Cow::Owned(name.to_string())),
+            type_name: self.type_name.clone(),
+            location: self
+                .location
+                .as_deref()
+                .map(|location| Cow::Owned(location.to_vec())),
+            param: self.param,
+            _non_exhaustive: (),
+        }
+    }
+}
+
+
 /// The context of an address finding request.
 ///
 /// This type passes additional parameters to resolvers.
@@ -89,4 +204,21 @@ pub(crate) struct FindAddrOpts {
     /// Return the symbol(s) matching a given type. Unknown, by default,
     /// means all types.
     pub sym_type: SymType,
+    /// The source of symbols to prefer for ELF files.
+    pub sym_source: ElfSymSource,
+    /// The name of the section to restrict symbols to, if any.
+    pub section: Option<String>,
+    /// Whether to report only a single symbol for each distinct name
+    /// and address combination.
+    ///
+    /// If multiple symbols share both, the one conveying the most
+    /// information (i.e., having a size and the strongest binding) is
+    /// reported. (False by default)
+    pub dedup: bool,
+    /// The maximum number of symbols to report for each name, if any.
+    ///
+    /// Symbols are reported in the order in which the source
+    /// enumerates them and matching stops once the limit is reached,
+    /// meaning that this order determines which symbols are kept.
+    pub limit: Option<usize>,
 }
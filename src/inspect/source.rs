@@ -1,6 +1,8 @@
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::elf::ElfSymSource;
+
 
 /// An ELF file.
 #[derive(Clone, Debug, PartialEq)]
@@ -10,6 +12,44 @@ pub struct Elf {
     /// Whether or not to consult debug information to satisfy the request (if
     /// present).
     pub debug_info: bool,
+    /// The source of symbols to prefer when looking up symbols by name.
+    ///
+    /// DWARF debug information is only considered if `debug_info` is
+    /// enabled. Defaults to [`ElfSymSource::Dwarf`].
+    pub sym_source: ElfSymSource,
+    /// The name of the section (e.g., `.text.unlikely`) to restrict
+    /// reported symbols to.
+    ///
+    /// ELF symbols are attributed to a section based on their section
+    /// index, while symbols from DWARF debug information are reported
+    /// if their address falls within the section. If `None`, symbols
+    /// from all sections are reported.
+    pub section: Option<String>,
+    /// Whether to report only a single symbol for each distinct name
+    /// and address combination.
+    ///
+    /// Symbol tables may contain multiple entries for the same
+    /// function, for example, a local alias lacking size information.
+    /// If enabled, name based lookups only report the entry conveying
+    /// the most information (i.e., having a size and the strongest
+    /// binding).
+    /// Defaults to `false`.
+    pub dedup: bool,
+    /// Whether to report the demangled names of symbols found by name
+    /// alongside their raw ones.
+    ///
+    /// Lookups are always performed using raw names. If enabled,
+    /// [`SymInfo::demangled_name`][crate::inspect::SymInfo::demangled_name]
+    /// is set for each reported symbol. Defaults to `false`.
+    pub demangle: bool,
+    /// The maximum number of symbols to report for each name looked
+    /// up.
+    ///
+    /// Matching stops once the limit is reached, so the order in which
+    /// the underlying symbol source enumerates symbols (e.g., symbol
+    /// table order for ELF symbols) determines which ones are
+    /// reported. If `None`, all matching symbols are reported.
+    pub limit: Option<usize>,
     /// The struct is non-exhaustive and open to extension.
     #[doc(hidden)]
     pub _non_exhaustive: (),
@@ -21,6 +61,11 @@ impl Elf {
         Self {
             path: path.into(),
             debug_info: true,
+            sym_source: ElfSymSource::default(),
+            section: None,
+            dedup: false,
+            demangle: false,
+            limit: None,
             _non_exhaustive: (),
         }
     }
@@ -7,6 +7,7 @@ use std::rc::Rc;
 use crate::elf::ElfResolver;
 use crate::inspect::FindAddrOpts;
 use crate::inspect::SymInfo;
+use crate::kmod::KernelModuleResolver;
 use crate::ksym::KSymResolver;
 use crate::symbolize::AddrCodeInfo;
 use crate::symbolize::IntSym;
@@ -16,15 +17,24 @@ use crate::Result;
 use crate::SymResolver;
 
 
+/// A symbol resolver for the Linux kernel.
+///
+/// Live patch modules may be layered on top of the kernel proper.
+/// Lookups consult them first, in reverse order of their provision,
+/// so that the most recently applied patch wins. Only if none of them
+/// covers an address is the kernel itself consulted.
 pub(crate) struct KernelResolver {
     pub ksym_resolver: Option<Rc<KSymResolver>>,
     pub elf_resolver: Option<Rc<ElfResolver>>,
+    /// Resolvers for live patch modules applied to the kernel.
+    pub patches: Vec<Rc<KernelModuleResolver>>,
 }
 
 impl KernelResolver {
     pub fn new(
         ksym_resolver: Option<Rc<KSymResolver>>,
         elf_resolver: Option<Rc<ElfResolver>>,
+        patches: Vec<Rc<KernelModuleResolver>>,
     ) -> Result<KernelResolver> {
         if ksym_resolver.is_none() && elf_resolver.is_none() {
             return Err(Error::with_not_found(
@@ -35,12 +45,27 @@ impl KernelResolver {
         Ok(KernelResolver {
             ksym_resolver,
             elf_resolver,
+            patches,
         })
     }
+
+    /// Find the live patch module covering `addr`, if any.
+    fn find_patch(&self, addr: Addr) -> Result<Option<(&KernelModuleResolver, IntSym<'_>)>> {
+        for patch in self.patches.iter().rev() {
+            if let Some(sym) = patch.find_sym(addr)? {
+                return Ok(Some((patch, sym)))
+            }
+        }
+        Ok(None)
+    }
 }
 
 impl SymResolver for KernelResolver {
     fn find_sym(&self, addr: Addr) -> Result<Option<IntSym<'_>>> {
+        if let Some((_patch, sym)) = self.find_patch(addr)? {
+            return Ok(Some(sym))
+        }
+
         if let Some(ksym_resolver) = self.ksym_resolver.as_ref() {
             ksym_resolver.find_sym(addr)
         } else {
@@ -56,9 +81,18 @@ impl SymResolver for KernelResolver {
         Ok(Vec::new())
     }
 
-    fn find_code_info(&self, addr: Addr, inlined_fns: bool) -> Result<Option<AddrCodeInfo>> {
+    fn find_code_info(
+        &self,
+        addr: Addr,
+        inlined_fns: bool,
+        line_only: bool,
+    ) -> Result<Option<AddrCodeInfo>> {
+        if let Some((patch, _sym)) = self.find_patch(addr)? {
+            return patch.find_code_info(addr, inlined_fns, line_only)
+        }
+
         if let Some(resolver) = self.elf_resolver.as_ref() {
-            resolver.find_code_info(addr, inlined_fns)
+            resolver.find_code_info(addr, inlined_fns, line_only)
         } else {
             Ok(None)
         }
@@ -80,6 +114,11 @@ impl Debug for KernelResolver {
                 .map(|resolver| resolver.file_name())
                 .unwrap_or_else(|| Path::new(""))
                 .display(),
-        )
+        )?;
+
+        for patch in &self.patches {
+            let () = write!(f, " {patch:?}")?;
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+#[cfg(feature = "dwarf")]
+use crate::dwarf::DwarfResolver;
+use crate::elf::types::ET_REL;
+use crate::elf::types::STT_FUNC;
+use crate::elf::ElfParser;
+use crate::inspect::FindAddrOpts;
+use crate::inspect::SymInfo;
+use crate::log::debug;
+use crate::symbolize::AddrCodeInfo;
+use crate::symbolize::IntSym;
+use crate::symbolize::SrcLang;
+use crate::Addr;
+use crate::Error;
+use crate::IntoError as _;
+use crate::Result;
+use crate::SymResolver;
+
+
+/// A symbol resolver for a loaded Linux kernel module.
+///
+/// Kernel modules are relocatable ELF files (`ET_REL`). Such files do
+/// not contain program headers and symbol values are relative to the
+/// section they reside in. Hence, to map an address to a symbol we need
+/// to know the load address of each section.
+///
+/// The same holds for any other relocatable object, such as `.o`
+/// files.
+pub(crate) struct KernelModuleResolver {
+    parser: Rc<ElfParser>,
+    file_name: PathBuf,
+    /// The address ranges of the module's sections, along with their
+    /// section indices, sorted by start address.
+    sections: Box<[(Range<Addr>, usize)]>,
+    /// A resolver for the module's debug information, relocated
+    /// according to the provided section addresses, if any.
+    #[cfg(feature = "dwarf")]
+    dwarf: Option<DwarfResolver>,
+}
+
+impl KernelModuleResolver {
+    pub(crate) fn new(
+        file_name: &Path,
+        parser: Rc<ElfParser>,
+        section_addrs: &HashMap<String, Addr>,
+    ) -> Result<Self> {
+        let elf_type = parser.elf_type()?;
+        if elf_type != ET_REL {
+            return Err(Error::with_invalid_data(format!(
+                "{} is not a relocatable ELF file (type {elf_type})",
+                file_name.display()
+            )))
+        }
+
+        let shdrs = parser.section_headers()?;
+        let mut sections = Vec::with_capacity(section_addrs.len());
+        for (idx, shdr) in shdrs.iter().enumerate().skip(1) {
+            let name = parser.section_name(idx)?;
+            if let Some(addr) = section_addrs.get(name) {
+                let end = addr.checked_add(shdr.sh_size).ok_or_invalid_data(|| {
+                    format!(
+                        "section {name} of size {} does not fit at {addr:#x}",
+                        shdr.sh_size
+                    )
+                })?;
+                let () = sections.push((*addr..end, idx));
+            }
+        }
+
+        if sections.len() != section_addrs.len() {
+            debug!(
+                "{} does not contain all sections for which load addresses were provided",
+                file_name.display()
+            );
+        }
+
+        let () = sections.sort_by_key(|(range, _idx)| range.start);
+
+        let slf = Self {
+            parser,
+            file_name: file_name.to_path_buf(),
+            sections: sections.into_boxed_slice(),
+            #[cfg(feature = "dwarf")]
+            dwarf: None,
+        };
+        Ok(slf)
+    }
+
+    /// Use the provided DWARF resolver for source code information.
+    ///
+    /// The resolver is expected to be based on a parser that has been
+    /// [provided][ElfParser::set_section_addrs] the same section load
+    /// addresses as this object.
+    #[cfg(feature = "dwarf")]
+    pub(crate) fn set_dwarf(&mut self, dwarf: DwarfResolver) {
+        self.dwarf = Some(dwarf);
+    }
+}
+
+impl SymResolver for KernelModuleResolver {
+    fn find_sym(&self, addr: Addr) -> Result<Option<IntSym<'_>>> {
+        let section = self
+            .sections
+            .iter()
+            .find(|(range, _idx)| range.contains(&addr));
+        let (range, idx) = match section {
+            Some(section) => section,
+            None => return Ok(None),
+        };
+
+        let offset = addr - range.start;
+        let sym = match self.parser.find_section_sym(*idx, offset, STT_FUNC)? {
+            Some((name, sym_offset, size)) => IntSym {
+                name,
+                // We report the symbol's relocated address.
+                addr: range.start + sym_offset,
+                size: Some(size),
+                // ELF does not carry any source code language information.
+                lang: SrcLang::Unknown,
+            },
+            None => return Ok(None),
+        };
+        Ok(Some(sym))
+    }
+
+    fn find_addr<'slf>(
+        &'slf self,
+        _name: &str,
+        _opts: &FindAddrOpts,
+    ) -> Result<Vec<SymInfo<'slf>>> {
+        Ok(Vec::new())
+    }
+
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+    fn find_code_info(
+        &self,
+        addr: Addr,
+        inlined_fns: bool,
+        line_only: bool,
+    ) -> Result<Option<AddrCodeInfo<'_>>> {
+        // Debug information in relocatable files only becomes usable
+        // after applying relocations, which the DWARF resolver's
+        // parser takes care of.
+        #[cfg(feature = "dwarf")]
+        if let Some(dwarf) = &self.dwarf {
+            return dwarf.find_code_info(addr, inlined_fns, line_only)
+        }
+        Ok(None)
+    }
+}
+
+impl Debug for KernelModuleResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "KernelModuleResolver {}", self.file_name.display())
+    }
+}
@@ -16,6 +16,7 @@ use crate::symbolize::AddrCodeInfo;
 use crate::symbolize::IntSym;
 use crate::symbolize::SrcLang;
 use crate::util::find_match_or_lower_bound_by_key;
+use crate::util::StrArena;
 use crate::Addr;
 use crate::Result;
 use crate::SymResolver;
@@ -26,7 +27,9 @@ const DFL_KSYM_CAP: usize = 200000;
 #[derive(Debug)]
 pub struct Ksym {
     pub addr: Addr,
-    pub name: String,
+    // SAFETY: The name is owned by the `KSymResolver` containing this
+    //         object and must never outlive it.
+    pub name: &'static str,
 }
 
 impl<'ksym> From<&'ksym Ksym> for IntSym<'ksym> {
@@ -44,6 +47,30 @@ impl<'ksym> From<&'ksym Ksym> for IntSym<'ksym> {
     }
 }
 
+/// The storage backing the names of kernel symbols.
+#[derive(Debug)]
+enum Names {
+    /// Each name is allocated individually.
+    Individual(Vec<Box<str>>),
+    /// Names are bump allocated from an arena.
+    Arena(StrArena),
+}
+
+impl Names {
+    /// Store a copy of `name`.
+    fn alloc(&mut self, name: &str) -> &str {
+        match self {
+            Self::Individual(names) => {
+                let () = names.push(Box::from(name));
+                // SANITY: We just pushed an element.
+                names.last().unwrap()
+            }
+            Self::Arena(arena) => arena.alloc(name),
+        }
+    }
+}
+
+
 /// The symbol resolver for /proc/kallsyms.
 ///
 /// The users should provide the path of kallsyms, so you can provide
@@ -55,15 +82,28 @@ pub struct KSymResolver {
     //         to make sure we never end up with dangling references.
     sym_to_addr: OnceCell<Vec<(&'static str, Addr)>>,
     syms: Vec<Ksym>,
+    /// The storage of all symbol names. Has to be listed after `syms`
+    /// and `sym_to_addr`, which reference it.
+    _names: Names,
     file_name: PathBuf,
 }
 
 impl KSymResolver {
-    pub fn load_file_name(filename: PathBuf) -> Result<Self> {
+    /// Load kernel symbols from the kallsyms file at `filename`.
+    ///
+    /// If `name_arena` is `true`, symbol names are placed in an arena
+    /// that is released in one go together with the resolver, instead
+    /// of being allocated individually.
+    pub fn load_file_name(filename: PathBuf, name_arena: bool) -> Result<Self> {
         let f = File::open(&filename)?;
         let mut reader = BufReader::new(f);
         let mut line = String::new();
         let mut syms = Vec::with_capacity(DFL_KSYM_CAP);
+        let mut names = if name_arena {
+            Names::Arena(StrArena::default())
+        } else {
+            Names::Individual(Vec::with_capacity(DFL_KSYM_CAP))
+        };
 
         loop {
             let sz = reader.read_line(&mut line)?;
@@ -80,7 +120,11 @@ impl KSymResolver {
                     line.truncate(0);
                     continue
                 }
-                let name = String::from(func);
+                let name = names.alloc(func);
+                // SAFETY: Names are never moved once allocated and we
+                //         ensure that no `Ksym` outlives `names`, so
+                //         conjuring up a 'static lifetime is fine.
+                let name = unsafe { &*(name as *const str) };
                 syms.push(Ksym { addr, name });
             }
 
@@ -92,6 +136,7 @@ impl KSymResolver {
         let slf = Self {
             syms,
             sym_to_addr: OnceCell::new(),
+            _names: names,
             file_name: filename,
         };
         Ok(slf)
@@ -102,6 +147,12 @@ impl KSymResolver {
             .and_then(|idx| self.syms.get(idx))
     }
 
+    /// Retrieve the address of the first symbol located past `addr`.
+    fn next_ksym_addr(&self, addr: Addr) -> Option<Addr> {
+        let idx = self.syms.partition_point(|ksym| ksym.addr <= addr);
+        self.syms.get(idx).map(|ksym| ksym.addr)
+    }
+
     /// Retrieve the path to the kallsyms file used by this resolver.
     pub(crate) fn file_name(&self) -> &Path {
         &self.file_name
@@ -123,13 +174,7 @@ impl SymResolver for KSymResolver {
             let mut syms = self
                 .syms
                 .iter()
-                .map(|Ksym { name, addr }| {
-                    // SAFETY: We ensure that all `Ksym` objects outlive the
-                    //         `syms` member, so conjuring up a 'static
-                    //         lifetime is fine.
-                    let name = unsafe { &*(name.as_ref() as *const str) };
-                    (name, *addr)
-                })
+                .map(|Ksym { name, addr }| (*name, *addr))
                 .collect::<Vec<_>>();
             let () =
                 syms.sort_by(|sym1, sym2| sym1.0.cmp(sym2.0).then_with(|| sym1.1.cmp(&sym2.1)));
@@ -142,12 +187,17 @@ impl SymResolver for KSymResolver {
                 .iter()
                 .map(|(name, addr)| SymInfo {
                     name: Cow::Borrowed(*name),
+                    demangled_name: None,
                     addr: *addr,
                     size: 0,
+                    // There is no size information in kallsyms, so the
+                    // next symbol's address is the best we can do.
+                    end: self.next_ksym_addr(*addr),
                     sym_type: SymType::Function,
                     file_offset: None,
                     obj_file_name: None,
                 })
+                .take(opts.limit.unwrap_or(usize::MAX))
                 .collect()
         } else {
             Vec::new()
@@ -155,7 +205,12 @@ impl SymResolver for KSymResolver {
         Ok(syms)
     }
 
-    fn find_code_info(&self, _addr: Addr, _inlined_fns: bool) -> Result<Option<AddrCodeInfo>> {
+    fn find_code_info(
+        &self,
+        _addr: Addr,
+        _inlined_fns: bool,
+        _line_only: bool,
+    ) -> Result<Option<AddrCodeInfo>> {
         Ok(None)
     }
 }
@@ -182,13 +237,14 @@ mod tests {
         let resolver = KSymResolver {
             syms: Vec::new(),
             sym_to_addr: OnceCell::new(),
+            _names: Names::Arena(StrArena::default()),
             file_name: PathBuf::new(),
         };
         assert_ne!(format!("{resolver:?}"), "");
 
         let ksym = Ksym {
             addr: 0x1337,
-            name: "3l33t".to_string(),
+            name: "3l33t",
         };
         assert_ne!(format!("{ksym:?}"), "");
     }
@@ -196,7 +252,7 @@ mod tests {
     /// Check that we can use a `KSymResolver` to find symbols.
     #[test]
     fn ksym_resolver_load_find() {
-        let result = KSymResolver::load_file_name(PathBuf::from(KALLSYMS));
+        let result = KSymResolver::load_file_name(PathBuf::from(KALLSYMS), false);
         let resolver = match result {
             Ok(resolver) => resolver,
             Err(err) if err.kind() == ErrorKind::NotFound => return,
@@ -212,6 +268,7 @@ mod tests {
             let opts = FindAddrOpts {
                 offset_in_file: false,
                 sym_type: SymType::Function,
+                ..Default::default()
             };
             let found = resolver.find_addr(name, &opts).unwrap();
             assert!(
@@ -242,28 +299,50 @@ mod tests {
         ensure_addr_for_name(found.name, addr);
     }
 
+    /// Check that placing symbol names in an arena does not affect
+    /// lookup results.
+    #[test]
+    fn ksym_name_arena() {
+        let kallsyms = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("kallsyms");
+        let resolver = KSymResolver::load_file_name(kallsyms.clone(), false).unwrap();
+        let arena_resolver = KSymResolver::load_file_name(kallsyms, true).unwrap();
+        assert!(matches!(arena_resolver._names, Names::Arena(..)));
+
+        assert_eq!(resolver.syms.len(), arena_resolver.syms.len());
+        for (sym, arena_sym) in resolver.syms.iter().zip(&arena_resolver.syms) {
+            assert_eq!(sym.addr, arena_sym.addr);
+            assert_eq!(sym.name, arena_sym.name);
+        }
+
+        let sym = arena_resolver.find_sym(0xc0008001).unwrap().unwrap();
+        assert_eq!(sym.name, "stext");
+    }
+
     #[test]
     fn find_ksym() {
         let resolver = KSymResolver {
             syms: vec![
                 Ksym {
                     addr: 0x123,
-                    name: "1".to_string(),
+                    name: "1",
                 },
                 Ksym {
                     addr: 0x123,
-                    name: "1.5".to_string(),
+                    name: "1.5",
                 },
                 Ksym {
                     addr: 0x1234,
-                    name: "2".to_string(),
+                    name: "2",
                 },
                 Ksym {
                     addr: 0x12345,
-                    name: "3".to_string(),
+                    name: "3",
                 },
             ],
             sym_to_addr: OnceCell::new(),
+            _names: Names::Individual(Vec::new()),
             file_name: PathBuf::new(),
         };
 
@@ -15,6 +15,7 @@
 //! - [`inspect`] contains APIs for inspecting files such as ELF and Gsym to
 //!   lookup addresses to symbol names, for example
 //! - [`normalize`] exposes address normalization functionality
+//! - [`demangle`] provides standalone symbol name demangling
 //!
 //! C API bindings are defined in a cross-cutting manner as part of the
 //! `cblazesym` crate (note that Rust code should not have to consume
@@ -40,6 +41,8 @@
 #[cfg(feature = "nightly")]
 extern crate test;
 
+mod auxv;
+pub mod demangle;
 #[cfg(feature = "dwarf")]
 mod dwarf;
 mod elf;
@@ -49,14 +52,17 @@ mod gsym;
 mod insert_map;
 pub mod inspect;
 mod kernel;
+mod kmod;
 mod ksym;
 mod maps;
 mod mmap;
 pub mod normalize;
 mod once;
+mod perf_map;
 mod resolver;
 pub mod symbolize;
 mod util;
+mod wasm;
 mod zip;
 
 use std::fmt::Display;
@@ -83,7 +89,28 @@ pub type Addr = u64;
 
 /// Utility functionality not specific to any overarching theme.
 pub mod helper {
+    pub use crate::auxv::exe_load_addr_from_auxv;
+    pub use crate::elf::abi::read_elf_abi_tag;
+    pub use crate::elf::abi::AbiTag;
+    pub use crate::elf::abi::AbiTagOs;
+    pub use crate::elf::group::read_elf_section_groups;
+    pub use crate::elf::group::SectionGroup;
+    pub use crate::elf::ident::read_elf_ident;
+    pub use crate::elf::ident::ElfIdent;
+    pub use crate::elf::property::read_elf_gnu_properties;
+    pub use crate::elf::property::GnuProperties;
+    pub use crate::elf::recover::read_elf_recovered_tables;
+    pub use crate::elf::recover::RecoveredTable;
+    pub use crate::elf::reloc::read_elf_relocations_for;
+    pub use crate::elf::reloc::RelocInfo;
+    pub use crate::elf::segment::read_elf_exec_stack;
+    pub use crate::elf::segment::read_elf_tls_template;
+    pub use crate::elf::segment::TlsTemplate;
+    pub use crate::gsym::elf_to_gsym;
     pub use crate::normalize::buildid::read_elf_build_id;
+    pub use crate::normalize::buildid::read_elf_go_build_id;
+    pub use crate::normalize::module_id::module_id;
+    pub use crate::normalize::module_id::ModuleId;
 }
 
 
@@ -6,9 +6,10 @@ use std::os::unix::io::AsRawFd;
 #[cfg(test)]
 use std::path::Path;
 use std::ptr::null_mut;
-use std::rc::Rc;
 use std::slice;
+use std::sync::Arc;
 
+use crate::util::ReadAt;
 use crate::Error;
 use crate::ErrorExt as _;
 use crate::Result;
@@ -69,7 +70,58 @@ impl Builder {
 
         let mapping = Mapping { ptr, len };
         let mmap = Mmap {
-            mapping: Rc::new(mapping),
+            mapping: Arc::new(mapping),
+            view: 0..len as u64,
+        };
+        Ok(mmap)
+    }
+
+    /// Create an anonymous memory mapping of `len` bytes and fill it
+    /// with data from `reader`.
+    pub fn read<R>(self, reader: &R, len: u64) -> Result<Mmap>
+    where
+        R: ReadAt + ?Sized,
+    {
+        let len = libc::size_t::try_from(len)
+            .map_err(Error::with_invalid_data)
+            .context("data is too large to mmap")?;
+
+        // SAFETY: `mmap` with the provided arguments is always safe to call.
+        let ptr = unsafe {
+            libc::mmap(
+                null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+
+        if ptr == libc::MAP_FAILED {
+            return Err(Error::from(io::Error::last_os_error()))
+        }
+
+        // Wrap the mapping right away, so that it gets unmapped should
+        // anything below fail.
+        let mapping = Mapping { ptr, len };
+        // SAFETY: We know that the pointer is valid, writable, and
+        //         represents a region of `len` bytes that is not
+        //         referenced anywhere else.
+        let buf = unsafe { slice::from_raw_parts_mut(ptr.cast::<u8>(), len) };
+        let () = reader
+            .read_at(0, buf)
+            .context("failed to read data into memory mapping")?;
+
+        // SAFETY: `mprotect` with the provided arguments is always safe
+        //         to call.
+        let rc = unsafe { libc::mprotect(ptr, len, self.protection) };
+        if rc != 0 {
+            return Err(Error::from(io::Error::last_os_error()))
+        }
+
+        let mmap = Mmap {
+            mapping: Arc::new(mapping),
             view: 0..len as u64,
         };
         Ok(mmap)
@@ -93,6 +145,12 @@ impl Deref for Mapping {
     }
 }
 
+// SAFETY: Once a `Mapping` got created, the mapped memory is never
+//         modified and it stays valid until the object is dropped, so
+//         it is fine to share and send it across threads.
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
 impl Drop for Mapping {
     fn drop(&mut self) {
         // SAFETY: The `ptr` is valid.
@@ -106,7 +164,7 @@ impl Drop for Mapping {
 #[derive(Clone, Debug)]
 pub(crate) struct Mmap {
     /// The actual memory mapping.
-    mapping: Rc<Mapping>,
+    mapping: Arc<Mapping>,
     /// The view on the memory mapping that this object represents.
     view: Range<u64>,
 }
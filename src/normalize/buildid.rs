@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::fs::metadata;
+use std::os::unix::fs::MetadataExt as _;
 use std::path::Path;
 
 use crate::elf;
@@ -9,19 +12,13 @@ use crate::IntoError as _;
 use crate::Result;
 
 
-/// Typedefs for functions reading build IDs.
-pub(crate) type BuildIdFn = dyn Fn(&Path) -> Result<Option<Vec<u8>>>;
-
-
 /// Iterate over all note sections to find one of type
 /// [`NT_GNU_BUILD_ID`][elf::types::NT_GNU_BUILD_ID].
 fn read_build_id_from_notes(parser: &ElfParser) -> Result<Option<Vec<u8>>> {
     let shdrs = parser.section_headers()?;
     for (idx, shdr) in shdrs.iter().enumerate() {
         if shdr.sh_type == elf::types::SHT_NOTE {
-            // SANITY: We just found the index so the section data should always
-            //         be found.
-            let mut bytes = parser.section_data(idx).unwrap();
+            let mut bytes = parser.section_data(idx)?;
             let header = bytes
                 .read_pod_ref::<Elf64_Nhdr>()
                 .ok_or_invalid_data(|| "failed to read build ID section header")?;
@@ -59,9 +56,7 @@ fn read_build_id_from_section_name(parser: &ElfParser) -> Result<Option<Vec<u8>>
             return Ok(None)
         }
 
-        // SANITY: We just found the index so the section should always be
-        //         found.
-        let mut bytes = parser.section_data(idx).unwrap();
+        let mut bytes = parser.section_data(idx)?;
         let header = bytes
             .read_pod_ref::<Elf64_Nhdr>()
             .ok_or_invalid_data(|| "failed to read build ID section header")?;
@@ -126,6 +121,43 @@ impl BuildIdReader for NoBuildIdReader {
 }
 
 
+/// A cache of build IDs, keyed by the identity (device and inode
+/// number) of the file they were read from.
+///
+/// The cache allows for reading the build ID of a file mapped into
+/// multiple processes only once.
+#[derive(Debug, Default)]
+pub(super) struct BuildIdCache {
+    build_ids: HashMap<(u64, u64), Option<Vec<u8>>>,
+}
+
+impl BuildIdCache {
+    /// Retrieve the build ID of the ELF file at `path`, reading it using
+    /// `R` if it is not yet cached.
+    pub(super) fn get_or_read<R>(&mut self, path: &Path) -> Result<Option<Vec<u8>>>
+    where
+        R: BuildIdReader,
+    {
+        let meta = metadata(path)?;
+        let key = (meta.dev(), meta.ino());
+        if let Some(build_id) = self.build_ids.get(&key) {
+            return Ok(build_id.clone())
+        }
+
+        let build_id = R::read_build_id_from_elf(path)?;
+        let _prev = self.build_ids.insert(key, build_id.clone());
+        Ok(build_id)
+    }
+}
+
+
+/// Read the build ID of the ELF file represented by `parser`.
+#[inline]
+pub(crate) fn read_build_id(parser: &ElfParser) -> Result<Option<Vec<u8>>> {
+    DefaultBuildIdReader::read_build_id(parser)
+}
+
+
 /// Read the build ID of an ELF file located at the given path.
 ///
 /// Build IDs can have variable length, depending on which flavor is used (e.g.,
@@ -171,10 +203,43 @@ where
 }
 
 
+/// Read the build ID recorded by the Go toolchain in the
+/// `.note.go.buildid` note of an ELF file located at the given path.
+///
+/// Contrary to GNU build IDs, Go build IDs are textual and they are
+/// reported as-is.
+///
+/// Returns [`None`] if the file does not contain a Go build ID, which is
+/// the case for binaries not produced by Go.
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("test-go-buildid.bin");
+/// match blazesym::helper::read_elf_go_build_id(&path).unwrap() {
+///     Some(id) => println!("{} has Go build ID {id}", path.display()),
+///     None => println!("{} has no Go build ID", path.display()),
+/// }
+/// ```
+pub fn read_elf_go_build_id<P>(path: &P) -> Result<Option<String>>
+where
+    P: AsRef<Path>,
+{
+    let parser = ElfParser::open(path.as_ref())?;
+    parser.go_build_id()
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::os::unix::fs::symlink;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use tempfile::tempdir;
     use test_log::test;
 
 
@@ -196,6 +261,51 @@ mod tests {
         test(read_build_id_from_notes);
     }
 
+    /// Check that the `BuildIdCache` reads the build ID of a given file
+    /// only once.
+    #[test]
+    fn build_id_caching() {
+        static READS: AtomicUsize = AtomicUsize::new(0);
+
+        struct CountingBuildIdReader;
+
+        impl BuildIdReader for CountingBuildIdReader {
+            fn read_build_id_from_elf(path: &Path) -> Result<Option<Vec<u8>>> {
+                let _cnt = READS.fetch_add(1, Ordering::Relaxed);
+                DefaultBuildIdReader::read_build_id_from_elf(path)
+            }
+            fn read_build_id(parser: &ElfParser) -> Result<Option<Vec<u8>>> {
+                DefaultBuildIdReader::read_build_id(parser)
+            }
+        }
+
+        let elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+
+        let mut cache = BuildIdCache::default();
+        let build_id1 = cache.get_or_read::<CountingBuildIdReader>(&elf).unwrap();
+        let build_id2 = cache.get_or_read::<CountingBuildIdReader>(&elf).unwrap();
+        assert_eq!(READS.load(Ordering::Relaxed), 1);
+        assert_eq!(build_id1, build_id2);
+        assert_eq!(build_id1, read_elf_build_id(&elf).unwrap());
+
+        let elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-no-debug.bin");
+        let build_id = cache.get_or_read::<CountingBuildIdReader>(&elf).unwrap();
+        assert_eq!(build_id, None);
+        assert_eq!(READS.load(Ordering::Relaxed), 2);
+
+        // A symbolic link refers to the very same file and so the build
+        // ID should be served from the cache.
+        let dir = tempdir().unwrap();
+        let link = dir.path().join("test-no-debug.bin");
+        let () = symlink(&elf, &link).unwrap();
+        let _build_id = cache.get_or_read::<CountingBuildIdReader>(&link).unwrap();
+        assert_eq!(READS.load(Ordering::Relaxed), 2);
+    }
+
     /// Check that we can read a binary's build ID.
     #[test]
     fn build_id_reading() {
@@ -0,0 +1,318 @@
+//! A compact binary encoding for [`UserOutput`] objects.
+//!
+//! The format is meant for persisting normalized captures, e.g., to ship
+//! them to a different system for symbolization. All multi-byte fixed
+//! width integers are stored in little endian byte order, variable
+//! width integers are stored as unsigned LEB128 values.
+//!
+//! ```text
+//! header:  magic (4 bytes, "BZNO") | version (u16) | flags (u16)
+//! meta:    count (uleb) | count * (tag (u8) | payload)
+//! outputs: count (uleb) | count * (offset (uleb) | meta index (uleb))
+//! ```
+
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt as _;
+use std::path::PathBuf;
+
+use crate::util::ReadRaw as _;
+use crate::Error;
+use crate::IntoError as _;
+use crate::Result;
+
+use super::meta::Apk;
+use super::meta::Elf;
+use super::meta::Unknown;
+use super::meta::UserMeta;
+use super::UserOutput;
+
+
+/// The magic identifying an encoded [`UserOutput`].
+const MAGIC: [u8; 4] = *b"BZNO";
+/// The most recent version of the format; the one we produce.
+const VERSION: u16 = 1;
+
+const TAG_UNKNOWN: u8 = 0;
+const TAG_ELF: u8 = 1;
+const TAG_APK: u8 = 2;
+
+
+/// Append `value` to `data`, encoded as unsigned LEB128 value.
+fn write_u64_leb128(data: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0b0111_1111) as u8;
+        value >>= 7;
+        if value == 0 {
+            let () = data.push(byte);
+            break
+        }
+        let () = data.push(byte | 0b1000_0000);
+    }
+}
+
+fn write_bytes(data: &mut Vec<u8>, bytes: &[u8]) {
+    let () = write_u64_leb128(data, bytes.len() as u64);
+    let () = data.extend_from_slice(bytes);
+}
+
+fn read_usize(data: &mut &[u8]) -> Result<usize> {
+    let (value, _bytes) = data
+        .read_u64_leb128()
+        .ok_or_invalid_data(|| "failed to read variable length integer")?;
+    usize::try_from(value).map_err(Error::with_invalid_data)
+}
+
+fn read_bytes<'data>(data: &mut &'data [u8]) -> Result<&'data [u8]> {
+    let len = read_usize(data)?;
+    data.read_slice(len)
+        .ok_or_invalid_data(|| format!("failed to read byte string of length {len}"))
+}
+
+fn read_meta(data: &mut &[u8]) -> Result<UserMeta> {
+    let tag = data
+        .read_u8()
+        .ok_or_invalid_data(|| "failed to read meta tag")?;
+    let meta = match tag {
+        TAG_UNKNOWN => UserMeta::Unknown(Unknown::default()),
+        TAG_ELF => {
+            let path = PathBuf::from(OsStr::from_bytes(read_bytes(data)?));
+            let build_id = match data
+                .read_u8()
+                .ok_or_invalid_data(|| "failed to read build ID marker")?
+            {
+                0 => None,
+                1 => Some(read_bytes(data)?.to_vec()),
+                marker => {
+                    return Err(Error::with_invalid_data(format!(
+                        "encountered invalid build ID marker: {marker}"
+                    )))
+                }
+            };
+            UserMeta::Elf(Elf {
+                path,
+                build_id,
+                _non_exhaustive: (),
+            })
+        }
+        TAG_APK => {
+            let path = PathBuf::from(OsStr::from_bytes(read_bytes(data)?));
+            UserMeta::Apk(Apk {
+                path,
+                _non_exhaustive: (),
+            })
+        }
+        tag => {
+            return Err(Error::with_invalid_data(format!(
+                "encountered unknown meta tag: {tag}"
+            )))
+        }
+    };
+    Ok(meta)
+}
+
+
+impl UserOutput {
+    /// Encode this object into a compact binary representation.
+    ///
+    /// The result can be turned back into a [`UserOutput`] using
+    /// [`UserOutput::decode`], potentially on a different system. The
+    /// format carries a version and is kept stable across releases.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        let () = data.extend_from_slice(&MAGIC);
+        let () = data.extend_from_slice(&VERSION.to_le_bytes());
+        // Flags; reserved for future use.
+        let () = data.extend_from_slice(&0u16.to_le_bytes());
+
+        let () = write_u64_leb128(&mut data, self.meta.len() as u64);
+        for meta in &self.meta {
+            match meta {
+                UserMeta::Unknown(..) => data.push(TAG_UNKNOWN),
+                UserMeta::Elf(Elf { path, build_id, .. }) => {
+                    let () = data.push(TAG_ELF);
+                    let () = write_bytes(&mut data, path.as_os_str().as_bytes());
+                    if let Some(build_id) = build_id {
+                        let () = data.push(1);
+                        let () = write_bytes(&mut data, build_id);
+                    } else {
+                        let () = data.push(0);
+                    }
+                }
+                UserMeta::Apk(Apk { path, .. }) => {
+                    let () = data.push(TAG_APK);
+                    let () = write_bytes(&mut data, path.as_os_str().as_bytes());
+                }
+            }
+        }
+
+        let () = write_u64_leb128(&mut data, self.outputs.len() as u64);
+        for (output, meta_idx) in &self.outputs {
+            let () = write_u64_leb128(&mut data, *output);
+            let () = write_u64_leb128(&mut data, *meta_idx as u64);
+        }
+        data
+    }
+
+    /// Decode a [`UserOutput`] from data produced by
+    /// [`UserOutput::encode`].
+    ///
+    /// Data produced by a newer, unsupported version of the format is
+    /// rejected with an [`ErrorKind::Unsupported`][crate::ErrorKind::Unsupported]
+    /// error. Malformed data results in an
+    /// [`ErrorKind::InvalidData`][crate::ErrorKind::InvalidData] error.
+    pub fn decode(mut data: &[u8]) -> Result<Self> {
+        let data = &mut data;
+        let magic = data
+            .read_slice(MAGIC.len())
+            .ok_or_invalid_data(|| "failed to read normalized output magic")?;
+        if magic != MAGIC {
+            return Err(Error::with_invalid_data(
+                "data does not contain encoded normalized output",
+            ))
+        }
+
+        let version = data
+            .read_u16()
+            .ok_or_invalid_data(|| "failed to read normalized output version")?;
+        let version = u16::from_le(version);
+        if version > VERSION {
+            return Err(Error::with_unsupported(format!(
+                "normalized output version {version} is not supported (maximum supported: {VERSION})"
+            )))
+        }
+        let flags = data
+            .read_u16()
+            .ok_or_invalid_data(|| "failed to read normalized output flags")?;
+        let _flags = u16::from_le(flags);
+
+        let count = read_usize(data)?;
+        // Cap the pre-allocation so that bogus counts cannot cause
+        // excessive allocations; each entry occupies at least one byte.
+        let mut meta = Vec::with_capacity(count.min(data.len()));
+        for _ in 0..count {
+            let () = meta.push(read_meta(data)?);
+        }
+
+        let count = read_usize(data)?;
+        let mut outputs = Vec::with_capacity(count.min(data.len()));
+        for _ in 0..count {
+            let (output, _bytes) = data
+                .read_u64_leb128()
+                .ok_or_invalid_data(|| "failed to read normalized output")?;
+            let meta_idx = read_usize(data)?;
+            if meta_idx >= meta.len() {
+                return Err(Error::with_invalid_data(format!(
+                    "meta index {meta_idx} is out of bounds ({} entries)",
+                    meta.len()
+                )))
+            }
+            let () = outputs.push((output, meta_idx));
+        }
+
+        if !data.is_empty() {
+            return Err(Error::with_invalid_data(
+                "encountered trailing data after normalized output",
+            ))
+        }
+
+        Ok(Self { outputs, meta })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+    use crate::ErrorKind;
+
+
+    fn output() -> UserOutput {
+        UserOutput {
+            outputs: vec![(0x1234, 1), (u64::MAX, 0), (0, 2), (0x42, 1)],
+            meta: vec![
+                UserMeta::Unknown(Unknown::default()),
+                UserMeta::Elf(Elf {
+                    path: PathBuf::from("/usr/lib/libc.so.6"),
+                    build_id: Some(vec![0xde, 0xad, 0xbe, 0xef]),
+                    _non_exhaustive: (),
+                }),
+                UserMeta::Apk(Apk {
+                    path: PathBuf::from("/data/app/base.apk"),
+                    _non_exhaustive: (),
+                }),
+                UserMeta::Elf(Elf {
+                    path: PathBuf::from("/bin/true"),
+                    build_id: None,
+                    _non_exhaustive: (),
+                }),
+            ],
+        }
+    }
+
+
+    /// Check that we can round trip a `UserOutput` through its binary
+    /// representation.
+    #[test]
+    fn encode_decode_roundtrip() {
+        let output = output();
+        let data = output.encode();
+        let decoded = UserOutput::decode(&data).unwrap();
+        assert_eq!(decoded.outputs, output.outputs);
+        assert_eq!(decoded.meta, output.meta);
+
+        let empty = UserOutput {
+            outputs: Vec::new(),
+            meta: Vec::new(),
+        };
+        let decoded = UserOutput::decode(&empty.encode()).unwrap();
+        assert!(decoded.outputs.is_empty());
+        assert!(decoded.meta.is_empty());
+    }
+
+    /// Make sure that we reject data without the expected magic.
+    #[test]
+    fn decode_bad_magic() {
+        let mut data = output().encode();
+        data[0] = b'X';
+        let err = UserOutput::decode(&data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    /// Check that data from a future format version is reported as
+    /// unsupported.
+    #[test]
+    fn decode_future_version() {
+        let mut data = output().encode();
+        data[4..6].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        let err = UserOutput::decode(&data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(err.to_string().contains("version 2"), "{err}");
+    }
+
+    /// Make sure that truncated and otherwise malformed data is
+    /// rejected gracefully.
+    #[test]
+    fn decode_malformed() {
+        let data = output().encode();
+        for len in 0..data.len() {
+            let err = UserOutput::decode(&data[..len]).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData, "{len}");
+        }
+
+        let mut data = data;
+        let () = data.push(0);
+        let err = UserOutput::decode(&data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let output = UserOutput {
+            outputs: vec![(0x1234, 1)],
+            meta: vec![UserMeta::Unknown(Unknown::default())],
+        };
+        let err = UserOutput::decode(&output.encode()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().contains("out of bounds"), "{err}");
+    }
+}
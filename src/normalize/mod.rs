@@ -39,7 +39,9 @@
 //! ```
 
 pub(crate) mod buildid;
+mod codec;
 mod meta;
+pub(crate) mod module_id;
 mod normalizer;
 mod user;
 
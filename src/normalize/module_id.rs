@@ -0,0 +1,181 @@
+//! Computation of stable module identifiers.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fs::metadata;
+use std::os::unix::ffi::OsStrExt as _;
+use std::os::unix::fs::MetadataExt as _;
+use std::path::Path;
+
+use crate::util::hex_encode;
+use crate::Result;
+
+use super::buildid::read_elf_build_id;
+
+
+/// The version of the module identifier algorithm.
+const VERSION: u32 = 1;
+
+/// The FNV-1a 64 bit offset basis.
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+/// The FNV-1a 64 bit prime.
+const FNV_PRIME: u64 = 0x100000001b3;
+
+
+/// Feed `data` into the FNV-1a hash `hash`.
+fn fnv1a(hash: u64, data: &[u8]) -> u64 {
+    data.iter().fold(hash, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+
+/// A stable identifier of a module, suitable for use as a cache key.
+///
+/// Objects of this type are created via [`module_id`].
+///
+/// # Algorithm
+///
+/// The current algorithm version is 1. A module identifier is a string
+/// of one of the following forms:
+///
+/// - `v1:build-id:<build ID>`, if the ELF file has a build ID, which is
+///   rendered as a lower case hexadecimal string
+/// - `v1:file:<hash>` otherwise, where `<hash>` is a 64 bit FNV-1a hash
+///   rendered as 16 lower case hexadecimal digits
+///
+/// The hash is calculated over, in this order:
+///
+/// | Size      | Contents                                           |
+/// |-----------|----------------------------------------------------|
+/// | variable  | The path to the file, as provided                  |
+/// | 1         | A zero byte                                        |
+/// | 8         | The file size, little endian                       |
+/// | 8         | The modification time: seconds since epoch, little endian |
+/// | 4         | The modification time: nanoseconds, little endian  |
+///
+/// Should the algorithm ever change, the version will be bumped, so
+/// that identifiers produced by different versions never compare equal.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ModuleId(String);
+
+impl ModuleId {
+    /// Retrieve the textual representation of the identifier.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for ModuleId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.0)
+    }
+}
+
+
+/// Compute a stable identifier for the ELF file located at the given
+/// path.
+///
+/// The file's build ID is used if present, as it identifies the
+/// module irrespective of its location. Otherwise the identifier is
+/// derived from the path as well as the file's size and modification
+/// time, meaning that it changes whenever the file gets replaced. For
+/// such files, callers should provide paths in a canonical form to
+/// get consistent identifiers.
+///
+/// The algorithm is versioned and identifiers are reproducible across
+/// blazesym versions using the same algorithm version. Please refer to
+/// the documentation of [`ModuleId`] for details.
+///
+/// ```
+/// # use std::path::Path;
+/// # let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+/// #     .join("data")
+/// #     .join("libtest-so.so");
+/// let id = blazesym::helper::module_id(&path).unwrap();
+/// assert!(id.as_str().starts_with("v1:build-id:"));
+/// ```
+pub fn module_id<P>(path: &P) -> Result<ModuleId>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let id = if let Some(build_id) = read_elf_build_id(&path)? {
+        format!("v{VERSION}:build-id:{}", hex_encode(&build_id))
+    } else {
+        let meta = metadata(path)?;
+        let hash = fnv1a(FNV_OFFSET_BASIS, path.as_os_str().as_bytes());
+        let hash = fnv1a(hash, &[0]);
+        let hash = fnv1a(hash, &meta.size().to_le_bytes());
+        let hash = fnv1a(hash, &meta.mtime().to_le_bytes());
+        let hash = fnv1a(hash, &(meta.mtime_nsec() as u32).to_le_bytes());
+        format!("v{VERSION}:file:{hash:016x}")
+    };
+    Ok(ModuleId(id))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::copy;
+
+    use tempfile::tempdir;
+    use test_log::test;
+
+    use crate::ErrorKind;
+
+
+    /// Make sure that our FNV-1a implementation produces the reference
+    /// values.
+    #[test]
+    fn fnv1a_hashing() {
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"foobar"), 0x85944171f73967e8);
+        assert_eq!(
+            fnv1a(fnv1a(FNV_OFFSET_BASIS, b"foo"), b"bar"),
+            fnv1a(FNV_OFFSET_BASIS, b"foobar")
+        );
+    }
+
+    /// Check that we compute module identifiers as expected.
+    #[test]
+    fn module_id_computation() {
+        let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+
+        let path = data.join("libtest-so.so");
+        let build_id = read_elf_build_id(&path).unwrap().unwrap();
+        let id = module_id(&path).unwrap();
+        assert_eq!(
+            id.as_str(),
+            format!("v1:build-id:{}", hex_encode(&build_id))
+        );
+        assert_eq!(id.to_string(), id.as_str());
+
+        // The build ID identifies the module, wherever it is located.
+        let dir = tempdir().unwrap();
+        let tmp = dir.path().join("libtest-so.so");
+        let _size = copy(&path, &tmp).unwrap();
+        assert_eq!(module_id(&tmp).unwrap(), id);
+
+        // Without a build ID, the identifier is derived from file
+        // meta data.
+        let path = data.join("test-stable-addresses.bin");
+        let id = module_id(&path).unwrap();
+        let hash = id.as_str().strip_prefix("v1:file:").unwrap();
+        assert_eq!(hash.len(), 16);
+        assert!(hash.bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_eq!(module_id(&path).unwrap(), id);
+
+        let tmp = dir.path().join("test-stable-addresses.bin");
+        let _size = copy(&path, &tmp).unwrap();
+        assert_ne!(module_id(&tmp).unwrap(), id);
+
+        let err = module_id(&data.join("does-not-exist")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+}
@@ -3,6 +3,7 @@ use crate::Addr;
 use crate::Pid;
 use crate::Result;
 
+use super::buildid::BuildIdCache;
 use super::user::normalize_user_addrs_sorted_impl;
 use super::user::UserOutput;
 
@@ -114,7 +115,7 @@ impl Normalizer {
     /// non-normalized ones were provided.
     #[cfg_attr(feature = "tracing", crate::log::instrument(skip(self)))]
     pub fn normalize_user_addrs_sorted(&self, addrs: &[Addr], pid: Pid) -> Result<UserOutput> {
-        normalize_user_addrs_sorted_impl(addrs.iter().copied(), pid, self.build_ids)
+        normalize_user_addrs_sorted_impl(addrs.iter().copied(), pid, self.build_ids, None)
     }
 
 
@@ -125,15 +126,52 @@ impl Normalizer {
     /// does not have to be sorted, but otherwise the functions behave
     /// identically. If you do happen to know that `addrs` is sorted, using
     /// [`Normalizer::normalize_user_addrs_sorted`] instead will result in
-    /// slightly faster normalization.
+    /// slightly faster normalization. Sorted input is detected, though,
+    /// and internal sorting skipped for it.
     #[cfg_attr(feature = "tracing", crate::log::instrument(skip(self)))]
     pub fn normalize_user_addrs(&self, addrs: &[Addr], pid: Pid) -> Result<UserOutput> {
         util::with_ordered_elems(
             addrs,
             |normalized: &mut UserOutput| normalized.outputs.as_mut_slice(),
-            |sorted_addrs| normalize_user_addrs_sorted_impl(sorted_addrs, pid, self.build_ids),
+            |sorted_addrs| {
+                normalize_user_addrs_sorted_impl(sorted_addrs, pid, self.build_ids, None)
+            },
         )
     }
+
+    /// Normalize addresses belonging to multiple processes.
+    ///
+    /// This method behaves as if [`Normalizer::normalize_user_addrs`]
+    /// were invoked for each `(pid, addrs)` pair in `inputs`, but files
+    /// mapped into multiple processes (such as `libc`) are only parsed
+    /// once for the purpose of reading their build IDs. As such, it is
+    /// the preferred way for normalizing addresses captured system wide.
+    ///
+    /// Results are reported in the order of `inputs`. An error
+    /// normalizing addresses of any of the processes (e.g., because it
+    /// exited already) causes the entire operation to fail.
+    #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all))]
+    pub fn normalize_user_addrs_batch(&self, inputs: &[(Pid, &[Addr])]) -> Result<Vec<UserOutput>> {
+        let mut build_id_cache = BuildIdCache::default();
+
+        inputs
+            .iter()
+            .map(|(pid, addrs)| {
+                util::with_ordered_elems(
+                    addrs,
+                    |normalized: &mut UserOutput| normalized.outputs.as_mut_slice(),
+                    |sorted_addrs| {
+                        normalize_user_addrs_sorted_impl(
+                            sorted_addrs,
+                            *pid,
+                            self.build_ids,
+                            Some(&mut build_id_cache),
+                        )
+                    },
+                )
+            })
+            .collect()
+    }
 }
 
 
@@ -235,6 +273,39 @@ mod tests {
             .contains("libc.so"));
     }
 
+    /// Check that batch normalization of addresses from multiple
+    /// processes produces the same results as individual normalization.
+    #[test]
+    fn user_address_batch_normalization() {
+        let addrs1 = [
+            libc::__errno_location as *const () as Addr,
+            user_address_normalization as *const () as Addr,
+            0x500,
+        ];
+        let addrs2 = [
+            libc::fopen as *const () as Addr,
+            libc::dlopen as *const () as Addr,
+        ];
+
+        let normalizer = Normalizer::new();
+        let inputs = [
+            (Pid::Slf, addrs1.as_slice()),
+            (Pid::Slf, addrs2.as_slice()),
+            (Pid::Slf, [].as_slice()),
+        ];
+        let batch = normalizer.normalize_user_addrs_batch(&inputs).unwrap();
+        assert_eq!(batch.len(), inputs.len());
+
+        for ((pid, addrs), normalized) in inputs.iter().zip(batch) {
+            let expected = normalizer.normalize_user_addrs(addrs, *pid).unwrap();
+            assert_eq!(normalized.outputs, expected.outputs);
+            assert_eq!(normalized.meta, expected.meta);
+        }
+
+        let batch = normalizer.normalize_user_addrs_batch(&[]).unwrap();
+        assert!(batch.is_empty());
+    }
+
     /// Check that we can normalize user addresses in our own shared object.
     #[test]
     fn user_address_normalization_custom_so() {
@@ -313,6 +384,7 @@ mod tests {
             let opts = FindAddrOpts {
                 sym_type: SymType::Function,
                 offset_in_file: true,
+                ..Default::default()
             };
             let syms = elf_parser.find_addr("the_answer", &opts).unwrap();
             // There is only one symbol with this address in there.
@@ -12,7 +12,7 @@ use crate::Addr;
 use crate::Pid;
 use crate::Result;
 
-use super::buildid::BuildIdFn;
+use super::buildid::BuildIdCache;
 use super::buildid::BuildIdReader;
 use super::buildid::DefaultBuildIdReader;
 use super::buildid::NoBuildIdReader;
@@ -24,7 +24,10 @@ use super::normalizer::Output;
 
 
 /// Make a [`UserMeta::Elf`] variant.
-fn make_elf_meta(entry: &PathMapsEntry, get_build_id: &BuildIdFn) -> Result<UserMeta> {
+fn make_elf_meta<F>(entry: &PathMapsEntry, get_build_id: F) -> Result<UserMeta>
+where
+    F: FnOnce(&Path) -> Result<Option<Vec<u8>>>,
+{
     let elf = Elf {
         path: entry.path.symbolic_path.to_path_buf(),
         build_id: get_build_id(&entry.path.maps_file)?,
@@ -110,7 +113,7 @@ pub(crate) trait Handler {
 }
 
 
-struct NormalizationHandler<R> {
+struct NormalizationHandler<'cache, R> {
     /// The user output we are building up.
     normalized: UserOutput,
     /// Lookup table from path (as used in each proc maps entry) to index into
@@ -119,13 +122,24 @@ struct NormalizationHandler<R> {
     /// The index of the `Unknown` entry in `meta_lookup`, used for all unknown
     /// addresses.
     unknown_idx: Option<usize>,
+    /// An optional cache of build IDs, shared with other handlers.
+    build_id_cache: Option<&'cache mut BuildIdCache>,
     #[doc(hidden)]
     _phanton: PhantomData<R>,
 }
 
-impl<R> NormalizationHandler<R> {
+impl<'cache, R> NormalizationHandler<'cache, R> {
     /// Instantiate a new `NormalizationHandler` object.
     fn new(addr_cnt: usize) -> Self {
+        Self::with_build_id_cache(addr_cnt, None)
+    }
+
+    /// Instantiate a new `NormalizationHandler` object using the
+    /// provided build ID cache.
+    fn with_build_id_cache(
+        addr_cnt: usize,
+        build_id_cache: Option<&'cache mut BuildIdCache>,
+    ) -> Self {
         Self {
             normalized: UserOutput {
                 outputs: Vec::with_capacity(addr_cnt),
@@ -133,12 +147,13 @@ impl<R> NormalizationHandler<R> {
             },
             meta_lookup: HashMap::<PathBuf, usize>::new(),
             unknown_idx: None,
+            build_id_cache,
             _phanton: PhantomData,
         }
     }
 }
 
-impl<R> Handler for NormalizationHandler<R>
+impl<R> Handler for NormalizationHandler<'_, R>
 where
     R: BuildIdReader,
 {
@@ -162,12 +177,21 @@ where
                 &mut self.meta_lookup,
                 || make_apk_meta(entry),
             ),
-            _ => self.normalized.add_normalized_offset(
-                file_off,
-                &entry.path.symbolic_path,
-                &mut self.meta_lookup,
-                || make_elf_meta(entry, &R::read_build_id_from_elf),
-            ),
+            _ => {
+                let build_id_cache = &mut self.build_id_cache;
+                self.normalized.add_normalized_offset(
+                    file_off,
+                    &entry.path.symbolic_path,
+                    &mut self.meta_lookup,
+                    || {
+                        if let Some(cache) = build_id_cache {
+                            make_elf_meta(entry, |path| cache.get_or_read::<R>(path))
+                        } else {
+                            make_elf_meta(entry, R::read_build_id_from_elf)
+                        }
+                    },
+                )
+            }
         }
     }
 }
@@ -255,10 +279,13 @@ where
 ///
 /// File offsets are reported in the exact same order in which the
 /// non-normalized addresses ones were provided.
+///
+/// If provided, build IDs are read through `build_id_cache`.
 pub(super) fn normalize_user_addrs_sorted_impl<A>(
     addrs: A,
     pid: Pid,
     read_build_ids: bool,
+    build_id_cache: Option<&mut BuildIdCache>,
 ) -> Result<UserOutput>
 where
     A: ExactSizeIterator<Item = Addr> + Clone,
@@ -267,7 +294,10 @@ where
     let entries = maps::parse(pid)?;
 
     if read_build_ids {
-        let handler = NormalizationHandler::<DefaultBuildIdReader>::new(addrs_cnt);
+        let handler = NormalizationHandler::<DefaultBuildIdReader>::with_build_id_cache(
+            addrs_cnt,
+            build_id_cache,
+        );
         let handler = normalize_sorted_user_addrs_with_entries(addrs, entries, handler)?;
         debug_assert_eq!(handler.normalized.outputs.len(), addrs_cnt);
         Ok(handler.normalized)
@@ -0,0 +1,291 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fs::File;
+use std::io::BufRead as _;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::inspect::FindAddrOpts;
+use crate::inspect::SymInfo;
+use crate::inspect::SymType;
+use crate::log::debug;
+use crate::symbolize::AddrCodeInfo;
+use crate::symbolize::IntSym;
+use crate::symbolize::SrcLang;
+use crate::Addr;
+use crate::Result;
+use crate::SymResolver;
+
+
+/// A symbol as listed in a perf map file.
+#[derive(Debug, PartialEq)]
+struct PerfMapSym {
+    addr: Addr,
+    size: u64,
+    name: String,
+}
+
+impl PerfMapSym {
+    #[inline]
+    fn contains(&self, addr: Addr) -> bool {
+        addr >= self.addr && addr - self.addr < self.size
+    }
+}
+
+impl<'sym> From<&'sym PerfMapSym> for IntSym<'sym> {
+    fn from(other: &'sym PerfMapSym) -> Self {
+        let PerfMapSym { addr, size, name } = other;
+        IntSym {
+            name,
+            addr: *addr,
+            size: usize::try_from(*size).ok(),
+            // Perf maps don't carry any source code language
+            // information.
+            lang: SrcLang::Unknown,
+        }
+    }
+}
+
+
+/// Parse a hexadecimal number, with an optional `0x` prefix.
+fn parse_hex(s: &str) -> Option<u64> {
+    let s = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    u64::from_str_radix(s, 16).ok()
+}
+
+/// Parse a single perf map line of the form `START SIZE NAME`.
+///
+/// `START` and `SIZE` are hexadecimal numbers. `NAME` is the remainder
+/// of the line and may contain whitespace.
+fn parse_line(line: &str) -> Option<PerfMapSym> {
+    let line = line.trim();
+    let (addr, rest) = line.split_once(char::is_whitespace)?;
+    let (size, name) = rest.trim_start().split_once(char::is_whitespace)?;
+    let name = name.trim_start();
+    if name.is_empty() {
+        return None
+    }
+
+    let sym = PerfMapSym {
+        addr: parse_hex(addr)?,
+        size: parse_hex(size)?,
+        name: name.to_string(),
+    };
+    Some(sym)
+}
+
+/// Parse perf map data from the provided reader.
+///
+/// Malformed lines are skipped. If multiple symbols start at the same
+/// address, the one listed last wins, as JIT compilers may reuse
+/// memory for newly compiled code.
+fn parse_perf_map<R>(reader: R) -> Result<Vec<PerfMapSym>>
+where
+    R: Read,
+{
+    let mut syms = Vec::new();
+    for (idx, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue
+        }
+
+        match parse_line(&line) {
+            Some(sym) => syms.push(sym),
+            None => debug!("skipping malformed perf map line {}: {line}", idx + 1),
+        }
+    }
+
+    // Sort stably so that among symbols starting at the same address
+    // the one listed last ends up last, and then keep only that one.
+    let () = syms.sort_by_key(|sym| sym.addr);
+    let () = syms.reverse();
+    let () = syms.dedup_by_key(|sym| sym.addr);
+    let () = syms.reverse();
+    Ok(syms)
+}
+
+
+/// The symbol resolver for perf map files.
+///
+/// Perf map files (conventionally `/tmp/perf-<pid>.map`) are written by
+/// JIT compilers and runtimes such as the JVM or V8 and list symbols for
+/// generated code, one per line, in the form `START SIZE NAME`. Addresses
+/// are absolute runtime addresses.
+pub(crate) struct PerfMapResolver {
+    syms: Vec<PerfMapSym>,
+    file_name: PathBuf,
+}
+
+impl PerfMapResolver {
+    /// Create a `PerfMapResolver` that loads data from the provided file.
+    pub(crate) fn from_file(path: PathBuf, file: &File) -> Result<Self> {
+        let syms = parse_perf_map(file)?;
+        let slf = Self {
+            syms,
+            file_name: path,
+        };
+        Ok(slf)
+    }
+
+    /// Find the symbol containing the provided address.
+    fn find_perf_sym(&self, addr: Addr) -> Option<&PerfMapSym> {
+        let idx = self.syms.partition_point(|sym| sym.addr <= addr);
+        let sym = self.syms.get(idx.checked_sub(1)?)?;
+        sym.contains(addr).then_some(sym)
+    }
+
+    /// Retrieve the path to the perf map file used by this resolver.
+    #[allow(dead_code)]
+    pub(crate) fn file_name(&self) -> &Path {
+        &self.file_name
+    }
+}
+
+impl SymResolver for PerfMapResolver {
+    fn find_sym(&self, addr: Addr) -> Result<Option<IntSym<'_>>> {
+        let sym = self.find_perf_sym(addr).map(IntSym::from);
+        Ok(sym)
+    }
+
+    fn find_addr<'slf>(&'slf self, name: &str, opts: &FindAddrOpts) -> Result<Vec<SymInfo<'slf>>> {
+        if let SymType::Variable = opts.sym_type {
+            return Ok(Vec::new())
+        }
+
+        let syms = self
+            .syms
+            .iter()
+            .filter(|sym| sym.name == name)
+            .map(|sym| SymInfo {
+                name: Cow::Borrowed(&sym.name),
+                demangled_name: None,
+                addr: sym.addr,
+                size: usize::try_from(sym.size).unwrap_or(usize::MAX),
+                end: sym.addr.checked_add(sym.size),
+                sym_type: SymType::Function,
+                // Generated code is not backed by a file.
+                file_offset: None,
+                obj_file_name: Some(Cow::Borrowed(&self.file_name)),
+            })
+            .take(opts.limit.unwrap_or(usize::MAX))
+            .collect();
+        Ok(syms)
+    }
+
+    fn find_code_info(
+        &self,
+        _addr: Addr,
+        _inlined_fns: bool,
+        _line_only: bool,
+    ) -> Result<Option<AddrCodeInfo<'_>>> {
+        Ok(None)
+    }
+}
+
+impl Debug for PerfMapResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "PerfMap {}", self.file_name.display())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write as _;
+
+    use tempfile::NamedTempFile;
+    use test_log::test;
+
+
+    /// Check that we can parse individual perf map lines.
+    #[test]
+    fn line_parsing() {
+        let sym = parse_line("7f1234 10 LazyCompile:~foo bar.js:1").unwrap();
+        assert_eq!(
+            sym,
+            PerfMapSym {
+                addr: 0x7f1234,
+                size: 0x10,
+                name: "LazyCompile:~foo bar.js:1".to_string(),
+            }
+        );
+
+        let sym = parse_line("0x1000\t0x20   java.lang.String::hashCode  ").unwrap();
+        assert_eq!(sym.addr, 0x1000);
+        assert_eq!(sym.size, 0x20);
+        assert_eq!(sym.name, "java.lang.String::hashCode");
+
+        assert_eq!(parse_line(""), None);
+        assert_eq!(parse_line("1000"), None);
+        assert_eq!(parse_line("1000 20"), None);
+        assert_eq!(parse_line("1000 20 "), None);
+        assert_eq!(parse_line("xyz 20 foo"), None);
+        assert_eq!(parse_line("1000 -1 foo"), None);
+    }
+
+    /// Check that we can symbolize addresses using a perf map file,
+    /// skipping over malformed lines.
+    #[test]
+    fn perf_map_symbolization() {
+        let mut file = NamedTempFile::new().unwrap();
+        let () = writeln!(
+            file,
+            "\
+3000 100 third
+this is garbage
+1000 10 first
+2000 10 stale
+2000 20 second
+
+ffffffffffffff00 100 huge"
+        )
+        .unwrap();
+
+        let path = file.path().to_path_buf();
+        let resolver = PerfMapResolver::from_file(path, &File::open(file.path()).unwrap()).unwrap();
+        assert_ne!(format!("{resolver:?}"), "");
+        assert_eq!(resolver.file_name(), file.path());
+
+        let sym = resolver.find_sym(0x1000).unwrap().unwrap();
+        assert_eq!(sym.name, "first");
+        assert_eq!(sym.addr, 0x1000);
+        assert_eq!(sym.size, Some(0x10));
+
+        let sym = resolver.find_sym(0x100f).unwrap().unwrap();
+        assert_eq!(sym.name, "first");
+        assert!(resolver.find_sym(0x1010).unwrap().is_none());
+        assert!(resolver.find_sym(0xfff).unwrap().is_none());
+
+        // The later definition at the same address wins.
+        let sym = resolver.find_sym(0x2018).unwrap().unwrap();
+        assert_eq!(sym.name, "second");
+
+        let sym = resolver.find_sym(0x30ff).unwrap().unwrap();
+        assert_eq!(sym.name, "third");
+
+        let sym = resolver.find_sym(u64::MAX).unwrap().unwrap();
+        assert_eq!(sym.name, "huge");
+
+        let syms = resolver
+            .find_addr("second", &FindAddrOpts::default())
+            .unwrap();
+        assert_eq!(syms.len(), 1);
+        assert_eq!(syms[0].addr, 0x2000);
+        assert_eq!(syms[0].end, Some(0x2020));
+
+        let syms = resolver
+            .find_addr("stale", &FindAddrOpts::default())
+            .unwrap();
+        assert!(syms.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 
+use crate::elf::ElfSymSource;
 use crate::inspect::FindAddrOpts;
 use crate::inspect::SymInfo;
 use crate::symbolize::AddrCodeInfo;
@@ -18,8 +19,27 @@ where
 {
     /// Find the symbol corresponding to the given address.
     fn find_sym(&self, addr: Addr) -> Result<Option<IntSym<'_>>>;
+    /// Find the symbol corresponding to the given address, consulting
+    /// only the symbol sources `sources`, in the given order.
+    ///
+    /// The default implementation, as used by resolvers not based on
+    /// ELF files, disregards `sources`.
+    fn find_sym_in(&self, addr: Addr, sources: &[ElfSymSource]) -> Result<Option<IntSym<'_>>> {
+        let _sources = sources;
+        self.find_sym(addr)
+    }
     /// Find information about a symbol given its name.
     fn find_addr(&self, name: &str, opts: &FindAddrOpts) -> Result<Vec<SymInfo<'_>>>;
+    /// Find information about a list of symbols given their names.
+    ///
+    /// Results are reported in the order of `names`, with an empty list
+    /// for each name that could not be found.
+    fn find_addrs(&self, names: &[&str], opts: &FindAddrOpts) -> Result<Vec<Vec<SymInfo<'_>>>> {
+        names
+            .iter()
+            .map(|name| self.find_addr(name, opts))
+            .collect()
+    }
     /// Finds the source code location for a given address.
     ///
     /// This function tries to find source code information for the given
@@ -27,5 +47,16 @@ where
     /// `inlined_fns` is true, information about inlined calls at the very
     /// address will also be looked up and reported as the optional
     /// [`AddrCodeInfo::inlined`] attribute.
-    fn find_code_info(&self, addr: Addr, inlined_fns: bool) -> Result<Option<AddrCodeInfo<'_>>>;
+    ///
+    /// If `line_only` is true, only the source code location of `addr`
+    /// itself is reported. The lookup of the containing function
+    /// ([`AddrCodeInfo::function`]) and of inlined functions is skipped
+    /// (irrespective of `inlined_fns`), which may be considerably
+    /// cheaper.
+    fn find_code_info(
+        &self,
+        addr: Addr,
+        inlined_fns: bool,
+        line_only: bool,
+    ) -> Result<Option<AddrCodeInfo<'_>>>;
 }
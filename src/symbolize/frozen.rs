@@ -0,0 +1,623 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use crate::elf::types::PT_LOAD;
+use crate::elf::types::SHF_ALLOC;
+use crate::elf::ElfResolver;
+use crate::elf::ElfSymSource;
+use crate::inspect::FindAddrOpts;
+use crate::inspect::SymType;
+use crate::Addr;
+use crate::Error;
+use crate::Result;
+use crate::SymResolver as _;
+
+use super::CodeInfo;
+use super::Input;
+use super::LineFlags;
+use super::SrcLang;
+use super::Sym;
+use super::Symbolized;
+
+#[cfg(doc)]
+use super::Symbolizer;
+
+
+/// Flags controlling which information
+/// [`FrozenResolver::resolve_full`] reports.
+///
+/// By default, all information is requested.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResolveFlags {
+    /// Whether to report the symbol.
+    pub sym: bool,
+    /// Whether to report the name of the section containing the
+    /// address.
+    pub section: bool,
+    /// Whether to report source code information.
+    pub code_info: bool,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl Default for ResolveFlags {
+    fn default() -> Self {
+        Self {
+            sym: true,
+            section: true,
+            code_info: true,
+            _non_exhaustive: (),
+        }
+    }
+}
+
+
+/// The combined result of a [`FrozenResolver::resolve_full`] lookup.
+///
+/// Members that were not requested via [`ResolveFlags`] are always
+/// `None`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FullResolution<'res> {
+    /// The symbol the input resolved to, if any.
+    pub sym: Option<Sym<'res>>,
+    /// The name of the section containing the input, if any.
+    pub section: Option<&'res str>,
+    /// Source code information for the input, if available.
+    pub code_info: Option<CodeInfo<'res>>,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// Options controlling what information a [`FrozenResolver`] captures.
+#[derive(Debug)]
+pub(crate) struct FreezeOpts<'src> {
+    /// The symbol sources to consult, in order, if not the resolver's
+    /// default ones.
+    pub sym_sources: Option<&'src [ElfSymSource]>,
+    /// Whether the file does not have the expected build ID.
+    pub build_id_mismatch: bool,
+    /// Whether to capture source code information.
+    pub code_info: bool,
+    /// Whether to capture line table row flags.
+    pub line_flags: bool,
+    /// Whether to check for the existence of source files.
+    pub check_source_files: bool,
+    /// The maximum offset of an address from the start of its symbol.
+    pub max_sym_offset: Option<u64>,
+}
+
+
+/// A function symbol captured by a [`FrozenResolver`].
+#[derive(Clone, Debug)]
+struct FrozenSym {
+    /// The (potentially demangled) name of the symbol.
+    name: Box<str>,
+    /// The raw name of the symbol, if it differs from `name`.
+    mangled_name: Option<Box<str>>,
+    addr: Addr,
+    size: Option<usize>,
+}
+
+
+/// A source file referenced by the line table of a
+/// [`FrozenResolver`].
+#[derive(Clone, Debug)]
+struct FrozenFile {
+    dir: Option<PathBuf>,
+    file: OsString,
+    md5: Option<[u8; 16]>,
+    exists: Option<bool>,
+}
+
+
+/// A line table row captured by a [`FrozenResolver`].
+#[derive(Clone, Debug)]
+struct FrozenLine {
+    /// The address range covered by the row.
+    range: Range<Addr>,
+    /// The index of the row's source file in
+    /// [`FrozenResolver::files`].
+    file: usize,
+    line: Option<u32>,
+    column: Option<u16>,
+    flags: Option<LineFlags>,
+}
+
+
+/// A loadable segment, used for mapping file offsets to addresses.
+#[derive(Clone, Debug)]
+struct Segment {
+    /// The file offsets backing the segment.
+    offsets: Range<u64>,
+    /// The virtual address of the segment.
+    addr: Addr,
+}
+
+
+/// A read-only snapshot of the symbol and line tables of an ELF file.
+///
+/// A `FrozenResolver` is created by [`Symbolizer::freeze`], which
+/// eagerly parses the function symbols and line tables of a file and
+/// captures them. The object does not hold any interior mutable state
+/// and owns all of its data, meaning that it is [`Send`] and [`Sync`]
+/// and lookups can be performed concurrently from many threads without
+/// any locking.
+///
+/// Arbitrary addresses can be looked up. However, inlined functions and
+/// source code context are not captured and never reported.
+#[derive(Clone, Debug)]
+pub struct FrozenResolver {
+    /// Function symbols, sorted by address, with at most one symbol
+    /// per address.
+    syms: Box<[FrozenSym]>,
+    /// The source files referenced by `lines`.
+    files: Box<[FrozenFile]>,
+    /// Line table rows, sorted by start address.
+    lines: Box<[FrozenLine]>,
+    /// The address ranges and names of all allocated sections.
+    sections: Box<[(Range<Addr>, Box<str>)]>,
+    /// The loadable segments of the file.
+    segments: Box<[Segment]>,
+    /// The maximum offset of an address from the start of its symbol.
+    max_sym_offset: Option<u64>,
+    /// Whether the file does not have the expected build ID, in which
+    /// case nothing is captured.
+    build_id_mismatch: bool,
+}
+
+impl FrozenResolver {
+    /// Capture the symbol and line tables of the ELF file represented
+    /// by `resolver`.
+    ///
+    /// `demangle` is used for converting raw symbol names into the
+    /// names to report.
+    pub(crate) fn from_elf<D>(
+        resolver: &ElfResolver,
+        opts: &FreezeOpts<'_>,
+        demangle: D,
+    ) -> Result<Self>
+    where
+        D: for<'name> Fn(Cow<'name, str>, SrcLang) -> Cow<'name, str>,
+    {
+        let FreezeOpts {
+            sym_sources,
+            build_id_mismatch,
+            code_info,
+            line_flags,
+            check_source_files,
+            max_sym_offset,
+        } = *opts;
+
+        if build_id_mismatch {
+            let slf = Self {
+                syms: Box::default(),
+                files: Box::default(),
+                lines: Box::default(),
+                sections: Box::default(),
+                segments: Box::default(),
+                max_sym_offset,
+                build_id_mismatch,
+            };
+            return Ok(slf)
+        }
+
+        let find_opts = FindAddrOpts {
+            sym_type: SymType::Function,
+            ..Default::default()
+        };
+        let mut addrs = resolver
+            .syms_in_range(0, Addr::MAX, &find_opts)?
+            .into_iter()
+            .map(|sym| sym.addr)
+            .collect::<Vec<_>>();
+        let () = addrs.dedup();
+
+        let mut syms = Vec::with_capacity(addrs.len());
+        for addr in addrs {
+            // We report whatever symbol the resolver reports for the
+            // start address, so that its configured symbol source
+            // preferences are honored.
+            let sym = match sym_sources {
+                Some(sources) => resolver.find_sym_in(addr, sources)?,
+                None => resolver.find_sym(addr)?,
+            };
+            if let Some(sym) = sym.filter(|sym| sym.addr == addr) {
+                let name = demangle(Cow::Borrowed(sym.name), sym.lang);
+                let mangled_name = (name != sym.name).then(|| Box::from(sym.name));
+                let sym = FrozenSym {
+                    name: Box::from(name),
+                    mangled_name,
+                    addr,
+                    size: sym.size,
+                };
+                let () = syms.push(sym);
+            }
+        }
+
+        let mut files = Vec::<FrozenFile>::new();
+        let mut lines = Vec::new();
+        if code_info {
+            let mut file_idxs = HashMap::new();
+            let () = resolver.for_each_line(|range, info| {
+                // Consecutive rows typically reference the same file,
+                // so check the one used last first, to avoid having to
+                // allocate a key for the map.
+                let idx = match lines.last() {
+                    Some(FrozenLine { file, .. })
+                        if files[*file].dir.as_deref() == info.dir.as_deref()
+                            && files[*file].file == info.file =>
+                    {
+                        *file
+                    }
+                    _ => {
+                        let key = (
+                            info.dir.as_deref().map(|dir| dir.to_path_buf()),
+                            info.file.to_os_string(),
+                        );
+                        *file_idxs.entry(key).or_insert_with_key(|(dir, file)| {
+                            let () = files.push(FrozenFile {
+                                dir: dir.clone(),
+                                file: file.clone(),
+                                md5: info.md5,
+                                exists: None,
+                            });
+                            files.len() - 1
+                        })
+                    }
+                };
+
+                let line = FrozenLine {
+                    range,
+                    file: idx,
+                    line: info.line,
+                    column: info.column,
+                    flags: info.flags.filter(|_| line_flags),
+                };
+                let () = lines.push(line);
+            })?;
+            let () = lines.sort_by_key(|line| line.range.start);
+
+            if check_source_files {
+                for file in files.iter_mut() {
+                    let path = file
+                        .dir
+                        .as_ref()
+                        .map_or_else(|| PathBuf::from(&file.file), |dir| dir.join(&file.file));
+                    file.exists = Some(path.exists());
+                }
+            }
+        }
+
+        let parser = resolver.parser();
+        let mut sections = Vec::new();
+        for (idx, shdr) in parser.section_headers()?.iter().enumerate() {
+            if shdr.sh_flags & SHF_ALLOC != 0 && shdr.sh_size != 0 {
+                let range = shdr.sh_addr..shdr.sh_addr.saturating_add(shdr.sh_size);
+                let name = Box::from(parser.section_name(idx)?);
+                let () = sections.push((range, name));
+            }
+        }
+
+        let segments = parser
+            .program_headers()?
+            .iter()
+            .filter(|phdr| phdr.p_type == PT_LOAD)
+            .map(|phdr| Segment {
+                offsets: phdr.p_offset..phdr.p_offset.saturating_add(phdr.p_filesz),
+                addr: phdr.p_vaddr,
+            })
+            .collect();
+
+        let slf = Self {
+            syms: syms.into_boxed_slice(),
+            files: files.into_boxed_slice(),
+            lines: lines.into_boxed_slice(),
+            sections: sections.into_boxed_slice(),
+            segments,
+            max_sym_offset,
+            build_id_mismatch,
+        };
+        Ok(slf)
+    }
+
+    /// Convert `input` into a virtual address, if possible.
+    fn input_addr(&self, input: Input<u64>) -> Result<Option<Addr>> {
+        match input {
+            Input::VirtOffset(addr) => Ok(Some(addr)),
+            Input::AbsAddr(..) => Err(Error::with_unsupported(
+                "ELF symbolization does not support absolute address inputs",
+            )),
+            Input::FileOffset(offset) => {
+                // Only the part of a segment backed by file contents
+                // can be addressed by a file offset.
+                let addr = self.segments.iter().find_map(|segment| {
+                    segment
+                        .offsets
+                        .contains(&offset)
+                        .then(|| offset - segment.offsets.start + segment.addr)
+                });
+                Ok(addr)
+            }
+        }
+    }
+
+    /// Find the function symbol containing `addr`.
+    ///
+    /// Only the closest symbol starting at or before `addr` is
+    /// considered. A symbol without size information is assumed to
+    /// contain all addresses up to the next symbol.
+    fn find_sym(&self, addr: Addr) -> Option<&FrozenSym> {
+        let idx = self
+            .syms
+            .partition_point(|sym| sym.addr <= addr)
+            .checked_sub(1)?;
+        let sym = &self.syms[idx];
+        match sym.size {
+            Some(size) if size != 0 && addr - sym.addr >= size as Addr => None,
+            _ => Some(sym),
+        }
+    }
+
+    /// Find source code information for `addr`.
+    fn find_code_info(&self, addr: Addr) -> Option<CodeInfo<'_>> {
+        let idx = self
+            .lines
+            .partition_point(|line| line.range.start <= addr)
+            .checked_sub(1)?;
+        let line = &self.lines[idx];
+        if !line.range.contains(&addr) {
+            return None
+        }
+
+        let file = &self.files[line.file];
+        let code_info = CodeInfo {
+            dir: file.dir.as_deref().map(Cow::Borrowed),
+            file: Cow::Borrowed(&file.file),
+            line: line.line,
+            column: line.column,
+            md5: file.md5,
+            flags: line.flags,
+            context: None,
+            file_exists: file.exists,
+            _non_exhaustive: (),
+        };
+        Some(code_info)
+    }
+
+    /// Find the name of the section containing `addr`.
+    fn find_section(&self, addr: Addr) -> Option<&str> {
+        self.sections
+            .iter()
+            .find(|(range, _name)| range.contains(&addr))
+            .map(|(_range, name)| &**name)
+    }
+
+    /// Symbolize the virtual address `addr`.
+    fn symbolize_addr(&self, addr: Addr) -> Symbolized<'_> {
+        let sym = match self.find_sym(addr) {
+            Some(sym) => sym,
+            None => return Symbolized::Unknown,
+        };
+
+        if let Some(max_offset) = self.max_sym_offset {
+            if addr - sym.addr > max_offset {
+                return Symbolized::Unknown
+            }
+        }
+
+        let sym = Sym {
+            name: Cow::Borrowed(&sym.name),
+            mangled_name: sym.mangled_name.as_deref().map(Cow::Borrowed),
+            addr: sym.addr,
+            offset: (addr - sym.addr) as usize,
+            size: sym.size,
+            code_info: self.find_code_info(addr),
+            inlined: Box::new([]),
+            inlined_truncated: false,
+            _non_exhaustive: (),
+        };
+        Symbolized::Sym(sym)
+    }
+
+    /// Symbolize a single input address/offset.
+    ///
+    /// Absolute address inputs are not supported.
+    pub fn symbolize_single(&self, input: Input<u64>) -> Result<Symbolized<'_>> {
+        if self.build_id_mismatch {
+            return Ok(Symbolized::BuildIdMismatch)
+        }
+
+        match self.input_addr(input)? {
+            Some(addr) => Ok(self.symbolize_addr(addr)),
+            None => Ok(Symbolized::Unknown),
+        }
+    }
+
+    /// Look up the symbol, section, and source code information for a
+    /// single input address/offset in one go.
+    ///
+    /// Which pieces of information are reported is controlled by
+    /// `flags`. Absolute address inputs are not supported.
+    pub fn resolve_full(
+        &self,
+        input: Input<u64>,
+        flags: ResolveFlags,
+    ) -> Result<FullResolution<'_>> {
+        let ResolveFlags {
+            sym,
+            section,
+            code_info,
+            _non_exhaustive: (),
+        } = flags;
+
+        let addr = match self.input_addr(input)? {
+            Some(addr) if !self.build_id_mismatch => addr,
+            _ => return Ok(FullResolution::default()),
+        };
+
+        let resolution = FullResolution {
+            sym: sym.then(|| self.symbolize_addr(addr).into_sym()).flatten(),
+            section: section.then(|| self.find_section(addr)).flatten(),
+            code_info: code_info.then(|| self.find_code_info(addr)).flatten(),
+            _non_exhaustive: (),
+        };
+        Ok(resolution)
+    }
+
+    /// Symbolize a list of input addresses/offsets.
+    ///
+    /// This method returns exactly one [`Symbolized`] object for each
+    /// input, in the order of inputs. See
+    /// [`symbolize_single`][Self::symbolize_single] for details.
+    pub fn symbolize(&self, input: Input<&[u64]>) -> Result<Vec<Symbolized<'_>>> {
+        input
+            .into_inner()
+            .iter()
+            .map(|addr| self.symbolize_single(input.map(|_| *addr)))
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    use test_log::test;
+
+    use crate::symbolize::Elf;
+    use crate::symbolize::Source;
+    use crate::symbolize::Symbolizer;
+    use crate::ErrorKind;
+
+
+    /// Make sure that `FrozenResolver` can be shared between threads.
+    #[test]
+    fn send_sync() {
+        fn test<T: Send + Sync>() {}
+
+        let () = test::<FrozenResolver>();
+    }
+
+    /// Check that we can look up arbitrary addresses in a frozen ELF
+    /// file.
+    #[test]
+    fn lookup() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let src = Source::Elf(Elf::new(path));
+        let frozen = Symbolizer::new().freeze(&src).unwrap();
+
+        for addr in [0x2000100, 0x2000105, 0x2000100 + 0x2b - 1] {
+            let sym = frozen
+                .symbolize_single(Input::VirtOffset(addr))
+                .unwrap()
+                .into_sym()
+                .unwrap();
+            assert_eq!(sym.name, "factorial");
+            assert_eq!(sym.addr, 0x2000100);
+            assert_eq!(sym.offset, (addr - 0x2000100) as usize);
+            assert_eq!(sym.size, Some(0x2b));
+            let code_info = sym.code_info.unwrap();
+            assert_eq!(code_info.file, OsStr::new("test-stable-addresses.c"));
+            assert_ne!(code_info.line, None);
+        }
+
+        let result = frozen.symbolize_single(Input::VirtOffset(0x0)).unwrap();
+        assert_eq!(result, Symbolized::Unknown);
+
+        let err = frozen
+            .symbolize_single(Input::AbsAddr(0x2000100))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let results = frozen
+            .symbolize(Input::VirtOffset(&[0x2000100, 0x0]))
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_sym().unwrap().name, "factorial");
+        assert_eq!(results[1], Symbolized::Unknown);
+    }
+
+    /// Check that `resolve_full` only reports the requested information.
+    #[test]
+    fn full_resolution() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let src = Source::Elf(Elf::new(path));
+        let frozen = Symbolizer::new().freeze(&src).unwrap();
+
+        let input = Input::VirtOffset(0x2000102);
+        let full = frozen.resolve_full(input, ResolveFlags::default()).unwrap();
+        assert_eq!(full.sym.unwrap().name, "factorial");
+        assert_eq!(full.section, Some(".text"));
+        assert_ne!(full.code_info.unwrap().line, None);
+
+        let flags = ResolveFlags {
+            sym: false,
+            code_info: false,
+            ..Default::default()
+        };
+        let full = frozen.resolve_full(input, flags).unwrap();
+        assert_eq!(full.sym, None);
+        assert_eq!(full.section, Some(".text"));
+        assert_eq!(full.code_info, None);
+
+        let input = Input::VirtOffset(0x0);
+        let full = frozen.resolve_full(input, ResolveFlags::default()).unwrap();
+        assert_eq!(full, FullResolution::default());
+    }
+
+    /// Check that we honor the symbolizer's configuration when
+    /// freezing.
+    #[test]
+    fn configuration() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let src = Source::Elf(Elf::new(path));
+        let symbolizer = Symbolizer::builder()
+            .enable_code_info(false)
+            .set_max_sym_offset(Some(2))
+            .build();
+        let frozen = symbolizer.freeze(&src).unwrap();
+
+        let sym = frozen
+            .symbolize_single(Input::VirtOffset(0x2000102))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(sym.name, "factorial");
+        assert_eq!(sym.code_info, None);
+
+        let result = frozen
+            .symbolize_single(Input::VirtOffset(0x2000103))
+            .unwrap();
+        assert_eq!(result, Symbolized::Unknown);
+    }
+
+    /// Check that we report a build ID mismatch for all inputs.
+    #[test]
+    fn build_id_mismatch() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let mut elf = Elf::new(path);
+        elf.build_id = Some(vec![0xde, 0xad]);
+        let frozen = Symbolizer::new().freeze(&Source::Elf(elf)).unwrap();
+
+        let result = frozen.symbolize_single(Input::VirtOffset(0x0)).unwrap();
+        assert_eq!(result, Symbolized::BuildIdMismatch);
+    }
+}
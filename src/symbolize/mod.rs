@@ -89,28 +89,48 @@
 //!                 print_frame(&frame.name, None, &frame.code_info);
 //!             }
 //!         }
-//!         Symbolized::Unknown => {
+//!         Symbolized::Unknown | Symbolized::BuildIdMismatch => {
 //!             println!("{input_addr:#0width$x}: <no-symbol>", width = ADDR_WIDTH)
 //!         }
 //!     }
 //! }
 //! ```
 
+mod frozen;
 mod source;
+#[cfg(feature = "stats")]
+mod stats;
 mod symbolizer;
 
 use std::borrow::Cow;
 use std::ffi::OsStr;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::ops::BitOr;
 use std::path::Path;
 
+pub use crate::elf::ElfSymSource;
+pub use crate::util::ReadAt;
+pub use frozen::FrozenResolver;
+pub use frozen::FullResolution;
+pub use frozen::ResolveFlags;
 pub use source::Apk;
 pub use source::Elf;
+pub use source::ElfReader;
 pub use source::Gsym;
 pub use source::GsymData;
 pub use source::GsymFile;
 pub use source::Kernel;
+pub use source::KernelModule;
+pub use source::PerfMap;
 pub use source::Process;
 pub use source::Source;
+pub use source::Wasm;
+#[cfg(feature = "stats")]
+pub use stats::Stats;
+#[cfg(feature = "stats")]
+pub(crate) use stats::StatsCounters;
 pub use symbolizer::Builder;
 pub use symbolizer::Symbolizer;
 
@@ -152,8 +172,117 @@ impl<T> Input<T> {
             Self::AbsAddr(x) | Self::VirtOffset(x) | Self::FileOffset(x) => x,
         }
     }
+
+    /// Convert the inner payload, retaining the input type.
+    pub(crate) fn map<U, F>(self, f: F) -> Input<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            Self::AbsAddr(x) => Input::AbsAddr(f(x)),
+            Self::VirtOffset(x) => Input::VirtOffset(f(x)),
+            Self::FileOffset(x) => Input::FileOffset(f(x)),
+        }
+    }
+}
+
+
+/// An address captured as part of a stack trace, as provided to
+/// [`Symbolizer::symbolize_frames`].
+///
+/// Return addresses point to the instruction following a call. That
+/// instruction may belong to a different line or, in the case of a
+/// call at the very end of a function (e.g., to a `noreturn`
+/// function), even to a different function altogether. Hence, the
+/// convention is to symbolize a return address minus one, which is
+/// guaranteed to fall into the call instruction itself.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Frame {
+    /// The address of the instruction that was executing, as is the
+    /// case for the leaf frame of a stack trace. The address is
+    /// symbolized as is.
+    Exact(Addr),
+    /// A return address. The address is adjusted by one before being
+    /// symbolized.
+    Return(Addr),
+}
+
+impl Frame {
+    /// Convert the addresses of a stack trace into [`Frame`] objects,
+    /// following the common convention that the first (leaf) address
+    /// is exact and all others are return addresses.
+    ///
+    /// ```rust
+    /// # use blazesym::symbolize::Frame;
+    /// let frames = Frame::from_trace(&[0x1000, 0x2000, 0x3000]);
+    /// assert_eq!(
+    ///     frames,
+    ///     [Frame::Exact(0x1000), Frame::Return(0x2000), Frame::Return(0x3000)]
+    /// );
+    /// ```
+    pub fn from_trace(addrs: &[Addr]) -> Vec<Frame> {
+        addrs
+            .iter()
+            .enumerate()
+            .map(|(idx, addr)| {
+                if idx == 0 {
+                    Frame::Exact(*addr)
+                } else {
+                    Frame::Return(*addr)
+                }
+            })
+            .collect()
+    }
+
+    /// Retrieve the address to symbolize for this frame.
+    #[inline]
+    pub(crate) fn symbolization_addr(&self) -> Addr {
+        match self {
+            Self::Exact(addr) => *addr,
+            Self::Return(addr) => addr.saturating_sub(1),
+        }
+    }
+}
+
+
+/// An enumeration describing how the addresses provided to
+/// [`Symbolizer::symbolize_with_kind`] are to be interpreted.
+///
+/// Historically, callers had to convert runtime addresses into
+/// addresses as they appear in the file themselves before providing
+/// them as [`Input::VirtOffset`], for example by subtracting the load
+/// base, or by using a base of zero for binaries loaded at a fixed
+/// address. Using [`AddressKind::Runtime`] instead, the load address
+/// is stated explicitly and the conversion is performed internally.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum AddressKind {
+    /// Addresses are virtual addresses as they appear in the file, i.e.,
+    /// any relocation has already been undone.
+    ///
+    /// This kind is equivalent to [`Input::VirtOffset`].
+    FileVirtual,
+    /// Addresses are runtime addresses, as seen by a process that has
+    /// the file loaded.
+    Runtime {
+        /// The address at which the file's lowest loadable segment is
+        /// mapped (e.g., the start of the first mapping of the file as
+        /// reported in `/proc/<pid>/maps`).
+        ///
+        /// For binaries loaded at a fixed address this is the address
+        /// the file was linked at, and not zero. The same holds for
+        /// prelinked shared objects loaded at their prelinked address.
+        load_addr: Addr,
+    },
+    /// Addresses are offsets relative to the start of the file's
+    /// `.text` section, as recorded by some capture tools.
+    ///
+    /// Offsets are converted into file virtual addresses using the
+    /// address of the `.text` section.
+    TextOffset,
 }
 
+
 #[cfg(test)]
 impl<T> Input<&[T]>
 where
@@ -184,6 +313,84 @@ pub(crate) struct AddrCodeInfo<'src> {
 }
 
 
+/// Flags of a row in a DWARF line table.
+///
+/// The flags are reported as decoded from the line program and are
+/// mostly of interest for tools reasoning about statement boundaries or
+/// function prologues, e.g., for the purpose of breakpoint placement.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LineFlags(u8);
+
+impl LineFlags {
+    /// The instruction is a recommended breakpoint location (the
+    /// beginning of a statement).
+    pub const IS_STMT: Self = Self(1 << 0);
+    /// The instruction is the beginning of a basic block.
+    pub const BASIC_BLOCK: Self = Self(1 << 1);
+    /// The row terminates a sequence of instructions.
+    ///
+    /// Such rows do not cover any instructions themselves and so this
+    /// flag is never reported as part of address symbolization.
+    pub const END_SEQUENCE: Self = Self(1 << 2);
+    /// The instruction is one where execution should be suspended for a
+    /// breakpoint at the entry of a function.
+    pub const PROLOGUE_END: Self = Self(1 << 3);
+    /// The instruction is one where execution should be suspended for a
+    /// breakpoint just prior to the exit of a function.
+    pub const EPILOGUE_BEGIN: Self = Self(1 << 4);
+
+    /// Create an empty set of flags.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Retrieve the raw bit representation of the flags.
+    #[inline]
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Check whether all flags in `other` are set in `self`.
+    #[inline]
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Set or clear the flags in `other`, depending on `value`.
+    #[inline]
+    pub(crate) fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.0 |= other.0
+        } else {
+            self.0 &= !other.0
+        }
+    }
+}
+
+impl BitOr for LineFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+
+/// Lines of source code read from a source file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceContext {
+    /// The (1-based) line number of the first line in
+    /// [`lines`][Self::lines].
+    pub first_line: u32,
+    /// The lines of source code, without line terminators.
+    pub lines: Box<[String]>,
+    /// The struct is non-exhaustive and open to extension.
+    pub _non_exhaustive: (),
+}
+
+
 /// Source code location information for a symbol or inlined function.
 #[derive(Clone, Debug, PartialEq)]
 pub struct CodeInfo<'src> {
@@ -201,6 +408,31 @@ pub struct CodeInfo<'src> {
     /// The column number of the symbolized instruction in the source
     /// code.
     pub column: Option<u16>,
+    /// The MD5 checksum of the source file, as recorded at compile time.
+    ///
+    /// This information is only available for DWARF 5 line tables that
+    /// include it. It can be used to check whether a source file on disk
+    /// matches the one the code was compiled from.
+    pub md5: Option<[u8; 16]>,
+    /// The flags of the line table row covering the symbolized
+    /// instruction.
+    ///
+    /// This information is only available for DWARF based
+    /// symbolization and only reported if requested via
+    /// [`Builder::enable_line_flags`].
+    pub flags: Option<LineFlags>,
+    /// Lines of source code surrounding [`line`][Self::line].
+    ///
+    /// This information is only reported if requested via
+    /// [`Builder::set_source_context`] and if the source file could be
+    /// read.
+    pub context: Option<SourceContext>,
+    /// Whether the source file (as per [`to_path`][Self::to_path])
+    /// exists on disk.
+    ///
+    /// This information is only reported if requested via
+    /// [`Builder::enable_source_file_checks`].
+    pub file_exists: Option<bool>,
     /// The struct is non-exhaustive and open to extension.
     pub _non_exhaustive: (),
 }
@@ -227,6 +459,10 @@ impl CodeInfo<'_> {
             file: Cow::Owned(self.file.to_os_string()),
             line: self.line,
             column: self.column,
+            md5: self.md5,
+            flags: self.flags,
+            context: self.context.clone(),
+            file_exists: self.file_exists,
             _non_exhaustive: (),
         }
     }
@@ -240,17 +476,45 @@ pub struct InlinedFn<'src> {
     pub name: Cow<'src, str>,
     /// Source code location information for the call to the function.
     pub code_info: Option<CodeInfo<'src>>,
+    /// The number of consecutive inlined calls to this function that
+    /// this object represents.
+    ///
+    /// This count is always `1`, unless folding of recursive inlined
+    /// functions was requested (see
+    /// [`Builder::enable_inlined_fn_folding`]), in which case it may be
+    /// larger.
+    pub repeat: usize,
     /// The struct is non-exhaustive and open to extension.
     pub _non_exhaustive: (),
 }
 
+impl InlinedFn<'_> {
+    /// Convert this object into one with all references converted into
+    /// guaranteed owned (i.e., heap allocated) members.
+    pub fn to_owned(&self) -> InlinedFn<'static> {
+        InlinedFn {
+            name: Cow::Owned(self.name.to_string()),
+            code_info: self.code_info.as_ref().map(CodeInfo::to_owned),
+            repeat: self.repeat,
+            _non_exhaustive: (),
+        }
+    }
+}
+
 
 /// The source code language from which a symbol originates.
-#[derive(Clone, Copy, Default, Debug)]
-pub(crate) enum SrcLang {
+///
+/// The language is used for selecting the demangling scheme and for
+/// deciding whether a leading underscore may be stripped from a
+/// symbol name.
+#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SrcLang {
     /// The language is unknown.
     #[default]
     Unknown,
+    /// The language is C.
+    C,
     /// The language is C++.
     Cpp,
     /// The language is Rust.
@@ -259,6 +523,7 @@ pub(crate) enum SrcLang {
 
 
 /// Our internal representation of a symbol.
+#[derive(Debug, PartialEq)]
 pub(crate) struct IntSym<'src> {
     /// The name of the symbol.
     pub(crate) name: &'src str,
@@ -275,7 +540,20 @@ pub(crate) struct IntSym<'src> {
 #[derive(Clone, Debug, PartialEq)]
 pub struct Sym<'src> {
     /// The symbol name that an address belongs to.
+    ///
+    /// For an address inside inlined code, this is the concrete (i.e.,
+    /// non-inlined) function containing it, with the inlined calls
+    /// being reported in [`inlined`][Self::inlined].
     pub name: Cow<'src, str>,
+    /// The raw (i.e., mangled) name of the symbol, if it differs from
+    /// [`name`][Self::name].
+    ///
+    /// This member is set if demangling is enabled (see
+    /// [`Builder::enable_demangling`]) and changed the name, e.g., for a
+    /// DWARF function with a `DW_AT_linkage_name` attribute or a
+    /// mangled ELF symbol. It is `None` for names that did not require
+    /// demangling, such as ones lacking a linkage name.
+    pub mangled_name: Option<Cow<'src, str>>,
     /// The address at which the symbol is located (i.e., its "start").
     ///
     /// This is the "normalized" address of the symbol, as present in
@@ -308,20 +586,55 @@ pub struct Sym<'src> {
     /// contains an inlined call to `h`, the symbols will be reported in the
     /// order `f`, `g`, `h`.
     pub inlined: Box<[InlinedFn<'src>]>,
+    /// Whether the reported inlined functions were truncated, because
+    /// more than the configured maximum were present (see
+    /// [`Builder::set_max_inline_depth`]). Only the innermost ones are
+    /// reported in this case.
+    pub inlined_truncated: bool,
     /// The struct is non-exhaustive and open to extension.
     pub _non_exhaustive: (),
 }
 
+impl Sym<'_> {
+    /// Convert this object into one with all references converted into
+    /// guaranteed owned (i.e., heap allocated) members.
+    pub fn to_owned(&self) -> Sym<'static> {
+        Sym {
+            name: Cow::Owned(self.name.to_string()),
+            mangled_name: self
+                .mangled_name
+                .as_ref()
+                .map(|name| Cow::Owned(name.to_string())),
+            addr: self.addr,
+            offset: self.offset,
+            size: self.size,
+            code_info: self.code_info.as_ref().map(CodeInfo::to_owned),
+            inlined: self.inlined.iter().map(InlinedFn::to_owned).collect(),
+            inlined_truncated: self.inlined_truncated,
+            _non_exhaustive: (),
+        }
+    }
+}
+
 
 /// An enumeration used as reporting vehicle for address symbolization.
 // We keep this enum as exhaustive because additions to it, should they occur,
 // are expected to be backwards-compatibility breaking.
+// The vast majority of objects are expected to be `Sym` variants, so
+// boxing it would only add overhead.
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, PartialEq)]
 pub enum Symbolized<'src> {
     /// The input address was symbolized as the provided symbol.
     Sym(Sym<'src>),
     /// The input address was not found and could not be symbolized.
     Unknown,
+    /// The input address was not symbolized, because the file to
+    /// symbolize it with does not have the expected build ID.
+    ///
+    /// This variant is only reported if an expected build ID was
+    /// provided (e.g., via [`Elf::build_id`][crate::symbolize::Elf::build_id]).
+    BuildIdMismatch,
 }
 
 impl<'src> Symbolized<'src> {
@@ -331,7 +644,7 @@ impl<'src> Symbolized<'src> {
     pub fn as_sym(&self) -> Option<&Sym<'src>> {
         match self {
             Self::Sym(sym) => Some(sym),
-            Self::Unknown => None,
+            Self::Unknown | Self::BuildIdMismatch => None,
         }
     }
 
@@ -341,9 +654,75 @@ impl<'src> Symbolized<'src> {
     pub fn into_sym(self) -> Option<Sym<'src>> {
         match self {
             Self::Sym(sym) => Some(sym),
-            Self::Unknown => None,
+            Self::Unknown | Self::BuildIdMismatch => None,
         }
     }
+
+    /// Retrieve a [`SymbolizedFrame`] object for rendering the
+    /// symbolization result in textual form.
+    #[inline]
+    pub fn display(&self) -> SymbolizedFrame<'_, 'src> {
+        SymbolizedFrame { symbolized: self }
+    }
+}
+
+
+/// A helper type for rendering a [`Symbolized`] object as text.
+///
+/// A symbol is rendered as `name+0xoffset (file:line)`, similar to
+/// the output of many debugging tools. The source code location is
+/// included only if available, potentially extended by a column
+/// number. Inlined functions, if any, follow on separate lines,
+/// indented and in the order in which they are reported in
+/// [`Sym::inlined`]. Addresses that could not be symbolized are
+/// rendered as `<no-symbol>` and ones belonging to a file with an
+/// unexpected build ID as `<build-id-mismatch>`.
+///
+/// Objects of this type are created via [`Symbolized::display`].
+#[derive(Clone, Copy, Debug)]
+pub struct SymbolizedFrame<'sym, 'src> {
+    symbolized: &'sym Symbolized<'src>,
+}
+
+impl SymbolizedFrame<'_, '_> {
+    /// Write the source code location ` (file:line:column)`, with
+    /// each component present as far as available.
+    fn fmt_code_info(f: &mut Formatter<'_>, code_info: &Option<CodeInfo<'_>>) -> FmtResult {
+        if let Some(code_info) = code_info {
+            let path = code_info.to_path();
+            let path = path.display();
+
+            match (code_info.line, code_info.column) {
+                (Some(line), Some(col)) => write!(f, " ({path}:{line}:{col})")?,
+                (Some(line), None) => write!(f, " ({path}:{line})")?,
+                (None, _) => write!(f, " ({path})")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for SymbolizedFrame<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let sym = match self.symbolized {
+            Symbolized::Sym(sym) => sym,
+            Symbolized::Unknown => return f.write_str("<no-symbol>"),
+            Symbolized::BuildIdMismatch => return f.write_str("<build-id-mismatch>"),
+        };
+
+        let () = write!(f, "{}+{:#x}", sym.name, sym.offset)?;
+        let () = Self::fmt_code_info(f, &sym.code_info)?;
+
+        for inlined in sym.inlined.iter() {
+            let () = write!(f, "\n    {}", inlined.name)?;
+            let () = Self::fmt_code_info(f, &inlined.code_info)?;
+            let () = match inlined.repeat {
+                1 => f.write_str(" [inlined]")?,
+                repeat => write!(f, " [inlined x{repeat}]")?,
+            };
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -365,11 +744,16 @@ mod tests {
             file: Cow::Borrowed(OsStr::new("test.c")),
             line: Some(1337),
             column: None,
+            md5: None,
+            flags: Some(LineFlags::IS_STMT),
+            context: None,
+            file_exists: None,
             _non_exhaustive: (),
         };
 
         let sym = Sym {
             name: Cow::Borrowed("test"),
+            mangled_name: None,
             addr: 1337,
             offset: 42,
             size: None,
@@ -377,8 +761,10 @@ mod tests {
             inlined: Box::new([InlinedFn {
                 name: Cow::Borrowed("inlined_test"),
                 code_info: Some(code_info.clone()),
+                repeat: 1,
                 _non_exhaustive: (),
             }]),
+            inlined_truncated: false,
             _non_exhaustive: (),
         };
         assert_ne!(format!("{sym:?}"), "");
@@ -393,6 +779,82 @@ mod tests {
         assert_ne!(format!("{addr_code_info:?}"), "");
     }
 
+    /// Check that we can set and query line table flags.
+    #[test]
+    fn line_flags() {
+        let mut flags = LineFlags::empty();
+        assert_eq!(flags.bits(), 0);
+        assert!(!flags.contains(LineFlags::IS_STMT));
+
+        let () = flags.set(LineFlags::IS_STMT, true);
+        let () = flags.set(LineFlags::PROLOGUE_END, true);
+        assert!(flags.contains(LineFlags::IS_STMT | LineFlags::PROLOGUE_END));
+        assert!(!flags.contains(LineFlags::IS_STMT | LineFlags::BASIC_BLOCK));
+
+        let () = flags.set(LineFlags::IS_STMT, false);
+        assert_eq!(flags, LineFlags::PROLOGUE_END);
+    }
+
+    /// Check that we can render symbolization results in textual form.
+    #[test]
+    fn symbolized_display() {
+        let code_info = CodeInfo {
+            dir: Some(Cow::Borrowed(Path::new("/tmp/some-dir"))),
+            file: Cow::Borrowed(OsStr::new("test.c")),
+            line: Some(1337),
+            column: None,
+            md5: None,
+            flags: None,
+            context: None,
+            file_exists: None,
+            _non_exhaustive: (),
+        };
+
+        let sym = Sym {
+            name: Cow::Borrowed("test"),
+            mangled_name: None,
+            addr: 0x1000,
+            offset: 0x2a,
+            size: None,
+            code_info: None,
+            inlined: Box::new([]),
+            inlined_truncated: false,
+            _non_exhaustive: (),
+        };
+        let symbolized = Symbolized::Sym(sym.clone());
+        assert_eq!(symbolized.display().to_string(), "test+0x2a");
+
+        let sym = Sym {
+            code_info: Some(CodeInfo {
+                column: Some(5),
+                ..code_info.clone()
+            }),
+            inlined: Box::new([
+                InlinedFn {
+                    name: Cow::Borrowed("inlined_test"),
+                    code_info: Some(code_info.clone()),
+                    repeat: 1,
+                    _non_exhaustive: (),
+                },
+                InlinedFn {
+                    name: Cow::Borrowed("recursive_test"),
+                    code_info: None,
+                    repeat: 3,
+                    _non_exhaustive: (),
+                },
+            ]),
+            ..sym
+        };
+        let symbolized = Symbolized::Sym(sym);
+        let expected = "test+0x2a (/tmp/some-dir/test.c:1337:5)
+    inlined_test (/tmp/some-dir/test.c:1337) [inlined]
+    recursive_test [inlined x3]";
+        assert_eq!(symbolized.display().to_string(), expected);
+
+        let symbolized = Symbolized::Unknown;
+        assert_eq!(symbolized.display().to_string(), "<no-symbol>");
+    }
+
     /// Test the `Symbolized::*_sym()` conversion methods for the `Unknown`
     /// variant.
     #[test]
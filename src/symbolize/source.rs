@@ -1,9 +1,13 @@
 use std::cmp::min;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::path::PathBuf;
 
+use crate::elf::ElfSymSource;
+use crate::util::ReadAt;
+use crate::Addr;
 use crate::Pid;
 
 #[cfg(doc)]
@@ -63,6 +67,63 @@ pub struct Elf {
     /// For example, passing `"/bin/sh"` will load symbols and debug information from `sh`.
     /// Whereas passing `"/lib/libc.so.xxx"` will load symbols and debug information from the libc.
     pub path: PathBuf,
+    /// The path to a separate debug file belonging to the ELF file at
+    /// `path`, if any.
+    ///
+    /// When set, symbols and debug information are read from the debug
+    /// file first (e.g., its full `.symtab` section), while the ELF
+    /// file at `path` (potentially containing only `.dynsym`) is used
+    /// for address mapping and as a fallback for symbol lookup. Should
+    /// the two disagree on section addresses, the ELF file at `path` is
+    /// considered authoritative.
+    pub debug_file: Option<PathBuf>,
+    /// The names of the sections making up the overlay to scope symbol
+    /// lookup to, if any.
+    ///
+    /// Firmware using overlays (or bank switching) links multiple
+    /// sections to the same address range, making addresses in it
+    /// ambiguous. When set, symbols defined in sections that overlap
+    /// with, but are not part of, the overlay are disregarded. With GNU
+    /// ld's `OVERLAY` command, each overlay corresponds to a single
+    /// output section, e.g., `.ovly0`.
+    ///
+    /// DWARF does not convey section membership, so for ambiguous
+    /// addresses only ELF symbols are used and no source code
+    /// information is reported.
+    pub overlay: Option<Vec<String>>,
+    /// The build ID the ELF file at `path` is expected to have, if any.
+    ///
+    /// When set, the file is only used for symbolization if its build
+    /// ID matches. Otherwise all addresses are reported as
+    /// [`Symbolized::BuildIdMismatch`][crate::symbolize::Symbolized::BuildIdMismatch],
+    /// instead of being silently attributed to the wrong symbols (e.g.,
+    /// because the file got updated since addresses were captured).
+    /// Typically, the value is taken from the
+    /// [`build_id`][crate::normalize::Elf::build_id] reported during
+    /// address normalization.
+    pub build_id: Option<Vec<u8>>,
+    /// Whether to report inlined functions for addresses in this file,
+    /// overriding the symbolizer wide setting (see
+    /// [`Builder::enable_inlined_fns`][crate::symbolize::Builder::enable_inlined_fns]).
+    ///
+    /// `None` inherits the symbolizer wide setting. This setting is
+    /// useful, for example, for reporting inlined functions only for
+    /// one's own binaries but not for system libraries when
+    /// symbolizing offsets in a batch (see
+    /// [`Symbolizer::symbolize_module_offsets`][crate::symbolize::Symbolizer::symbolize_module_offsets]).
+    /// It has no effect if source code information is not reported.
+    pub inlined_fns: Option<bool>,
+    /// The symbol sources to consult for address based lookups in this
+    /// file, in order of preference, overriding the symbolizer wide
+    /// setting (see
+    /// [`Builder::set_sym_source`][crate::symbolize::Builder::set_sym_source]).
+    ///
+    /// Contrary to the symbolizer wide setting, sources not listed are
+    /// not consulted at all. E.g., `[ElfSymSource::Dynsym]` restricts
+    /// symbolization to exported functions. `None` inherits the
+    /// symbolizer wide setting. Source code information is read from
+    /// DWARF regardless.
+    pub sym_sources: Option<Vec<ElfSymSource>>,
     /// The struct is non-exhaustive and open to extension.
     #[doc(hidden)]
     pub _non_exhaustive: (),
@@ -74,6 +135,11 @@ impl Elf {
     pub fn new(path: impl Into<PathBuf>) -> Self {
         Self {
             path: path.into(),
+            debug_file: None,
+            overlay: None,
+            build_id: None,
+            inlined_fns: None,
+            sym_sources: None,
             _non_exhaustive: (),
         }
     }
@@ -90,10 +156,85 @@ impl Debug for Elf {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         let Self {
             path,
+            debug_file,
+            overlay,
+            build_id,
+            inlined_fns,
+            sym_sources,
             _non_exhaustive: (),
         } = self;
 
-        f.debug_tuple(stringify!(Elf)).field(path).finish()
+        let mut tuple = f.debug_tuple(stringify!(Elf));
+        let tuple = tuple.field(path);
+        if let Some(debug_file) = debug_file {
+            let _tuple = tuple.field(debug_file);
+        }
+        if let Some(overlay) = overlay {
+            let _tuple = tuple.field(overlay);
+        }
+        if let Some(build_id) = build_id {
+            let _tuple = tuple.field(build_id);
+        }
+        if let Some(inlined_fns) = inlined_fns {
+            let _tuple = tuple.field(inlined_fns);
+        }
+        if let Some(sym_sources) = sym_sources {
+            let _tuple = tuple.field(sym_sources);
+        }
+        tuple.finish()
+    }
+}
+
+
+/// An ELF object whose contents are provided by a reader.
+///
+/// This type is used in the [`Source::ElfReader`] variant. It allows for
+/// symbolizing ELF data that is not present on the local file system.
+/// Note that the object's contents are retrieved in their entirety
+/// whenever it is used for symbolization and no data is cached between
+/// requests. Symbols from DWARF debug information are used as per the
+/// [`Symbolizer`] configuration, but no separate debug files are
+/// consulted.
+#[derive(Clone)]
+pub struct ElfReader<'dat> {
+    /// The reader providing the contents of the ELF object.
+    pub reader: &'dat dyn ReadAt,
+    /// The size of the ELF object, in bytes.
+    pub len: u64,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl<'dat> ElfReader<'dat> {
+    /// Create a new [`ElfReader`] object, referencing the provided
+    /// reader.
+    #[inline]
+    pub fn new(reader: &'dat dyn ReadAt, len: u64) -> Self {
+        Self {
+            reader,
+            len,
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl<'dat> From<ElfReader<'dat>> for Source<'dat> {
+    #[inline]
+    fn from(elf: ElfReader<'dat>) -> Self {
+        Source::ElfReader(elf)
+    }
+}
+
+impl Debug for ElfReader<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self {
+            reader: _,
+            len,
+            _non_exhaustive: (),
+        } = self;
+
+        f.debug_tuple(stringify!(ElfReader)).field(len).finish()
     }
 }
 
@@ -118,6 +259,19 @@ pub struct Kernel {
     /// kernel image of the running kernel in `"/boot/"` or
     /// `"/usr/lib/debug/boot/"`.
     pub kernel_image: Option<PathBuf>,
+    /// Live patch modules (as created by, e.g., kpatch or the kernel's
+    /// livepatch infrastructure) loaded on top of the kernel.
+    ///
+    /// Addresses are looked up in these modules before falling back to
+    /// the kernel proper. Because patch modules typically define
+    /// functions using the same names as the ones they replace, the
+    /// patched version is reported for addresses inside the patch
+    /// module, while the original function is still reported for
+    /// addresses inside its (now bypassed) kernel image body. If
+    /// multiple patch modules cover an address, later entries take
+    /// precedence over earlier ones, mirroring the kernel's behavior of
+    /// the most recently applied patch being active.
+    pub livepatches: Vec<KernelModule>,
     /// The struct is non-exhaustive and open to extension.
     #[doc(hidden)]
     pub _non_exhaustive: (),
@@ -131,6 +285,76 @@ impl From<Kernel> for Source<'static> {
 }
 
 
+/// A loaded Linux kernel module.
+///
+/// This type is used in the [`Source::KernelModule`] variant.
+///
+/// The corresponding addresses supplied to [`Symbolizer::symbolize`] are
+/// expected to be absolute addresses
+/// ([`Input::AbsAddr`][crate::symbolize::Input::AbsAddr]) as valid in the
+/// kernel's address space.
+///
+/// Kernel modules (`.ko` files) are relocatable ELF objects, which lack
+/// program headers and whose sections are placed individually when
+/// the module is loaded. As such, the load addresses of all sections of
+/// interest (typically at least `.text`) have to be provided via the
+/// [`section_addrs`][KernelModule::section_addrs] member. For a loaded
+/// module, the kernel reports them in `/sys/module/<module>/sections/`
+/// (root privileges are required to see actual addresses). Addresses
+/// not falling into any of the provided sections are not symbolized.
+///
+/// The same mechanism works for other relocatable objects, such as `.o`
+/// files. If the object contains DWARF debug information, its
+/// relocations are applied based on the provided section addresses in
+/// order to report source code information.
+#[derive(Clone, PartialEq)]
+pub struct KernelModule {
+    /// The path to the kernel module's `.ko` file.
+    pub path: PathBuf,
+    /// The load addresses of the module's sections, keyed by section
+    /// name (e.g., `.text`).
+    pub section_addrs: HashMap<String, Addr>,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl KernelModule {
+    /// Create a new [`KernelModule`] object, referencing the provided
+    /// path and using the given section load addresses.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>, section_addrs: HashMap<String, Addr>) -> Self {
+        Self {
+            path: path.into(),
+            section_addrs,
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl From<KernelModule> for Source<'static> {
+    #[inline]
+    fn from(module: KernelModule) -> Self {
+        Source::KernelModule(module)
+    }
+}
+
+impl Debug for KernelModule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self {
+            path,
+            section_addrs,
+            _non_exhaustive: (),
+        } = self;
+
+        f.debug_tuple(stringify!(KernelModule))
+            .field(path)
+            .field(section_addrs)
+            .finish()
+    }
+}
+
+
 /// Configuration for process based address symbolization.
 ///
 /// This type is used in the [`Source::Process`] variant.
@@ -143,6 +367,19 @@ impl From<Kernel> for Source<'static> {
 pub struct Process {
     /// The referenced process' ID.
     pub pid: Pid,
+    /// Whether to resolve calls through procedure linkage table (PLT)
+    /// entries to the function actually being invoked.
+    ///
+    /// Addresses inside a PLT entry are attributed to the target
+    /// currently stored in the corresponding global offset table
+    /// slot, which is read from the process' memory. That is useful,
+    /// for example, for finding out which implementation of an
+    /// `ifunc` got selected at runtime. If the slot has not been
+    /// resolved yet, the PLT entry itself is reported.
+    ///
+    /// This feature requires permission to read the process' memory
+    /// and is only supported on x86-64 and AArch64.
+    pub resolve_plt: bool,
     /// The struct is non-exhaustive and open to extension.
     #[doc(hidden)]
     pub _non_exhaustive: (),
@@ -154,6 +391,7 @@ impl Process {
     pub fn new(pid: Pid) -> Self {
         Self {
             pid,
+            resolve_plt: false,
             _non_exhaustive: (),
         }
     }
@@ -163,6 +401,7 @@ impl Debug for Process {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         let Self {
             pid,
+            resolve_plt: _,
             _non_exhaustive: (),
         } = self;
 
@@ -291,6 +530,104 @@ impl From<GsymFile> for Source<'static> {
 }
 
 
+/// A WebAssembly module.
+///
+/// This type is used in the [`Source::Wasm`] variant.
+///
+/// The corresponding addresses supplied to [`Symbolizer::symbolize`] are
+/// expected to be offsets into the module
+/// ([`Input::FileOffset`][crate::symbolize::Input::FileOffset]), as is
+/// common for runtimes to report. Symbols are looked up using the
+/// function names contained in the module's `name` custom section.
+#[derive(Clone)]
+pub struct Wasm {
+    /// The path to the WebAssembly module.
+    pub path: PathBuf,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl Wasm {
+    /// Create a new [`Wasm`] object, referencing the provided path.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl From<Wasm> for Source<'static> {
+    #[inline]
+    fn from(wasm: Wasm) -> Self {
+        Source::Wasm(wasm)
+    }
+}
+
+impl Debug for Wasm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self {
+            path,
+            _non_exhaustive: (),
+        } = self;
+
+        f.debug_tuple(stringify!(Wasm)).field(path).finish()
+    }
+}
+
+
+/// A perf map file, as written by JIT compilers.
+///
+/// This type is used in the [`Source::PerfMap`] variant.
+///
+/// Perf map files (conventionally located at `/tmp/perf-<pid>.map`)
+/// list symbols for generated code, one per line, in the form
+/// `START SIZE NAME`, with `START` and `SIZE` being hexadecimal
+/// numbers. Malformed lines are skipped. By convention, addresses are
+/// absolute runtime addresses and so the corresponding addresses
+/// supplied to [`Symbolizer::symbolize`] are expected to be
+/// [`Input::AbsAddr`][crate::symbolize::Input::AbsAddr] ones.
+#[derive(Clone)]
+pub struct PerfMap {
+    /// The path to the perf map file.
+    pub path: PathBuf,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+impl PerfMap {
+    /// Create a new [`PerfMap`] object, referencing the provided path.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl From<PerfMap> for Source<'static> {
+    #[inline]
+    fn from(perf_map: PerfMap) -> Self {
+        Source::PerfMap(perf_map)
+    }
+}
+
+impl Debug for PerfMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self {
+            path,
+            _non_exhaustive: (),
+        } = self;
+
+        f.debug_tuple(stringify!(PerfMap)).field(path).finish()
+    }
+}
+
+
 /// The description of a source of symbols and debug information.
 ///
 /// The source of symbols and debug information can be an ELF file, kernel
@@ -302,12 +639,20 @@ pub enum Source<'dat> {
     Apk(Apk),
     /// A single ELF file.
     Elf(Elf),
+    /// A single ELF object provided by a reader.
+    ElfReader(ElfReader<'dat>),
     /// Information about the Linux kernel.
     Kernel(Kernel),
+    /// A loaded Linux kernel module.
+    KernelModule(KernelModule),
     /// Information about a process.
     Process(Process),
     /// A Gsym file.
     Gsym(Gsym<'dat>),
+    /// A WebAssembly module.
+    Wasm(Wasm),
+    /// A perf map file.
+    PerfMap(PerfMap),
 }
 
 impl Debug for Source<'_> {
@@ -315,9 +660,13 @@ impl Debug for Source<'_> {
         match self {
             Self::Apk(apk) => Debug::fmt(apk, f),
             Self::Elf(elf) => Debug::fmt(elf, f),
+            Self::ElfReader(elf) => Debug::fmt(elf, f),
             Self::Kernel(kernel) => Debug::fmt(kernel, f),
+            Self::KernelModule(module) => Debug::fmt(module, f),
             Self::Process(process) => Debug::fmt(process, f),
             Self::Gsym(gsym) => Debug::fmt(gsym, f),
+            Self::Wasm(wasm) => Debug::fmt(wasm, f),
+            Self::PerfMap(perf_map) => Debug::fmt(perf_map, f),
         }
     }
 }
@@ -341,6 +690,24 @@ mod tests {
         let src = Source::from(elf);
         assert_eq!(format!("{src:?}"), "Elf(\"/a-path/with/components.elf\")");
 
+        let elf = Elf {
+            debug_file: Some(PathBuf::from("/a-path/with/components.debug")),
+            ..Elf::new("/a-path/with/components.elf")
+        };
+        assert_eq!(
+            format!("{elf:?}"),
+            "Elf(\"/a-path/with/components.elf\", \"/a-path/with/components.debug\")"
+        );
+
+        let elf = Elf {
+            overlay: Some(vec![".ovly0".to_string()]),
+            ..Elf::new("/a-path/with/components.elf")
+        };
+        assert_eq!(
+            format!("{elf:?}"),
+            "Elf(\"/a-path/with/components.elf\", [\".ovly0\"])"
+        );
+
         let gsym_data = GsymData::new(b"12345");
         assert_eq!(format!("{gsym_data:?}"), "GsymData([49, 50, 51, 52, 53])");
         let gsym = Gsym::Data(gsym_data.clone());
@@ -360,11 +727,32 @@ mod tests {
         let src = Source::from(kernel);
         assert_ne!(format!("{src:?}"), "");
 
+        let module = KernelModule::new(
+            "/a-path/module.ko",
+            HashMap::from([(".text".to_string(), 0xffffffffc0000000)]),
+        );
+        assert_eq!(
+            format!("{module:?}"),
+            "KernelModule(\"/a-path/module.ko\", {\".text\": 18446744072635809792})"
+        );
+        let src = Source::from(module);
+        assert_ne!(format!("{src:?}"), "");
+
         let process = Process::new(Pid::Slf);
         assert_eq!(format!("{process:?}"), "Process(self)");
         let process = Process::new(Pid::from(1234));
         assert_eq!(format!("{process:?}"), "Process(1234)");
         let src = Source::from(process);
         assert_eq!(format!("{src:?}"), "Process(1234)");
+
+        let wasm = Wasm::new("/a-path/module.wasm");
+        assert_eq!(format!("{wasm:?}"), "Wasm(\"/a-path/module.wasm\")");
+        let src = Source::from(wasm);
+        assert_eq!(format!("{src:?}"), "Wasm(\"/a-path/module.wasm\")");
+
+        let perf_map = PerfMap::new("/tmp/perf-1234.map");
+        assert_eq!(format!("{perf_map:?}"), "PerfMap(\"/tmp/perf-1234.map\")");
+        let src = Source::from(perf_map);
+        assert_eq!(format!("{src:?}"), "PerfMap(\"/tmp/perf-1234.map\")");
     }
 }
@@ -0,0 +1,62 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+
+/// Statistics about the work performed by a
+/// [`Symbolizer`][crate::symbolize::Symbolizer].
+///
+/// Statistics are only gathered when the `stats` feature is enabled.
+/// They are meant to help diagnose performance problems and all
+/// counters only ever increase over the lifetime of the
+/// `Symbolizer`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    /// The number of symbol lookups performed by ELF resolvers.
+    pub sym_lookups: u64,
+    /// The number of symbol lookups satisfied using DWARF debug
+    /// information.
+    pub dwarf_hits: u64,
+    /// The number of symbol lookups satisfied using ELF symbol tables,
+    /// typically because DWARF information was unavailable or did not
+    /// cover the address.
+    pub elf_fallbacks: u64,
+    /// The number of times a cached ELF resolver was reused.
+    pub cache_hits: u64,
+    /// The number of times an ELF resolver had to be created.
+    pub cache_misses: u64,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub _non_exhaustive: (),
+}
+
+
+/// The counters backing [`Stats`], shared between a `Symbolizer` and
+/// the resolvers it creates.
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    pub sym_lookups: AtomicU64,
+    pub dwarf_hits: AtomicU64,
+    pub elf_fallbacks: AtomicU64,
+    pub cache_hits: AtomicU64,
+    pub cache_misses: AtomicU64,
+}
+
+impl StatsCounters {
+    /// Increment the provided counter.
+    #[inline]
+    pub fn inc(counter: &AtomicU64) {
+        let _prev = counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Create a [`Stats`] snapshot of the current counter values.
+    pub fn snapshot(&self) -> Stats {
+        Stats {
+            sym_lookups: self.sym_lookups.load(Ordering::Relaxed),
+            dwarf_hits: self.dwarf_hits.load(Ordering::Relaxed),
+            elf_fallbacks: self.elf_fallbacks.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.cache_misses.load(Ordering::Relaxed),
+            _non_exhaustive: (),
+        }
+    }
+}
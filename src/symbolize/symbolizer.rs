@@ -1,23 +1,35 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fmt::Debug;
+use std::fs::read as read_file;
 use std::fs::File;
+use std::hash::Hash;
+use std::mem;
+use std::num::NonZeroUsize;
 use std::ops::Deref as _;
 use std::ops::Range;
+use std::panic;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::thread;
 
+use crate::demangle::maybe_demangle;
 #[cfg(feature = "dwarf")]
 use crate::dwarf::DwarfResolver;
 use crate::elf;
 use crate::elf::ElfBackend;
 use crate::elf::ElfParser;
 use crate::elf::ElfResolver;
+use crate::elf::ElfSymSource;
+use crate::elf::OverlayResolver;
+use crate::elf::SymIndexKey;
 use crate::file_cache::FileCache;
 use crate::gsym::GsymResolver;
 use crate::insert_map::InsertMap;
 use crate::kernel::KernelResolver;
+use crate::kmod::KernelModuleResolver;
 use crate::ksym::KSymResolver;
 use crate::ksym::KALLSYMS;
 use crate::log;
@@ -25,32 +37,52 @@ use crate::maps;
 use crate::maps::PathMapsEntry;
 use crate::mmap::Mmap;
 use crate::normalize;
+use crate::normalize::buildid::read_build_id;
 use crate::normalize::normalize_sorted_user_addrs_with_entries;
 use crate::normalize::Handler as _;
+use crate::perf_map::PerfMapResolver;
 use crate::util;
+use crate::util::hex_encode;
 use crate::util::uname_release;
+use crate::util::ReadAt;
+use crate::wasm::WasmResolver;
 use crate::zip;
 use crate::Addr;
 use crate::Error;
 use crate::ErrorExt as _;
+use crate::ErrorKind;
 use crate::IntoError as _;
 use crate::Pid;
 use crate::Result;
 use crate::SymResolver;
 
+use super::frozen::FreezeOpts;
 use super::source::Apk;
 use super::source::Elf;
+use super::source::ElfReader;
 use super::source::Gsym;
 use super::source::GsymData;
 use super::source::GsymFile;
 use super::source::Kernel;
+use super::source::KernelModule;
+use super::source::PerfMap;
 use super::source::Process;
 use super::source::Source;
+use super::source::Wasm;
 use super::AddrCodeInfo;
+use super::AddressKind;
+use super::CodeInfo;
+use super::Frame;
+use super::FrozenResolver;
 use super::InlinedFn;
 use super::Input;
 use super::IntSym;
+use super::SourceContext;
 use super::SrcLang;
+#[cfg(feature = "stats")]
+use super::Stats;
+#[cfg(feature = "stats")]
+use super::StatsCounters;
 use super::Sym;
 use super::Symbolized;
 
@@ -77,33 +109,19 @@ fn create_apk_elf_path(apk: &Path, elf: &Path) -> Result<PathBuf> {
 }
 
 
-/// Demangle a symbol name using the demangling scheme for the given language.
-#[cfg(feature = "demangle")]
-fn maybe_demangle(name: Cow<'_, str>, language: SrcLang) -> Cow<'_, str> {
+/// Strip a single leading underscore from a symbol name, if the
+/// language it originates from may use one as a prefix.
+fn strip_leading_underscore(name: Cow<'_, str>, language: SrcLang) -> Cow<'_, str> {
     match language {
-        SrcLang::Rust => rustc_demangle::try_demangle(name.as_ref())
-            .ok()
-            .as_ref()
-            .map(|x| Cow::Owned(format!("{x:#}"))),
-        SrcLang::Cpp => cpp_demangle::Symbol::new(name.as_ref())
-            .ok()
-            .and_then(|x| x.demangle(&Default::default()).ok().map(Cow::Owned)),
-        SrcLang::Unknown => rustc_demangle::try_demangle(name.as_ref())
-            .map(|x| Cow::Owned(format!("{x:#}")))
-            .ok()
-            .or_else(|| {
-                cpp_demangle::Symbol::new(name.as_ref())
-                    .ok()
-                    .and_then(|sym| sym.demangle(&Default::default()).ok().map(Cow::Owned))
-            }),
+        SrcLang::C | SrcLang::Unknown => match name {
+            Cow::Borrowed(name) => Cow::Borrowed(name.strip_prefix('_').unwrap_or(name)),
+            Cow::Owned(name) => match name.strip_prefix('_') {
+                Some(stripped) => Cow::Owned(stripped.to_string()),
+                None => Cow::Owned(name),
+            },
+        },
+        SrcLang::Cpp | SrcLang::Rust => name,
     }
-    .unwrap_or(name)
-}
-
-#[cfg(not(feature = "demangle"))]
-fn maybe_demangle(name: Cow<'_, str>, _language: SrcLang) -> Cow<'_, str> {
-    // Demangling is disabled.
-    name
 }
 
 
@@ -111,8 +129,13 @@ fn elf_offset_to_address(offset: u64, parser: &ElfParser) -> Result<Option<Addr>
     let phdrs = parser.program_headers()?;
     let addr = phdrs.iter().find_map(|phdr| {
         if phdr.p_type == elf::types::PT_LOAD {
-            if (phdr.p_offset..phdr.p_offset + phdr.p_memsz).contains(&offset) {
-                return Some((offset - phdr.p_offset + phdr.p_vaddr) as Addr)
+            let offset = offset.checked_sub(phdr.p_offset)?;
+            // Only the part of a segment backed by file contents can
+            // be addressed by a file offset. Anything beyond that, e.g.,
+            // a zero-initialized tail, may overlap with the file
+            // offsets of subsequent segments.
+            if offset < phdr.p_filesz {
+                return offset.checked_add(phdr.p_vaddr)
             }
         }
         None
@@ -121,10 +144,137 @@ fn elf_offset_to_address(offset: u64, parser: &ElfParser) -> Result<Option<Addr>
     Ok(addr)
 }
 
+/// Convert a runtime address into a virtual address as it appears in
+/// the ELF file, given the address at which the file's lowest loadable
+/// segment got mapped.
+///
+/// The virtual address of said segment is taken into account, so that
+/// files linked at a non-zero base (such as executables or prelinked
+/// shared objects) are handled correctly.
+fn elf_runtime_to_address(addr: Addr, load_addr: Addr, parser: &ElfParser) -> Result<Option<Addr>> {
+    let base = parser.load_base()?;
+    let addr = base.and_then(|base| addr.checked_sub(load_addr)?.checked_add(base));
+    Ok(addr)
+}
+
+/// Convert an offset relative to the start of the `.text` section into
+/// a virtual address as it appears in the ELF file.
+///
+/// `None` is returned if the offset cannot be mapped to an address.
+fn elf_text_offset_to_address(offset: Addr, parser: &ElfParser) -> Result<Option<Addr>> {
+    let idx = parser
+        .find_section(".text")?
+        .ok_or_else(|| Error::with_not_found("ELF file does not contain a .text section"))?;
+    let shdrs = parser.section_headers()?;
+    // SANITY: The index was reported by the parser and is valid.
+    let addr = shdrs[idx].sh_addr.checked_add(offset);
+    Ok(addr)
+}
+
+/// Check whether the ELF file represented by `resolver` has the build
+/// ID `expected`.
+///
+/// If no build ID is expected, any file is considered matching.
+fn has_build_id(resolver: &ElfResolver, expected: Option<&[u8]>) -> Result<bool> {
+    match expected {
+        Some(expected) => {
+            let build_id = read_build_id(resolver.parser())?;
+            Ok(build_id.as_deref() == Some(expected))
+        }
+        None => Ok(true),
+    }
+}
+
+/// Read an address sized value from the memory of the process with
+/// the given `pid`.
+fn read_process_addr(pid: Pid, addr: Addr) -> Result<Addr> {
+    let path = format!("/proc/{pid}/mem");
+    let file = File::open(&path).with_context(|| format!("failed to open {path}"))?;
+    let mut buf = [0; mem::size_of::<Addr>()];
+    let () = file
+        .read_at(addr, &mut buf)
+        .with_context(|| format!("failed to read process memory at {addr:#x}"))?;
+    Ok(Addr::from_ne_bytes(buf))
+}
+
+/// Canonicalize `path`, following symbolic links.
+///
+/// If canonicalization fails, `path` is returned unchanged.
+fn canonicalize_path(path: &Path) -> Cow<'_, Path> {
+    match path.canonicalize() {
+        Ok(canonical) => Cow::Owned(canonical),
+        Err(err) => {
+            log::debug!("failed to canonicalize {}: {err}", path.display());
+            Cow::Borrowed(path)
+        }
+    }
+}
+
+/// Initialize the symbol table of the ELF file represented by `parser`
+/// from the index stored in `dir`, creating or updating said index if
+/// necessary.
+fn apply_sym_index(dir: &Path, parser: &ElfParser, file: &File) -> Result<()> {
+    let build_id = match read_build_id(parser)? {
+        Some(build_id) if !build_id.is_empty() => build_id,
+        _ => return Ok(()),
+    };
+    let key = SymIndexKey {
+        build_id: &build_id,
+        mtime: file.metadata()?.modified()?,
+    };
+    let path = dir.join(key.file_name());
+
+    match parser.load_sym_index(&path, &key) {
+        Ok(true) => return Ok(()),
+        Ok(false) => (),
+        Err(err) => log::warn!("ignoring invalid symbol index {}: {err}", path.display()),
+    }
+    parser.write_sym_index(&path, &key)
+}
+
+/// Create an [`ElfParser`] for the ELF file `file`, located at `path`.
+///
+/// This function does not depend on any [`Symbolizer`] state and can be
+/// used from arbitrary threads.
+fn open_elf_parser(
+    path: &Path,
+    file: &File,
+    lenient: bool,
+    sym_index_dir: Option<&Path>,
+) -> Result<ElfParser> {
+    let mut parser = ElfParser::open_file(file)?;
+    let () = parser.set_lenient(lenient);
+    if let Some(dir) = sym_index_dir {
+        if let Err(err) = apply_sym_index(dir, &parser, file) {
+            log::warn!("failed to use symbol index for {}: {err}", path.display());
+        }
+    }
+    Ok(parser)
+}
+
+/// Collapse consecutive inlined function calls to the same function
+/// into a single one, retaining the source code location of the
+/// innermost call.
+fn fold_inlined_fns(inlined: Vec<InlinedFn<'_>>) -> Vec<InlinedFn<'_>> {
+    let mut folded = Vec::<InlinedFn>::with_capacity(inlined.len());
+    for inlined_fn in inlined {
+        if let Some(last) = folded.last_mut() {
+            if last.name == inlined_fn.name {
+                last.code_info = inlined_fn.code_info;
+                last.repeat += inlined_fn.repeat;
+                continue
+            }
+        }
+        let () = folded.push(inlined_fn);
+    }
+    folded
+}
+
 
 /// A builder for configurable construction of [`Symbolizer`] objects.
 ///
-/// By default all features are enabled.
+/// By default all features are enabled, with the exception of inlined
+/// function folding and line table flag reporting.
 #[derive(Clone, Debug)]
 pub struct Builder {
     /// Whether to enable usage of debug symbols.
@@ -136,12 +286,50 @@ pub struct Builder {
     code_info: bool,
     /// Whether to report inlined functions as part of symbolization.
     inlined_fns: bool,
+    /// Whether to report the flags of line table rows as part of source
+    /// code information.
+    line_flags: bool,
+    /// Whether to report source code information for addresses in a
+    /// function's prologue as of the end of the prologue.
+    skip_prologue: bool,
+    /// Whether to fold consecutive inlined calls to the same function
+    /// into a single reported inlined function.
+    fold_inlined_fns: bool,
+    /// The maximum number of inlined functions to report per address.
+    max_inline_depth: Option<usize>,
+    /// The maximum distance between an address and the start of the
+    /// symbol it gets attributed to.
+    max_sym_offset: Option<u64>,
+    /// Whether to report addresses past the end of the symbol they
+    /// would get attributed to as unknown.
+    detect_padding: bool,
     /// Whether or not to transparently demangle symbols.
     ///
     /// Demangling happens on a best-effort basis. Currently supported
     /// languages are Rust and C++ and the flag will have no effect if
     /// the underlying language does not mangle symbols (such as C).
     demangle: bool,
+    /// Whether to strip a single leading underscore from C symbol
+    /// names.
+    strip_leading_underscore: bool,
+    /// Root directories to search for separate debug files by build ID.
+    debug_dirs: Vec<PathBuf>,
+    /// The directory in which to store and look up symbol indices.
+    sym_index_dir: Option<PathBuf>,
+    /// The source of symbols to prefer for ELF files.
+    sym_source: ElfSymSource,
+    /// Whether to canonicalize ELF file paths for caching purposes.
+    canonicalize_paths: bool,
+    /// The number of lines of source code context to report around
+    /// the line of a symbolized instruction.
+    source_context: Option<usize>,
+    /// Whether to check for the existence of source files referenced
+    /// by reported source code information.
+    check_source_files: bool,
+    /// Whether to place kernel symbol names in an arena.
+    name_arena: bool,
+    /// Whether to parse ELF files leniently.
+    lenient_elf: bool,
 }
 
 impl Builder {
@@ -166,34 +354,304 @@ impl Builder {
         self
     }
 
+    /// Enable/disable reporting of line table flags.
+    ///
+    /// If enabled, [`CodeInfo::flags`][crate::symbolize::CodeInfo::flags]
+    /// contains the flags (such as `is_stmt` or `prologue_end`) of the
+    /// DWARF line table row covering the symbolized instruction.
+    /// Disabled by default, in which case no flags are reported.
+    pub fn enable_line_flags(mut self, enable: bool) -> Builder {
+        self.line_flags = enable;
+        self
+    }
+
+    /// Enable/disable skipping of function prologues when reporting
+    /// source code information.
+    ///
+    /// Addresses inside a function's prologue are commonly attributed
+    /// to the line containing the function's opening brace. If
+    /// enabled, source code information for such addresses is instead
+    /// reported for the first instruction past the prologue (as
+    /// indicated by the `prologue_end` flag of the DWARF line table),
+    /// which typically maps to the first statement of the function
+    /// body. Only addresses in ELF files with DWARF debug information
+    /// are affected. Disabled by default, in which case source code
+    /// information is reported for the exact address.
+    pub fn enable_prologue_skipping(mut self, enable: bool) -> Builder {
+        self.skip_prologue = enable;
+        self
+    }
+
+    /// Set the number of lines of source code context to report.
+    ///
+    /// If set, the source file referenced by reported source code
+    /// information is read from disk and up to `lines` lines before
+    /// and after the line of the symbolized instruction, along with
+    /// said line itself, are reported in
+    /// [`CodeInfo::context`][crate::symbolize::CodeInfo::context].
+    /// Source files are read lazily and kept in memory for the
+    /// lifetime of the [`Symbolizer`]. If a file cannot be read, no
+    /// context is reported. `None`, the default, disables the reading
+    /// of source files altogether.
+    pub fn set_source_context(mut self, lines: Option<usize>) -> Builder {
+        self.source_context = lines;
+        self
+    }
+
+    /// Enable/disable checking whether reported source files exist.
+    ///
+    /// If enabled, the path of each source file referenced by reported
+    /// source code information is checked for existence on disk and the
+    /// result reported in
+    /// [`CodeInfo::file_exists`][crate::symbolize::CodeInfo::file_exists].
+    /// That can be used to decide whether source code can be shown,
+    /// for example. Doing so requires a file system lookup per reported
+    /// source file. Disabled by default.
+    pub fn enable_source_file_checks(mut self, enable: bool) -> Builder {
+        self.check_source_files = enable;
+        self
+    }
+
+    /// Enable/disable folding of recursive inlined functions.
+    ///
+    /// If enabled, consecutive inlined calls to the same function (as
+    /// identified by its name) are collapsed into a single
+    /// [`InlinedFn`], with [`InlinedFn::repeat`] conveying the number
+    /// of calls represented. The reported source code location is that
+    /// of the innermost call. If disabled (the default), the raw chain
+    /// of inlined functions is reported.
+    pub fn enable_inlined_fn_folding(mut self, enable: bool) -> Builder {
+        self.fold_inlined_fns = enable;
+        self
+    }
+
+    /// Set the maximum number of inlined functions to report for a single
+    /// address.
+    ///
+    /// If an address is contained in more nested inlined function calls
+    /// than that, only the innermost `depth` ones are reported and
+    /// [`Sym::inlined_truncated`][crate::symbolize::Sym::inlined_truncated]
+    /// is set. Truncation happens before any demangling or folding
+    /// (see [`Builder::enable_inlined_fn_folding`]) is performed.
+    /// `None`, the default, means that the depth is unlimited.
+    pub fn set_max_inline_depth(mut self, depth: Option<usize>) -> Builder {
+        self.max_inline_depth = depth;
+        self
+    }
+
+    /// Set the maximum offset of an address from the start of the symbol
+    /// it is attributed to.
+    ///
+    /// Symbols are looked up on a nearest-preceding basis where size
+    /// information is unavailable, which can attribute addresses to a
+    /// far away symbol if there is a large gap in between. If the
+    /// offset of an address into its symbol exceeds `max_offset`, the
+    /// address is reported as unknown instead. `None`, the default,
+    /// means that the offset is unlimited.
+    pub fn set_max_sym_offset(mut self, max_offset: Option<u64>) -> Builder {
+        self.max_sym_offset = max_offset;
+        self
+    }
+
+    /// Enable/disable detection of padding between symbols.
+    ///
+    /// Some symbolization sources (e.g., Gsym) attribute an address to
+    /// the nearest preceding symbol, even if it lies past that symbol's
+    /// end. Such addresses are typically part of padding or alignment
+    /// between functions. When enabled, addresses beyond the end of a
+    /// symbol of known size are reported as unknown instead. Symbols
+    /// without size information are not affected. Disabled by default.
+    pub fn enable_padding_detection(mut self, enable: bool) -> Builder {
+        self.detect_padding = enable;
+        self
+    }
+
     /// Enable/disable transparent demangling of symbol names.
     ///
     /// Demangling happens on a best-effort basis. Currently supported languages
     /// are Rust and C++ and the flag will have no effect if the underlying
     /// language does not mangle symbols (such as C).
+    ///
+    /// The demangling scheme to use is picked based on the source language of
+    /// a symbol, if known (e.g., from DWARF debug information). Otherwise all
+    /// supported schemes are tried in turn.
+    ///
+    /// When disabled, all symbol names (including those of inlined
+    /// functions) are reported verbatim, exactly as they appear in the
+    /// symbolization source, and no demangling is attempted at all. Source
+    /// language information is irrelevant in this case. Disabling
+    /// demangling is equivalent to building without the `demangle` feature.
     pub fn enable_demangling(mut self, enable: bool) -> Builder {
         self.demangle = enable;
         self
     }
 
+    /// Enable/disable stripping of a single leading underscore from C
+    /// symbol names.
+    ///
+    /// Some platforms (most prominently ones using the Mach-O format)
+    /// prefix the names of C symbols with an underscore. Enabling this
+    /// option removes said underscore for display purposes, which keeps
+    /// names consistent across formats. Stripping is performed for
+    /// symbols known to originate from C as well as ones of unknown
+    /// language, and it happens before any demangling. Disabled by
+    /// default, in which case names are reported as they appear in the
+    /// symbolization source.
+    pub fn enable_leading_underscore_stripping(mut self, enable: bool) -> Builder {
+        self.strip_leading_underscore = enable;
+        self
+    }
+
+    /// Set the source of symbols to prefer when symbolizing addresses
+    /// in ELF files.
+    ///
+    /// DWARF debug information and the ELF symbol table do not
+    /// necessarily contain the same set of symbols. By default, DWARF
+    /// is consulted first (if usage of debug symbols is enabled), with
+    /// the symbol table acting as a fallback. Source code location
+    /// information is always retrieved from DWARF, irrespective of this
+    /// setting.
+    pub fn set_sym_source(mut self, sym_source: ElfSymSource) -> Builder {
+        self.sym_source = sym_source;
+        self
+    }
+
+    /// Enable/disable canonicalization of ELF file paths for caching
+    /// purposes.
+    ///
+    /// If enabled, paths to ELF files (and separate debug files) are
+    /// canonicalized, following symbolic links, before consulting the
+    /// internal caches. That way the same file referenced via different
+    /// paths (e.g., relative ones or ones traversing symbolic links)
+    /// gets parsed only once. If canonicalization fails, the path is
+    /// used as provided. Disabled by default.
+    pub fn enable_path_canonicalization(mut self, enable: bool) -> Builder {
+        self.canonicalize_paths = enable;
+        self
+    }
+
+    /// Enable/disable arena allocation of kernel symbol names.
+    ///
+    /// If enabled, the names of symbols parsed from kallsyms are copied
+    /// into a few large chunks of memory owned by the respective
+    /// resolver, instead of being allocated one by one. All of them
+    /// are released at once when the resolver is dropped, which
+    /// reduces allocator pressure and heap fragmentation for services
+    /// creating and discarding many symbolizers. Disabled by default.
+    pub fn enable_name_arena(mut self, enable: bool) -> Builder {
+        self.name_arena = enable;
+        self
+    }
+
+    /// Enable/disable lenient parsing of ELF files.
+    ///
+    /// In lenient mode, ELF files lacking usable section headers, as is
+    /// common for images carved from a memory snapshot, can still be
+    /// symbolized. Symbols are then recovered from the tables referenced
+    /// by the dynamic segment (`PT_DYNAMIC`). Use
+    /// [`helper::read_elf_recovered_tables`][crate::helper::read_elf_recovered_tables]
+    /// to check which tables can be recovered from a file. Disabled by
+    /// default.
+    pub fn enable_lenient_elf_parsing(mut self, enable: bool) -> Builder {
+        self.lenient_elf = enable;
+        self
+    }
+
+    /// Set the root directories to search for separate debug files.
+    ///
+    /// When symbolizing an ELF file without DWARF debug information,
+    /// a debug file is looked up based on the file's build ID, using
+    /// the `<dir>/.build-id/ab/cdef...debug` layout that distributions
+    /// commonly install debug information under. Directories are
+    /// searched in the provided order and the first debug file whose
+    /// build ID matches is used. Candidates that cannot be read are
+    /// skipped with a warning, instead of failing the lookup as a
+    /// whole. Defaults to `/usr/lib/debug`. An empty
+    /// list disables the lookup. The lookup is only performed if usage
+    /// of debug symbols is enabled.
+    pub fn set_debug_dirs<D, P>(mut self, dirs: D) -> Builder
+    where
+        D: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.debug_dirs = dirs
+            .into_iter()
+            .map(|dir| dir.as_ref().to_path_buf())
+            .collect();
+        self
+    }
+
+    /// Set the directory in which to store and look up indices of
+    /// parsed ELF symbol tables.
+    ///
+    /// Parsing the symbol table of a large binary can take a
+    /// considerable amount of time. If a directory is set, the outcome
+    /// of this work is stored in a sidecar file in it when an ELF file
+    /// is first symbolized and reused on subsequent runs, for as long
+    /// as the file's build ID and modification time remain unchanged.
+    /// ELF files without a build ID are not indexed. Failure to read or
+    /// write an index is not fatal and merely logged. Disabled (`None`)
+    /// by default.
+    pub fn set_sym_index_dir(mut self, dir: Option<PathBuf>) -> Builder {
+        self.sym_index_dir = dir;
+        self
+    }
+
     /// Create the [`Symbolizer`] object.
     pub fn build(self) -> Symbolizer {
         let Builder {
             debug_syms,
             code_info,
             inlined_fns,
+            line_flags,
+            skip_prologue,
+            fold_inlined_fns,
+            max_inline_depth,
+            max_sym_offset,
+            detect_padding,
             demangle,
+            strip_leading_underscore,
+            debug_dirs,
+            sym_index_dir,
+            sym_source,
+            canonicalize_paths,
+            source_context,
+            check_source_files,
+            name_arena,
+            lenient_elf,
         } = self;
 
         Symbolizer {
             apk_cache: FileCache::new(),
             elf_cache: FileCache::new(),
+            elf_debug_cache: FileCache::new(),
             gsym_cache: FileCache::new(),
             ksym_cache: FileCache::new(),
+            kmod_cache: FileCache::new(),
+            wasm_cache: FileCache::new(),
+            perf_map_cache: FileCache::new(),
             debug_syms,
             code_info,
             inlined_fns,
+            line_flags,
+            skip_prologue,
+            fold_inlined_fns,
+            max_inline_depth,
+            max_sym_offset,
+            detect_padding,
             demangle,
+            strip_leading_underscore,
+            debug_dirs,
+            sym_index_dir,
+            sym_source,
+            canonicalize_paths,
+            source_context,
+            check_source_files,
+            name_arena,
+            lenient_elf,
+            source_cache: InsertMap::new(),
+            #[cfg(feature = "stats")]
+            stats: Rc::default(),
         }
     }
 }
@@ -204,7 +662,22 @@ impl Default for Builder {
             debug_syms: true,
             code_info: true,
             inlined_fns: true,
+            line_flags: false,
+            skip_prologue: false,
+            fold_inlined_fns: false,
+            max_inline_depth: None,
+            max_sym_offset: None,
+            detect_padding: false,
             demangle: true,
+            strip_leading_underscore: false,
+            debug_dirs: vec![PathBuf::from("/usr/lib/debug")],
+            sym_index_dir: None,
+            sym_source: ElfSymSource::default(),
+            canonicalize_paths: false,
+            source_context: None,
+            check_source_files: false,
+            name_arena: false,
+            lenient_elf: false,
         }
     }
 }
@@ -246,12 +719,45 @@ pub struct Symbolizer {
     #[allow(clippy::type_complexity)]
     apk_cache: FileCache<(zip::Archive, InsertMap<Range<u64>, Rc<ElfResolver>>)>,
     elf_cache: FileCache<Rc<ElfResolver>>,
+    /// Resolvers for ELF files with a separate debug file, keyed by
+    /// the path to said debug file.
+    elf_debug_cache: FileCache<InsertMap<PathBuf, Rc<ElfResolver>>>,
     gsym_cache: FileCache<Rc<GsymResolver<'static>>>,
     ksym_cache: FileCache<Rc<KSymResolver>>,
+    /// Parsers for kernel module files, along with resolvers for
+    /// concrete module instances, keyed by their section addresses.
+    #[allow(clippy::type_complexity)]
+    kmod_cache: FileCache<(
+        Rc<ElfParser>,
+        InsertMap<Box<[(String, Addr)]>, Rc<KernelModuleResolver>>,
+    )>,
+    wasm_cache: FileCache<Rc<WasmResolver>>,
+    perf_map_cache: FileCache<Rc<PerfMapResolver>>,
     debug_syms: bool,
     code_info: bool,
     inlined_fns: bool,
+    line_flags: bool,
+    skip_prologue: bool,
+    fold_inlined_fns: bool,
+    max_inline_depth: Option<usize>,
+    max_sym_offset: Option<u64>,
+    detect_padding: bool,
     demangle: bool,
+    strip_leading_underscore: bool,
+    debug_dirs: Vec<PathBuf>,
+    sym_index_dir: Option<PathBuf>,
+    sym_source: ElfSymSource,
+    canonicalize_paths: bool,
+    source_context: Option<usize>,
+    check_source_files: bool,
+    name_arena: bool,
+    lenient_elf: bool,
+    /// The lines of source files read for reporting source code
+    /// context, if they could be read.
+    source_cache: InsertMap<PathBuf, Option<Box<[String]>>>,
+    /// Counters for statistics about the work performed.
+    #[cfg(feature = "stats")]
+    stats: Rc<StatsCounters>,
 }
 
 impl Symbolizer {
@@ -267,7 +773,19 @@ impl Symbolizer {
     }
 
     /// Demangle the provided symbol if asked for and possible.
+    ///
+    /// If configured, a leading underscore is stripped from the symbol
+    /// before demangling is attempted.
+    /// If demangling is disabled, the symbol is handed back unchanged
+    /// without consulting the demangler.
+    #[inline]
     fn maybe_demangle<'sym>(&self, symbol: Cow<'sym, str>, language: SrcLang) -> Cow<'sym, str> {
+        let symbol = if self.strip_leading_underscore {
+            strip_leading_underscore(symbol, language)
+        } else {
+            symbol
+        };
+
         if self.demangle {
             maybe_demangle(symbol, language)
         } else {
@@ -275,16 +793,50 @@ impl Symbolizer {
         }
     }
 
+    /// Retain only the innermost inlined functions, as per the configured
+    /// maximum inline depth.
+    ///
+    /// The second member of the returned tuple indicates whether any
+    /// inlined functions were dropped.
+    fn limit_inline_depth<T>(&self, mut inlined: Vec<T>) -> (Vec<T>, bool) {
+        match self.max_inline_depth {
+            Some(depth) if inlined.len() > depth => {
+                let inner = inlined.split_off(inlined.len() - depth);
+                (inner, true)
+            }
+            _ => (inlined, false),
+        }
+    }
+
     /// Symbolize an address using the provided [`SymResolver`].
-    #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all, fields(addr = format_args!("{addr:#x}"), resolver = ?resolver)))]
     fn symbolize_with_resolver<'slf>(
         &'slf self,
         addr: Addr,
         resolver: &Resolver<'_, 'slf>,
+    ) -> Result<Symbolized<'slf>> {
+        self.symbolize_with_resolver_impl(addr, resolver, self.inlined_fns, None)
+    }
+
+    /// Symbolize an address using the provided [`SymResolver`],
+    /// reporting inlined functions as per `inlined_fns`.
+    ///
+    /// If provided, only the symbol sources `sym_sources` are consulted
+    /// for finding the symbol, in the given order.
+    #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all, fields(addr = format_args!("{addr:#x}"), resolver = ?resolver)))]
+    fn symbolize_with_resolver_impl<'slf>(
+        &'slf self,
+        addr: Addr,
+        resolver: &Resolver<'_, 'slf>,
+        inlined_fns: bool,
+        sym_sources: Option<&[ElfSymSource]>,
     ) -> Result<Symbolized<'slf>> {
         let (sym_name, sym_addr, sym_size, lang) = match resolver {
             Resolver::Uncached(resolver) => {
-                if let Some(sym) = resolver.find_sym(addr)? {
+                let sym = match sym_sources {
+                    Some(sources) => resolver.find_sym_in(addr, sources)?,
+                    None => resolver.find_sym(addr)?,
+                };
+                if let Some(sym) = sym {
                     let IntSym {
                         name: sym_name,
                         addr: sym_addr,
@@ -298,7 +850,11 @@ impl Symbolizer {
                 }
             }
             Resolver::Cached(resolver) => {
-                if let Some(sym) = resolver.find_sym(addr)? {
+                let sym = match sym_sources {
+                    Some(sources) => resolver.find_sym_in(addr, sources)?,
+                    None => resolver.find_sym(addr)?,
+                };
+                if let Some(sym) = sym {
                     let IntSym {
                         name: sym_name,
                         addr: sym_addr,
@@ -313,10 +869,28 @@ impl Symbolizer {
             }
         };
 
-        let (name, code_info, inlined) = if self.code_info {
+        if let Some(max_offset) = self.max_sym_offset {
+            if addr.saturating_sub(sym_addr) > max_offset {
+                return Ok(Symbolized::Unknown)
+            }
+        }
+
+        if self.detect_padding {
+            // A size of zero conveys that the size is unknown.
+            if let Some(size) = sym_size.filter(|size| *size != 0) {
+                if addr.saturating_sub(sym_addr) >= size as u64 {
+                    return Ok(Symbolized::Unknown)
+                }
+            }
+        }
+
+        let (name, mut code_info, mut inlined, inlined_truncated) = if self.code_info {
+            // We already know the symbol and so, unless inlined functions
+            // were requested, only care about the source location.
+            let line_only = !inlined_fns;
             match resolver {
                 Resolver::Uncached(resolver) => {
-                    let addr_code_info = resolver.find_code_info(addr, self.inlined_fns)?;
+                    let addr_code_info = resolver.find_code_info(addr, inlined_fns, line_only)?;
                     if let Some(AddrCodeInfo {
                         direct: (direct_name, direct_code_info),
                         inlined,
@@ -324,6 +898,7 @@ impl Symbolizer {
                     {
                         let direct_name = direct_name.map(|name| Cow::Owned(name.to_string()));
                         let direct_code_info = direct_code_info.to_owned();
+                        let (inlined, truncated) = self.limit_inline_depth(inlined);
                         let inlined = inlined
                             .into_iter()
                             .map(|(name, info)| {
@@ -331,23 +906,25 @@ impl Symbolizer {
                                 InlinedFn {
                                     name,
                                     code_info: info.map(|info| info.to_owned()),
+                                    repeat: 1,
                                     _non_exhaustive: (),
                                 }
                             })
                             .collect();
-                        (direct_name, Some(direct_code_info), inlined)
+                        (direct_name, Some(direct_code_info), inlined, truncated)
                     } else {
-                        (None, None, Vec::new())
+                        (None, None, Vec::new(), false)
                     }
                 }
                 Resolver::Cached(resolver) => {
-                    let addr_code_info = resolver.find_code_info(addr, self.inlined_fns)?;
+                    let addr_code_info = resolver.find_code_info(addr, inlined_fns, line_only)?;
                     if let Some(AddrCodeInfo {
                         direct: (direct_name, direct_code_info),
                         inlined,
                     }) = addr_code_info
                     {
                         let direct_name = direct_name.map(Cow::Borrowed);
+                        let (inlined, truncated) = self.limit_inline_depth(inlined);
                         let inlined = inlined
                             .into_iter()
                             .map(|(name, info)| {
@@ -355,32 +932,122 @@ impl Symbolizer {
                                 InlinedFn {
                                     name,
                                     code_info: info,
+                                    repeat: 1,
                                     _non_exhaustive: (),
                                 }
                             })
                             .collect();
-                        (direct_name, Some(direct_code_info), inlined)
+                        (direct_name, Some(direct_code_info), inlined, truncated)
                     } else {
-                        (None, None, Vec::new())
+                        (None, None, Vec::new(), false)
                     }
                 }
             }
         } else {
-            (None, None, Vec::new())
+            (None, None, Vec::new(), false)
+        };
+
+        if !self.line_flags {
+            let () = code_info
+                .iter_mut()
+                .chain(
+                    inlined
+                        .iter_mut()
+                        .filter_map(|inlined_fn| inlined_fn.code_info.as_mut()),
+                )
+                .for_each(|info| info.flags = None);
+        }
+
+        if let Some(lines) = self.source_context {
+            let () = code_info
+                .iter_mut()
+                .chain(
+                    inlined
+                        .iter_mut()
+                        .filter_map(|inlined_fn| inlined_fn.code_info.as_mut()),
+                )
+                .for_each(|info| info.context = self.source_context(info, lines));
+        }
+
+        if self.check_source_files {
+            let () = code_info
+                .iter_mut()
+                .chain(
+                    inlined
+                        .iter_mut()
+                        .filter_map(|inlined_fn| inlined_fn.code_info.as_mut()),
+                )
+                .for_each(|info| info.file_exists = Some(info.to_path().exists()));
+        }
+
+        let inlined = if self.fold_inlined_fns {
+            fold_inlined_fns(inlined)
+        } else {
+            inlined
         };
 
+        let raw_name = name.unwrap_or(sym_name);
+        let name = self.maybe_demangle(raw_name.clone(), lang);
+        let mangled_name = (name != raw_name).then_some(raw_name);
         let sym = Sym {
-            name: self.maybe_demangle(name.unwrap_or(sym_name), lang),
+            name,
+            mangled_name,
             addr: sym_addr,
             offset: (addr - sym_addr) as usize,
             size: sym_size,
             code_info,
             inlined: inlined.into_boxed_slice(),
+            inlined_truncated,
             _non_exhaustive: (),
         };
         Ok(Symbolized::Sym(sym))
     }
 
+    /// Retrieve up to `lines` lines of source code around the line
+    /// referenced by `code_info`.
+    fn source_context(&self, code_info: &CodeInfo<'_>, lines: usize) -> Option<SourceContext> {
+        let line = usize::try_from(code_info.line?).ok()?;
+        if line == 0 {
+            return None
+        }
+
+        let path = code_info.to_path();
+        let source = self
+            .source_cache
+            .get_or_insert(path.to_path_buf(), || match read_file(&path) {
+                Ok(data) => {
+                    let source = String::from_utf8_lossy(&data)
+                        .lines()
+                        .map(str::to_string)
+                        .collect();
+                    Some(source)
+                }
+                Err(err) => {
+                    log::debug!("failed to read source file {}: {err}", path.display());
+                    None
+                }
+            })
+            .as_deref()?;
+
+        // `line` is 1-based, the indices we work with are not.
+        let idx = line - 1;
+        if idx >= source.len() {
+            return None
+        }
+        let start = idx.saturating_sub(lines);
+        let end = idx
+            .saturating_add(lines)
+            .saturating_add(1)
+            .min(source.len());
+
+        let context = SourceContext {
+            first_line: u32::try_from(start + 1).ok()?,
+            lines: source[start..end].to_vec().into_boxed_slice(),
+            _non_exhaustive: (),
+        };
+        Some(context)
+    }
+
     /// Symbolize a list of addresses using the provided [`SymResolver`].
     fn symbolize_addrs<'slf>(
         &'slf self,
@@ -393,11 +1060,7 @@ impl Symbolizer {
             .collect()
     }
 
-    fn elf_resolver_from_parser(
-        &self,
-        path: &Path,
-        parser: Rc<ElfParser>,
-    ) -> Result<Rc<ElfResolver>> {
+    fn elf_backend(&self, parser: Rc<ElfParser>) -> Result<ElfBackend> {
         #[cfg(feature = "dwarf")]
         let backend = if self.debug_syms {
             ElfBackend::Dwarf(Rc::new(DwarfResolver::from_parser(parser, self.code_info)?))
@@ -407,18 +1070,184 @@ impl Symbolizer {
 
         #[cfg(not(feature = "dwarf"))]
         let backend = ElfBackend::Elf(parser);
-        let resolver = Rc::new(ElfResolver::with_backend(path, backend)?);
-        Ok(resolver)
+        Ok(backend)
+    }
+
+    fn elf_resolver_from_parser(
+        &self,
+        path: &Path,
+        parser: Rc<ElfParser>,
+    ) -> Result<Rc<ElfResolver>> {
+        // Relocatable objects have no notion of load addresses on their
+        // own; their sections have to be placed explicitly.
+        if parser.elf_type()? == elf::types::ET_REL {
+            return Err(Error::with_unsupported(format!(
+                "{} is a relocatable object; use a kernel module source with section addresses instead",
+                path.display()
+            )))
+        }
+
+        let backend = self.elf_backend(parser)?;
+        let mut resolver = ElfResolver::with_backend(path, backend)?;
+        let () = resolver.set_sym_source(self.sym_source);
+        #[cfg(feature = "dwarf")]
+        let () = resolver.set_skip_prologue(self.skip_prologue);
+        #[cfg(feature = "stats")]
+        let () = resolver.set_stats(self.stats.clone());
+        Ok(Rc::new(resolver))
+    }
+
+    /// Create an [`ElfResolver`] for the ELF file at `path`, using the
+    /// separate debug file represented by `debug_parser` for symbols and
+    /// debug information.
+    fn elf_resolver_from_debug_parser(
+        &self,
+        path: &Path,
+        parser: Rc<ElfParser>,
+        debug_parser: Rc<ElfParser>,
+    ) -> Result<Rc<ElfResolver>> {
+        let backend = self.elf_backend(debug_parser)?;
+        let mut resolver = ElfResolver::with_debug_backend(path, parser, backend)?;
+        let () = resolver.set_sym_source(self.sym_source);
+        #[cfg(feature = "dwarf")]
+        let () = resolver.set_skip_prologue(self.skip_prologue);
+        #[cfg(feature = "stats")]
+        let () = resolver.set_stats(self.stats.clone());
+        Ok(Rc::new(resolver))
+    }
+
+    /// Look up the separate debug file for the ELF file represented by
+    /// `parser` in the configured debug directories, based on its build
+    /// ID.
+    fn find_build_id_debug_file(&self, parser: &ElfParser) -> Result<Option<ElfParser>> {
+        if self.debug_dirs.is_empty() {
+            return Ok(None)
+        }
+
+        let build_id = match read_build_id(parser)? {
+            Some(build_id) if build_id.len() > 1 => build_id,
+            _ => return Ok(None),
+        };
+        let hex = hex_encode(&build_id);
+        let (dir, file) = hex.split_at(2);
+
+        for debug_dir in &self.debug_dirs {
+            let path = debug_dir
+                .join(".build-id")
+                .join(dir)
+                .join(format!("{file}.debug"));
+            let debug_parser = match ElfParser::open(&path) {
+                Ok(debug_parser) => debug_parser,
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => {
+                    log::warn!("failed to open debug file {}: {err}", path.display());
+                    continue
+                }
+            };
+
+            // A broken file in one directory should not prevent us from
+            // finding a usable one in another.
+            match read_build_id(&debug_parser) {
+                Ok(id) if id.as_deref() == Some(build_id.as_slice()) => {
+                    log::debug!("using debug file {}", path.display());
+                    return Ok(Some(debug_parser))
+                }
+                Ok(_) => log::warn!(
+                    "build ID of debug file {} does not match; ignoring it",
+                    path.display()
+                ),
+                Err(err) => log::warn!(
+                    "failed to read build ID of debug file {}: {err}",
+                    path.display()
+                ),
+            }
+        }
+        Ok(None)
     }
 
     fn create_elf_resolver(&self, path: &Path, file: &File) -> Result<Rc<ElfResolver>> {
-        let parser = Rc::new(ElfParser::open_file(file)?);
+        let parser = open_elf_parser(path, file, self.lenient_elf, self.sym_index_dir.as_deref())?;
+        self.elf_resolver_from_file_parser(path, Rc::new(parser))
+    }
+
+    /// Create an [`ElfResolver`] for the ELF file at `path`, represented
+    /// by `parser`, looking up a separate debug file if necessary.
+    fn elf_resolver_from_file_parser(
+        &self,
+        path: &Path,
+        parser: Rc<ElfParser>,
+    ) -> Result<Rc<ElfResolver>> {
+        if self.debug_syms && parser.find_section(".debug_info")?.is_none() {
+            if let Some(debug_parser) = self.find_build_id_debug_file(&parser)? {
+                return self.elf_resolver_from_debug_parser(path, parser, Rc::new(debug_parser))
+            }
+        }
         self.elf_resolver_from_parser(path, parser)
     }
 
-    fn elf_resolver<'slf>(&'slf self, path: &Path) -> Result<&'slf Rc<ElfResolver>> {
-        let (file, cell) = self.elf_cache.entry(path)?;
-        let resolver = cell.get_or_try_init(|| self.create_elf_resolver(path, file))?;
+    /// Create an [`ElfResolver`] for the ELF object of `len` bytes
+    /// provided by `reader`.
+    fn create_reader_resolver(&self, reader: &dyn ReadAt, len: u64) -> Result<Rc<ElfResolver>> {
+        let mut parser = ElfParser::from_reader(reader, len)?;
+        let () = parser.set_lenient(self.lenient_elf);
+        let parser = Rc::new(parser);
+        self.elf_resolver_from_parser(Path::new("<reader>"), parser)
+    }
+
+    /// Create an [`ElfResolver`] for the ELF file at `path`, using the
+    /// already opened debug file `debug_file` for symbols and debug
+    /// information.
+    fn create_elf_debug_resolver(&self, path: &Path, debug_file: &File) -> Result<Rc<ElfResolver>> {
+        let parser = Rc::new(ElfParser::open(path)?);
+        let debug_parser = Rc::new(ElfParser::open_file(debug_file)?);
+        self.elf_resolver_from_debug_parser(path, parser, debug_parser)
+    }
+
+    /// Canonicalize `path` for caching purposes, if configured to.
+    fn canonicalize<'path>(&self, path: &'path Path) -> Cow<'path, Path> {
+        if self.canonicalize_paths {
+            canonicalize_path(path)
+        } else {
+            Cow::Borrowed(path)
+        }
+    }
+
+    fn elf_resolver<'slf>(
+        &'slf self,
+        path: &Path,
+        debug_file: Option<&Path>,
+    ) -> Result<&'slf Rc<ElfResolver>> {
+        #[cfg(feature = "stats")]
+        let mut hit = true;
+        let canonicalize = |path| self.canonicalize(path);
+
+        let resolver = if let Some(debug_file) = debug_file {
+            let (file, cell) = self.elf_debug_cache.entry(&canonicalize(debug_file))?;
+            let resolvers = cell.get_or_init(InsertMap::new);
+            resolvers.get_or_try_insert(canonicalize(path).into_owned(), || {
+                #[cfg(feature = "stats")]
+                {
+                    hit = false;
+                }
+                self.create_elf_debug_resolver(path, file)
+            })?
+        } else {
+            let (file, cell) = self.elf_cache.entry(&canonicalize(path))?;
+            cell.get_or_try_init(|| {
+                #[cfg(feature = "stats")]
+                {
+                    hit = false;
+                }
+                self.create_elf_resolver(path, file)
+            })?
+        };
+
+        #[cfg(feature = "stats")]
+        let () = StatsCounters::inc(if hit {
+            &self.stats.cache_hits
+        } else {
+            &self.stats.cache_misses
+        });
         Ok(resolver)
     }
 
@@ -433,6 +1262,89 @@ impl Symbolizer {
         Ok(resolver)
     }
 
+    fn kmod_resolver<'slf>(
+        &'slf self,
+        module: &KernelModule,
+    ) -> Result<&'slf Rc<KernelModuleResolver>> {
+        let KernelModule {
+            path,
+            section_addrs,
+            _non_exhaustive: (),
+        } = module;
+
+        let (file, cell) = self.kmod_cache.entry(path)?;
+        let (parser, resolvers) = cell.get_or_try_init(|| {
+            let parser = Rc::new(ElfParser::open_file(file)?);
+            let resolvers = InsertMap::new();
+            Result::<_, Error>::Ok((parser, resolvers))
+        })?;
+
+        // Relocation of debug information is specific to where sections
+        // got placed, so module instances are distinguished by their
+        // section addresses.
+        let mut key = section_addrs
+            .iter()
+            .map(|(name, addr)| (name.clone(), *addr))
+            .collect::<Box<[_]>>();
+        let () = key.sort_unstable();
+        let resolver = resolvers.get_or_try_insert(key, || {
+            self.create_kmod_resolver(path, Rc::clone(parser), section_addrs)
+                .map(Rc::new)
+        })?;
+        Ok(resolver)
+    }
+
+    fn create_kmod_resolver(
+        &self,
+        path: &Path,
+        parser: Rc<ElfParser>,
+        section_addrs: &HashMap<String, Addr>,
+    ) -> Result<KernelModuleResolver> {
+        #[cfg_attr(not(feature = "dwarf"), allow(unused_mut))]
+        let mut resolver = KernelModuleResolver::new(path, Rc::clone(&parser), section_addrs)?;
+
+        #[cfg(feature = "dwarf")]
+        if self.debug_syms && parser.find_section(".debug_info")?.is_some() {
+            // DWARF data of relocatable objects needs to be relocated
+            // according to where sections got placed, which is specific
+            // to this module instance. Hence, use a dedicated parser.
+            let result = ElfParser::open(path).and_then(|mut parser| {
+                let () = parser.set_section_addrs(section_addrs.clone());
+                DwarfResolver::from_parser(Rc::new(parser), self.code_info)
+            });
+            match result {
+                Ok(dwarf) => resolver.set_dwarf(dwarf),
+                Err(err) => log::warn!(
+                    "failed to load DWARF information for {}: {err}",
+                    path.display()
+                ),
+            }
+        }
+        Ok(resolver)
+    }
+
+    fn create_wasm_resolver(&self, path: &Path, file: &File) -> Result<Rc<WasmResolver>> {
+        let resolver = WasmResolver::from_file(path.to_path_buf(), file)?;
+        Ok(Rc::new(resolver))
+    }
+
+    fn wasm_resolver<'slf>(&'slf self, path: &Path) -> Result<&'slf Rc<WasmResolver>> {
+        let (file, cell) = self.wasm_cache.entry(path)?;
+        let resolver = cell.get_or_try_init(|| self.create_wasm_resolver(path, file))?;
+        Ok(resolver)
+    }
+
+    fn create_perf_map_resolver(&self, path: &Path, file: &File) -> Result<Rc<PerfMapResolver>> {
+        let resolver = PerfMapResolver::from_file(path.to_path_buf(), file)?;
+        Ok(Rc::new(resolver))
+    }
+
+    fn perf_map_resolver<'slf>(&'slf self, path: &Path) -> Result<&'slf Rc<PerfMapResolver>> {
+        let (file, cell) = self.perf_map_cache.entry(path)?;
+        let resolver = cell.get_or_try_init(|| self.create_perf_map_resolver(path, file))?;
+        Ok(resolver)
+    }
+
     fn create_apk_resolver<'slf>(
         &'slf self,
         apk: &zip::Archive,
@@ -492,27 +1404,92 @@ impl Symbolizer {
     }
 
     fn resolve_addr_in_elf(&self, addr: Addr, path: &Path) -> Result<Symbolized> {
-        let resolver = self.elf_resolver(path)?;
+        let resolver = self.elf_resolver(path, None)?;
         let symbolized = self.symbolize_with_resolver(addr, &Resolver::Cached(resolver.deref()))?;
         Ok(symbolized)
     }
 
     /// Symbolize the given list of user space addresses in the provided
     /// process.
-    fn symbolize_user_addrs(&self, addrs: &[Addr], pid: Pid) -> Result<Vec<Symbolized>> {
+    ///
+    /// If `sorted` is `true`, `addrs` are known to be sorted in
+    /// ascending order already.
+    fn symbolize_user_addrs(
+        &self,
+        addrs: &[Addr],
+        pid: Pid,
+        resolve_plt: bool,
+        sorted: bool,
+    ) -> Result<Vec<Symbolized>> {
         struct SymbolizeHandler<'sym> {
             /// The "outer" `Symbolizer` instance.
             symbolizer: &'sym Symbolizer,
+            /// The process whose addresses we symbolize.
+            pid: Pid,
+            /// Whether to resolve PLT entries to their current targets.
+            resolve_plt: bool,
             /// Symbols representing the symbolized addresses.
             all_symbols: Vec<Symbolized<'sym>>,
         }
 
-        impl SymbolizeHandler<'_> {
-            fn handle_apk_addr(&mut self, addr: Addr, entry: &PathMapsEntry) -> Result<()> {
-                let file_off = addr - entry.range.start + entry.offset;
-                let apk_path = &entry.path.symbolic_path;
-                match self.symbolizer.apk_resolver(apk_path, file_off)? {
-                    Some((elf_resolver, elf_addr)) => {
+        impl<'sym> SymbolizeHandler<'sym> {
+            /// Symbolize the function that the PLT entry at `addr`
+            /// (`norm_addr` in `parser`) currently jumps to.
+            ///
+            /// `None` is returned if `addr` is not part of a PLT entry or
+            /// if the target has not been resolved yet.
+            fn resolve_plt_addr(
+                &self,
+                addr: Addr,
+                norm_addr: Addr,
+                parser: &ElfParser,
+            ) -> Result<Option<Symbolized<'sym>>> {
+                let slot = match parser.plt_got_slot(norm_addr)? {
+                    Some(slot) => slot,
+                    None => return Ok(None),
+                };
+                // The GOT slot address is relative to the object's load
+                // address, just like `norm_addr`.
+                let slot = slot.wrapping_add(addr.wrapping_sub(norm_addr));
+                let target = read_process_addr(self.pid, slot)?;
+
+                let mut entries = maps::parse(self.pid)?;
+                let entry = loop {
+                    match entries.next().transpose()? {
+                        Some(entry) if entry.range.contains(&target) => {
+                            match maps::filter_map_relevant(entry) {
+                                Some(entry) => break entry,
+                                None => return Ok(None),
+                            }
+                        }
+                        Some(_) => continue,
+                        None => return Ok(None),
+                    }
+                };
+
+                let path = &entry.path.maps_file;
+                let parser = ElfParser::open(path)
+                    .with_context(|| format!("failed to open map file {}", path.display()))?;
+                let file_off = target - entry.range.start + entry.offset;
+                let target = match elf_offset_to_address(file_off, &parser)? {
+                    Some(target) => target,
+                    None => return Ok(None),
+                };
+                // With lazy binding, unresolved slots point back into the
+                // PLT.
+                if parser.plt_got_slot(target)?.is_some() {
+                    return Ok(None)
+                }
+
+                let symbol = self.symbolizer.resolve_addr_in_elf(target, path)?;
+                Ok(Some(symbol))
+            }
+
+            fn handle_apk_addr(&mut self, addr: Addr, entry: &PathMapsEntry) -> Result<()> {
+                let file_off = addr - entry.range.start + entry.offset;
+                let apk_path = &entry.path.symbolic_path;
+                match self.symbolizer.apk_resolver(apk_path, file_off)? {
+                    Some((elf_resolver, elf_addr)) => {
                         let symbol = self.symbolizer.symbolize_with_resolver(
                             elf_addr,
                             &Resolver::Cached(elf_resolver.deref()),
@@ -533,6 +1510,18 @@ impl Symbolizer {
 
                 match elf_offset_to_address(file_off, &parser)? {
                     Some(norm_addr) => {
+                        if self.resolve_plt {
+                            let result = self
+                                .resolve_plt_addr(addr, norm_addr, &parser)
+                                .with_context(|| {
+                                    format!("failed to resolve PLT entry at {addr:#x}")
+                                })?;
+                            if let Some(symbol) = result {
+                                let () = self.all_symbols.push(symbol);
+                                return Ok(())
+                            }
+                        }
+
                         let symbol = self
                             .symbolizer
                             .resolve_addr_in_elf(norm_addr, path)
@@ -573,20 +1562,28 @@ impl Symbolizer {
         let entries = maps::parse(pid)?;
         let handler = SymbolizeHandler {
             symbolizer: self,
+            pid,
+            resolve_plt,
             all_symbols: Vec::with_capacity(addrs.len()),
         };
 
-        let handler = util::with_ordered_elems(
-            addrs,
-            |handler: &mut SymbolizeHandler<'_>| handler.all_symbols.as_mut_slice(),
-            |sorted_addrs| normalize_sorted_user_addrs_with_entries(sorted_addrs, entries, handler),
-        )?;
+        let handler = if sorted {
+            normalize_sorted_user_addrs_with_entries(addrs.iter().copied(), entries, handler)?
+        } else {
+            util::with_ordered_elems(
+                addrs,
+                |handler: &mut SymbolizeHandler<'_>| handler.all_symbols.as_mut_slice(),
+                |sorted_addrs| {
+                    normalize_sorted_user_addrs_with_entries(sorted_addrs, entries, handler)
+                },
+            )?
+        };
         Ok(handler.all_symbols)
     }
 
     fn create_ksym_resolver(&self, path: &Path, _file: &File) -> Result<Rc<KSymResolver>> {
         // TODO: Should really use `file` and not `path` for the instantiation.
-        let resolver = KSymResolver::load_file_name(path.to_path_buf())?;
+        let resolver = KSymResolver::load_file_name(path.to_path_buf(), self.name_arena)?;
         let resolver = Rc::new(resolver);
         Ok(resolver)
     }
@@ -601,6 +1598,7 @@ impl Symbolizer {
         let Kernel {
             kallsyms,
             kernel_image,
+            livepatches,
             _non_exhaustive: (),
         } = src;
 
@@ -623,7 +1621,7 @@ impl Symbolizer {
         };
 
         let elf_resolver = if let Some(image) = kernel_image {
-            let resolver = self.elf_resolver(image)?;
+            let resolver = self.elf_resolver(image, None)?;
             Some(resolver)
         } else {
             let release = uname_release()?.to_str().unwrap().to_string();
@@ -635,7 +1633,7 @@ impl Symbolizer {
             });
 
             if let Some(image) = kernel_image {
-                let result = self.elf_resolver(&image);
+                let result = self.elf_resolver(&image, None);
                 match result {
                     Ok(resolver) => Some(resolver),
                     Err(err) => {
@@ -651,7 +1649,12 @@ impl Symbolizer {
             }
         };
 
-        KernelResolver::new(ksym_resolver.cloned(), elf_resolver.cloned())
+        let patches = livepatches
+            .iter()
+            .map(|module| self.kmod_resolver(module).cloned())
+            .collect::<Result<Vec<_>>>()?;
+
+        KernelResolver::new(ksym_resolver.cloned(), elf_resolver.cloned(), patches)
     }
 
     /// Symbolize a list of addresses.
@@ -681,11 +1684,56 @@ impl Symbolizer {
     /// | Ksym   | symbol size                      | no                   | N/A                    |
     /// |        | source code location information | no                   | N/A                    |
     /// |        | inlined function information     | no                   | N/A                    |
+    /// | Wasm   | symbol size                      | yes                  | yes                    |
+    /// |        | source code location information | no                   | N/A                    |
+    /// |        | inlined function information     | no                   | N/A                    |
+    /// | Perf   | symbol size                      | yes                  | yes                    |
+    /// | map    | source code location information | no                   | N/A                    |
+    /// |        | inlined function information     | no                   | N/A                    |
+    ///
+    /// Addresses do not have to be sorted. Where the symbolization of a
+    /// source benefits from sorted input (e.g., for [`Source::Process`]),
+    /// already sorted input is detected and internal sorting skipped.
+    /// Callers that know their input to be sorted may want to use
+    /// [`Symbolizer::symbolize_sorted`] instead.
     #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all, fields(src = ?src, addrs = format_args!("{input:#x?}"))))]
     pub fn symbolize<'slf>(
         &'slf self,
         src: &Source,
         input: Input<&[u64]>,
+    ) -> Result<Vec<Symbolized<'slf>>> {
+        self.symbolize_impl(src, input, false)
+    }
+
+    /// Symbolize a list of addresses sorted in ascending order.
+    ///
+    /// This method behaves like [`Symbolizer::symbolize`], but the
+    /// caller promises that the provided addresses are sorted in
+    /// ascending order, saving the need to check for sortedness. This
+    /// is the common case for profilers processing samples in address
+    /// order.
+    ///
+    /// Sortedness is asserted in debug builds. In release builds,
+    /// unsorted input may cause an error to be reported for sources
+    /// that rely on sorted input.
+    #[cfg_attr(feature = "tracing", crate::log::instrument(skip_all, fields(src = ?src, addrs = format_args!("{input:#x?}"))))]
+    pub fn symbolize_sorted<'slf>(
+        &'slf self,
+        src: &Source,
+        input: Input<&[u64]>,
+    ) -> Result<Vec<Symbolized<'slf>>> {
+        debug_assert!(
+            input.into_inner().windows(2).all(|pair| pair[0] <= pair[1]),
+            "input addresses are not sorted"
+        );
+        self.symbolize_impl(src, input, true)
+    }
+
+    fn symbolize_impl<'slf>(
+        &'slf self,
+        src: &Source,
+        input: Input<&[u64]>,
+        sorted: bool,
     ) -> Result<Vec<Symbolized<'slf>>> {
         match src {
             Source::Apk(Apk {
@@ -715,14 +1763,39 @@ impl Symbolizer {
             },
             Source::Elf(Elf {
                 path,
+                debug_file,
+                overlay,
+                build_id,
+                inlined_fns,
+                sym_sources,
                 _non_exhaustive: (),
             }) => {
-                let resolver = self.elf_resolver(path)?;
+                let inlined_fns = inlined_fns.unwrap_or(self.inlined_fns);
+                let sym_sources = sym_sources.as_deref();
+                let resolver = self.elf_resolver(path, debug_file.as_deref())?;
+                if !has_build_id(resolver, build_id.as_deref())? {
+                    let count = input.into_inner().len();
+                    return Ok(vec![Symbolized::BuildIdMismatch; count])
+                }
+
+                let overlay = overlay
+                    .as_deref()
+                    .map(|sections| OverlayResolver::new(resolver, sections))
+                    .transpose()?;
+                let sym_resolver = match &overlay {
+                    Some(overlay) => Resolver::Uncached(overlay),
+                    None => Resolver::Cached(resolver.deref()),
+                };
                 match input {
                     Input::VirtOffset(addrs) => addrs
                         .iter()
                         .map(|addr| {
-                            self.symbolize_with_resolver(*addr, &Resolver::Cached(resolver.deref()))
+                            self.symbolize_with_resolver_impl(
+                                *addr,
+                                &sym_resolver,
+                                inlined_fns,
+                                sym_sources,
+                            )
                         })
                         .collect(),
                     Input::AbsAddr(..) => {
@@ -730,13 +1803,42 @@ impl Symbolizer {
                             "ELF symbolization does not support absolute address inputs",
                         ))
                     }
+                    Input::FileOffset(offsets) => offsets
+                        .iter()
+                        .map(
+                            |offset| match elf_offset_to_address(*offset, resolver.parser())? {
+                                Some(addr) => self.symbolize_with_resolver_impl(
+                                    addr,
+                                    &sym_resolver,
+                                    inlined_fns,
+                                    sym_sources,
+                                ),
+                                None => Ok(Symbolized::Unknown),
+                            },
+                        )
+                        .collect(),
+                }
+            }
+            Source::ElfReader(ElfReader {
+                reader,
+                len,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.create_reader_resolver(*reader, *len)?;
+                match input {
+                    Input::VirtOffset(addrs) => {
+                        self.symbolize_addrs(addrs, &Resolver::Uncached(resolver.deref()))
+                    }
+                    Input::AbsAddr(..) => Err(Error::with_unsupported(
+                        "ELF symbolization does not support absolute address inputs",
+                    )),
                     Input::FileOffset(offsets) => offsets
                         .iter()
                         .map(
                             |offset| match elf_offset_to_address(*offset, resolver.parser())? {
                                 Some(addr) => self.symbolize_with_resolver(
                                     addr,
-                                    &Resolver::Cached(resolver.deref()),
+                                    &Resolver::Uncached(resolver.deref()),
                                 ),
                                 None => Ok(Symbolized::Unknown),
                             },
@@ -763,8 +1865,28 @@ impl Symbolizer {
                 let symbols = self.symbolize_addrs(addrs, &Resolver::Uncached(resolver.deref()))?;
                 Ok(symbols)
             }
+            Source::KernelModule(module) => {
+                let addrs = match input {
+                    Input::AbsAddr(addrs) => addrs,
+                    Input::VirtOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "kernel module symbolization does not support virtual offsets",
+                        ))
+                    }
+                    Input::FileOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "kernel module symbolization does not support file offsets",
+                        ))
+                    }
+                };
+
+                let resolver = self.kmod_resolver(module)?;
+                let symbols = self.symbolize_addrs(addrs, &Resolver::Cached(resolver.deref()))?;
+                Ok(symbols)
+            }
             Source::Process(Process {
                 pid,
+                resolve_plt,
                 _non_exhaustive: (),
             }) => {
                 let addrs = match input {
@@ -781,7 +1903,7 @@ impl Symbolizer {
                     }
                 };
 
-                self.symbolize_user_addrs(addrs, *pid)
+                self.symbolize_user_addrs(addrs, *pid, *resolve_plt, sorted)
             }
             Source::Gsym(Gsym::Data(GsymData {
                 data,
@@ -827,6 +1949,393 @@ impl Symbolizer {
                 let symbols = self.symbolize_addrs(addrs, &Resolver::Cached(resolver.deref()))?;
                 Ok(symbols)
             }
+            Source::Wasm(Wasm {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let offsets = match input {
+                    Input::FileOffset(offsets) => offsets,
+                    Input::VirtOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "Wasm symbolization does not support virtual offset inputs",
+                        ))
+                    }
+                    Input::AbsAddr(..) => {
+                        return Err(Error::with_unsupported(
+                            "Wasm symbolization does not support absolute address inputs",
+                        ))
+                    }
+                };
+
+                let resolver = self.wasm_resolver(path)?;
+                let symbols = self.symbolize_addrs(offsets, &Resolver::Cached(resolver.deref()))?;
+                Ok(symbols)
+            }
+            Source::PerfMap(PerfMap {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let addrs = match input {
+                    Input::AbsAddr(addrs) => addrs,
+                    Input::VirtOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "perf map symbolization does not support virtual offset inputs",
+                        ))
+                    }
+                    Input::FileOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "perf map symbolization does not support file offset inputs",
+                        ))
+                    }
+                };
+
+                let resolver = self.perf_map_resolver(path)?;
+                let symbols = self.symbolize_addrs(addrs, &Resolver::Cached(resolver.deref()))?;
+                Ok(symbols)
+            }
+        }
+    }
+
+    /// Symbolize a list of module relative file offsets.
+    ///
+    /// Each frame is provided as a `(module_id, file_offset)` pair, where
+    /// `module_id` is used to look up the symbolization [`Source`] in
+    /// `modules`. This is convenient for consumers that already captured
+    /// or normalized addresses to their respective modules (e.g., by
+    /// using the output of [`Normalizer`][crate::normalize::Normalizer]),
+    /// as no load address handling is necessary.
+    ///
+    /// Offsets are interpreted as [`Input::FileOffset`] and so all
+    /// referenced sources are required to support this input type.
+    ///
+    /// This function returns exactly one [`Symbolized`] object for each
+    /// input frame, in the order of input frames. Frames referencing a
+    /// module not present in `modules` are reported as
+    /// [`Symbolized::Unknown`]. The same is true for all frames of a
+    /// module that failed to be symbolized (e.g., because its file could
+    /// not be read or is malformed), without affecting frames of other
+    /// modules.
+    pub fn symbolize_module_offsets<'slf, K>(
+        &'slf self,
+        modules: &HashMap<K, Source>,
+        frames: &[(K, u64)],
+    ) -> Result<Vec<Symbolized<'slf>>>
+    where
+        K: Eq + Hash,
+    {
+        // Group offsets by module, so that we can symbolize them in
+        // batches, while remembering their original position.
+        let mut batches = HashMap::<&K, (Vec<usize>, Vec<u64>)>::new();
+        for (idx, (module, offset)) in frames.iter().enumerate() {
+            if modules.contains_key(module) {
+                let (idxs, offsets) = batches.entry(module).or_default();
+                let () = idxs.push(idx);
+                let () = offsets.push(*offset);
+            } else {
+                log::debug!("no symbolization source registered for frame {idx}");
+            }
+        }
+
+        let mut results = Vec::with_capacity(frames.len());
+        let () = results.resize_with(frames.len(), || Symbolized::Unknown);
+
+        for (module, (idxs, offsets)) in batches {
+            // SANITY: We only created batches for modules we know about.
+            let src = modules.get(module).unwrap();
+            // A single module failing to symbolize, e.g., because its
+            // file is unreadable, should not render the remaining
+            // frames unusable. Its frames are left as unknown.
+            let syms = match self.symbolize(src, Input::FileOffset(&offsets)) {
+                Ok(syms) => syms,
+                Err(err) => {
+                    log::warn!("failed to symbolize frames of module {src:?}: {err}");
+                    continue
+                }
+            };
+            debug_assert_eq!(syms.len(), idxs.len());
+            let () = idxs
+                .into_iter()
+                .zip(syms)
+                .for_each(|(idx, sym)| results[idx] = sym);
+        }
+        Ok(results)
+    }
+
+    /// Symbolize the frames of a stack trace.
+    ///
+    /// This method is a variant of [`symbolize`][Self::symbolize] that
+    /// allows for stating on a per-frame basis whether an address is a
+    /// return address. Return addresses ([`Frame::Return`]) are
+    /// adjusted by one before symbolization, so that they are
+    /// attributed to the call instruction and not the one following
+    /// it. The reported [`Sym::offset`][crate::symbolize::Sym::offset]
+    /// is relative to the adjusted address. Exact addresses
+    /// ([`Frame::Exact`]), such as that of the leaf frame, are
+    /// symbolized unchanged. Use [`Frame::from_trace`] to apply the
+    /// common convention to a list of raw addresses.
+    pub fn symbolize_frames<'slf>(
+        &'slf self,
+        src: &Source,
+        input: Input<&[Frame]>,
+    ) -> Result<Vec<Symbolized<'slf>>> {
+        let addrs = input
+            .into_inner()
+            .iter()
+            .map(Frame::symbolization_addr)
+            .collect::<Vec<_>>();
+        let input = input.map(|_| addrs.as_slice());
+        self.symbolize(src, input)
+    }
+
+    /// Symbolize a run-length encoded list of addresses.
+    ///
+    /// This method is a variant of [`symbolize`][Self::symbolize] for
+    /// input in which addresses repeat, as is common for profiling
+    /// samples. Each `(addr, count)` pair is expected to refer to a
+    /// distinct address, which gets symbolized only once. Counts are
+    /// not interpreted and merely passed through, alongside the
+    /// symbolization result, in the order of input pairs.
+    pub fn symbolize_counted<'slf>(
+        &'slf self,
+        src: &Source,
+        input: Input<&[(u64, usize)]>,
+    ) -> Result<Vec<(Symbolized<'slf>, usize)>> {
+        let pairs = input.into_inner();
+        let addrs = pairs.iter().map(|(addr, _count)| *addr).collect::<Vec<_>>();
+        let input = input.map(|_| addrs.as_slice());
+        let syms = self.symbolize(src, input)?;
+        debug_assert_eq!(syms.len(), pairs.len());
+        let counted = syms
+            .into_iter()
+            .zip(pairs)
+            .map(|(sym, (_addr, count))| (sym, *count))
+            .collect();
+        Ok(counted)
+    }
+
+    /// Symbolize a list of addresses of the given [`AddressKind`].
+    ///
+    /// This method is a variant of [`symbolize`][Self::symbolize] that
+    /// makes the interpretation of the provided addresses explicit.
+    /// [`AddressKind::FileVirtual`] addresses are symbolized exactly as
+    /// [`Input::VirtOffset`] would be. For [`AddressKind::Runtime`]
+    /// addresses, the provided load address is used to convert them into
+    /// file virtual addresses first. Runtime addresses are currently only
+    /// supported for [`Source::Elf`].
+    ///
+    /// Callers that previously subtracted a load base themselves (with a
+    /// base of zero for fixed-address binaries) and then used
+    /// [`Input::VirtOffset`] should pass the unmodified runtime addresses
+    /// along with the address at which the file got mapped, instead.
+    ///
+    /// [`AddressKind::TextOffset`] offsets are converted based on the
+    /// address of the file's `.text` section and are likewise only
+    /// supported for [`Source::Elf`]. Offsets that cannot be mapped
+    /// are reported as [`Symbolized::Unknown`].
+    pub fn symbolize_with_kind<'slf>(
+        &'slf self,
+        src: &Source,
+        kind: AddressKind,
+        addrs: &[Addr],
+    ) -> Result<Vec<Symbolized<'slf>>> {
+        if let AddressKind::FileVirtual = kind {
+            return self.symbolize(src, Input::VirtOffset(addrs))
+        }
+
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_file,
+                overlay,
+                build_id,
+                inlined_fns,
+                sym_sources,
+                _non_exhaustive: (),
+            }) => {
+                let inlined_fns = inlined_fns.unwrap_or(self.inlined_fns);
+                let sym_sources = sym_sources.as_deref();
+                let resolver = self.elf_resolver(path, debug_file.as_deref())?;
+                if !has_build_id(resolver, build_id.as_deref())? {
+                    return Ok(vec![Symbolized::BuildIdMismatch; addrs.len()])
+                }
+
+                let overlay = overlay
+                    .as_deref()
+                    .map(|sections| OverlayResolver::new(resolver, sections))
+                    .transpose()?;
+                let sym_resolver = match &overlay {
+                    Some(overlay) => Resolver::Uncached(overlay),
+                    None => Resolver::Cached(resolver.deref()),
+                };
+                let parser = resolver.parser();
+                let to_file_addr = |addr| match kind {
+                    AddressKind::FileVirtual => Ok(Some(addr)),
+                    AddressKind::Runtime { load_addr } => {
+                        elf_runtime_to_address(addr, load_addr, parser)
+                    }
+                    AddressKind::TextOffset => elf_text_offset_to_address(addr, parser),
+                };
+                addrs
+                    .iter()
+                    .map(|addr| match to_file_addr(*addr)? {
+                        Some(addr) => self.symbolize_with_resolver_impl(
+                            addr,
+                            &sym_resolver,
+                            inlined_fns,
+                            sym_sources,
+                        ),
+                        None => Ok(Symbolized::Unknown),
+                    })
+                    .collect()
+            }
+            _ => Err(Error::with_unsupported(format!(
+                "symbolization of {kind:?} addresses is only supported for ELF sources"
+            ))),
+        }
+    }
+
+    /// Retrieve statistics about the work performed by this
+    /// `Symbolizer` so far.
+    ///
+    /// Counters cover ELF symbolization (including ELF files inside
+    /// APKs and ones used for process symbolization).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Stats {
+        self.stats.snapshot()
+    }
+
+    /// Populate the internal caches with the ELF files at the provided
+    /// paths.
+    ///
+    /// Each file is opened and its symbol tables are parsed, just as
+    /// would happen lazily on the first symbolization request for a
+    /// [`Source::Elf`] referencing it. Doing so upfront can be used to
+    /// move the associated cost out of latency sensitive paths, e.g.,
+    /// when a large number of modules is known at startup. Files are
+    /// parsed concurrently, using up to as many threads as the system
+    /// provides parallelism.
+    ///
+    /// A failure to process one file does not prevent processing of the
+    /// remaining ones. All failures are reported, along with the path
+    /// of the file in question.
+    pub fn warm_up<P>(&self, paths: &[P]) -> Vec<(PathBuf, Error)>
+    where
+        P: AsRef<Path>,
+    {
+        let mut errors = Vec::new();
+        // The caches are not thread-safe, so we have to look up entries
+        // on the calling thread.
+        let mut pending = Vec::new();
+        let mut cells = Vec::new();
+        for path in paths {
+            let path = path.as_ref();
+            match self.elf_cache.entry(&self.canonicalize(path)) {
+                Ok((_file, cell)) if cell.get().is_some() => {
+                    #[cfg(feature = "stats")]
+                    let () = StatsCounters::inc(&self.stats.cache_hits);
+                }
+                Ok((file, cell)) => {
+                    let () = pending.push((path, file));
+                    let () = cells.push(cell);
+                }
+                Err(err) => errors.push((path.to_path_buf(), err)),
+            }
+        }
+
+        // Parsing of the symbol tables is the expensive part and it is
+        // independent for each file, so it is done concurrently.
+        let lenient = self.lenient_elf;
+        let sym_index_dir = self.sym_index_dir.as_deref();
+        let threads = thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1);
+        let chunk_size = ((pending.len() + threads - 1) / threads).max(1);
+        let parsers = thread::scope(|scope| {
+            let handles = pending
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(path, file)| {
+                                let parser = open_elf_parser(path, file, lenient, sym_index_dir)?;
+                                let () = parser.parse_syms()?;
+                                Ok(parser)
+                            })
+                            .collect::<Vec<Result<ElfParser>>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| match handle.join() {
+                    Ok(parsers) => parsers,
+                    Err(panic) => panic::resume_unwind(panic),
+                })
+                .collect::<Vec<_>>()
+        });
+
+        for (((path, _file), cell), result) in pending.into_iter().zip(cells).zip(parsers) {
+            let result = result
+                .and_then(|parser| {
+                    cell.get_or_try_init(|| {
+                        #[cfg(feature = "stats")]
+                        let () = StatsCounters::inc(&self.stats.cache_misses);
+                        self.elf_resolver_from_file_parser(path, Rc::new(parser))
+                    })
+                })
+                // A separate debug file may still need parsing.
+                .and_then(|resolver| resolver.warm_up());
+            if let Err(err) = result {
+                let () = errors.push((path.to_path_buf(), err));
+            }
+        }
+        errors
+    }
+
+    /// Eagerly parse the symbol and line tables of an ELF file and
+    /// capture them in a read-only [`FrozenResolver`].
+    ///
+    /// The resulting [`FrozenResolver`] owns all the data it needs and
+    /// can be shared between threads for lock-free lookups of arbitrary
+    /// addresses. The configuration of this `Symbolizer` (e.g., whether
+    /// to demangle symbol names or to report source code information)
+    /// is applied upfront and captured as well.
+    ///
+    /// Only [`Source::Elf`] sources without an
+    /// [`overlay`][Elf::overlay] are supported.
+    pub fn freeze(&self, src: &Source) -> Result<FrozenResolver> {
+        match src {
+            Source::Elf(Elf {
+                path,
+                debug_file,
+                overlay,
+                build_id,
+                inlined_fns: _,
+                sym_sources,
+                _non_exhaustive: (),
+            }) => {
+                if overlay.is_some() {
+                    return Err(Error::with_unsupported(
+                        "freezing ELF sources with an overlay is not supported",
+                    ))
+                }
+
+                let resolver = self.elf_resolver(path, debug_file.as_deref())?;
+                let opts = FreezeOpts {
+                    sym_sources: sym_sources.as_deref(),
+                    build_id_mismatch: !has_build_id(resolver, build_id.as_deref())?,
+                    code_info: self.code_info,
+                    line_flags: self.line_flags,
+                    check_source_files: self.check_source_files,
+                    max_sym_offset: self.max_sym_offset,
+                };
+                FrozenResolver::from_elf(resolver, &opts, |name, lang| {
+                    self.maybe_demangle(name, lang)
+                })
+            }
+            _ => Err(Error::with_unsupported("only ELF sources can be frozen")),
         }
     }
 
@@ -864,9 +2373,20 @@ impl Symbolizer {
             },
             Source::Elf(Elf {
                 path,
+                debug_file,
+                overlay,
+                build_id,
+                inlined_fns,
+                sym_sources,
                 _non_exhaustive: (),
             }) => {
-                let resolver = self.elf_resolver(path)?;
+                let inlined_fns = inlined_fns.unwrap_or(self.inlined_fns);
+                let sym_sources = sym_sources.as_deref();
+                let resolver = self.elf_resolver(path, debug_file.as_deref())?;
+                if !has_build_id(resolver, build_id.as_deref())? {
+                    return Ok(Symbolized::BuildIdMismatch)
+                }
+
                 let addr = match input {
                     Input::VirtOffset(addr) => addr,
                     Input::AbsAddr(..) => {
@@ -882,7 +2402,46 @@ impl Symbolizer {
                     }
                 };
 
-                self.symbolize_with_resolver(addr, &Resolver::Cached(resolver.deref()))
+                match overlay {
+                    Some(sections) => {
+                        let overlay = OverlayResolver::new(resolver, sections)?;
+                        self.symbolize_with_resolver_impl(
+                            addr,
+                            &Resolver::Uncached(&overlay),
+                            inlined_fns,
+                            sym_sources,
+                        )
+                    }
+                    None => self.symbolize_with_resolver_impl(
+                        addr,
+                        &Resolver::Cached(resolver.deref()),
+                        inlined_fns,
+                        sym_sources,
+                    ),
+                }
+            }
+            Source::ElfReader(ElfReader {
+                reader,
+                len,
+                _non_exhaustive: (),
+            }) => {
+                let resolver = self.create_reader_resolver(*reader, *len)?;
+                let addr = match input {
+                    Input::VirtOffset(addr) => addr,
+                    Input::AbsAddr(..) => {
+                        return Err(Error::with_unsupported(
+                            "ELF symbolization does not support absolute address inputs",
+                        ))
+                    }
+                    Input::FileOffset(offset) => {
+                        match elf_offset_to_address(offset, resolver.parser())? {
+                            Some(addr) => addr,
+                            None => return Ok(Symbolized::Unknown),
+                        }
+                    }
+                };
+
+                self.symbolize_with_resolver(addr, &Resolver::Uncached(resolver.deref()))
             }
             Source::Kernel(kernel) => {
                 let addr = match input {
@@ -902,8 +2461,27 @@ impl Symbolizer {
                 let resolver = Rc::new(self.create_kernel_resolver(kernel)?);
                 self.symbolize_with_resolver(addr, &Resolver::Uncached(resolver.deref()))
             }
+            Source::KernelModule(module) => {
+                let addr = match input {
+                    Input::AbsAddr(addr) => addr,
+                    Input::VirtOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "kernel module symbolization does not support virtual offsets",
+                        ))
+                    }
+                    Input::FileOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "kernel module symbolization does not support file offsets",
+                        ))
+                    }
+                };
+
+                let resolver = self.kmod_resolver(module)?;
+                self.symbolize_with_resolver(addr, &Resolver::Cached(resolver.deref()))
+            }
             Source::Process(Process {
                 pid,
+                resolve_plt,
                 _non_exhaustive: (),
             }) => {
                 let addr = match input {
@@ -920,7 +2498,7 @@ impl Symbolizer {
                     }
                 };
 
-                let mut symbols = self.symbolize_user_addrs(&[addr], *pid)?;
+                let mut symbols = self.symbolize_user_addrs(&[addr], *pid, *resolve_plt, true)?;
                 debug_assert!(symbols.len() <= 1, "{symbols:#?}");
                 Ok(symbols.pop().unwrap_or(Symbolized::Unknown))
             }
@@ -966,6 +2544,48 @@ impl Symbolizer {
                 let resolver = self.gsym_resolver(path)?;
                 self.symbolize_with_resolver(addr, &Resolver::Cached(resolver.deref()))
             }
+            Source::Wasm(Wasm {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let offset = match input {
+                    Input::FileOffset(offset) => offset,
+                    Input::VirtOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "Wasm symbolization does not support virtual offset inputs",
+                        ))
+                    }
+                    Input::AbsAddr(..) => {
+                        return Err(Error::with_unsupported(
+                            "Wasm symbolization does not support absolute address inputs",
+                        ))
+                    }
+                };
+
+                let resolver = self.wasm_resolver(path)?;
+                self.symbolize_with_resolver(offset, &Resolver::Cached(resolver.deref()))
+            }
+            Source::PerfMap(PerfMap {
+                path,
+                _non_exhaustive: (),
+            }) => {
+                let addr = match input {
+                    Input::AbsAddr(addr) => addr,
+                    Input::VirtOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "perf map symbolization does not support virtual offset inputs",
+                        ))
+                    }
+                    Input::FileOffset(..) => {
+                        return Err(Error::with_unsupported(
+                            "perf map symbolization does not support file offset inputs",
+                        ))
+                    }
+                };
+
+                let resolver = self.perf_map_resolver(path)?;
+                self.symbolize_with_resolver(addr, &Resolver::Cached(resolver.deref()))
+            }
         }
     }
 }
@@ -981,6 +2601,7 @@ impl Default for Symbolizer {
 mod tests {
     use super::*;
 
+    use std::fs::read_to_string;
     use std::mem::transmute;
 
     use crate::elf::ElfParser;
@@ -1023,6 +2644,10 @@ mod tests {
             file: Cow::Borrowed(OsStr::new("source.c")),
             line: Some(1),
             column: Some(2),
+            md5: None,
+            flags: None,
+            context: None,
+            file_exists: None,
             _non_exhaustive: (),
         };
         assert_eq!(info.to_path(), Path::new("source.c"));
@@ -1031,23 +2656,75 @@ mod tests {
         assert_eq!(info.to_path(), Path::new("/foobar/source.c"));
     }
 
-    /// Make sure that we can demangle symbols.
+    /// Check that we fold consecutive inlined calls to the same
+    /// function as expected.
     #[test]
-    fn demangle() {
-        if !cfg!(feature = "demangle") {
-            return
-        }
-
-        let symbol = Cow::Borrowed("_ZN4core9panicking9panic_fmt17h5f1a6fd39197ad62E");
-        let name = maybe_demangle(symbol, SrcLang::Rust);
-        assert_eq!(name, "core::panicking::panic_fmt");
+    fn inlined_fn_folding() {
+        let inlined_fn = |name: &'static str, line| InlinedFn {
+            name: Cow::Borrowed(name),
+            code_info: Some(CodeInfo {
+                dir: None,
+                file: Cow::Borrowed(OsStr::new("source.c")),
+                line: Some(line),
+                column: None,
+                md5: None,
+                flags: None,
+                context: None,
+                file_exists: None,
+                _non_exhaustive: (),
+            }),
+            repeat: 1,
+            _non_exhaustive: (),
+        };
 
-        let symbol = Cow::Borrowed("_ZStlsISt11char_traitsIcEERSt13basic_ostreamIcT_ES5_PKc");
-        let name = maybe_demangle(symbol, SrcLang::Cpp);
+        let inlined = vec![
+            inlined_fn("outer", 1),
+            inlined_fn("recurse", 2),
+            inlined_fn("recurse", 3),
+            inlined_fn("recurse", 4),
+            inlined_fn("inner", 5),
+            inlined_fn("recurse", 6),
+        ];
+        let folded = fold_inlined_fns(inlined);
+        let folded = folded
+            .iter()
+            .map(|inlined_fn| {
+                (
+                    inlined_fn.name.as_ref(),
+                    inlined_fn.code_info.as_ref().unwrap().line.unwrap(),
+                    inlined_fn.repeat,
+                )
+            })
+            .collect::<Vec<_>>();
         assert_eq!(
-            name,
-            "std::basic_ostream<char, std::char_traits<char> >& std::operator<< <std::char_traits<char> >(std::basic_ostream<char, std::char_traits<char> >&, char const*)"
+            folded,
+            vec![
+                ("outer", 1, 1),
+                ("recurse", 4, 3),
+                ("inner", 5, 1),
+                ("recurse", 6, 1)
+            ]
         );
+
+        assert_eq!(fold_inlined_fns(Vec::new()), Vec::new());
+    }
+
+    /// Check that we strip leading underscores from symbol names only
+    /// where appropriate.
+    #[test]
+    fn leading_underscore_stripping() {
+        let name = strip_leading_underscore(Cow::Borrowed("_start"), SrcLang::Unknown);
+        assert_eq!(name, "start");
+        let name = strip_leading_underscore(Cow::Owned("__libc".to_string()), SrcLang::C);
+        assert_eq!(name, "_libc");
+        let name = strip_leading_underscore(Cow::Borrowed("main"), SrcLang::C);
+        assert_eq!(name, "main");
+
+        let symbol = "_ZN4core9panicking9panic_fmt17h5f1a6fd39197ad62E";
+        let name = strip_leading_underscore(Cow::Borrowed(symbol), SrcLang::Rust);
+        assert_eq!(name, symbol);
+        let name = strip_leading_underscore(Cow::Borrowed(symbol), SrcLang::Cpp);
+        assert_eq!(name, symbol);
     }
 
     /// Make sure that we error out as expected on certain input
@@ -1157,4 +2834,320 @@ mod tests {
         assert_eq!(result.name, "the_answer");
         assert_eq!(result.addr, sym.addr);
     }
+
+    /// Check that we can symbolize offsets relative to the start of the
+    /// `.text` section.
+    #[test]
+    fn symbolize_text_offsets() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("libtest-so.so");
+        let parser = ElfParser::open(&path).unwrap();
+        let opts = FindAddrOpts {
+            sym_type: SymType::Function,
+            ..Default::default()
+        };
+        let syms = parser.find_addr("the_answer", &opts).unwrap();
+        let sym = syms.first().unwrap();
+        let text = parser.find_section(".text").unwrap().unwrap();
+        let text_addr = parser.section_headers().unwrap()[text].sh_addr;
+        assert_ne!(text_addr, 0);
+
+        let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+        let symbolizer = Symbolizer::new();
+        let offset = sym.addr - text_addr;
+        let results = symbolizer
+            .symbolize_with_kind(
+                &src,
+                AddressKind::TextOffset,
+                &[offset, offset + 1, u64::MAX],
+            )
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        let result = results[0].as_sym().unwrap();
+        assert_eq!(result.name, "the_answer");
+        assert_eq!(result.addr, sym.addr);
+        assert_eq!(result.offset, 0);
+        let result = results[1].as_sym().unwrap();
+        assert_eq!(result.name, "the_answer");
+        assert_eq!(result.offset, 1);
+        // Offsets not mapping to any address can't be symbolized.
+        assert_eq!(results[2], Symbolized::Unknown);
+    }
+
+    /// Check that we can symbolize addresses in a file with multiple
+    /// separately mapped executable segments.
+    #[test]
+    fn symbolize_multiple_exec_segments() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-multi-exec.bin");
+        let parser = ElfParser::open(&path).unwrap();
+        let phdrs = parser.program_headers().unwrap();
+        let exec_loads = phdrs
+            .iter()
+            .filter(|phdr| {
+                phdr.p_type == elf::types::PT_LOAD && phdr.p_flags & elf::types::PF_X != 0
+            })
+            .count();
+        assert_eq!(exec_loads, 2);
+
+        let opts = FindAddrOpts {
+            sym_type: SymType::Function,
+            ..Default::default()
+        };
+        let first = parser.find_addr("first", &opts).unwrap()[0].addr;
+        let second = parser.find_addr("second", &opts).unwrap()[0].addr;
+        let first_off = parser.find_file_offset(first).unwrap().unwrap();
+        let second_off = parser.find_file_offset(second).unwrap().unwrap();
+        assert_ne!(first_off, second_off);
+
+        for (offset, addr) in [(first_off, first), (second_off, second)] {
+            assert_eq!(elf_offset_to_address(offset, &parser).unwrap(), Some(addr));
+        }
+
+        let src = symbolize::Source::Elf(symbolize::Elf::new(&path));
+        let symbolizer = Symbolizer::new();
+        let results = symbolizer
+            .symbolize(&src, Input::FileOffset(&[first_off, second_off + 1]))
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        let result = results[0].as_sym().unwrap();
+        assert_eq!(result.name, "first");
+        assert_eq!(result.offset, 0);
+        let result = results[1].as_sym().unwrap();
+        assert_eq!(result.name, "second");
+        assert_eq!(result.offset, 1);
+
+        let base = parser.load_base().unwrap().unwrap();
+        let load_addr = 0x7f0000000000;
+        let results = symbolizer
+            .symbolize_with_kind(
+                &src,
+                AddressKind::Runtime { load_addr },
+                &[load_addr + first - base, load_addr + second - base],
+            )
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_sym().unwrap().name, "first");
+        assert_eq!(results[1].as_sym().unwrap().name, "second");
+    }
+
+    /// Check that ELF resolvers are shared between different paths to
+    /// the same file if path canonicalization is enabled.
+    #[test]
+    fn path_canonicalization() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let other = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("..")
+            .join("data")
+            .join("test-stable-addresses.bin");
+
+        let symbolizer = Symbolizer::new();
+        let resolver = symbolizer.elf_resolver(&path, None).unwrap();
+        let other_resolver = symbolizer.elf_resolver(&other, None).unwrap();
+        assert!(!Rc::ptr_eq(resolver, other_resolver));
+
+        let symbolizer = Symbolizer::builder()
+            .enable_path_canonicalization(true)
+            .build();
+        let resolver = symbolizer.elf_resolver(&path, None).unwrap();
+        let other_resolver = symbolizer.elf_resolver(&other, None).unwrap();
+        assert!(Rc::ptr_eq(resolver, other_resolver));
+        // The file name is reported as originally provided.
+        assert_eq!(resolver.file_name(), path);
+
+        // Failure to canonicalize is not fatal and the path is used as
+        // is.
+        let path = Path::new("/does/not/exist");
+        assert_eq!(canonicalize_path(path), path);
+    }
+
+    /// Check that we report source code context if asked to.
+    #[test]
+    fn source_context_reporting() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+
+        let symbolizer = Symbolizer::new();
+        let sym = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        let code_info = sym.code_info.unwrap();
+        assert_eq!(code_info.context, None);
+
+        let symbolizer = Symbolizer::builder().set_source_context(Some(1)).build();
+        let sym = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        let code_info = sym.code_info.unwrap();
+        let line = code_info.line.unwrap();
+        let context = code_info.context.as_ref().unwrap();
+        assert_eq!(context.first_line, line - 1);
+        assert_eq!(context.lines.len(), 3);
+
+        let source = read_to_string(code_info.to_path()).unwrap();
+        let expected = source
+            .lines()
+            .skip(line as usize - 2)
+            .take(3)
+            .collect::<Vec<_>>();
+        assert_eq!(*context.lines, expected);
+
+        // Source files that cannot be read result in no context.
+        let code_info = CodeInfo {
+            dir: None,
+            file: Cow::Borrowed(OsStr::new("/does/not/exist.c")),
+            line: Some(1),
+            column: None,
+            md5: None,
+            flags: None,
+            context: None,
+            file_exists: None,
+            _non_exhaustive: (),
+        };
+        assert_eq!(symbolizer.source_context(&code_info, 1), None);
+    }
+
+    /// Check that we report whether source files exist if asked to.
+    #[test]
+    fn source_file_checking() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+
+        let symbolizer = Symbolizer::new();
+        let sym = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(sym.code_info.unwrap().file_exists, None);
+
+        let symbolizer = Symbolizer::builder()
+            .enable_source_file_checks(true)
+            .build();
+        let sym = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(sym.code_info.unwrap().file_exists, Some(true));
+
+        // The line table of this file references an assembly source
+        // file under a `.c` name, which does not exist.
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-prologue.bin");
+        let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+        let sym = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x1000))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        let code_info = sym.code_info.unwrap();
+        assert_eq!(code_info.file, OsStr::new("test-prologue.c"));
+        assert_eq!(code_info.file_exists, Some(false));
+    }
+
+    /// Check that we gather statistics about the work performed.
+    #[cfg(feature = "stats")]
+    #[test]
+    fn stats_gathering() {
+        let symbolizer = Symbolizer::new();
+        assert_eq!(symbolizer.stats(), Stats::default());
+
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+        for _ in 0..2 {
+            let sym = symbolizer
+                .symbolize_single(&src, Input::VirtOffset(0x2000100))
+                .unwrap()
+                .into_sym()
+                .unwrap();
+            assert_eq!(sym.name, "factorial");
+        }
+
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-no-dwarf.bin");
+        let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+        let sym = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(sym.name, "factorial");
+
+        let stats = symbolizer.stats();
+        assert_eq!(stats.sym_lookups, 3);
+        assert_eq!(stats.dwarf_hits, 2);
+        assert_eq!(stats.elf_fallbacks, 1);
+        assert_eq!(stats.cache_hits, 1);
+        assert_eq!(stats.cache_misses, 2);
+    }
+
+    /// Check that warming up the symbolizer populates its caches.
+    #[cfg(feature = "stats")]
+    #[test]
+    fn cache_warm_up() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses.bin");
+        let symbolizer = Symbolizer::new();
+        let errors = symbolizer.warm_up(&[&path]);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(symbolizer.stats().cache_misses, 1);
+
+        let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+        let _sym = symbolizer
+            .symbolize_single(&src, Input::VirtOffset(0x2000100))
+            .unwrap();
+        let stats = symbolizer.stats();
+        assert_eq!(stats.cache_hits, 1);
+        assert_eq!(stats.cache_misses, 1);
+    }
+
+    /// Check that resolvers for kernel modules are cached per module
+    /// instance.
+    #[test]
+    fn kmod_resolver_caching() {
+        let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("test-stable-addresses-rel-dwarf.bin");
+        let section_addrs = HashMap::from([(".text.factorial".to_string(), 0xffffffffc0001000)]);
+        let module = KernelModule::new(&path, section_addrs);
+        let symbolizer = Symbolizer::new();
+
+        let resolver = symbolizer.kmod_resolver(&module).unwrap();
+        let cached = symbolizer.kmod_resolver(&module).unwrap();
+        assert!(Rc::ptr_eq(resolver, cached));
+
+        // A module instance loaded at a different address requires its
+        // own resolver.
+        let section_addrs = HashMap::from([(".text.factorial".to_string(), 0xffffffffc0005000)]);
+        let other = KernelModule::new(&path, section_addrs);
+        let other_resolver = symbolizer.kmod_resolver(&other).unwrap();
+        assert!(!Rc::ptr_eq(resolver, other_resolver));
+
+        let src = Source::from(other);
+        let result = symbolizer
+            .symbolize_single(&src, Input::AbsAddr(0xffffffffc0005004))
+            .unwrap();
+        let sym = result.as_sym().unwrap();
+        assert_eq!(sym.name, "factorial");
+        assert!(sym.code_info.is_some());
+    }
 }
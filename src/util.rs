@@ -1,11 +1,14 @@
 use std::cmp::Ordering;
 use std::ffi::CStr;
 use std::ffi::CString;
+use std::fs::File;
 use std::io;
 use std::iter;
 use std::mem::align_of;
 use std::mem::size_of;
+use std::mem::swap;
 use std::mem::MaybeUninit;
+use std::os::unix::fs::FileExt as _;
 use std::os::unix::io::RawFd;
 use std::slice;
 
@@ -31,6 +34,8 @@ fn reorder<T, U>(array: &mut [T], indices: Vec<(U, usize)>) {
 /// invoke a function `handle` on the vector, take the result of this function
 /// and "extract" a mutable reference to a slice, reordered this slice in such a
 /// way that the original order of `slice` is preserved.
+///
+/// If `slice` is already sorted, sorting and reordering are skipped.
 pub(crate) fn with_ordered_elems<T, U, E, H, R, Err>(
     slice: &[T],
     extract: E,
@@ -46,14 +51,54 @@ where
         .enumerate()
         .map(|(idx, t)| (*t, idx))
         .collect::<Vec<_>>();
-    let () = vec.sort_unstable();
+    // Callers frequently provide input that is sorted already (e.g.,
+    // samples captured in address order), in which case there is no
+    // need to sort and restore the original order.
+    let sorted = slice.windows(2).all(|pair| pair[0] <= pair[1]);
+    if !sorted {
+        let () = vec.sort_unstable();
+    }
 
     let mut result = handle(vec.iter().map(|(t, _idx)| *t))?;
-    let () = reorder(extract(&mut result), vec);
+    if !sorted {
+        let () = reorder(extract(&mut result), vec);
+    }
     Ok(result)
 }
 
 
+/// A source of data that supports reads at arbitrary positions.
+///
+/// Implementations of this trait allow for working with data that is
+/// not present on the local file system, e.g., because it is only
+/// accessible via the network or the memory of a virtual machine.
+pub trait ReadAt {
+    /// Read exactly `buf.len()` bytes starting at `offset` into `buf`.
+    ///
+    /// An error of kind [`UnexpectedEof`][io::ErrorKind::UnexpectedEof]
+    /// should be reported if not enough data is available.
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<()>;
+}
+
+impl ReadAt for File {
+    #[inline]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+        self.read_exact_at(buf, offset)
+    }
+}
+
+impl ReadAt for [u8] {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+        let data = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| self.get(offset..)?.get(..buf.len()))
+            .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+        let () = buf.copy_from_slice(data);
+        Ok(())
+    }
+}
+
+
 pub(crate) fn fstat(fd: RawFd) -> io::Result<libc::stat> {
     let mut dst = MaybeUninit::uninit();
     let rc = unsafe { libc::fstat(fd, dst.as_mut_ptr()) };
@@ -164,6 +209,88 @@ where
 }
 
 
+/// Render `bytes` as a lower case hexadecimal string.
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut s, b| {
+            let () = s.push_str(&format!("{b:02x}"));
+            s
+        })
+}
+
+
+/// Calculate the Levenshtein edit distance between `a` and `b`, if it
+/// does not exceed `max`.
+///
+/// The calculation is aborted early once it is clear that the distance
+/// exceeds `max`, in which case `None` is returned.
+pub(crate) fn edit_distance_bounded(a: &[u8], b: &[u8], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None
+    }
+
+    // The distances between the prefixes of `a` and the prefix of `b`
+    // processed so far.
+    let mut prev = (0..=a.len()).collect::<Vec<_>>();
+    let mut cur = vec![0; a.len() + 1];
+    for (j, cb) in b.iter().enumerate() {
+        cur[0] = j + 1;
+        let mut row_min = cur[0];
+        for (i, ca) in a.iter().enumerate() {
+            let subst = prev[i] + usize::from(ca != cb);
+            cur[i + 1] = subst.min(prev[i + 1] + 1).min(cur[i] + 1);
+            row_min = row_min.min(cur[i + 1]);
+        }
+        if row_min > max {
+            return None
+        }
+        let () = swap(&mut prev, &mut cur);
+    }
+
+    let distance = prev[a.len()];
+    (distance <= max).then_some(distance)
+}
+
+
+/// A bump allocator for strings.
+///
+/// Strings are copied into large chunks of memory instead of being
+/// allocated individually. Chunks are never reallocated, so strings
+/// stay at a fixed address for as long as the arena exists, and they
+/// are all released at once when it is dropped.
+#[derive(Debug, Default)]
+pub(crate) struct StrArena {
+    chunks: Vec<String>,
+}
+
+impl StrArena {
+    /// The minimum capacity of a chunk, in bytes.
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    /// Copy `s` into the arena.
+    pub(crate) fn alloc(&mut self, s: &str) -> &str {
+        let fits = self
+            .chunks
+            .last()
+            .map(|chunk| chunk.capacity() - chunk.len() >= s.len())
+            .unwrap_or(false);
+        if !fits {
+            let chunk = String::with_capacity(s.len().max(Self::CHUNK_SIZE));
+            let () = self.chunks.push(chunk);
+        }
+
+        // SANITY: We made sure that there is at least one chunk.
+        let chunk = self.chunks.last_mut().unwrap();
+        let start = chunk.len();
+        // The chunk has sufficient capacity left, so this will not
+        // reallocate and move previously allocated strings.
+        let () = chunk.push_str(s);
+        &chunk[start..]
+    }
+}
+
+
 /// A marker trait for "plain old data" data types.
 ///
 /// # Safety
@@ -298,6 +425,10 @@ pub(crate) trait ReadRaw<'data> {
         let mut value = 0u64;
         while let Some(bytes) = self.read_slice(1) {
             if let [byte] = bytes {
+                if shift >= 64 {
+                    // The encoded value does not fit into a `u64`.
+                    return None
+                }
                 value |= ((byte & 0b0111_1111) as u64) << shift;
                 shift += 7;
                 if (byte & 0b1000_0000) == 0 {
@@ -444,6 +575,20 @@ mod tests {
         assert_eq!(result, vec.into_iter().map(|x| x + 2).collect::<Vec<_>>());
     }
 
+    /// Check that `with_ordered_elems` does not reorder results for
+    /// input that is sorted already.
+    #[test]
+    fn with_element_ordering_sorted() {
+        let vec = vec![0u8, 1, 1, 5, 99];
+        let result = with_ordered_elems(
+            &vec,
+            |_x: &mut Vec<u8>| -> &mut [u8] { panic!("results should not be reordered") },
+            |iter| Result::<_, ()>::Ok(iter.map(|x| x + 2).collect::<Vec<_>>()),
+        )
+        .unwrap();
+        assert_eq!(result, vec.into_iter().map(|x| x + 2).collect::<Vec<_>>());
+    }
+
     /// Make sure that `[u8]::ensure` works as expected.
     #[test]
     fn u8_slice_len_ensurance() {
@@ -645,6 +790,23 @@ mod tests {
         test(|data, item| find_lowest_match_by_key(data, item, |elem| *elem));
     }
 
+    /// Check that we calculate bounded edit distances correctly.
+    #[test]
+    fn bounded_edit_distance() {
+        assert_eq!(edit_distance_bounded(b"", b"", 0), Some(0));
+        assert_eq!(edit_distance_bounded(b"abc", b"abc", 0), Some(0));
+        assert_eq!(edit_distance_bounded(b"", b"abc", 3), Some(3));
+        assert_eq!(edit_distance_bounded(b"factorial", b"factoral", 1), Some(1));
+        assert_eq!(
+            edit_distance_bounded(b"factorial", b"fcatorial", 2),
+            Some(2)
+        );
+        assert_eq!(edit_distance_bounded(b"kitten", b"sitting", 3), Some(3));
+        assert_eq!(edit_distance_bounded(b"kitten", b"sitting", 2), None);
+        // Length differences exceeding the bound are rejected upfront.
+        assert_eq!(edit_distance_bounded(b"a", b"abcd", 2), None);
+    }
+
     /// Test that we correctly binary search for a match or a lower bound.
     #[test]
     fn search_match_or_lower_bound() {
@@ -681,4 +843,30 @@ mod tests {
         assert_eq!(find_match_or_lower_bound(&data, 100), Some(4));
         assert_eq!(find_match_or_lower_bound(&data, 1337), Some(4));
     }
+
+    /// Check that strings allocated from a `StrArena` stay intact.
+    #[test]
+    fn str_arena_allocation() {
+        let mut arena = StrArena::default();
+        let mut strs = Vec::new();
+        for i in 0..20000 {
+            let s = arena.alloc(&format!("string-{i}")) as *const str;
+            let () = strs.push(s);
+        }
+        let large = "x".repeat(2 * StrArena::CHUNK_SIZE);
+        let s = arena.alloc(&large) as *const str;
+        let () = strs.push(s);
+        let s = arena.alloc("") as *const str;
+        let () = strs.push(s);
+
+        for (i, s) in strs[..20000].iter().enumerate() {
+            // SAFETY: The arena is still alive and never moves strings.
+            assert_eq!(unsafe { &**s }, format!("string-{i}"));
+        }
+        // SAFETY: The arena is still alive and never moves strings.
+        assert_eq!(unsafe { &*strs[20000] }, large);
+        // SAFETY: The arena is still alive and never moves strings.
+        assert_eq!(unsafe { &*strs[20001] }, "");
+        assert!(arena.chunks.len() > 2, "{}", arena.chunks.len());
+    }
 }
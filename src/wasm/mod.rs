@@ -0,0 +1,4 @@
+mod parser;
+mod resolver;
+
+pub(crate) use resolver::WasmResolver;
@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::str;
+
+use crate::log::warn;
+use crate::util::ReadRaw as _;
+use crate::Error;
+use crate::ErrorExt as _;
+use crate::IntoError as _;
+use crate::Result;
+
+
+/// The magic bytes at the start of every WebAssembly module.
+const WASM_MAGIC: &[u8; 4] = b"\0asm";
+/// The only WebAssembly binary format version we support.
+const WASM_VERSION: u32 = 1;
+
+const SECTION_CUSTOM: u8 = 0;
+const SECTION_IMPORT: u8 = 2;
+const SECTION_CODE: u8 = 10;
+
+const IMPORT_KIND_FUNC: u8 = 0;
+const IMPORT_KIND_TABLE: u8 = 1;
+const IMPORT_KIND_MEMORY: u8 = 2;
+const IMPORT_KIND_GLOBAL: u8 = 3;
+const IMPORT_KIND_TAG: u8 = 4;
+
+/// The sub-section of the `name` custom section containing function
+/// names.
+const NAME_SUBSECTION_FUNCTION: u8 = 1;
+
+
+/// Read an unsigned LEB128 encoded `u32`.
+fn read_u32_leb128(data: &mut &[u8]) -> Option<u32> {
+    let (value, _bytes) = data.read_u64_leb128()?;
+    u32::try_from(value).ok()
+}
+
+/// Read a length prefixed byte vector.
+fn read_bytes<'dat>(data: &mut &'dat [u8]) -> Option<&'dat [u8]> {
+    let len = read_u32_leb128(data)?;
+    data.read_slice(len as usize)
+}
+
+/// Read a length prefixed UTF-8 name.
+fn read_name<'dat>(data: &mut &'dat [u8]) -> Result<&'dat str> {
+    let bytes = read_bytes(data).ok_or_unexpected_eof(|| "failed to read name")?;
+    let name = str::from_utf8(bytes)
+        .map_err(Error::with_invalid_data)
+        .context("encountered invalid UTF-8 name")?;
+    Ok(name)
+}
+
+/// Count the number of imported functions.
+///
+/// Imported functions occupy the lowest indices of the function index
+/// space.
+fn count_imported_funcs(mut data: &[u8]) -> Result<u32> {
+    let count = read_u32_leb128(&mut data)
+        .ok_or_invalid_data(|| "failed to read import section entry count")?;
+
+    let mut funcs = 0u32;
+    for _ in 0..count {
+        let _module = read_name(&mut data)?;
+        let _field = read_name(&mut data)?;
+        let kind = data
+            .read_u8()
+            .ok_or_invalid_data(|| "failed to read import kind")?;
+        let () = match kind {
+            IMPORT_KIND_FUNC => {
+                let _type_idx = read_u32_leb128(&mut data)
+                    .ok_or_invalid_data(|| "failed to read imported function type")?;
+                funcs = funcs
+                    .checked_add(1)
+                    .ok_or_invalid_data(|| "too many imported functions")?;
+            }
+            IMPORT_KIND_TABLE => {
+                let _elem_type = data
+                    .read_u8()
+                    .ok_or_invalid_data(|| "failed to read imported table type")?;
+                let () = skip_limits(&mut data)?;
+            }
+            IMPORT_KIND_MEMORY => {
+                let () = skip_limits(&mut data)?;
+            }
+            IMPORT_KIND_GLOBAL => {
+                let _global = data
+                    .read_slice(2)
+                    .ok_or_invalid_data(|| "failed to read imported global type")?;
+            }
+            IMPORT_KIND_TAG => {
+                let _attribute = data
+                    .read_u8()
+                    .ok_or_invalid_data(|| "failed to read imported tag attribute")?;
+                let _type_idx = read_u32_leb128(&mut data)
+                    .ok_or_invalid_data(|| "failed to read imported tag type")?;
+            }
+            _ => {
+                // We do not know the size of the entry and so we can't
+                // continue parsing. Function names may end up being
+                // attributed incorrectly if more functions are
+                // imported, but that is still preferable over not
+                // symbolizing at all.
+                warn!("encountered unsupported import kind {kind:#x}; ignoring remaining imports");
+                break
+            }
+        };
+    }
+    Ok(funcs)
+}
+
+/// Skip over a `limits` entry as used by table and memory types.
+fn skip_limits(data: &mut &[u8]) -> Result<()> {
+    let flags = data
+        .read_u8()
+        .ok_or_invalid_data(|| "failed to read limits flags")?;
+    let _min = read_u32_leb128(data).ok_or_invalid_data(|| "failed to read limits minimum")?;
+    if flags & 0x1 != 0 {
+        let _max = read_u32_leb128(data).ok_or_invalid_data(|| "failed to read limits maximum")?;
+    }
+    Ok(())
+}
+
+/// Parse the function names contained in a `name` custom section.
+fn parse_func_names(mut data: &[u8]) -> Result<HashMap<u32, &str>> {
+    let mut names = HashMap::new();
+    while !data.is_empty() {
+        let id = data
+            .read_u8()
+            .ok_or_invalid_data(|| "failed to read name sub-section ID")?;
+        let mut subsection =
+            read_bytes(&mut data).ok_or_invalid_data(|| "failed to read name sub-section")?;
+        if id != NAME_SUBSECTION_FUNCTION {
+            continue
+        }
+
+        let count = read_u32_leb128(&mut subsection)
+            .ok_or_invalid_data(|| "failed to read function name count")?;
+        for _ in 0..count {
+            let idx = read_u32_leb128(&mut subsection)
+                .ok_or_invalid_data(|| "failed to read function index")?;
+            let name = read_name(&mut subsection)?;
+            let _prev = names.insert(idx, name);
+        }
+    }
+    Ok(names)
+}
+
+
+/// A function defined in a WebAssembly module.
+#[derive(Debug)]
+pub(crate) struct Function<'dat> {
+    /// The function's name, if known.
+    pub name: Option<&'dat str>,
+    /// The range of the function's body, expressed as offsets into the
+    /// module.
+    pub range: Range<u64>,
+}
+
+
+/// Parse the functions defined in the provided WebAssembly module.
+///
+/// Functions are returned sorted by their offset in the module.
+pub(crate) fn parse_functions(module: &[u8]) -> Result<Vec<Function<'_>>> {
+    let mut data = module;
+    let magic = data
+        .read_slice(WASM_MAGIC.len())
+        .ok_or_invalid_data(|| "failed to read WebAssembly magic")?;
+    if magic != WASM_MAGIC {
+        return Err(Error::with_invalid_data(format!(
+            "encountered unexpected WebAssembly magic: {magic:x?}"
+        )))
+    }
+
+    let version = data
+        .read_u32()
+        .ok_or_invalid_data(|| "failed to read WebAssembly version")?;
+    let version = u32::from_le(version);
+    if version != WASM_VERSION {
+        return Err(Error::with_unsupported(format!(
+            "WebAssembly version {version} is unsupported"
+        )))
+    }
+
+    let mut imported_funcs = 0;
+    let mut bodies = Vec::new();
+    let mut names = HashMap::new();
+
+    while !data.is_empty() {
+        let id = data
+            .read_u8()
+            .ok_or_invalid_data(|| "failed to read section ID")?;
+        let mut section = read_bytes(&mut data)
+            .ok_or_invalid_data(|| format!("failed to read contents of section {id}"))?;
+
+        match id {
+            SECTION_CUSTOM => {
+                let name = read_name(&mut section)?;
+                if name == "name" {
+                    names = parse_func_names(section).context("failed to parse `name` section")?;
+                }
+            }
+            SECTION_IMPORT => {
+                imported_funcs =
+                    count_imported_funcs(section).context("failed to parse import section")?;
+            }
+            SECTION_CODE => {
+                let count = read_u32_leb128(&mut section)
+                    .ok_or_invalid_data(|| "failed to read code section entry count")?;
+                for _ in 0..count {
+                    let body = read_bytes(&mut section)
+                        .ok_or_invalid_data(|| "failed to read function body")?;
+                    // SAFETY: `body` is a sub-slice of `module`.
+                    let start = unsafe { body.as_ptr().offset_from(module.as_ptr()) } as u64;
+                    let () = bodies.push(start..start + body.len() as u64);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let functions = bodies
+        .into_iter()
+        .enumerate()
+        .map(|(i, range)| {
+            let index = u32::try_from(i)
+                .ok()
+                .and_then(|i| i.checked_add(imported_funcs))
+                .ok_or_invalid_data(|| "encountered too many functions")?;
+            let function = Function {
+                name: names.get(&index).copied(),
+                range,
+            };
+            Ok(function)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Function bodies are stored in order in the code section, so we
+    // are sorted by construction.
+    debug_assert!(functions
+        .windows(2)
+        .all(|funcs| funcs[0].range.end <= funcs[1].range.start));
+    Ok(functions)
+}
+
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use test_log::test;
+
+
+    /// Encode a `u32` as unsigned LEB128.
+    fn leb128(mut value: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                let () = bytes.push(byte);
+                break bytes
+            }
+            let () = bytes.push(byte | 0x80);
+        }
+    }
+
+    fn section(id: u8, contents: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![id];
+        let () = bytes.extend(leb128(contents.len() as u32));
+        let () = bytes.extend(contents);
+        bytes
+    }
+
+    fn name(name: &str) -> Vec<u8> {
+        let mut bytes = leb128(name.len() as u32);
+        let () = bytes.extend(name.as_bytes());
+        bytes
+    }
+
+    /// Create a WebAssembly module importing a single function and
+    /// defining two more, with names for the imported function and the
+    /// second defined one.
+    pub(crate) fn create_module() -> Vec<u8> {
+        let mut module = Vec::new();
+        let () = module.extend(WASM_MAGIC);
+        let () = module.extend(WASM_VERSION.to_le_bytes());
+
+        let mut import = leb128(1);
+        let () = import.extend(name("env"));
+        let () = import.extend(name("imported"));
+        let () = import.extend([IMPORT_KIND_FUNC, 0]);
+        let () = module.extend(section(SECTION_IMPORT, &import));
+
+        let mut code = leb128(2);
+        // Function bodies; their contents are irrelevant to us.
+        let () = code.extend(leb128(4));
+        let () = code.extend([0x00, 0x01, 0x01, 0x0b]);
+        let () = code.extend(leb128(6));
+        let () = code.extend([0x00, 0x01, 0x01, 0x01, 0x01, 0x0b]);
+        let () = module.extend(section(SECTION_CODE, &code));
+
+        let mut funcs = leb128(2);
+        let () = funcs.extend(leb128(0));
+        let () = funcs.extend(name("imported"));
+        let () = funcs.extend(leb128(2));
+        let () = funcs.extend(name("second"));
+        let mut names = name("name");
+        // A module name sub-section, which should be ignored.
+        let () = names.extend(section(0, &name("module")));
+        let () = names.extend(section(NAME_SUBSECTION_FUNCTION, &funcs));
+        let () = module.extend(section(SECTION_CUSTOM, &names));
+        module
+    }
+
+
+    /// Check that we can parse functions from a WebAssembly module.
+    #[test]
+    fn function_parsing() {
+        let module = create_module();
+        let functions = parse_functions(&module).unwrap();
+        assert_eq!(functions.len(), 2);
+
+        assert_eq!(functions[0].name, None);
+        let body = &module[functions[0].range.start as usize..functions[0].range.end as usize];
+        assert_eq!(body, [0x00, 0x01, 0x01, 0x0b]);
+
+        assert_eq!(functions[1].name, Some("second"));
+        assert_eq!(functions[1].range.end - functions[1].range.start, 6);
+    }
+
+    /// Check that we skip over imports of kinds we do not understand.
+    #[test]
+    fn unknown_import_kinds() {
+        let mut import = leb128(3);
+        let () = import.extend(name("env"));
+        let () = import.extend(name("func"));
+        let () = import.extend([IMPORT_KIND_FUNC, 0]);
+        let () = import.extend(name("env"));
+        let () = import.extend(name("tag"));
+        let () = import.extend([IMPORT_KIND_TAG, 0, 0]);
+        let () = import.extend(name("env"));
+        let () = import.extend(name("unknown"));
+        let () = import.extend([0x7f, 0x42]);
+        assert_eq!(count_imported_funcs(&import).unwrap(), 1);
+    }
+
+    /// Make sure that we reject invalid modules gracefully.
+    #[test]
+    fn invalid_module() {
+        let err = parse_functions(b"\x7fELF").unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidData);
+
+        let module = create_module();
+        for len in 0..module.len() {
+            let _result = parse_functions(&module[..len]);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fs::File;
+use std::mem;
+use std::path::PathBuf;
+
+use crate::inspect::FindAddrOpts;
+use crate::inspect::SymInfo;
+use crate::inspect::SymType;
+use crate::mmap::Mmap;
+use crate::symbolize::AddrCodeInfo;
+use crate::symbolize::IntSym;
+use crate::symbolize::SrcLang;
+use crate::Addr;
+use crate::Result;
+use crate::SymResolver;
+
+use super::parser::parse_functions;
+use super::parser::Function;
+
+
+/// The symbol resolver for WebAssembly modules.
+///
+/// Symbols are resolved based on the function names contained in the
+/// module's `name` custom section. Addresses are interpreted as offsets
+/// into the module, which is the convention used by most runtimes for
+/// reporting code locations.
+pub(crate) struct WasmResolver {
+    file_name: PathBuf,
+    // SAFETY: We must not hand out references with a 'static lifetime to
+    //         this member. Rather, they should never outlive `self`.
+    //         Furthermore, this member has to be listed before `_mmap`
+    //         to make sure we never end up with a dangling reference.
+    functions: Vec<Function<'static>>,
+    _mmap: Mmap,
+}
+
+impl WasmResolver {
+    /// Create a `WasmResolver` that loads data from the provided file.
+    pub(crate) fn from_file(path: PathBuf, file: &File) -> Result<Self> {
+        let mmap = Mmap::map(file)?;
+        let functions = parse_functions(&mmap)?;
+        let slf = Self {
+            file_name: path,
+            // SAFETY: We own the underlying `Mmap` object and never hand out
+            //         any 'static references to its data. So it is safe for us
+            //         to transmute the lifetime.
+            functions: unsafe {
+                mem::transmute::<Vec<Function<'_>>, Vec<Function<'static>>>(functions)
+            },
+            _mmap: mmap,
+        };
+        Ok(slf)
+    }
+
+    /// Find the function containing the provided module offset.
+    fn find_function(&self, addr: Addr) -> Option<&Function<'_>> {
+        let idx = self
+            .functions
+            .partition_point(|function| function.range.end <= addr);
+        let function = self.functions.get(idx)?;
+        function.range.contains(&addr).then_some(function)
+    }
+}
+
+impl SymResolver for WasmResolver {
+    fn find_sym(&self, addr: Addr) -> Result<Option<IntSym<'_>>> {
+        let sym = self.find_function(addr).and_then(|function| {
+            let name = function.name?;
+            let sym = IntSym {
+                name,
+                addr: function.range.start,
+                size: usize::try_from(function.range.end - function.range.start).ok(),
+                // The `name` section carries no source language
+                // information.
+                lang: SrcLang::Unknown,
+            };
+            Some(sym)
+        });
+        Ok(sym)
+    }
+
+    fn find_addr<'slf>(&'slf self, name: &str, opts: &FindAddrOpts) -> Result<Vec<SymInfo<'slf>>> {
+        if let SymType::Variable = opts.sym_type {
+            return Ok(Vec::new())
+        }
+
+        let syms = self
+            .functions
+            .iter()
+            .filter_map(|function| {
+                let sym_name = function.name?;
+                let sym = SymInfo {
+                    name: Cow::Borrowed(sym_name),
+                    demangled_name: None,
+                    addr: function.range.start,
+                    size: usize::try_from(function.range.end - function.range.start)
+                        .unwrap_or(usize::MAX),
+                    end: Some(function.range.end),
+                    sym_type: SymType::Function,
+                    // Addresses already are module offsets.
+                    file_offset: opts.offset_in_file.then_some(function.range.start),
+                    obj_file_name: Some(Cow::Borrowed(&self.file_name)),
+                };
+                (sym_name == name).then_some(sym)
+            })
+            .take(opts.limit.unwrap_or(usize::MAX))
+            .collect();
+        Ok(syms)
+    }
+
+    fn find_code_info(
+        &self,
+        _addr: Addr,
+        _inlined_fns: bool,
+        _line_only: bool,
+    ) -> Result<Option<AddrCodeInfo<'_>>> {
+        // TODO: We may want to support DWARF embedded in custom sections
+        //       eventually.
+        Ok(None)
+    }
+}
+
+impl Debug for WasmResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Wasm {}", self.file_name.display())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write as _;
+
+    use tempfile::NamedTempFile;
+
+    use test_log::test;
+
+    use crate::wasm::parser::tests::create_module;
+
+
+    /// Check that we can look up symbols in a WebAssembly module.
+    #[test]
+    fn symbol_lookup() {
+        let module = create_module();
+        let mut file = NamedTempFile::new().unwrap();
+        let () = file.write_all(&module).unwrap();
+        let resolver = WasmResolver::from_file(file.path().to_path_buf(), file.as_file()).unwrap();
+        assert!(format!("{resolver:?}").starts_with("Wasm "));
+
+        let second = &resolver.functions[1];
+        let sym = resolver.find_sym(second.range.start + 2).unwrap().unwrap();
+        assert_eq!(sym.name, "second");
+        assert_eq!(sym.addr, second.range.start);
+        assert_eq!(sym.size, Some(6));
+
+        // The first function does not have a name.
+        let first = &resolver.functions[0];
+        assert!(resolver.find_sym(first.range.start).unwrap().is_none());
+        // Offsets outside of any function should not resolve either.
+        assert!(resolver.find_sym(0).unwrap().is_none());
+        assert!(resolver.find_sym(second.range.end).unwrap().is_none());
+        assert!(resolver
+            .find_code_info(second.range.start, true, false)
+            .unwrap()
+            .is_none());
+
+        let opts = FindAddrOpts {
+            offset_in_file: true,
+            sym_type: SymType::Function,
+            ..Default::default()
+        };
+        let syms = resolver.find_addr("second", &opts).unwrap();
+        assert_eq!(syms.len(), 1);
+        assert_eq!(syms[0].addr, second.range.start);
+        assert_eq!(syms[0].file_offset, Some(second.range.start));
+        assert!(resolver.find_addr("imported", &opts).unwrap().is_empty());
+    }
+}
@@ -4,24 +4,43 @@
     clippy::let_unit_value
 )]
 
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::ffi::c_void;
 use std::ffi::CString;
 use std::ffi::OsStr;
+use std::fs::copy;
+use std::fs::create_dir_all;
+use std::fs::metadata;
 use std::fs::read as read_file;
+use std::fs::write as write_file;
+use std::fs::File;
+use std::io;
 use std::io::Error;
+use std::mem::transmute;
 use std::os::unix::ffi::OsStringExt as _;
 use std::path::Path;
+use std::path::PathBuf;
+use std::thread;
+use std::time::UNIX_EPOCH;
 
+use blazesym::helper::elf_to_gsym;
 use blazesym::helper::read_elf_build_id;
+use blazesym::helper::read_elf_recovered_tables;
+use blazesym::helper::RecoveredTable;
 use blazesym::inspect;
 use blazesym::inspect::Inspector;
 use blazesym::normalize::Normalizer;
 use blazesym::symbolize;
+use blazesym::symbolize::ElfSymSource;
+use blazesym::symbolize::LineFlags;
+use blazesym::symbolize::ReadAt;
 use blazesym::symbolize::Symbolizer;
 use blazesym::Addr;
 use blazesym::ErrorKind;
 use blazesym::Pid;
 
+use tempfile::tempdir;
 use test_log::test;
 
 
@@ -124,6 +143,58 @@ fn symbolize_elf_dwarf_gsym() {
     test(src, true);
 }
 
+/// Check that we can symbolize an ELF object provided by a custom
+/// reader.
+#[test]
+fn symbolize_elf_reader() {
+    /// A reader serving data from memory, as a stand-in for, say, a
+    /// remote file.
+    struct Remote(Vec<u8>);
+
+    impl ReadAt for Remote {
+        fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+            let data = usize::try_from(offset)
+                .ok()
+                .and_then(|offset| self.0.get(offset..)?.get(..buf.len()))
+                .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+            let () = buf.copy_from_slice(data);
+            Ok(())
+        }
+    }
+
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let remote = Remote(read_file(path).unwrap());
+    let len = remote.0.len() as u64;
+    let src = symbolize::Source::from(symbolize::ElfReader::new(&remote, len));
+    let symbolizer = Symbolizer::new();
+
+    let sym = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(sym.name, "factorial");
+    let code_info = sym.code_info.as_ref().unwrap();
+    assert_eq!(code_info.file, OsStr::new("test-stable-addresses.c"));
+
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::VirtOffset(&[0x2000100, 0x0]))
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_sym().unwrap().name, "factorial");
+    assert_eq!(results[1], symbolize::Symbolized::Unknown);
+
+    // A reader providing less data than claimed should result in an
+    // error.
+    let src = symbolize::Source::from(symbolize::ElfReader::new(&remote, len + 1));
+    let err = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
 /// Make sure that we report (enabled) or don't report (disabled) inlined
 /// functions with DWARF and Gsym sources.
 #[test]
@@ -182,177 +253,790 @@ fn symbolize_dwarf_gsym_inlined() {
     test(src, false);
 }
 
-/// Check that we can symbolize the `abort_creds` function inside a
-/// kernel image properly. Inside of
-/// vmlinux-5.17.12-100.fc34.x86_64.dwarf, this function's address range
-/// and name are in separate attributes:
-/// ```text
-/// 0x01273e11: DW_TAG_subprogram
-///               DW_AT_abstract_origin     (0x0126ff5d "abort_creds")
-///               DW_AT_low_pc      (0xffffffff8110ecb0)
-///               DW_AT_high_pc     (0xffffffff8110ecce)
-///               DW_AT_frame_base  (DW_OP_call_frame_cfa)
-///               DW_AT_call_all_calls      (true)
-///               DW_AT_sibling     (0x01273f66)
-///
-/// 0x0110932c: DW_TAG_subprogram
-///               DW_AT_external    (true)
-///               DW_AT_name        ("abort_creds")
-///               DW_AT_decl_file   ("<...>/include/linux/cred.h")
-///               DW_AT_decl_line   (163)
-///               DW_AT_decl_column (0x0d)
-///               DW_AT_prototyped  (true)
-///               DW_AT_declaration (true)
-///               DW_AT_sibling     (0x0110933e)
-/// ```
-/// In the past we were unable to handle this case properly.
+/// Check that we can convert an ELF file into a GSYM one and symbolize
+/// addresses using it.
 #[test]
-#[cfg_attr(not(feature = "generate-large-test-files"), ignore)]
-fn symbolize_dwarf_complex() {
-    let test_dwarf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+fn symbolize_elf_to_gsym() {
+    let elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
         .join("data")
-        .join("vmlinux-5.17.12-100.fc34.x86_64.dwarf");
-    let src = symbolize::Source::Elf(symbolize::Elf::new(test_dwarf));
+        .join("test-stable-addresses.bin");
+    let dir = tempdir().unwrap();
+    let gsym = dir.path().join("test-stable-addresses.gsym");
+    let () = elf_to_gsym(&elf, &gsym).unwrap();
+
+    let src = symbolize::Source::from(symbolize::GsymFile::new(&gsym));
     let symbolizer = Symbolizer::new();
     let result = symbolizer
-        .symbolize_single(&src, symbolize::Input::VirtOffset(0xffffffff8110ecb0))
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
         .unwrap()
         .into_sym()
         .unwrap();
+    assert_eq!(result.name, "factorial");
+    assert_eq!(result.addr, 0x2000100);
+    assert_eq!(result.offset, 0);
+    let code_info = result.code_info.as_ref().unwrap();
+    assert_ne!(code_info.dir, None);
+    assert_eq!(code_info.file, OsStr::new("test-stable-addresses.c"));
+    assert_eq!(code_info.line, Some(8));
 
-    assert_eq!(result.name, "abort_creds");
-    assert_eq!(result.addr, 0xffffffff8110ecb0);
-    assert_eq!(result.code_info.as_ref().unwrap().line, Some(534));
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x200020a))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    let code_info = result.code_info.as_ref().unwrap();
+    assert_eq!(code_info.file, OsStr::new("test-stable-addresses.c"));
+    assert_eq!(code_info.line, Some(32));
+
+    assert_eq!(result.inlined.len(), 2, "{:#?}", result.inlined);
+    assert_eq!(result.inlined[0].name, "factorial_inline_wrapper");
+    let frame = result.inlined[0].code_info.as_ref().unwrap();
+    assert_eq!(frame.file, OsStr::new("test-stable-addresses.c"));
+    assert_eq!(frame.line, Some(26));
+    assert_eq!(result.inlined[1].name, "factorial_2nd_layer_inline_wrapper");
+    let frame = result.inlined[1].code_info.as_ref().unwrap();
+    assert_eq!(frame.file, OsStr::new("test-stable-addresses.c"));
+    assert_eq!(frame.line, Some(21));
 }
 
+/// Check that per-source inlined function reporting settings take
+/// precedence over the symbolizer wide one.
+#[test]
+fn symbolize_per_source_inlined_fns() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+
+    for (global, per_src, expected) in [
+        (true, None, 2),
+        (true, Some(false), 0),
+        (false, Some(true), 2),
+        (false, None, 0),
+    ] {
+        let mut elf = symbolize::Elf::new(&path);
+        elf.inlined_fns = per_src;
+        let src = symbolize::Source::Elf(elf);
+        let symbolizer = Symbolizer::builder().enable_inlined_fns(global).build();
+        let results = symbolizer
+            .symbolize(&src, symbolize::Input::VirtOffset(&[0x200020a]))
+            .unwrap();
+        let sym = results[0].as_sym().unwrap();
+        assert_eq!(sym.name, "factorial_inline_test");
+        assert_ne!(sym.code_info, None);
+        assert_eq!(sym.inlined.len(), expected, "{global} {per_src:?}");
+    }
+}
 
-/// Symbolize an address inside a DWARF file, with and without auto-demangling
-/// enabled.
+/// Check that we consult symbol sources in the order requested for
+/// an ELF source.
 #[test]
-fn symbolize_dwarf_demangle() {
-    fn test(test_dwarf: &Path, addr: Addr) -> Result<(), ()> {
-        let src = symbolize::Source::Elf(symbolize::Elf::new(test_dwarf));
-        let symbolizer = Symbolizer::builder().enable_demangling(false).build();
+fn symbolize_per_source_sym_sources() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so.so");
+
+    let inspector = Inspector::new();
+    let inspect_src = inspect::Source::Elf(inspect::Elf::new(&path));
+    let results = inspector
+        .lookup(&["the_answer", "frame_dummy"], &inspect_src)
+        .unwrap();
+    // `the_answer` is exported and, hence, part of `.dynsym`, while
+    // `frame_dummy` is a local symbol only present in `.symtab`.
+    let exported = results[0][0].addr;
+    let local = results[1][0].addr;
+
+    let symbolizer = Symbolizer::new();
+    let symbolize = |sym_sources: Option<Vec<ElfSymSource>>, addr| {
+        let mut elf = symbolize::Elf::new(&path);
+        elf.sym_sources = sym_sources;
+        let src = symbolize::Source::Elf(elf);
         let result = symbolizer
             .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
-            .unwrap()
-            .into_sym()
             .unwrap();
-
-        assert!(
-            result.name.contains("test13test_function"),
-            "{}",
-            result.name
+        result.into_sym().map(|sym| sym.name.into_owned())
+    };
+
+    for sources in [
+        None,
+        Some(vec![ElfSymSource::Dynsym]),
+        Some(vec![ElfSymSource::Symtab, ElfSymSource::Dwarf]),
+    ] {
+        assert_eq!(
+            symbolize(sources.clone(), exported).as_deref(),
+            Some("the_answer"),
+            "{sources:?}"
         );
+    }
 
-        if result.inlined.is_empty() {
-            return Err(())
-        }
-        assert!(
-            result.inlined[0].name.contains("test12inlined_call"),
-            "{}",
-            result.inlined[0].name
-        );
+    assert_eq!(symbolize(None, local).as_deref(), Some("frame_dummy"));
+    assert_eq!(symbolize(Some(vec![ElfSymSource::Dynsym]), local), None);
+    let sources = vec![ElfSymSource::Dynsym, ElfSymSource::Symtab];
+    assert_eq!(
+        symbolize(Some(sources), local).as_deref(),
+        Some("frame_dummy")
+    );
+    // Not consulting any source at all can't produce a symbol.
+    assert_eq!(symbolize(Some(Vec::new()), exported), None);
+}
 
-        // Do it again, this time with demangling enabled.
-        let symbolizer = Symbolizer::new();
+/// Check that we honor the configured maximum inline depth.
+#[test]
+fn symbolize_max_inline_depth() {
+    fn test(src: symbolize::Source) {
+        let symbolizer = Symbolizer::builder().set_max_inline_depth(Some(1)).build();
         let result = symbolizer
-            .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x200020a))
             .unwrap()
             .into_sym()
             .unwrap();
 
-        assert_eq!(result.name, "test::test_function");
+        // Only the innermost inlined function should be reported.
         assert_eq!(result.inlined.len(), 1, "{:#?}", result.inlined);
-        assert_eq!(result.inlined[0].name, "test::inlined_call");
-        Ok(())
+        assert_eq!(result.inlined[0].name, "factorial_2nd_layer_inline_wrapper");
+        assert!(result.inlined_truncated);
+
+        let symbolizer = Symbolizer::builder().set_max_inline_depth(Some(2)).build();
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x200020a))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(result.inlined.len(), 2, "{:#?}", result.inlined);
+        assert!(!result.inlined_truncated);
+
+        let symbolizer = Symbolizer::builder().set_max_inline_depth(Some(0)).build();
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x200020a))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert!(result.inlined.is_empty(), "{:#?}", result.inlined);
+        assert!(result.inlined_truncated);
     }
 
-    let test_dwarf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
         .join("data")
-        .join("test-rs.bin");
-    let elf = inspect::Elf::new(&test_dwarf);
-    let src = inspect::Source::Elf(elf);
+        .join("test-stable-addresses.gsym");
+    let src = symbolize::Source::from(symbolize::GsymFile::new(path));
+    test(src);
 
-    let inspector = Inspector::new();
-    let results = inspector
-        .lookup(&["_RNvCs69hjMPjVIJK_4test13test_function"], &src)
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-dwarf-only.bin");
+    let src = symbolize::Source::from(symbolize::Elf::new(path));
+    test(src);
+}
+
+/// Check that we honor the configured maximum symbol offset.
+#[test]
+fn symbolize_max_sym_offset() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-no-dwarf.bin");
+    let src = symbolize::Source::from(symbolize::Elf::new(path));
+
+    let symbolizer = Symbolizer::builder().set_max_sym_offset(Some(4)).build();
+    for offset in [0, 4] {
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100 + offset))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(result.name, "factorial");
+        assert_eq!(result.offset, offset as usize);
+    }
+
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000105))
+        .unwrap();
+    assert_eq!(result, symbolize::Symbolized::Unknown);
+
+    // Without a limit the address should be attributed to the symbol.
+    let symbolizer = Symbolizer::new();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000105))
         .unwrap()
-        .into_iter()
-        .flatten()
-        .collect::<Vec<_>>();
-    assert!(!results.is_empty());
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "factorial");
+}
 
-    let addr = results[0].addr;
-    let src = symbolize::Source::Elf(symbolize::Elf::new(&test_dwarf));
-    let symbolizer = Symbolizer::builder().enable_demangling(false).build();
+/// Check that we report addresses in padding between symbols as
+/// unknown when asked to.
+#[test]
+fn symbolize_padding_detection() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.gsym");
+    let src = symbolize::Source::from(symbolize::GsymFile::new(path));
+    // `factorial` spans 0x2000100 to 0x200012b and is followed by
+    // padding up to the next function at 0x2000200.
+    let padding = 0x2000150;
+
+    let symbolizer = Symbolizer::new();
     let result = symbolizer
-        .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+        .symbolize_single(&src, symbolize::Input::VirtOffset(padding))
         .unwrap()
         .into_sym()
         .unwrap();
+    assert_eq!(result.name, "factorial");
 
-    let addr = result.addr;
-    let size = result.size.unwrap() as u64;
-    for inst_addr in addr..addr + size {
-        if test(&test_dwarf, inst_addr).is_ok() {
-            return
-        }
-    }
+    let symbolizer = Symbolizer::builder().enable_padding_detection(true).build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(padding))
+        .unwrap();
+    assert_eq!(result, symbolize::Symbolized::Unknown);
 
-    panic!("failed to find inlined function call");
+    for addr in [0x2000100, 0x200012a] {
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(result.name, "factorial");
+    }
 }
 
-/// Check that we can symbolize addresses inside our own process.
+/// Check that we report line table flags only when asked to.
 #[test]
-fn symbolize_process() {
-    let src = symbolize::Source::Process(symbolize::Process::new(Pid::Slf));
-    let addrs = [symbolize_process as Addr, Symbolizer::symbolize as Addr];
+fn symbolize_line_flags() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-dwarf-only.bin");
+    let src = symbolize::Source::from(symbolize::Elf::new(path));
+
     let symbolizer = Symbolizer::new();
-    let results = symbolizer
-        .symbolize(&src, symbolize::Input::AbsAddr(&addrs))
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
         .unwrap()
-        .into_iter()
-        .collect::<Vec<_>>();
-    assert_eq!(results.len(), 2);
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "factorial");
+    assert_eq!(result.code_info.unwrap().flags, None);
 
-    let result = results[0].as_sym().unwrap();
-    assert!(result.name.contains("symbolize_process"), "{result:x?}");
+    let symbolizer = Symbolizer::builder().enable_line_flags(true).build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "factorial");
+    let flags = result.code_info.unwrap().flags.unwrap();
+    assert!(flags.contains(LineFlags::IS_STMT), "{flags:?}");
+    assert!(!flags.contains(LineFlags::END_SEQUENCE), "{flags:?}");
 
-    let result = results[1].as_sym().unwrap();
-    // It's not entirely clear why we have seen two different demangled
-    // symbols, but they both seem legit.
-    assert!(
-        result.name == "blazesym::symbolize::symbolizer::Symbolizer::symbolize"
-            || result.name == "<blazesym::symbolize::symbolizer::Symbolizer>::symbolize",
-        "{}",
-        result.name
-    );
+    // Gsym does not provide line table flags.
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.gsym");
+    let src = symbolize::Source::from(symbolize::GsymFile::new(path));
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.code_info.unwrap().flags, None);
 }
 
-/// Check that we can normalize addresses in an ELF shared object.
+/// Check that we report source code information for addresses in a
+/// function's prologue as of the prologue's end only when asked to.
 #[test]
-fn normalize_elf_addr() {
-    fn test(so: &str) {
-        let test_so = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data").join(so);
-        let so_cstr = CString::new(test_so.clone().into_os_string().into_vec()).unwrap();
-        let handle = unsafe { libc::dlopen(so_cstr.as_ptr(), libc::RTLD_NOW) };
-        assert!(!handle.is_null());
-
-        let the_answer_addr = unsafe { libc::dlsym(handle, "the_answer\0".as_ptr().cast()) };
-        assert!(!the_answer_addr.is_null());
+fn symbolize_prologue_skipping() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-prologue.bin");
+    let src = symbolize::Source::from(symbolize::Elf::new(path));
 
-        let normalizer = Normalizer::new();
-        let normalized = normalizer
-            .normalize_user_addrs_sorted([the_answer_addr as Addr].as_slice(), Pid::Slf)
-            .unwrap();
-        assert_eq!(normalized.outputs.len(), 1);
-        assert_eq!(normalized.meta.len(), 1);
+    let symbolizer = Symbolizer::new();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x1001))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "prologued");
+    assert_eq!(result.code_info.unwrap().line, Some(3));
 
-        let rc = unsafe { libc::dlclose(handle) };
-        assert_eq!(rc, 0, "{}", Error::last_os_error());
+    let symbolizer = Symbolizer::builder().enable_prologue_skipping(true).build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x1001))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "prologued");
+    assert_eq!(result.addr, 0x1000);
+    assert_eq!(result.code_info.unwrap().line, Some(4));
 
-        let output = normalized.outputs[0];
+    // Addresses past the prologue are unaffected.
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x1009))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.code_info.unwrap().line, Some(5));
+}
+
+/// Check that return addresses are adjusted before symbolization,
+/// while exact ones are not.
+#[test]
+fn symbolize_frames() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = symbolize::Source::from(symbolize::Elf::new(path));
+    let symbolizer = Symbolizer::new();
+
+    // `foo` immediately follows `factorial_wrapper`, so a return address
+    // at `foo`'s start address belongs to a call in the latter.
+    let frames = [
+        symbolize::Frame::Exact(0x2000045),
+        symbolize::Frame::Return(0x2000045),
+    ];
+    let results = symbolizer
+        .symbolize_frames(&src, symbolize::Input::VirtOffset(&frames))
+        .unwrap();
+    assert_eq!(results.len(), frames.len());
+
+    let sym = results[0].as_sym().unwrap();
+    assert_eq!(sym.name, "foo");
+    assert_eq!(sym.offset, 0);
+    let sym = results[1].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial_wrapper");
+    assert_eq!(sym.addr, 0x2000034);
+    assert_eq!(sym.offset, 0x10);
+
+    let frames = symbolize::Frame::from_trace(&[0x2000100, 0x2000045]);
+    let results = symbolizer
+        .symbolize_frames(&src, symbolize::Input::VirtOffset(&frames))
+        .unwrap();
+    assert_eq!(results[0].as_sym().unwrap().name, "factorial");
+    assert_eq!(results[1].as_sym().unwrap().name, "factorial_wrapper");
+}
+
+
+/// Check that we can symbolize run-length encoded addresses.
+#[test]
+fn symbolize_counted() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = symbolize::Source::from(symbolize::Elf::new(path));
+    let symbolizer = Symbolizer::new();
+
+    let input = [(0x2000100, 3), (0x2000045, 1), (0x2000034, 7)];
+    let results = symbolizer
+        .symbolize_counted(&src, symbolize::Input::VirtOffset(&input))
+        .unwrap();
+    let results = results
+        .iter()
+        .map(|(sym, count)| (sym.as_sym().unwrap().name.as_ref(), *count))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        vec![("factorial", 3), ("foo", 1), ("factorial_wrapper", 7)]
+    );
+
+    let results = symbolizer
+        .symbolize_counted(&src, symbolize::Input::VirtOffset(&[]))
+        .unwrap();
+    assert!(results.is_empty());
+}
+
+
+/// Check that we can warm up the symbolizer's caches, with failures
+/// being reported for individual files.
+#[test]
+fn symbolize_warm_up() {
+    let data = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data");
+    let paths = [
+        data.join("test-stable-addresses.bin"),
+        data.join("does-not-exist.bin"),
+        data.join("test-stable-addresses-no-dwarf.bin"),
+    ];
+
+    let symbolizer = Symbolizer::new();
+    let errors = symbolizer.warm_up(&paths);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    let (path, err) = &errors[0];
+    assert_eq!(path, &paths[1]);
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    let src = symbolize::Source::from(symbolize::Elf::new(&paths[2]));
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2000100))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "factorial");
+}
+
+/// Check that we can strip leading underscores from symbol names.
+#[test]
+fn symbolize_strip_leading_underscore() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-no-debug.bin");
+    let inspector = Inspector::new();
+    let src = inspect::Source::Elf(inspect::Elf::new(&path));
+    let results = inspector.lookup(&["_start"], &src).unwrap();
+    let addr = results[0][0].addr;
+
+    let src = symbolize::Source::from(symbolize::Elf::new(&path));
+    let symbolizer = Symbolizer::new();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "_start");
+
+    let symbolizer = Symbolizer::builder()
+        .enable_leading_underscore_stripping(true)
+        .build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "start");
+}
+
+/// Check that we refuse to symbolize addresses using a file that does
+/// not have the expected build ID.
+#[test]
+fn symbolize_elf_build_id_mismatch() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so.so");
+    let build_id = read_elf_build_id(&path).unwrap().unwrap();
+    let symbolizer = Symbolizer::new();
+
+    let elf = symbolize::Elf {
+        build_id: Some(build_id.clone()),
+        ..symbolize::Elf::new(&path)
+    };
+    let src = symbolize::Source::from(elf);
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x10f9))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "the_answer");
+
+    let mut other_build_id = build_id;
+    other_build_id[0] ^= 0xff;
+    let elf = symbolize::Elf {
+        build_id: Some(other_build_id),
+        ..symbolize::Elf::new(&path)
+    };
+    let src = symbolize::Source::from(elf);
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::VirtOffset(&[0x10f9, 0x0]))
+        .unwrap();
+    assert_eq!(
+        results,
+        vec![
+            symbolize::Symbolized::BuildIdMismatch,
+            symbolize::Symbolized::BuildIdMismatch
+        ]
+    );
+    assert_eq!(results[0].display().to_string(), "<build-id-mismatch>");
+
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::FileOffset(0x10f9))
+        .unwrap();
+    assert_eq!(result, symbolize::Symbolized::BuildIdMismatch);
+}
+
+/// Check that we can scope symbolization to a specific overlay.
+#[test]
+fn symbolize_elf_overlay() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-overlay.bin");
+    let symbolizer = Symbolizer::new();
+
+    for (section, name) in [(".ovly0", "ovly0_fn"), (".ovly1", "ovly1_fn")] {
+        let elf = symbolize::Elf {
+            overlay: Some(vec![section.to_string()]),
+            ..symbolize::Elf::new(&path)
+        };
+        let src = symbolize::Source::from(elf);
+        let results = symbolizer
+            .symbolize(&src, symbolize::Input::VirtOffset(&[0x2001004, 0x2000000]))
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        // Overlaid addresses are attributed to the selected overlay, but
+        // we can't report source code information for them.
+        let result = results[0].as_sym().unwrap();
+        assert_eq!(result.name, name);
+        assert_eq!(result.addr, 0x2001000);
+        assert_eq!(result.code_info, None);
+
+        // Addresses outside of the overlaid range are unaffected.
+        let result = results[1].as_sym().unwrap();
+        assert_eq!(result.name, "main");
+        let code_info = result.code_info.as_ref().unwrap();
+        assert_eq!(code_info.file, OsStr::new("test-overlay.c"));
+    }
+
+    let elf = symbolize::Elf {
+        overlay: Some(vec![".ovly2".to_string()]),
+        ..symbolize::Elf::new(&path)
+    };
+    let src = symbolize::Source::from(elf);
+    let err = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x2001004))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+/// Check that we can symbolize the `abort_creds` function inside a
+/// kernel image properly. Inside of
+/// vmlinux-5.17.12-100.fc34.x86_64.dwarf, this function's address range
+/// and name are in separate attributes:
+/// ```text
+/// 0x01273e11: DW_TAG_subprogram
+///               DW_AT_abstract_origin     (0x0126ff5d "abort_creds")
+///               DW_AT_low_pc      (0xffffffff8110ecb0)
+///               DW_AT_high_pc     (0xffffffff8110ecce)
+///               DW_AT_frame_base  (DW_OP_call_frame_cfa)
+///               DW_AT_call_all_calls      (true)
+///               DW_AT_sibling     (0x01273f66)
+///
+/// 0x0110932c: DW_TAG_subprogram
+///               DW_AT_external    (true)
+///               DW_AT_name        ("abort_creds")
+///               DW_AT_decl_file   ("<...>/include/linux/cred.h")
+///               DW_AT_decl_line   (163)
+///               DW_AT_decl_column (0x0d)
+///               DW_AT_prototyped  (true)
+///               DW_AT_declaration (true)
+///               DW_AT_sibling     (0x0110933e)
+/// ```
+/// In the past we were unable to handle this case properly.
+#[test]
+#[cfg_attr(not(feature = "generate-large-test-files"), ignore)]
+fn symbolize_dwarf_complex() {
+    let test_dwarf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("vmlinux-5.17.12-100.fc34.x86_64.dwarf");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(test_dwarf));
+    let symbolizer = Symbolizer::new();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0xffffffff8110ecb0))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+
+    assert_eq!(result.name, "abort_creds");
+    assert_eq!(result.addr, 0xffffffff8110ecb0);
+    assert_eq!(result.code_info.as_ref().unwrap().line, Some(534));
+}
+
+
+/// Symbolize an address inside a DWARF file, with and without auto-demangling
+/// enabled.
+#[test]
+fn symbolize_dwarf_demangle() {
+    fn test(test_dwarf: &Path, addr: Addr) -> Result<(), ()> {
+        let src = symbolize::Source::Elf(symbolize::Elf::new(test_dwarf));
+        let symbolizer = Symbolizer::builder().enable_demangling(false).build();
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+
+        // With demangling disabled names should be reported verbatim.
+        assert_eq!(result.name, "_RNvCs69hjMPjVIJK_4test13test_function");
+        assert_eq!(result.mangled_name, None);
+
+        if result.inlined.is_empty() {
+            return Err(())
+        }
+        assert!(
+            result.inlined[0].name.contains("test12inlined_call"),
+            "{}",
+            result.inlined[0].name
+        );
+
+        // Do it again, this time with demangling enabled.
+        let symbolizer = Symbolizer::new();
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+
+        assert_eq!(result.name, "test::test_function");
+        // The raw linkage name is reported alongside.
+        assert_eq!(
+            result.mangled_name.as_deref(),
+            Some("_RNvCs69hjMPjVIJK_4test13test_function")
+        );
+        assert_eq!(result.inlined.len(), 1, "{:#?}", result.inlined);
+        assert_eq!(result.inlined[0].name, "test::inlined_call");
+        Ok(())
+    }
+
+    let test_dwarf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-rs.bin");
+    let elf = inspect::Elf::new(&test_dwarf);
+    let src = inspect::Source::Elf(elf);
+
+    let inspector = Inspector::new();
+    let results = inspector
+        .lookup(&["_RNvCs69hjMPjVIJK_4test13test_function"], &src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert!(!results.is_empty());
+
+    let addr = results[0].addr;
+    let src = symbolize::Source::Elf(symbolize::Elf::new(&test_dwarf));
+    let symbolizer = Symbolizer::builder().enable_demangling(false).build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+
+    let addr = result.addr;
+    let size = result.size.unwrap() as u64;
+    for inst_addr in addr..addr + size {
+        if test(&test_dwarf, inst_addr).is_ok() {
+            return
+        }
+    }
+
+    panic!("failed to find inlined function call");
+}
+
+/// Check that we can symbolize addresses inside our own process.
+#[test]
+fn symbolize_process() {
+    let src = symbolize::Source::Process(symbolize::Process::new(Pid::Slf));
+    let addrs = [symbolize_process as Addr, Symbolizer::symbolize as Addr];
+    let symbolizer = Symbolizer::new();
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::AbsAddr(&addrs))
+        .unwrap()
+        .into_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 2);
+
+    let result = results[0].as_sym().unwrap();
+    assert!(result.name.contains("symbolize_process"), "{result:x?}");
+
+    let result = results[1].as_sym().unwrap();
+    // It's not entirely clear why we have seen two different demangled
+    // symbols, but they both seem legit.
+    assert!(
+        result.name == "blazesym::symbolize::symbolizer::Symbolizer::symbolize"
+            || result.name == "<blazesym::symbolize::symbolizer::Symbolizer>::symbolize",
+        "{}",
+        result.name
+    );
+}
+
+/// Check that we can symbolize sorted addresses inside our own process
+/// and that unsorted input is handled transparently.
+#[test]
+fn symbolize_process_sorted() {
+    let src = symbolize::Source::Process(symbolize::Process::new(Pid::Slf));
+    let mut addrs = [
+        symbolize_process_sorted as *const () as Addr,
+        Symbolizer::symbolize_sorted as *const () as Addr,
+    ];
+    let () = addrs.sort();
+    let symbolizer = Symbolizer::new();
+    let sorted = symbolizer
+        .symbolize_sorted(&src, symbolize::Input::AbsAddr(&addrs))
+        .unwrap();
+    assert_eq!(sorted.len(), 2);
+    let names = sorted
+        .iter()
+        .map(|sym| sym.as_sym().unwrap().name.to_string())
+        .collect::<Vec<_>>();
+    assert!(
+        names
+            .iter()
+            .any(|name| name.contains("symbolize_process_sorted")),
+        "{names:?}"
+    );
+
+    let () = addrs.reverse();
+    let unsorted = symbolizer
+        .symbolize(&src, symbolize::Input::AbsAddr(&addrs))
+        .unwrap();
+    assert_eq!(unsorted.len(), 2);
+    assert_eq!(unsorted[0], sorted[1]);
+    assert_eq!(unsorted[1], sorted[0]);
+}
+
+/// Check that we can symbolize an address in a PLT entry to the
+/// `ifunc` implementation selected at runtime.
+#[test]
+fn symbolize_process_plt_ifunc() {
+    let test_so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-ifunc.so");
+    let so_cstr = CString::new(test_so.into_os_string().into_vec()).unwrap();
+    let handle = unsafe { libc::dlopen(so_cstr.as_ptr(), libc::RTLD_NOW) };
+    assert!(!handle.is_null());
+
+    let plt_fn = unsafe { libc::dlsym(handle, "ifunc_fn_plt\0".as_ptr().cast()) };
+    assert!(!plt_fn.is_null());
+    let plt_fn = unsafe { transmute::<*mut c_void, extern "C" fn() -> *const c_void>(plt_fn) };
+    let plt_addr = plt_fn() as Addr;
+
+    let symbolizer = Symbolizer::new();
+    let mut process = symbolize::Process::new(Pid::Slf);
+    let src = symbolize::Source::Process(process.clone());
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(plt_addr))
+        .unwrap();
+    assert_eq!(result.as_sym().map(|sym| &*sym.name), None);
+
+    process.resolve_plt = true;
+    let src = symbolize::Source::Process(process);
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(plt_addr))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "ifunc_impl");
+
+    let rc = unsafe { libc::dlclose(handle) };
+    assert_eq!(rc, 0, "{}", Error::last_os_error());
+}
+
+/// Check that we can normalize addresses in an ELF shared object.
+#[test]
+fn normalize_elf_addr() {
+    fn test(so: &str) {
+        let test_so = Path::new(&env!("CARGO_MANIFEST_DIR")).join("data").join(so);
+        let so_cstr = CString::new(test_so.clone().into_os_string().into_vec()).unwrap();
+        let handle = unsafe { libc::dlopen(so_cstr.as_ptr(), libc::RTLD_NOW) };
+        assert!(!handle.is_null());
+
+        let the_answer_addr = unsafe { libc::dlsym(handle, "the_answer\0".as_ptr().cast()) };
+        assert!(!the_answer_addr.is_null());
+
+        let normalizer = Normalizer::new();
+        let normalized = normalizer
+            .normalize_user_addrs_sorted([the_answer_addr as Addr].as_slice(), Pid::Slf)
+            .unwrap();
+        assert_eq!(normalized.outputs.len(), 1);
+        assert_eq!(normalized.meta.len(), 1);
+
+        let rc = unsafe { libc::dlclose(handle) };
+        assert_eq!(rc, 0, "{}", Error::last_os_error());
+
+        let output = normalized.outputs[0];
         let meta = &normalized.meta[output.1];
         assert_eq!(meta.elf().unwrap().path, test_so);
 
@@ -367,17 +1051,743 @@ fn normalize_elf_addr() {
 
         assert_eq!(result.name, "the_answer");
 
-        let results = symbolizer
-            .symbolize(&src, symbolize::Input::FileOffset(&[output.0]))
-            .unwrap();
-        assert_eq!(results.len(), 1);
+        let results = symbolizer
+            .symbolize(&src, symbolize::Input::FileOffset(&[output.0]))
+            .unwrap();
+        assert_eq!(results.len(), 1);
+
+        let sym = results[0].as_sym().unwrap();
+        assert_eq!(sym.name, "the_answer");
+    }
+
+    test("libtest-so.so");
+    test("libtest-so-no-separate-code.so");
+}
+
+
+/// Check that we can symbolize module relative file offsets.
+#[test]
+fn symbolize_module_offsets() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-no-dwarf.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(&path));
+    let inspector = Inspector::new();
+    let results = inspector
+        .lookup(&["factorial"], &src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    let offset = results[0].file_offset.unwrap();
+
+    let modules = HashMap::from([
+        (1u32, symbolize::Source::Elf(symbolize::Elf::new(&path))),
+        (
+            2u32,
+            symbolize::Source::Elf(symbolize::Elf::new(
+                Path::new(&env!("CARGO_MANIFEST_DIR"))
+                    .join("data")
+                    .join("test-stable-addresses-dwarf-only.bin"),
+            )),
+        ),
+    ]);
+    let frames = [(1, offset), (42, offset), (2, offset), (1, offset + 1)];
+    let symbolizer = Symbolizer::new();
+    let results = symbolizer
+        .symbolize_module_offsets(&modules, &frames)
+        .unwrap();
+    assert_eq!(results.len(), frames.len());
+
+    let sym = results[0].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.offset, 0);
+    // Module 42 is unknown and so should not have been symbolized.
+    assert_eq!(results[1], symbolize::Symbolized::Unknown);
+    let sym = results[2].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_ne!(sym.code_info, None);
+    let sym = results[3].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.offset, 1);
+}
+
+
+/// Check that a module failing to symbolize does not affect frames of
+/// other modules when symbolizing module relative file offsets.
+#[test]
+fn symbolize_module_offsets_broken_module() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-no-dwarf.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(&path));
+    let inspector = Inspector::new();
+    let results = inspector
+        .lookup(&["factorial"], &src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    let offset = results[0].file_offset.unwrap();
+
+    let dir = tempdir().unwrap();
+    let garbage = dir.path().join("garbage.bin");
+    let () = write_file(&garbage, b"not an ELF file").unwrap();
+
+    let modules = HashMap::from([
+        (1u32, symbolize::Source::Elf(symbolize::Elf::new(&path))),
+        (2u32, symbolize::Source::Elf(symbolize::Elf::new(&garbage))),
+        (
+            3u32,
+            symbolize::Source::Elf(symbolize::Elf::new(dir.path().join("does-not-exist"))),
+        ),
+    ]);
+    let frames = [(2, offset), (1, offset), (3, offset), (1, offset + 1)];
+    let symbolizer = Symbolizer::new();
+    let results = symbolizer
+        .symbolize_module_offsets(&modules, &frames)
+        .unwrap();
+    assert_eq!(results.len(), frames.len());
+
+    assert_eq!(results[0], symbolize::Symbolized::Unknown);
+    let sym = results[1].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.offset, 0);
+    assert_eq!(results[2], symbolize::Symbolized::Unknown);
+    let sym = results[3].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.offset, 1);
+}
+
+
+/// Check that we can symbolize addresses in a relocatable ELF file,
+/// such as a kernel module.
+#[test]
+fn symbolize_kernel_module() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-rel.bin");
+    let section_addrs = HashMap::from([
+        (".text.factorial".to_string(), 0xffffffffc0001000),
+        (".text.inline".to_string(), 0xffffffffc0002000),
+    ]);
+    let src = symbolize::Source::from(symbolize::KernelModule::new(path, section_addrs));
+    let symbolizer = Symbolizer::new();
+
+    let addrs = [
+        0xffffffffc0001004,
+        0xffffffffc0002001,
+        0xffffffffc0002013,
+        0xffffffffc0003000,
+    ];
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::AbsAddr(&addrs))
+        .unwrap();
+    assert_eq!(results.len(), addrs.len());
+
+    let sym = results[0].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.addr, 0xffffffffc0001000);
+    assert_eq!(sym.offset, 4);
+    let sym = results[1].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial_inline_test");
+    assert_eq!(sym.offset, 1);
+    let sym = results[2].as_sym().unwrap();
+    assert_eq!(sym.name, "dummy");
+    assert_eq!(sym.addr, 0xffffffffc0002013);
+    // The address is not covered by any of the provided sections.
+    assert_eq!(results[3], symbolize::Symbolized::Unknown);
+
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x1000))
+        .unwrap_err();
+    assert_eq!(result.kind(), ErrorKind::Unsupported);
+
+    // Non-relocatable files are rejected.
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = symbolize::Source::from(symbolize::KernelModule::new(path, HashMap::new()));
+    let err = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(0x2000100))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+
+/// Check that we can report source code information for addresses in
+/// a relocatable ELF file, based on relocated DWARF debug information.
+#[test]
+fn symbolize_kernel_module_dwarf() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-rel-dwarf.bin");
+    let section_addrs = HashMap::from([(".text.factorial".to_string(), 0xffffffffc0001000)]);
+    let src = symbolize::Source::from(symbolize::KernelModule::new(path, section_addrs));
+    let symbolizer = Symbolizer::new();
+
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(0xffffffffc0001004))
+        .unwrap();
+    let sym = result.as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.addr, 0xffffffffc0001000);
+
+    let code_info = sym.code_info.as_ref().unwrap();
+    assert_eq!(code_info.file, OsStr::new("test-stable-addresses.c"));
+    assert_eq!(code_info.line, Some(8));
+
+    // Without debug symbols we only get symbol information.
+    let symbolizer = Symbolizer::builder().enable_debug_syms(false).build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(0xffffffffc0001004))
+        .unwrap();
+    let sym = result.as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.code_info, None);
+}
+
+
+/// Check that live patch modules take precedence over the kernel
+/// proper when symbolizing kernel addresses.
+#[test]
+fn symbolize_kernel_livepatch() {
+    let kallsyms = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("kallsyms");
+    let image = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let patch = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-rel.bin");
+    let kernel = symbolize::Kernel {
+        kallsyms: Some(kallsyms),
+        kernel_image: Some(image),
+        livepatches: vec![
+            symbolize::KernelModule::new(
+                &patch,
+                HashMap::from([(".text.factorial".to_string(), 0xc0008000)]),
+            ),
+            symbolize::KernelModule::new(
+                &patch,
+                HashMap::from([(".text.inline".to_string(), 0xc0008000)]),
+            ),
+        ],
+        ..Default::default()
+    };
+    let src = symbolize::Source::from(kernel.clone());
+    let symbolizer = Symbolizer::new();
+
+    let addrs = [0xc0008001, 0xc000807c];
+    let results = symbolizer
+        .symbolize(&src, symbolize::Input::AbsAddr(&addrs))
+        .unwrap();
+    assert_eq!(results.len(), addrs.len());
+
+    // Both patches cover the first address, but the later one wins.
+    let sym = results[0].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial_inline_test");
+    assert_eq!(sym.addr, 0xc0008000);
+    assert_eq!(sym.offset, 1);
+    // Addresses not covered by any patch are resolved by the kernel.
+    let sym = results[1].as_sym().unwrap();
+    assert_eq!(sym.name, "__create_page_tables");
+
+    // Without the patches the kernel's own symbol is reported.
+    let kernel = symbolize::Kernel {
+        livepatches: Vec::new(),
+        ..kernel
+    };
+    let src = symbolize::Source::from(kernel);
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::AbsAddr(0xc0008001))
+        .unwrap();
+    let sym = result.as_sym().unwrap();
+    assert_eq!(sym.name, "stext");
+}
+
+
+/// Check that relocatable ELF files are rejected when used as a plain
+/// ELF source.
+#[test]
+fn symbolize_elf_relocatable() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-rel.bin");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+    let symbolizer = Symbolizer::new();
+
+    let err = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x4))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
+
+
+/// Check that we can symbolize addresses of an explicit kind.
+#[test]
+fn symbolize_with_address_kind() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-no-dwarf.bin");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+    let symbolizer = Symbolizer::new();
+
+    let results = symbolizer
+        .symbolize_with_kind(&src, symbolize::AddressKind::FileVirtual, &[0x2000100])
+        .unwrap();
+    let sym = results[0].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.addr, 0x2000100);
+
+    // The file's lowest loadable segment starts at 0x2000000.
+    let kind = symbolize::AddressKind::Runtime {
+        load_addr: 0x7f0000000000,
+    };
+    let results = symbolizer
+        .symbolize_with_kind(&src, kind, &[0x7f0000000100, 0x7f0000000101, 0x1000])
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    let sym = results[0].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.addr, 0x2000100);
+    assert_eq!(sym.offset, 0);
+    let sym = results[1].as_sym().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.offset, 1);
+    // Addresses below the load address can't be symbolized.
+    assert_eq!(results[2], symbolize::Symbolized::Unknown);
+
+    // A binary loaded at its link address should symbolize its addresses
+    // unchanged.
+    let kind = symbolize::AddressKind::Runtime {
+        load_addr: 0x2000000,
+    };
+    let results = symbolizer
+        .symbolize_with_kind(&src, kind, &[0x2000100])
+        .unwrap();
+    assert_eq!(results[0].as_sym().unwrap().addr, 0x2000100);
+
+    let src = symbolize::Source::Process(symbolize::Process::new(Pid::Slf));
+    let err = symbolizer
+        .symbolize_with_kind(&src, kind, &[0x2000100])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
+
+
+/// Check that runtime addresses in a prelinked shared object, i.e., one
+/// whose first loadable segment has a non-zero virtual address, are
+/// symbolized correctly.
+#[test]
+fn symbolize_prelinked_runtime_addrs() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so-prelinked.so");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(&path));
+    let symbolizer = Symbolizer::new();
+
+    let inspector = Inspector::new();
+    let inspect_src = inspect::Source::Elf(inspect::Elf::new(&path));
+    let results = inspector
+        .lookup(&["the_answer"], &inspect_src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    let the_answer = &results[0];
+    assert!(the_answer.addr > 0x40000000, "{:#x}", the_answer.addr);
+    let file_offset = the_answer.file_offset.unwrap();
+
+    let load_addr = 0x7f0000000000;
+    let kind = symbolize::AddressKind::Runtime { load_addr };
+    let results = symbolizer
+        .symbolize_with_kind(&src, kind, &[load_addr + file_offset])
+        .unwrap();
+    let sym = results[0].as_sym().unwrap();
+    assert_eq!(sym.name, "the_answer");
+    assert_eq!(sym.addr, the_answer.addr);
+
+    // Loaded at its prelinked address, runtime addresses and file
+    // virtual addresses coincide.
+    let kind = symbolize::AddressKind::Runtime {
+        load_addr: 0x40000000,
+    };
+    let results = symbolizer
+        .symbolize_with_kind(&src, kind, &[the_answer.addr])
+        .unwrap();
+    assert_eq!(results[0].as_sym().unwrap().name, "the_answer");
+
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::FileOffset(file_offset))
+        .unwrap();
+    let sym = result.as_sym().unwrap();
+    assert_eq!(sym.name, "the_answer");
+    assert_eq!(sym.addr, the_answer.addr);
+}
+
+
+/// Check that we can symbolize addresses in an ELF file that has its
+/// `.symtab` split off into a separate debug file.
+#[test]
+fn symbolize_elf_with_debug_file() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so-split.so");
+    let debug_file = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so-split-debug.so");
+
+    // Determine the address of the hidden function, which is only
+    // present in the debug file's `.symtab`.
+    let inspector = Inspector::new();
+    let src = inspect::Source::Elf(inspect::Elf::new(&debug_file));
+    let results = inspector
+        .lookup(&["the_hidden_answer"], &src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    let hidden_addr = results[0].addr;
+
+    let symbolizer = Symbolizer::new();
+    // Without the debug file, the runtime file's `.dynsym` does not
+    // know about the hidden function.
+    let src = symbolize::Source::Elf(symbolize::Elf::new(&path));
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(hidden_addr))
+        .unwrap();
+    assert_eq!(result, symbolize::Symbolized::Unknown);
+
+    let elf = symbolize::Elf {
+        debug_file: Some(debug_file),
+        ..symbolize::Elf::new(&path)
+    };
+    let split_src = symbolize::Source::Elf(elf);
+    for _ in 0..2 {
+        let result = symbolizer
+            .symbolize_single(&split_src, symbolize::Input::VirtOffset(hidden_addr))
+            .unwrap();
+        let sym = result.as_sym().unwrap();
+        assert_eq!(sym.name, "the_hidden_answer");
+        assert_eq!(sym.addr, hidden_addr);
+    }
+
+    // File offsets are mapped based on the runtime file.
+    let src = inspect::Source::Elf(inspect::Elf::new(&path));
+    let results = inspector
+        .lookup(&["the_answer"], &src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    let the_answer = &results[0];
+    let file_offset = the_answer.file_offset.unwrap();
+
+    let result = symbolizer
+        .symbolize_single(&split_src, symbolize::Input::FileOffset(file_offset))
+        .unwrap();
+    let sym = result.as_sym().unwrap();
+    assert_eq!(sym.name, "the_answer");
+    assert_eq!(sym.addr, the_answer.addr);
+}
+
+
+/// Check that we can find a separate debug file based on the build ID
+/// of the ELF file being symbolized.
+#[test]
+fn symbolize_elf_with_build_id_debug_file() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so-split.so");
+    let debug_file = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so-split-debug.so");
+
+    let inspector = Inspector::new();
+    let src = inspect::Source::Elf(inspect::Elf::new(&debug_file));
+    let results = inspector
+        .lookup(&["the_hidden_answer"], &src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    let hidden_addr = results[0].addr;
+
+    let build_id = read_elf_build_id(&path).unwrap().unwrap();
+    let hex = build_id
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    let (dir, file) = hex.split_at(2);
+
+    let debug_dir = tempdir().unwrap();
+    let build_id_dir = debug_dir.path().join(".build-id").join(dir);
+    let () = create_dir_all(&build_id_dir).unwrap();
+    let build_id_file = build_id_dir.join(format!("{file}.debug"));
+
+    // A debug file with a mismatching build ID should be ignored.
+    let other = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so.so");
+    let _cnt = copy(other, &build_id_file).unwrap();
+
+    let src = symbolize::Source::Elf(symbolize::Elf::new(&path));
+    let symbolizer = Symbolizer::builder()
+        .set_debug_dirs([debug_dir.path()])
+        .build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(hidden_addr))
+        .unwrap();
+    assert_eq!(result, symbolize::Symbolized::Unknown);
+
+    let _cnt = copy(&debug_file, &build_id_file).unwrap();
+
+    // A corrupt candidate in a directory searched earlier should not
+    // prevent us from finding the proper debug file.
+    let corrupt_dir = tempdir().unwrap();
+    let corrupt_build_id_dir = corrupt_dir.path().join(".build-id").join(dir);
+    let () = create_dir_all(&corrupt_build_id_dir).unwrap();
+    let () = write_file(
+        corrupt_build_id_dir.join(format!("{file}.debug")),
+        b"not an ELF file",
+    )
+    .unwrap();
+
+    let symbolizer = Symbolizer::builder()
+        .set_debug_dirs([
+            Path::new("/does/not/exist"),
+            corrupt_dir.path(),
+            debug_dir.path(),
+        ])
+        .build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(hidden_addr))
+        .unwrap();
+    let sym = result.as_sym().unwrap();
+    assert_eq!(sym.name, "the_hidden_answer");
+    assert_eq!(sym.addr, hidden_addr);
+
+    // With debug symbols disabled no lookup should be performed.
+    let symbolizer = Symbolizer::builder()
+        .enable_debug_syms(false)
+        .set_debug_dirs([debug_dir.path()])
+        .build();
+    let result = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(hidden_addr))
+        .unwrap();
+    assert_eq!(result, symbolize::Symbolized::Unknown);
+}
+
+
+/// Check that symbol indices are created and used as expected.
+#[test]
+fn symbolize_with_sym_index() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so.so");
+    let build_id = read_elf_build_id(&path).unwrap().unwrap();
+    let hex = build_id
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    let mtime = metadata(&path)
+        .unwrap()
+        .modified()
+        .unwrap()
+        .duration_since(UNIX_EPOCH)
+        .unwrap();
+    let dir = tempdir().unwrap();
+    let index = dir.path().join(format!(
+        "{hex}-{}.{:09}.symidx",
+        mtime.as_secs(),
+        mtime.subsec_nanos()
+    ));
+
+    let inspector = Inspector::new();
+    let inspect_src = inspect::Source::Elf(inspect::Elf::new(&path));
+    let results = inspector
+        .lookup(&["the_answer"], &inspect_src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    let addr = results[0].addr;
+
+    let symbolize_path = |path: &Path| {
+        let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+        let symbolizer = Symbolizer::builder()
+            .enable_debug_syms(false)
+            .set_sym_index_dir(Some(dir.path().to_path_buf()))
+            .build();
+        let result = symbolizer
+            .symbolize_single(&src, symbolize::Input::VirtOffset(addr))
+            .unwrap()
+            .into_sym()
+            .unwrap();
+        assert_eq!(result.name, "the_answer");
+        assert_eq!(result.addr, addr);
+    };
+    let symbolize = || symbolize_path(&path);
+
+    let () = symbolize();
+    let data = read_file(&index).unwrap();
+    assert!(data.starts_with(b"BLZSYMIX"));
+
+    // The index should be used as is the second time around.
+    let modified = metadata(&index).unwrap().modified().unwrap();
+    let () = symbolize();
+    assert_eq!(metadata(&index).unwrap().modified().unwrap(), modified);
+
+    // A corrupt index should be ignored and replaced.
+    let () = write_file(&index, b"garbage").unwrap();
+    let () = symbolize();
+    assert_eq!(read_file(&index).unwrap(), data);
+
+    // A copy of the binary with a different modification time gets
+    // an index of its own, without replacing the existing one.
+    let copy_dir = tempdir().unwrap();
+    let copy_path = copy_dir.path().join("libtest-so.so");
+    let _size = copy(&path, &copy_path).unwrap();
+    let () = symbolize_path(&copy_path);
+    assert_eq!(read_file(&index).unwrap(), data);
+    assert_eq!(dir.path().read_dir().unwrap().count(), 2);
+}
+
+
+/// Check that we can symbolize addresses using a perf map file.
+#[test]
+fn symbolize_perf_map() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("perf-1234.map");
+    let () = write_file(
+        &path,
+        "7f0000001000 40 LazyCompile:~fib fib.js:1\n\
+         garbage\n\
+         7f0000002000 20 Interpreter\n",
+    )
+    .unwrap();
+
+    let src = symbolize::Source::from(symbolize::PerfMap::new(&path));
+    let symbolizer = Symbolizer::new();
+    let results = symbolizer
+        .symbolize(
+            &src,
+            symbolize::Input::AbsAddr(&[0x7f0000001010, 0x7f0000002000, 0x7f0000001040]),
+        )
+        .unwrap();
+    assert_eq!(results.len(), 3);
+
+    let sym = results[0].as_sym().unwrap();
+    assert_eq!(sym.name, "LazyCompile:~fib fib.js:1");
+    assert_eq!(sym.addr, 0x7f0000001000);
+    assert_eq!(sym.offset, 0x10);
+    assert_eq!(sym.size, Some(0x40));
+    let sym = results[1].as_sym().unwrap();
+    assert_eq!(sym.name, "Interpreter");
+    assert_eq!(results[2], symbolize::Symbolized::Unknown);
+
+    let err = symbolizer
+        .symbolize_single(&src, symbolize::Input::VirtOffset(0x7f0000001010))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
+
+
+/// Check that we can freeze symbolization results and share them
+/// between threads.
+#[test]
+fn symbolize_frozen() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-dwarf-only.bin");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+    let symbolizer = Symbolizer::new();
+    let frozen = symbolizer.freeze(&src).unwrap();
+    // We should be able to drop the symbolizer without affecting the
+    // frozen resolver.
+    let () = drop(symbolizer);
+
+    let () = thread::scope(|scope| {
+        for addr in [0x2000100, 0x2000101, 0x2000102] {
+            let frozen = &frozen;
+            let _handle = scope.spawn(move || {
+                let sym = frozen
+                    .symbolize_single(symbolize::Input::VirtOffset(addr))
+                    .unwrap()
+                    .into_sym()
+                    .unwrap();
+                assert_eq!(sym.name, "factorial");
+                assert_eq!(sym.addr, 0x2000100);
+                assert_eq!(sym.offset, (addr - 0x2000100) as usize);
+                assert_ne!(sym.code_info, None);
+            });
+        }
+    });
+
+    let syms = frozen
+        .symbolize(symbolize::Input::VirtOffset(&[0x0, 0x2000102]))
+        .unwrap();
+    assert_eq!(syms.len(), 2);
+    assert_eq!(syms[0], symbolize::Symbolized::Unknown);
+    assert_eq!(syms[1].as_sym().unwrap().name, "factorial");
+}
+
+
+/// Check that only ELF sources can be frozen.
+#[test]
+fn symbolize_frozen_unsupported() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.gsym");
+    let src = symbolize::Source::from(symbolize::GsymFile::new(path));
+    let err = Symbolizer::new().freeze(&src).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
+
 
-        let sym = results[0].as_sym().unwrap();
-        assert_eq!(sym.name, "the_answer");
-    }
+/// Check that frozen symbolization results report section information
+/// alongside symbols.
+#[test]
+fn symbolize_frozen_full() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = symbolize::Source::Elf(symbolize::Elf::new(path));
+    let symbolizer = Symbolizer::new();
+    let frozen = symbolizer.freeze(&src).unwrap();
 
-    test("libtest-so.so");
-    test("libtest-so-no-separate-code.so");
+    let input = symbolize::Input::VirtOffset(0x2000100);
+    let full = frozen
+        .resolve_full(input, symbolize::ResolveFlags::default())
+        .unwrap();
+    assert_eq!(full.sym.unwrap().name, "factorial");
+    assert_eq!(full.section, Some(".text"));
+    let code_info = full.code_info.unwrap();
+    assert_eq!(code_info.file, OsStr::new("test-stable-addresses.c"));
+
+    let flags = symbolize::ResolveFlags {
+        code_info: false,
+        ..Default::default()
+    };
+    let full = frozen.resolve_full(input, flags).unwrap();
+    assert_ne!(full.sym, None);
+    assert_eq!(full.code_info, None);
+
+    let input = symbolize::Input::VirtOffset(0x0);
+    let full = frozen
+        .resolve_full(input, symbolize::ResolveFlags::default())
+        .unwrap();
+    assert_eq!(full.sym, None);
+    assert_eq!(full.code_info, None);
 }
 
 
@@ -462,6 +1872,125 @@ fn inspect() {
     let () = test(src);
 }
 
+/// Check that we report demangled names alongside raw ones only when
+/// asked to.
+#[test]
+fn inspect_demangled_names() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-rs.bin");
+    let mut elf = inspect::Elf::new(path);
+    elf.debug_info = false;
+    let src = inspect::Source::Elf(elf.clone());
+
+    let name = "_RNvCs69hjMPjVIJK_4test13test_function";
+    let inspector = Inspector::new();
+    let results = inspector
+        .lookup(&[name], &src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, name);
+    assert_eq!(results[0].demangled_name, None);
+
+    elf.demangle = true;
+    let src = inspect::Source::Elf(elf);
+    let results = inspector
+        .lookup(&[name], &src)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, name);
+    let demangled = results[0].demangled_name.as_deref().unwrap();
+    if cfg!(feature = "demangle") {
+        assert_eq!(demangled, "test::test_function");
+    } else {
+        assert_eq!(demangled, name);
+    }
+}
+
+
+/// Check that we can suggest symbol names similar to a misspelled one.
+#[test]
+fn inspect_suggest() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-no-dwarf.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(path));
+    let inspector = Inspector::new();
+
+    let results = inspector.lookup(&["factoral"], &src).unwrap();
+    assert_eq!(results, vec![Vec::new()]);
+    let suggestions = inspector.suggest("factoral", &src).unwrap();
+    assert_eq!(suggestions, vec!["factorial".to_string()]);
+
+    let suggestions = inspector.suggest("fo", &src).unwrap();
+    assert_eq!(suggestions, vec!["foo".to_string()]);
+
+    // Exact matches are not reported.
+    let suggestions = inspector.suggest("factorial", &src).unwrap();
+    assert_eq!(suggestions, Vec::<String>::new());
+
+    let suggestions = inspector.suggest("completely-different", &src).unwrap();
+    assert_eq!(suggestions, Vec::<String>::new());
+}
+
+
+/// Check that we can restrict symbol lookups to a single section.
+#[test]
+fn inspect_section_filter() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-sections.bin");
+    let inspector = Inspector::new();
+
+    for debug_info in [false, true] {
+        let mut elf = inspect::Elf::new(&path);
+        elf.debug_info = debug_info;
+
+        let src = inspect::Source::Elf(elf.clone());
+        let results = inspector.lookup(&["handler"], &src).unwrap();
+        assert_eq!(results[0].len(), 2, "{results:?}");
+
+        let src = inspect::Source::Elf(inspect::Elf {
+            section: Some(".cold".to_string()),
+            ..elf.clone()
+        });
+        let results = inspector.lookup(&["handler", "hot_path"], &src).unwrap();
+        assert_eq!(results[0].len(), 1, "{results:?}");
+        assert_eq!(results[0][0].addr, 0x103d);
+        assert_eq!(results[1], Vec::new());
+
+        let names = inspector
+            .for_each(&src, Vec::new(), |mut names, sym| {
+                let () = names.push(sym.name.to_string());
+                names
+            })
+            .unwrap();
+        assert_eq!(names, vec!["handler".to_string()]);
+
+        let src = inspect::Source::Elf(inspect::Elf {
+            section: Some(".text".to_string()),
+            ..elf.clone()
+        });
+        let results = inspector.lookup(&["handler"], &src).unwrap();
+        assert_eq!(results[0].len(), 1, "{results:?}");
+        assert_eq!(results[0][0].addr, 0x1000);
+
+        // No symbols are reported for a section that does not exist.
+        let src = inspect::Source::Elf(inspect::Elf {
+            section: Some(".does-not-exist".to_string()),
+            ..elf
+        });
+        let results = inspector.lookup(&["handler"], &src).unwrap();
+        assert_eq!(results, vec![Vec::new()]);
+    }
+}
+
 
 /// Read four bytes at the given `offset` in the file identified by `path`.
 fn read_4bytes_at(path: &Path, offset: u64) -> [u8; 4] {
@@ -519,3 +2048,441 @@ fn inspect_all_symbols() {
     assert!(syms.contains("factorial_wrapper"));
     assert!(syms.contains("factorial_inline_test"));
 }
+
+
+/// Check that we can iterate over all inlined functions in an ELF file.
+#[test]
+fn inspect_all_inlined() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-dwarf-only.bin");
+    let mut elf = inspect::Elf::new(test_elf);
+    let src = inspect::Source::Elf(elf.clone());
+
+    let inspector = Inspector::new();
+    let inlined = inspector
+        .for_each_inlined(&src, Vec::new(), |mut inlined, instance| {
+            let () = inlined.push((
+                instance.name.map(str::to_string),
+                instance.ranges.clone(),
+                instance.call_site.as_ref().and_then(|info| info.line),
+            ));
+            inlined
+        })
+        .unwrap();
+
+    let (_, ranges, line) = inlined
+        .iter()
+        .find(|(name, ..)| name.as_deref() == Some("factorial_inline_wrapper"))
+        .unwrap();
+    assert!(ranges.iter().any(|range| range.contains(&0x200020a)));
+    assert_eq!(*line, Some(32));
+
+    // Without debug information no inlined functions are reported.
+    elf.debug_info = false;
+    let src = inspect::Source::Elf(elf);
+    let count = inspector
+        .for_each_inlined(&src, 0, |count, _instance| count + 1)
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+
+/// Check that we can find the addresses attributed to a source code
+/// line.
+#[test]
+fn inspect_line_addrs() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let mut elf = inspect::Elf::new(test_elf);
+    let src = inspect::Source::Elf(elf.clone());
+
+    let inspector = Inspector::new();
+    let addrs = inspector
+        .find_line_addrs(Path::new("test-stable-addresses.c"), 8, &src)
+        .unwrap();
+    assert!(addrs.contains(&0x2000100), "{addrs:#x?}");
+
+    let addrs = inspector
+        .find_line_addrs(Path::new("does-not-exist.c"), 8, &src)
+        .unwrap();
+    assert_eq!(addrs, Vec::<Addr>::new());
+
+    elf.debug_info = false;
+    let src = inspect::Source::Elf(elf);
+    let addrs = inspector
+        .find_line_addrs(Path::new("test-stable-addresses.c"), 8, &src)
+        .unwrap();
+    assert_eq!(addrs, Vec::<Addr>::new());
+}
+
+
+/// Check that we can look up symbols starting exactly at an address.
+#[test]
+fn inspect_exact_symbol() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(&test_elf));
+
+    let inspector = Inspector::new();
+    let sym = inspector.find_sym_exact(0x2000100, &src).unwrap().unwrap();
+    assert_eq!(sym.name, "factorial");
+    assert_eq!(sym.addr, 0x2000100);
+    assert_eq!(sym.sym_type, inspect::SymType::Function);
+    assert_ne!(sym.file_offset, None);
+    assert_eq!(sym.obj_file_name.as_deref(), Some(test_elf.as_path()));
+
+    let sym = inspector.find_sym_exact(0x2000101, &src).unwrap();
+    assert_eq!(sym, None);
+}
+
+
+/// Check that we can list the source files of a compilation unit.
+#[test]
+fn inspect_source_files() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+
+    let inspector = Inspector::new();
+    let files = inspector.source_files(0x2000100, &src).unwrap();
+    assert!(
+        files
+            .iter()
+            .any(|path| path.file_name() == Some(OsStr::new("test-stable-addresses.c"))),
+        "{files:?}"
+    );
+
+    let files = inspector.source_files(0x0, &src).unwrap();
+    assert_eq!(files, Vec::<PathBuf>::new());
+}
+
+
+/// Check that we can look up the symbol at a file's entry point.
+#[test]
+fn inspect_entry_symbol() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-no-debug.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+
+    let inspector = Inspector::new();
+    let sym = inspector.find_entry_sym(&src).unwrap().unwrap();
+    assert_eq!(sym.name, "_start");
+
+    let test_so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so.so");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_so));
+    let sym = inspector.find_entry_sym(&src).unwrap();
+    assert_eq!(sym, None);
+}
+
+
+/// Check that we can retrieve the name of the compilation unit covering
+/// an address.
+#[test]
+fn inspect_unit_name() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+
+    let inspector = Inspector::new();
+    let name = inspector.unit_name(0x2000100, &src).unwrap().unwrap();
+    assert_eq!(
+        name.file_name(),
+        Some(OsStr::new("test-stable-addresses.c"))
+    );
+    assert!(name.is_absolute(), "{name:?}");
+
+    let name = inspector.unit_name(0x0, &src).unwrap();
+    assert_eq!(name, None);
+}
+
+
+/// Check that we can look up all symbols covering an address.
+#[test]
+fn inspect_syms_at() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+
+    let inspector = Inspector::new();
+    for addr in [0x2000100, 0x2000101] {
+        let syms = inspector.find_syms_at(addr, &src).unwrap();
+        assert_eq!(syms.len(), 1, "{syms:?}");
+        assert_eq!(syms[0].name, "factorial");
+        assert_eq!(syms[0].addr, 0x2000100);
+    }
+
+    let syms = inspector.find_syms_at(0x0, &src).unwrap();
+    assert_eq!(syms, Vec::new());
+}
+
+
+/// Check that we can look up the function containing an address using
+/// only ELF symbol tables.
+#[test]
+fn inspect_find_func_fast() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+
+    let inspector = Inspector::new();
+    for addr in [0x2000100, 0x2000101] {
+        let sym = inspector.find_func_fast(addr, &src).unwrap().unwrap();
+        assert_eq!(sym.name, "factorial");
+        assert_eq!(sym.addr, 0x2000100);
+        assert_eq!(sym.sym_type, inspect::SymType::Function);
+        assert_ne!(sym.size, 0);
+    }
+
+    let sym = inspector.find_func_fast(0x0, &src).unwrap();
+    assert_eq!(sym, None);
+}
+
+
+/// Check that we can test whether a file defines a symbol.
+#[test]
+fn inspect_has_sym() {
+    let test_so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so.so");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_so));
+
+    let inspector = Inspector::new();
+    assert!(inspector.has_sym("the_answer", &src).unwrap());
+    assert!(!inspector.has_sym("does_not_exist", &src).unwrap());
+
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+    assert!(inspector.has_sym("factorial", &src).unwrap());
+    assert!(!inspector.has_sym("the_answer", &src).unwrap());
+}
+
+
+/// Check that we can list all symbols starting within an address range.
+#[test]
+fn inspect_syms_in_range() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+
+    let inspector = Inspector::new();
+    let syms = inspector.syms_in_range(0x2000000..0x2000100, &src).unwrap();
+    let syms = syms
+        .iter()
+        .map(|sym| (sym.name.as_ref(), sym.addr))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        syms,
+        vec![
+            ("main", 0x2000000),
+            ("factorial_wrapper", 0x2000034),
+            ("foo", 0x2000045),
+            ("factorial_wrapper", 0x2000056),
+        ]
+    );
+
+    // The start of the range is inclusive, while its end is not.
+    let syms = inspector.syms_in_range(0x2000100..0x2000101, &src).unwrap();
+    assert_eq!(syms.len(), 1, "{syms:?}");
+    assert_eq!(syms[0].name, "factorial");
+
+    let syms = inspector.syms_in_range(0x0..0x1000, &src).unwrap();
+    assert_eq!(syms, Vec::new());
+}
+
+
+/// Check that we can explain why addresses can or cannot be
+/// symbolized.
+#[test]
+fn inspect_explain_addr() {
+    let inspector = Inspector::new();
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+    let status = inspector.explain_addr(0x2000100, &src).unwrap();
+    assert_eq!(status, inspect::AddrStatus::Resolvable);
+    let status = inspector.explain_addr(0x0, &src).unwrap();
+    assert_eq!(status, inspect::AddrStatus::OutOfRange);
+
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-stripped.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+    let status = inspector.explain_addr(0x2000100, &src).unwrap();
+    assert_eq!(status, inspect::AddrStatus::NoSymbols);
+}
+
+
+/// Check that we can determine whether a file provides information
+/// about inlined functions.
+#[test]
+fn inspect_has_inline_info() {
+    let inspector = Inspector::new();
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let mut elf = inspect::Elf::new(test_elf);
+    let src = inspect::Source::Elf(elf.clone());
+    assert!(inspector.has_inline_info(&src).unwrap());
+
+    elf.debug_info = false;
+    let src = inspect::Source::Elf(elf);
+    assert!(!inspector.has_inline_info(&src).unwrap());
+
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-no-dwarf.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+    assert!(!inspector.has_inline_info(&src).unwrap());
+}
+
+
+/// Check that we can retrieve the parameters and local variables of a
+/// function.
+#[test]
+fn inspect_locals() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+
+    let inspector = Inspector::new();
+    let vars = inspector.locals(0x2000100, &src).unwrap();
+    assert_eq!(vars.len(), 1, "{vars:?}");
+    assert_eq!(vars[0].name.as_deref(), Some("n"));
+    assert_eq!(vars[0].type_name.as_deref(), Some("unsigned int"));
+    assert!(vars[0].param);
+    assert!(vars[0].location.is_some());
+
+    let vars = inspector.locals(0xffffffff, &src).unwrap();
+    assert_eq!(vars, Vec::new());
+
+    // Without debug information no variables are available.
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let mut elf = inspect::Elf::new(test_elf);
+    elf.debug_info = false;
+    let src = inspect::Source::Elf(elf);
+    let vars = inspector.locals(0x2000100, &src).unwrap();
+    assert_eq!(vars, Vec::new());
+}
+
+
+/// Check that we can map all addresses of a function to source lines.
+#[test]
+fn inspect_function_lines() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+
+    let inspector = Inspector::new();
+    // `factorial` spans 0x2000100..0x200012b.
+    let lines = inspector.function_lines(0x2000110, &src).unwrap();
+    assert!(!lines.is_empty());
+    assert_eq!(lines[0].0, 0x2000100);
+    assert!(lines
+        .iter()
+        .all(|(addr, ..)| (0x2000100..0x200012b).contains(addr)));
+    assert!(lines.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    assert!(lines
+        .iter()
+        .all(|(_addr, path, _line)| path.ends_with("test-stable-addresses.c")));
+
+    let lines = inspector.function_lines(0x0, &src).unwrap();
+    assert_eq!(lines, Vec::new());
+}
+
+
+/// Check that we can look up the line a function is defined at.
+#[test]
+fn inspect_function_decl_line() {
+    let test_elf = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses.bin");
+    let src = inspect::Source::Elf(inspect::Elf::new(test_elf));
+
+    let inspector = Inspector::new();
+    for (addr, line) in [(0x2000100, 8), (0x2000110, 8), (0x2000000, 50)] {
+        let (path, decl_line) = inspector.function_decl_line(addr, &src).unwrap().unwrap();
+        assert!(path.ends_with("test-stable-addresses.c"), "{path:?}");
+        assert_eq!(decl_line, line);
+    }
+
+    let decl = inspector.function_decl_line(0x0, &src).unwrap();
+    assert_eq!(decl, None);
+}
+
+
+/// Check that we can symbolize addresses in an ELF file lacking usable
+/// section headers when lenient parsing is enabled.
+#[test]
+fn symbolize_elf_lenient() {
+    let path = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("libtest-so.so");
+    let src = inspect::Source::Elf(inspect::Elf::new(&path));
+    let inspector = Inspector::new();
+    let syms = inspector.lookup(&["the_answer"], &src).unwrap();
+    let the_answer = syms[0][0].addr;
+
+    // Point the section header table past the end of the file, as if
+    // it got lost. `e_shoff` is located at offset 0x28 of the ELF
+    // header.
+    let mut data = read_file(&path).unwrap();
+    let shoff = (data.len() as u64 * 2).to_ne_bytes();
+    let () = data[0x28..0x30].copy_from_slice(&shoff);
+
+    let dir = tempdir().unwrap();
+    let corrupted = dir.path().join("libtest-so-corrupted.so");
+    let () = write_file(&corrupted, &data).unwrap();
+    assert_eq!(
+        read_elf_recovered_tables(&corrupted).unwrap(),
+        vec![
+            RecoveredTable::Dynamic,
+            RecoveredTable::DynSymtab,
+            RecoveredTable::DynStrtab,
+            RecoveredTable::GnuHash,
+        ]
+    );
+
+    let file = File::open(&corrupted).unwrap();
+    let len = data.len() as u64;
+    let src = symbolize::Source::from(symbolize::ElfReader::new(&file, len));
+    let input = symbolize::Input::VirtOffset(the_answer);
+    let symbolizer = Symbolizer::new();
+    let result = symbolizer.symbolize_single(&src, input);
+    assert!(result.is_err(), "{result:?}");
+
+    let symbolizer = Symbolizer::builder()
+        .enable_lenient_elf_parsing(true)
+        .build();
+    let sym = symbolizer
+        .symbolize_single(&src, input)
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(sym.name, "the_answer");
+
+    let src = symbolize::Source::from(symbolize::Elf::new(&corrupted));
+    let sym = symbolizer
+        .symbolize_single(&src, input)
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(sym.name, "the_answer");
+}
@@ -0,0 +1,108 @@
+//! This test relies on a global allocator counting all allocations
+//! performed by the process and, hence, relies on being run in a
+//! dedicated process. Do not add additional test cases unless they are
+//! guaranteed to not interfere.
+
+#![allow(clippy::let_and_return, clippy::let_unit_value)]
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::fs::read_to_string;
+use std::path::Path;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use blazesym::symbolize::Input;
+use blazesym::symbolize::Kernel;
+use blazesym::symbolize::Source;
+use blazesym::symbolize::Symbolizer;
+
+
+/// The number of heap allocations performed so far.
+static ALLOC_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator counting the allocations it performs.
+struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _count = ALLOC_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let _count = ALLOC_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _count = ALLOC_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+
+/// Count the heap allocations performed when symbolizing an address
+/// using kallsyms, end-to-end.
+fn symbolize_kallsyms_allocs(name_arena: bool) -> usize {
+    let kallsyms = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("kallsyms");
+    // Provide a (small) kernel image to prevent a search for one on
+    // the system.
+    let image = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("test-stable-addresses-no-dwarf.bin");
+    let src = Source::Kernel(Kernel {
+        kallsyms: Some(kallsyms),
+        kernel_image: Some(image),
+        ..Default::default()
+    });
+
+    let before = ALLOC_COUNT.load(Ordering::Relaxed);
+    let symbolizer = Symbolizer::builder()
+        .enable_code_info(false)
+        .enable_name_arena(name_arena)
+        .build();
+    let result = symbolizer
+        .symbolize_single(&src, Input::AbsAddr(0xc0008001))
+        .unwrap()
+        .into_sym()
+        .unwrap();
+    assert_eq!(result.name, "stext");
+    let () = drop(symbolizer);
+    ALLOC_COUNT.load(Ordering::Relaxed) - before
+}
+
+
+/// Make sure that placing kernel symbol names in an arena reduces the
+/// number of heap allocations performed.
+#[test]
+fn ksym_name_arena_allocs() {
+    // Exclude any one-time initialization from the counts.
+    let _allocs = symbolize_kallsyms_allocs(false);
+
+    let kallsyms = Path::new(&env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("kallsyms");
+    let sym_count = read_to_string(kallsyms).unwrap().lines().count();
+
+    let allocs = symbolize_kallsyms_allocs(false);
+    let arena_allocs = symbolize_kallsyms_allocs(true);
+    println!("{allocs} allocations without arena, {arena_allocs} with arena");
+    // Without the arena, every symbol name is allocated separately.
+    // The arena only needs a few large chunks instead, so it should
+    // save (almost) one allocation per symbol.
+    assert!(
+        arena_allocs + sym_count * 9 / 10 < allocs,
+        "{arena_allocs} vs. {allocs} allocations for {sym_count} symbols"
+    );
+}